@@ -1,7 +1,11 @@
 use ttrpc_codegen::{Codegen, Customize, ProtobufCustomize};
 
 fn main() -> std::io::Result<()> {
-    let protos = &["protos/attestation_agent.proto"];
+    let protos = &[
+        "protos/attestation_agent.proto",
+        "protos/measurement_control.proto",
+        "protos/nri.proto",
+    ];
     let includes = &["protos"];
 
     // Configure protobuf code generation
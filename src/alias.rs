@@ -0,0 +1,68 @@
+// src/alias.rs
+use std::collections::BTreeMap;
+
+/// Rewrites filesystem prefixes in operations to logical names.
+pub struct PathAliases {
+    /// `(prefix, alias)` pairs, longest prefix first so that nested mount
+    /// points win over their parents.
+    aliases: Vec<(String, String)>,
+}
+
+impl PathAliases {
+    pub fn from_config(config: &BTreeMap<String, String>) -> Option<Self> {
+        if config.is_empty() {
+            return None;
+        }
+        let mut aliases: Vec<(String, String)> = config
+            .iter()
+            .map(|(prefix, alias)| {
+                (
+                    prefix.trim_end_matches('/').to_string(),
+                    normalize_alias(alias),
+                )
+            })
+            .collect();
+        aliases.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        Some(Self { aliases })
+    }
+
+    /// Returns `operation` with the first aliased path replaced, or `None`
+    /// if it names no aliased path. Paths are recognized at the start of the
+    /// operation and after a `:` (as in `kernel:/path` or `name@v1:/path`).
+    pub fn apply(&self, operation: &str) -> Option<String> {
+        let starts = std::iter::once(0).chain(
+            operation
+                .char_indices()
+                .filter(|(_, c)| *c == ':')
+                .map(|(i, _)| i + 1),
+        );
+        for start in starts {
+            let path = &operation[start..];
+            for (prefix, alias) in &self.aliases {
+                match path.strip_prefix(prefix.as_str()) {
+                    Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+                        let rest = if alias.ends_with('/') {
+                            rest.strip_prefix('/').unwrap_or(rest)
+                        } else {
+                            rest
+                        };
+                        return Some(format!("{}{}{}", &operation[..start], alias, rest));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+}
+
+/// URI-style aliases such as `models://` keep their slashes, so that
+/// `/mnt/models/llama` becomes `models://llama`; other aliases lose a
+/// trailing `/`.
+fn normalize_alias(alias: &str) -> String {
+    if alias.ends_with("://") {
+        alias.to_string()
+    } else {
+        alias.trim_end_matches('/').to_string()
+    }
+}
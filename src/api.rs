@@ -0,0 +1,321 @@
+// src/api.rs
+use crate::config::ApiConfig;
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use crate::listener;
+use crate::sink::MeasurementSink;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+const DEFAULT_ALGORITHM: &str = "sha256";
+
+/// Domains extended by the tool itself. Clients cannot extend them, so that
+/// an API request can never be mistaken for a measurement the tool made.
+const RESERVED_DOMAINS: &[&str] = &[
+    "archive_member",
+    "boot",
+    "container",
+    "container_image",
+    "container_rootfs",
+    "container_spec",
+    "dataset",
+    "device",
+    "dir",
+    "efi",
+    "exec",
+    "file",
+    "fs_image",
+    "group",
+    "group_item",
+    "k8s_pod",
+    "kata_volume",
+    "kernel",
+    "measurement_policy",
+    "model_dir",
+    "model_shard",
+    "mounts",
+    "network",
+    "object",
+    "pod_path",
+    "process",
+    "sbom",
+    "verification",
+    "vmm",
+];
+
+/// Largest request body accepted; requests only carry a path and two names.
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// Longest request or header line accepted.
+const MAX_LINE_BYTES: u64 = 8 * 1024;
+const MAX_HEADER_LINES: usize = 64;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize)]
+struct MeasureRequest {
+    path: String,
+    #[serde(default)]
+    domain: Option<String>,
+    #[serde(default)]
+    algorithm: Option<String>,
+}
+
+#[derive(Serialize)]
+struct MeasureResponse {
+    path: String,
+    domain: String,
+    algorithm: String,
+    digest: String,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// A response ready to be written: status line and JSON body.
+struct Reply {
+    status: &'static str,
+    body: Vec<u8>,
+}
+
+impl Reply {
+    fn json<T: Serialize>(status: &'static str, value: &T) -> Self {
+        Self {
+            status,
+            body: serde_json::to_vec(value).unwrap_or_default(),
+        }
+    }
+
+    fn error(status: &'static str, message: impl Into<String>) -> Self {
+        Self::json(
+            status,
+            &ErrorResponse {
+                error: message.into(),
+            },
+        )
+    }
+}
+
+/// Minimal HTTP/1.1 server for `POST /measure`. Every connection carries
+/// one request and is closed after the response.
+struct ApiServer {
+    token: String,
+    pcr_index: Option<u64>,
+    allowed_domains: Vec<String>,
+    sink: Arc<dyn MeasurementSink>,
+}
+
+impl ApiServer {
+    async fn handle_connection(&self, stream: TcpStream) -> Result<()> {
+        let mut reader = BufReader::new(stream);
+        let reply = match timeout(REQUEST_TIMEOUT, self.read_and_dispatch(&mut reader)).await {
+            Ok(reply) => reply,
+            Err(_) => Reply::error("408 Request Timeout", "request not received in time"),
+        };
+        let mut stream = reader.into_inner();
+        let head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            reply.status,
+            reply.body.len()
+        );
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&reply.body).await?;
+        stream.shutdown().await?;
+        Ok(())
+    }
+
+    async fn read_and_dispatch(&self, reader: &mut BufReader<TcpStream>) -> Reply {
+        let mut request_line = String::new();
+        if read_limited_line(reader, &mut request_line).await.is_err() {
+            return Reply::error("400 Bad Request", "unreadable request");
+        }
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+        let mut content_length = 0usize;
+        let mut authorization = None;
+        for _ in 0..MAX_HEADER_LINES {
+            let mut line = String::new();
+            match read_limited_line(reader, &mut line).await {
+                Ok(0) => break,
+                Ok(_) if line == "\r\n" || line == "\n" => break,
+                Ok(_) => {}
+                Err(_) => return Reply::error("400 Bad Request", "unreadable headers"),
+            }
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().unwrap_or(usize::MAX);
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorization = value.strip_prefix("Bearer ").map(str::to_string);
+            }
+        }
+
+        if target != "/measure" {
+            return Reply::error("404 Not Found", "unknown endpoint");
+        }
+        if method != "POST" {
+            return Reply::error("405 Method Not Allowed", "use POST");
+        }
+        if !authorization.is_some_and(|token| constant_time_eq(&token, &self.token)) {
+            return Reply::error("401 Unauthorized", "missing or invalid bearer token");
+        }
+        if content_length > MAX_BODY_BYTES {
+            return Reply::error("413 Payload Too Large", "request body too large");
+        }
+        let mut body = vec![0u8; content_length];
+        if reader.read_exact(&mut body).await.is_err() {
+            return Reply::error("400 Bad Request", "truncated request body");
+        }
+        let request: MeasureRequest = match serde_json::from_slice(&body) {
+            Ok(request) => request,
+            Err(e) => return Reply::error("400 Bad Request", format!("invalid request: {}", e)),
+        };
+
+        match self.measure(request).await {
+            Ok(response) => Reply::json("200 OK", &response),
+            Err(e @ MeasurementError::UnsupportedHashAlgorithm(_))
+            | Err(e @ MeasurementError::InvalidDirectory(_)) => {
+                Reply::error("400 Bad Request", e.to_string())
+            }
+            Err(e @ MeasurementError::Config(_)) => Reply::error("403 Forbidden", e.to_string()),
+            Err(e) => {
+                warn!("API measurement failed: {}", e);
+                Reply::error("500 Internal Server Error", e.to_string())
+            }
+        }
+    }
+
+    async fn measure(&self, request: MeasureRequest) -> Result<MeasureResponse> {
+        // `serve` refuses an empty list, so the first entry always exists.
+        let domain = request
+            .domain
+            .unwrap_or_else(|| self.allowed_domains[0].clone());
+        if !self.allowed_domains.contains(&domain) {
+            return Err(MeasurementError::Config(format!(
+                "domain '{}' is not allowed",
+                domain
+            )));
+        }
+        let algorithm = request
+            .algorithm
+            .unwrap_or_else(|| DEFAULT_ALGORITHM.to_string())
+            .to_lowercase();
+        let path = PathBuf::from(&request.path);
+        if !path.is_absolute() || !path.is_file() {
+            return Err(MeasurementError::InvalidDirectory(format!(
+                "{} is not an absolute path to a regular file",
+                request.path
+            )));
+        }
+
+        let digest = {
+            let algorithm = algorithm.clone();
+            tokio::task::spawn_blocking(move || digest_reader(&mut File::open(&path)?, &algorithm))
+                .await
+                .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+        };
+        self.sink
+            .extend(&domain, &request.path, &digest, self.pcr_index)
+            .await?;
+        info!(
+            "Measured {} on request (domain '{}', {})",
+            request.path, domain, algorithm
+        );
+        Ok(MeasureResponse {
+            path: request.path,
+            domain,
+            algorithm,
+            digest,
+        })
+    }
+}
+
+/// Reads one line of at most `MAX_LINE_BYTES`, so that a client cannot make
+/// the server buffer an endless line.
+async fn read_limited_line(
+    reader: &mut BufReader<TcpStream>,
+    line: &mut String,
+) -> std::io::Result<usize> {
+    let read = (&mut *reader).take(MAX_LINE_BYTES).read_line(line).await?;
+    if read as u64 == MAX_LINE_BYTES && !line.ends_with('\n') {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "line too long",
+        ));
+    }
+    Ok(read)
+}
+
+/// Compares the presented token without returning early on the first
+/// mismatching byte.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+/// Serves the measurement API until the listener fails. Refuses to start
+/// without a non-empty bearer token or when `allowed_domains` is empty or
+/// names a domain the tool extends itself.
+pub async fn serve(config: ApiConfig, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+    if config.allowed_domains.is_empty() {
+        return Err(MeasurementError::Config(
+            "[api] allowed_domains must name at least one domain".to_string(),
+        ));
+    }
+    if let Some(domain) = config
+        .allowed_domains
+        .iter()
+        .find(|domain| RESERVED_DOMAINS.contains(&domain.as_str()))
+    {
+        return Err(MeasurementError::Config(format!(
+            "[api] allowed_domains cannot include '{}', which the tool extends itself",
+            domain
+        )));
+    }
+    let token_file = config.token_file.as_deref().ok_or_else(|| {
+        MeasurementError::Config("[api] token_file is required to enable the API".to_string())
+    })?;
+    let token = fs::read_to_string(token_file)
+        .map_err(|e| MeasurementError::Config(format!("Cannot read {}: {}", token_file, e)))?
+        .trim()
+        .to_string();
+    if token.is_empty() {
+        return Err(MeasurementError::Config(format!(
+            "API token file {} is empty",
+            token_file
+        )));
+    }
+
+    let listener = listener::tcp_listener("api", &config.listen).await?;
+    info!("Measurement API listening on {}", config.listen);
+    let server = Arc::new(ApiServer {
+        token,
+        pcr_index: config.pcr_index.map(|v| v as u64),
+        allowed_domains: config.allowed_domains,
+        sink,
+    });
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        debug!("API connection from {}", peer);
+        let server = server.clone();
+        tokio::spawn(async move {
+            if let Err(e) = server.handle_connection(stream).await {
+                debug!("API connection from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
@@ -0,0 +1,80 @@
+// src/archive.rs
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Archive formats whose members can be measured individually.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl ArchiveKind {
+    /// Detects the format from the file name.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar") {
+            Some(ArchiveKind::Tar)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else if name.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the path and `<algorithm>:<hex>` digest of every regular file
+/// in the archive, sorted by path.
+pub fn member_digests(
+    path: &Path,
+    kind: ArchiveKind,
+    hash_algorithm: &str,
+) -> Result<Vec<(String, String)>> {
+    let file = File::open(path)?;
+    let mut members = match kind {
+        ArchiveKind::Tar => tar_members(file, hash_algorithm)?,
+        ArchiveKind::TarGz => tar_members(GzDecoder::new(file), hash_algorithm)?,
+        ArchiveKind::Zip => zip_members(file, hash_algorithm)?,
+    };
+    members.sort();
+    Ok(members)
+}
+
+fn tar_members(reader: impl Read, hash_algorithm: &str) -> Result<Vec<(String, String)>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut members = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().into_owned();
+        members.push((name, digest_reader(&mut entry, hash_algorithm)?));
+    }
+    Ok(members)
+}
+
+fn zip_members(file: File, hash_algorithm: &str) -> Result<Vec<(String, String)>> {
+    let mut archive = zip::ZipArchive::new(file).map_err(zip_error)?;
+    let mut members = Vec::new();
+    for i in 0..archive.len() {
+        let mut member = archive.by_index(i).map_err(zip_error)?;
+        if member.is_dir() {
+            continue;
+        }
+        let name = member.name().to_string();
+        members.push((name, digest_reader(&mut member, hash_algorithm)?));
+    }
+    Ok(members)
+}
+
+fn zip_error(e: zip::result::ZipError) -> MeasurementError {
+    MeasurementError::Other(anyhow::anyhow!("Invalid zip archive: {}", e))
+}
@@ -0,0 +1,228 @@
+// src/canonical_json.rs
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+/// Serializes `value` canonically when `canonical` is set, otherwise with the
+/// compact `serde_json` encoding used before structured content existed.
+pub fn encode<T: Serialize + ?Sized>(value: &T, canonical: bool) -> serde_json::Result<Vec<u8>> {
+    if canonical {
+        to_vec(value)
+    } else {
+        serde_json::to_vec(value)
+    }
+}
+
+/// Serializes `value` in canonical JSON (RFC 8785): object members sorted by
+/// the UTF-16 code units of their keys, no insignificant whitespace, only the
+/// required string escapes, and numbers in the ECMAScript shortest round-trip
+/// form (`1e+21`, `1e-7`, `-0` written as `0`). The same value always encodes
+/// to the same bytes, whatever the platform or field order.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> serde_json::Result<Vec<u8>> {
+    let value = serde_json::to_value(value)?;
+    let mut out = Vec::new();
+    write_value(&value, &mut out);
+    Ok(out)
+}
+
+fn write_value(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.extend_from_slice(b"null"),
+        Value::Bool(true) => out.extend_from_slice(b"true"),
+        Value::Bool(false) => out.extend_from_slice(b"false"),
+        Value::Number(n) => write_number(n, out),
+        Value::String(s) => write_string(s, out),
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_value(item, out);
+            }
+            out.push(b']');
+        }
+        Value::Object(map) => {
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push(b'{');
+            for (i, (key, item)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_string(key, out);
+                out.push(b':');
+                write_value(item, out);
+            }
+            out.push(b'}');
+        }
+    }
+}
+
+fn write_number(n: &Number, out: &mut Vec<u8>) {
+    if let Some(i) = n.as_i64() {
+        out.extend_from_slice(i.to_string().as_bytes());
+    } else if let Some(u) = n.as_u64() {
+        out.extend_from_slice(u.to_string().as_bytes());
+    } else {
+        // serde_json never holds NaN or infinities.
+        let f = n.as_f64().unwrap_or_default();
+        out.extend_from_slice(format_f64(f).as_bytes());
+    }
+}
+
+fn format_f64(f: f64) -> String {
+    if f == 0.0 {
+        return "0".to_string();
+    }
+    let abs = f.abs();
+    if (1e-6..1e21).contains(&abs) {
+        // Display already gives the shortest round-trip digits without an
+        // exponent, and no fraction for integral values.
+        format!("{}", f)
+    } else {
+        let s = format!("{:e}", f);
+        match s.split_once('e') {
+            Some((mantissa, exp)) if !exp.starts_with('-') => format!("{}e+{}", mantissa, exp),
+            _ => s,
+        }
+    }
+}
+
+fn write_string(s: &str, out: &mut Vec<u8>) {
+    out.push(b'"');
+    for c in s.chars() {
+        match c {
+            '"' => out.extend_from_slice(b"\\\""),
+            '\\' => out.extend_from_slice(b"\\\\"),
+            '\u{08}' => out.extend_from_slice(b"\\b"),
+            '\u{0c}' => out.extend_from_slice(b"\\f"),
+            '\n' => out.extend_from_slice(b"\\n"),
+            '\r' => out.extend_from_slice(b"\\r"),
+            '\t' => out.extend_from_slice(b"\\t"),
+            c if (c as u32) < 0x20 => {
+                out.extend_from_slice(format!("\\u{:04x}", c as u32).as_bytes())
+            }
+            c => {
+                let mut buf = [0u8; 4];
+                out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+    }
+    out.push(b'"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn canonical(value: &Value) -> String {
+        String::from_utf8(to_vec(value).unwrap()).unwrap()
+    }
+
+    /// The key sorting example of RFC 8785, section 3.2.3: keys compare by
+    /// UTF-16 code units, so U+1F600 (a surrogate pair) sorts before U+FB33.
+    #[test]
+    fn sorts_keys_by_utf16_code_units() {
+        let value = json!({
+            "\u{20ac}": "Euro Sign",
+            "\r": "Carriage Return",
+            "\u{fb33}": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "\u{1f600}": "Emoji: Grinning Face",
+            "\u{80}": "Control",
+            "\u{f6}": "Latin Small Letter O With Diaeresis"
+        });
+        assert_eq!(
+            canonical(&value),
+            "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\
+             \"\u{f6}\":\"Latin Small Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",\
+             \"\u{1f600}\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+        );
+    }
+
+    #[test]
+    fn sorts_nested_objects_and_keeps_array_order() {
+        let value = json!({
+            "z": {"y": [3, {"b": null, "a": true}], "x": "s"},
+            "a": [],
+            "m": {}
+        });
+        assert_eq!(
+            canonical(&value),
+            r#"{"a":[],"m":{},"z":{"x":"s","y":[3,{"a":true,"b":null}]}}"#
+        );
+    }
+
+    /// Number examples of RFC 8785, appendix B.
+    #[test]
+    fn formats_numbers_like_ecmascript() {
+        let value = json!([
+            0.0,
+            -0.0,
+            1.0,
+            -1.5,
+            0.002,
+            0.000001,
+            1e-7,
+            333333333.3333333,
+            123456789012345680000.0,
+            1e21,
+            1e23,
+            5e-324,
+            1.7976931348623157e308,
+            9007199254740992u64,
+            -9007199254740992i64,
+            u64::MAX
+        ]);
+        assert_eq!(
+            canonical(&value),
+            "[0,0,1,-1.5,0.002,0.000001,1e-7,333333333.3333333,123456789012345680000,\
+             1e+21,1e+23,5e-324,1.7976931348623157e+308,9007199254740992,\
+             -9007199254740992,18446744073709551615]"
+        );
+    }
+
+    /// Only `"`, `\` and control characters are escaped, the latter with
+    /// the short forms where JSON has them; `/`, DEL and non-ASCII text are
+    /// written as UTF-8.
+    #[test]
+    fn escapes_only_what_json_requires() {
+        let value = json!("\"\\\u{08}\u{0c}\n\r\t\u{00}\u{1f}/\u{7f}é€😀");
+        assert_eq!(
+            canonical(&value),
+            "\"\\\"\\\\\\b\\f\\n\\r\\t\\u0000\\u001f/\u{7f}é€😀\""
+        );
+    }
+
+    #[test]
+    fn encodes_non_ascii_keys_and_values_as_utf8() {
+        let value = json!({"名前": "値", "ключ": "значение", "a": "ü"});
+        let encoded = to_vec(&value).unwrap();
+        assert_eq!(
+            encoded,
+            "{\"a\":\"ü\",\"ключ\":\"значение\",\"名前\":\"値\"}".as_bytes()
+        );
+    }
+
+    #[test]
+    fn ignores_field_order_of_structs() {
+        #[derive(Serialize)]
+        struct Spec {
+            version: u32,
+            name: &'static str,
+        }
+        let spec = Spec {
+            version: 2,
+            name: "app",
+        };
+        assert_eq!(
+            encode(&spec, true).unwrap(),
+            br#"{"name":"app","version":2}"#
+        );
+        assert_eq!(
+            encode(&spec, false).unwrap(),
+            br#"{"version":2,"name":"app"}"#
+        );
+    }
+}
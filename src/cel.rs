@@ -0,0 +1,232 @@
+// src/cel.rs
+use crate::config::CelFormat;
+use crate::digest::Hasher;
+use crate::error::{MeasurementError, Result};
+use crate::event_log::aael_event;
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Content type of the records, whose content is the AAEL event.
+const CONTENT_TYPE: &str = "aael";
+
+#[derive(Serialize, Deserialize)]
+struct CelDigest {
+    #[serde(rename = "hashAlg")]
+    hash_alg: String,
+    digest: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AaelContent {
+    domain: String,
+    operation: String,
+    content: String,
+}
+
+/// One record of the TCG Canonical Event Log.
+#[derive(Serialize, Deserialize)]
+struct CelRecord {
+    recnum: u64,
+    pcr: u64,
+    digests: Vec<CelDigest>,
+    content_type: String,
+    content: AaelContent,
+}
+
+/// Appends each measurement to a file in the TCG Canonical Event Log format,
+/// for verifiers that already parse CEL. It sits behind
+/// `sink::StagingSink`, so the event digest is taken over the staged AAEL
+/// event `<domain> <operation> <content>`, the same event the Attestation
+/// Agent extends.
+pub struct CelSink {
+    path: PathBuf,
+    format: CelFormat,
+    hash_algorithm: String,
+    pcr_index: u64,
+    /// Number of the next record; counted from the file on first use, so
+    /// that numbering continues across restarts.
+    next_recnum: Mutex<Option<u64>>,
+}
+
+impl CelSink {
+    pub fn new(path: PathBuf, format: CelFormat, hash_algorithm: String, pcr_index: u32) -> Self {
+        Self {
+            path,
+            format,
+            hash_algorithm,
+            pcr_index: pcr_index as u64,
+            next_recnum: Mutex::new(None),
+        }
+    }
+
+    /// Counts the records already in the file.
+    fn existing_records(&self) -> Result<u64> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let mut reader = BufReader::new(file);
+        let mut count = 0;
+        match self.format {
+            CelFormat::Json => {
+                for line in reader.lines() {
+                    if !line?.trim().is_empty() {
+                        count += 1;
+                    }
+                }
+            }
+            CelFormat::Cbor => {
+                while !reader.fill_buf()?.is_empty() {
+                    ciborium::de::from_reader::<CelRecord, _>(&mut reader).map_err(|e| {
+                        MeasurementError::Other(anyhow::anyhow!(
+                            "Cannot read CEL record {} of {:?}: {}",
+                            count,
+                            self.path,
+                            e
+                        ))
+                    })?;
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    fn encode(&self, record: &CelRecord) -> Result<Vec<u8>> {
+        match self.format {
+            CelFormat::Json => {
+                let mut line = serde_json::to_vec(record)
+                    .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+                line.push(b'\n');
+                Ok(line)
+            }
+            CelFormat::Cbor => {
+                let mut encoded = Vec::new();
+                ciborium::ser::into_writer(record, &mut encoded)
+                    .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+                Ok(encoded)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MeasurementSink for CelSink {
+    fn name(&self) -> &str {
+        "cel"
+    }
+
+    async fn extend(
+        &self,
+        domain: &str,
+        operation: &str,
+        content: &str,
+        pcr_index: Option<u64>,
+    ) -> Result<()> {
+        let event = aael_event(domain, operation, content);
+        let mut hasher = Hasher::new(&self.hash_algorithm)?;
+        hasher.update(event.as_bytes());
+        let digest = hasher.finalize_hex();
+
+        let mut next_recnum = self.next_recnum.lock().unwrap();
+        let recnum = match *next_recnum {
+            Some(recnum) => recnum,
+            None => self.existing_records()?,
+        };
+        let record = CelRecord {
+            recnum,
+            pcr: pcr_index.unwrap_or(self.pcr_index),
+            digests: vec![CelDigest {
+                hash_alg: self.hash_algorithm.to_lowercase(),
+                digest,
+            }],
+            content_type: CONTENT_TYPE.to_string(),
+            content: AaelContent {
+                domain: domain.to_string(),
+                operation: operation.to_string(),
+                content: content.to_string(),
+            },
+        };
+        let encoded = self.encode(&record)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&encoded)?;
+        *next_recnum = Some(recnum + 1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::rpc_client::AAClient;
+    use crate::sink;
+    use crate::tpm;
+    use std::fs;
+    use std::sync::Arc;
+
+    /// A CEL record must replay like the Attestation Agent path: its digest
+    /// is that of the local event log line without its timestamp, after the
+    /// path alias and the tenant prefix are applied.
+    #[tokio::test]
+    async fn digest_matches_the_aa_event() {
+        let dir = tempfile::tempdir().unwrap();
+        let cel_path = dir.path().join("measurements.cel.json");
+        let log_path = dir.path().join("eventlog");
+        let config: Config = toml::from_str(&format!(
+            r#"
+            tenant = "acme"
+
+            [path_aliases]
+            "/mnt/nvme0/models" = "models://"
+
+            [local_event_log]
+            enable = true
+            path = {:?}
+            forward_to_aa = false
+
+            [[sinks]]
+            kind = "cel"
+            path = {:?}
+            hash_algorithm = "sha384"
+            "#,
+            log_path, cel_path
+        ))
+        .unwrap();
+        let aa_client = Arc::new(AAClient::from_config(&config).await.unwrap());
+        let sink = sink::from_config(&config, aa_client.clone()).unwrap();
+        sink.extend("model_dir", "/mnt/nvme0/models/llama", "sha256:ab12", None)
+            .await
+            .unwrap();
+
+        let records = aa_client.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].domain, "acme/model_dir");
+        assert_eq!(records[0].operation, "models://llama");
+
+        let line = fs::read_to_string(&log_path).unwrap();
+        let (_, event) = line.trim_end().split_once(' ').unwrap();
+        assert_eq!(event, "acme/model_dir models://llama sha256:ab12");
+        let (_, expected) = tpm::event_digest(event.as_bytes(), "sha384").unwrap();
+
+        let cel = fs::read_to_string(&cel_path).unwrap();
+        let record: CelRecord = serde_json::from_str(cel.trim_end()).unwrap();
+        assert_eq!(record.recnum, 0);
+        assert_eq!(record.pcr, 18);
+        assert_eq!(record.digests.len(), 1);
+        assert_eq!(record.digests[0].hash_alg, "sha384");
+        assert_eq!(record.digests[0].digest, hex::encode(expected));
+        assert_eq!(record.content.domain, records[0].domain);
+        assert_eq!(record.content.operation, records[0].operation);
+        assert_eq!(record.content.content, records[0].content);
+    }
+}
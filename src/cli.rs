@@ -0,0 +1,534 @@
+// src/cli.rs
+use clap::error::ErrorKind;
+use clap::{ArgGroup, Args, CommandFactory, Parser, Subcommand};
+use measurement_tool::config::ConfigFormat;
+use measurement_tool::rvps::ManifestFormat;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Measures files, model directories, containers and the platform into the
+/// Attestation Agent.
+///
+/// Without a command, or with only a config path as in
+/// `measurement_tool /etc/measurement_tool/config.toml`, it measures and then
+/// watches the config, unless `one_shot` is set.
+#[derive(Debug, Parser)]
+#[command(name = "measurement_tool", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+    /// Config file of the legacy `measurement_tool CONFIG` form.
+    #[arg(value_name = "CONFIG", value_parser = legacy_config_path)]
+    config_path: Option<PathBuf>,
+    /// With the measuring commands (the default one, run, one-shot, daemon
+    /// and measure), log what would be extended instead of connecting to the
+    /// Attestation Agent.
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// With the same commands, extend measurements that state_path shows as
+    /// already extended in this boot.
+    #[arg(long, global = true)]
+    force_remeasure: bool,
+    #[command(flatten)]
+    options: ConfigOptions,
+}
+
+/// How the config is loaded. Accepted before and after the command, and
+/// applied in command-line order.
+#[derive(Debug, Args)]
+struct ConfigOptions {
+    /// Override a config key, e.g. --set file_measurement.enable=true; wins
+    /// over MEASURER_* environment variables and the file.
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    overrides: Vec<String>,
+    /// Format of the config file (toml, json or yaml), instead of detecting
+    /// it by its extension.
+    #[arg(long, value_name = "FORMAT", value_parser = config_format)]
+    config_format: Option<ConfigFormat>,
+}
+
+/// The optional config path most commands take, with the config options.
+#[derive(Debug, Args)]
+struct ConfigArg {
+    #[arg(value_name = "CONFIG")]
+    config_path: Option<PathBuf>,
+    #[command(flatten)]
+    options: ConfigOptions,
+}
+
+#[derive(Debug, Subcommand)]
+enum CliCommand {
+    /// Measure, then watch the config in the foreground.
+    Run(ConfigArg),
+    /// Measure once and exit.
+    OneShot(ConfigArg),
+    /// Validate the config and print the effective settings.
+    CheckConfig(ConfigArg),
+    /// Measure the given files and directories once.
+    #[command(group(ArgGroup::new("targets").required(true).multiple(true)))]
+    Measure {
+        #[arg(long = "file", value_name = "PATH", group = "targets")]
+        files: Vec<PathBuf>,
+        #[arg(long = "dir", value_name = "PATH", group = "targets")]
+        dirs: Vec<PathBuf>,
+        #[command(flatten)]
+        config: ConfigArg,
+    },
+    /// Run as a daemon, detaching unless --foreground.
+    Daemon {
+        #[arg(long, value_name = "PATH")]
+        pidfile: Option<PathBuf>,
+        #[arg(long)]
+        foreground: bool,
+        #[command(flatten)]
+        config: ConfigArg,
+    },
+    /// Compare two exported summary reports.
+    Diff { old: PathBuf, new: PathBuf },
+    /// Push unextended journal entries to the backend.
+    Replay {
+        #[arg(long = "journal", value_name = "PATH")]
+        journal_path: Option<PathBuf>,
+        #[command(flatten)]
+        config: ConfigArg,
+    },
+    /// Run as a containerd NRI plugin measuring new containers.
+    Nri(ConfigArg),
+    /// OCI hook: measure the bundle described on stdin.
+    Hook(ConfigArg),
+    /// Measure containers from the Docker engine event stream.
+    Docker(ConfigArg),
+    /// Reconcile MeasurementPolicy objects for this node.
+    Controller(ConfigArg),
+    /// Kata / peer-pods guest profile.
+    Kata(ConfigArg),
+    /// Register the dry-run measurement set with Trustee RVPS.
+    ProvisionRvps(ConfigArg),
+    /// Write the dry-run measurement set as a reference value manifest.
+    ExportReferenceValues {
+        /// rvps, json or toml.
+        #[arg(long, default_value = "rvps", value_parser = manifest_format)]
+        format: ManifestFormat,
+        #[arg(long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        config: ConfigArg,
+    },
+    /// Compare the current measurement set with a manifest.
+    Verify {
+        #[arg(long, value_name = "PATH")]
+        reference: PathBuf,
+        #[arg(long)]
+        extend_failure: bool,
+        #[command(flatten)]
+        config: ConfigArg,
+    },
+    /// Show every measurer and its settings.
+    ListMeasurers(ConfigArg),
+    /// Diagnose the environment and configuration.
+    Doctor(ConfigArg),
+}
+
+/// A parsed command line: the command and the config options that apply to
+/// every command.
+#[derive(Debug)]
+pub struct Invocation {
+    pub command: Command,
+    /// `KEY=VALUE` pairs given with `--set`.
+    pub overrides: Vec<String>,
+    pub config_format: Option<ConfigFormat>,
+}
+
+#[derive(Debug)]
+pub enum Command {
+    /// Default behavior: run the measurers, then keep watching the config.
+    Run {
+        config_path: Option<PathBuf>,
+        dry_run: bool,
+        force_remeasure: bool,
+    },
+    /// Run the measurers once and exit, whatever `one_shot` says.
+    OneShot {
+        config_path: Option<PathBuf>,
+        dry_run: bool,
+        force_remeasure: bool,
+    },
+    /// Explicit daemon mode: ignores `one_shot` and optionally detaches and
+    /// maintains a pidfile.
+    Daemon {
+        config_path: Option<PathBuf>,
+        pidfile: Option<PathBuf>,
+        foreground: bool,
+        dry_run: bool,
+        force_remeasure: bool,
+    },
+    /// Load and validate the configuration and print its effective values.
+    CheckConfig { config_path: Option<PathBuf> },
+    /// Measure the given files and model directories once with the settings
+    /// of `[file_measurement]` and `[model_dir_measurement]`.
+    Measure {
+        config_path: Option<PathBuf>,
+        files: Vec<PathBuf>,
+        dirs: Vec<PathBuf>,
+        dry_run: bool,
+        force_remeasure: bool,
+    },
+    /// Compare two summary reports and print added/removed/changed entries.
+    Diff { old: PathBuf, new: PathBuf },
+    /// Re-send journaled measurements that never reached the backend.
+    Replay {
+        config_path: Option<PathBuf>,
+        journal_path: Option<PathBuf>,
+    },
+    /// Register with containerd over NRI and measure containers as they are created.
+    Nri { config_path: Option<PathBuf> },
+    /// OCI createRuntime/poststart hook reading the container state from stdin.
+    Hook { config_path: Option<PathBuf> },
+    /// Follow Docker engine events and measure containers as they are created.
+    Docker { config_path: Option<PathBuf> },
+    /// Kubernetes controller applying MeasurementPolicy objects to this node.
+    Controller { config_path: Option<PathBuf> },
+    /// Run inside a Kata guest: discover the AA and measure pod volumes.
+    Kata { config_path: Option<PathBuf> },
+    /// Compute the measurement set without extending and register it as
+    /// reference values with Trustee RVPS.
+    ProvisionRvps { config_path: Option<PathBuf> },
+    /// Compute the measurement set without extending and write it as a
+    /// reference value manifest, to `output` or stdout.
+    ExportReferenceValues {
+        config_path: Option<PathBuf>,
+        format: ManifestFormat,
+        output: Option<PathBuf>,
+    },
+    /// Compare the dry-run measurement set with a reference manifest and
+    /// print the differences as JSON.
+    Verify {
+        config_path: Option<PathBuf>,
+        reference: PathBuf,
+        extend_failure: bool,
+    },
+    /// Print the registered measurers with their enable state and targets.
+    ListMeasurers { config_path: Option<PathBuf> },
+    /// Check the environment for common problems and print findings.
+    Doctor { config_path: Option<PathBuf> },
+}
+
+/// Parses the command line, including the program name.
+///
+/// A bare path as the first argument is still accepted as the config file so
+/// that existing service units keep working, but only if it looks like one
+/// (see [`looks_like_path`]): a mistyped command is an error rather than a
+/// config path. `--dry-run`, `--force-remeasure`, `--set` and
+/// `--config-format` may appear anywhere. Help and version requests are
+/// returned as errors too; `clap::Error::exit` prints them.
+pub fn parse<I, T>(args: I) -> Result<Invocation, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let mut cli = Cli::try_parse_from(args)?;
+    // `--set` is not a global option: clap would keep only the values given
+    // after the command. Both sets are collected here instead.
+    let mut overrides = cli.options.overrides;
+    let mut config_format = cli.options.config_format;
+    if let Some(config) = cli.command.as_mut().and_then(CliCommand::config_arg) {
+        overrides.append(&mut config.options.overrides);
+        config_format = config.options.config_format.or(config_format);
+    }
+    let (dry_run, force_remeasure) = (cli.dry_run, cli.force_remeasure);
+    let measuring = matches!(
+        cli.command,
+        None | Some(CliCommand::Run(_))
+            | Some(CliCommand::OneShot(_))
+            | Some(CliCommand::Measure { .. })
+            | Some(CliCommand::Daemon { .. })
+    );
+    for (set, flag) in [
+        (dry_run, "--dry-run"),
+        (force_remeasure, "--force-remeasure"),
+    ] {
+        if set && !measuring {
+            return Err(Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                format!("{} only applies to run, one-shot, daemon and measure", flag),
+            ));
+        }
+    }
+
+    let command = match cli.command {
+        None => Command::Run {
+            config_path: cli.config_path,
+            dry_run,
+            force_remeasure,
+        },
+        Some(CliCommand::Run(config)) => Command::Daemon {
+            config_path: config.config_path,
+            pidfile: None,
+            foreground: true,
+            dry_run,
+            force_remeasure,
+        },
+        Some(CliCommand::OneShot(config)) => Command::OneShot {
+            config_path: config.config_path,
+            dry_run,
+            force_remeasure,
+        },
+        Some(CliCommand::CheckConfig(config)) => Command::CheckConfig {
+            config_path: config.config_path,
+        },
+        Some(CliCommand::Measure {
+            files,
+            dirs,
+            config,
+        }) => Command::Measure {
+            config_path: config.config_path,
+            files,
+            dirs,
+            dry_run,
+            force_remeasure,
+        },
+        Some(CliCommand::Daemon {
+            pidfile,
+            foreground,
+            config,
+        }) => Command::Daemon {
+            config_path: config.config_path,
+            pidfile,
+            foreground,
+            dry_run,
+            force_remeasure,
+        },
+        Some(CliCommand::Diff { old, new }) => Command::Diff { old, new },
+        Some(CliCommand::Replay {
+            journal_path,
+            config,
+        }) => Command::Replay {
+            config_path: config.config_path,
+            journal_path,
+        },
+        Some(CliCommand::Nri(config)) => Command::Nri {
+            config_path: config.config_path,
+        },
+        Some(CliCommand::Hook(config)) => Command::Hook {
+            config_path: config.config_path,
+        },
+        Some(CliCommand::Docker(config)) => Command::Docker {
+            config_path: config.config_path,
+        },
+        Some(CliCommand::Controller(config)) => Command::Controller {
+            config_path: config.config_path,
+        },
+        Some(CliCommand::Kata(config)) => Command::Kata {
+            config_path: config.config_path,
+        },
+        Some(CliCommand::ProvisionRvps(config)) => Command::ProvisionRvps {
+            config_path: config.config_path,
+        },
+        Some(CliCommand::ExportReferenceValues {
+            format,
+            output,
+            config,
+        }) => Command::ExportReferenceValues {
+            config_path: config.config_path,
+            format,
+            output,
+        },
+        Some(CliCommand::Verify {
+            reference,
+            extend_failure,
+            config,
+        }) => Command::Verify {
+            config_path: config.config_path,
+            reference,
+            extend_failure,
+        },
+        Some(CliCommand::ListMeasurers(config)) => Command::ListMeasurers {
+            config_path: config.config_path,
+        },
+        Some(CliCommand::Doctor(config)) => Command::Doctor {
+            config_path: config.config_path,
+        },
+    };
+    Ok(Invocation {
+        command,
+        overrides,
+        config_format,
+    })
+}
+
+impl CliCommand {
+    /// The config path and options of the command; `diff` loads no config.
+    fn config_arg(&mut self) -> Option<&mut ConfigArg> {
+        match self {
+            CliCommand::Diff { .. } => None,
+            CliCommand::Run(config)
+            | CliCommand::OneShot(config)
+            | CliCommand::CheckConfig(config)
+            | CliCommand::Nri(config)
+            | CliCommand::Hook(config)
+            | CliCommand::Docker(config)
+            | CliCommand::Controller(config)
+            | CliCommand::Kata(config)
+            | CliCommand::ProvisionRvps(config)
+            | CliCommand::ListMeasurers(config)
+            | CliCommand::Doctor(config)
+            | CliCommand::Measure { config, .. }
+            | CliCommand::Daemon { config, .. }
+            | CliCommand::Replay { config, .. }
+            | CliCommand::ExportReferenceValues { config, .. }
+            | CliCommand::Verify { config, .. } => Some(config),
+        }
+    }
+}
+
+/// Accepts the config path of the legacy form only if it looks like one, so
+/// that a mistyped command is not taken for a config file.
+fn legacy_config_path(arg: &str) -> Result<PathBuf, String> {
+    if looks_like_path(arg) {
+        Ok(PathBuf::from(arg))
+    } else {
+        Err(format!("'{}' is neither a command nor a config path", arg))
+    }
+}
+
+/// Whether the first argument can be the config file of the legacy
+/// `measurement_tool CONFIG` form: it names a directory or has an extension,
+/// as in `/etc/measurement_tool/config.toml` or `config.yaml`, or is an
+/// existing file.
+fn looks_like_path(arg: &str) -> bool {
+    let path = Path::new(arg);
+    !arg.starts_with('-') && (arg.contains('/') || path.extension().is_some() || path.is_file())
+}
+
+fn config_format(name: &str) -> Result<ConfigFormat, String> {
+    ConfigFormat::from_name(name).ok_or_else(|| format!("unknown config format '{}'", name))
+}
+
+fn manifest_format(name: &str) -> Result<ManifestFormat, String> {
+    ManifestFormat::from_name(name).ok_or_else(|| format!("unknown manifest format '{}'", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Invocation, clap::Error> {
+        parse(std::iter::once("measurement_tool").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn accepts_a_bare_config_path() {
+        for path in ["/etc/measurement_tool/config.toml", "config.yaml", "./conf"] {
+            match parse_args(&[path]).unwrap().command {
+                Command::Run { config_path, .. } => {
+                    assert_eq!(config_path, Some(PathBuf::from(path)))
+                }
+                other => panic!("{} parsed as {:?}", path, other),
+            }
+        }
+        assert!(matches!(
+            parse_args(&[]).unwrap().command,
+            Command::Run {
+                config_path: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_commands_and_options() {
+        let error = parse_args(&["on-shot"]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ValueValidation);
+        assert!(error.to_string().contains("'on-shot'"), "{}", error);
+        let error = parse_args(&["--dryrun"]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnknownArgument);
+        assert!(parse_args(&["nri", "--foreground"]).is_err());
+    }
+
+    #[test]
+    fn set_does_not_take_a_flag_as_its_value() {
+        assert!(parse_args(&["one-shot", "--set", "--dry-run"]).is_err());
+
+        let invocation = parse_args(&[
+            "--set",
+            "file_measurement.enable=true",
+            "one-shot",
+            "--dry-run",
+            "--set=tenant=acme",
+        ])
+        .unwrap();
+        assert_eq!(
+            invocation.overrides,
+            ["file_measurement.enable=true", "tenant=acme"]
+        );
+        assert!(matches!(
+            invocation.command,
+            Command::OneShot { dry_run: true, .. }
+        ));
+    }
+
+    #[test]
+    fn options_require_values() {
+        assert!(parse_args(&["measure", "--file"]).is_err());
+        assert!(parse_args(&["measure", "config.toml"]).is_err());
+        assert!(parse_args(&["daemon", "--pidfile", "--foreground"]).is_err());
+        assert!(parse_args(&["--config-format", "--dry-run"]).is_err());
+        assert!(parse_args(&["verify", "config.toml"]).is_err());
+    }
+
+    #[test]
+    fn measuring_flags_only_apply_to_measuring_commands() {
+        let error = parse_args(&["--dry-run", "diff", "a.json", "b.json"]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+        assert!(matches!(
+            parse_args(&["--force-remeasure", "run"]).unwrap().command,
+            Command::Daemon {
+                foreground: true,
+                force_remeasure: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parses_command_options() {
+        match parse_args(&[
+            "export-reference-values",
+            "--format",
+            "json",
+            "--output",
+            "rv.json",
+            "config.toml",
+        ])
+        .unwrap()
+        .command
+        {
+            Command::ExportReferenceValues {
+                config_path,
+                format,
+                output,
+            } => {
+                assert_eq!(config_path, Some(PathBuf::from("config.toml")));
+                assert_eq!(format, ManifestFormat::Json);
+                assert_eq!(output, Some(PathBuf::from("rv.json")));
+            }
+            other => panic!("parsed as {:?}", other),
+        }
+        assert!(parse_args(&["export-reference-values", "--format", "xml"]).is_err());
+        match parse_args(&["measure", "--file", "/etc/a", "--dir", "/models/m"])
+            .unwrap()
+            .command
+        {
+            Command::Measure { files, dirs, .. } => {
+                assert_eq!(files, [PathBuf::from("/etc/a")]);
+                assert_eq!(dirs, [PathBuf::from("/models/m")]);
+            }
+            other => panic!("parsed as {:?}", other),
+        }
+    }
+}
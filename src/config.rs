@@ -1,35 +1,585 @@
 // src/config.rs
+use crate::schedule::BlackoutSchedule;
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Prefix of the environment variables that override config keys.
+const ENV_PREFIX: &str = "MEASURER_";
+/// Short environment variable names, without the prefix, for keys that are
+/// often set per node.
+const ENV_ALIASES: [(&str, &str); 1] = [("AA_SOCKET", "attestation_agent_socket")];
+
+/// `key=value` pairs given with `--set`, applied on every load.
+static CLI_OVERRIDES: OnceLock<Vec<(String, String)>> = OnceLock::new();
+/// Extensions of the files read from a config directory.
+const CONFIG_EXTENSIONS: [&str; 4] = ["toml", "json", "yaml", "yml"];
+/// Format of the main config file given with `--config-format`.
+static CONFIG_FORMAT: OnceLock<ConfigFormat> = OnceLock::new();
+
+/// Languages a config file or fragment can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "toml" => Some(ConfigFormat::Toml),
+            "json" => Some(ConfigFormat::Json),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// The format named by the extension of `path`, TOML for any other.
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|ext| Self::from_name(&ext.to_string_lossy().to_lowercase()))
+            .unwrap_or(ConfigFormat::Toml)
+    }
+
+    /// Parses `content` into the TOML value tree that includes, overrides
+    /// and deserialization work on.
+    fn parse(self, content: &str) -> Result<toml::Value> {
+        let document = match self {
+            ConfigFormat::Toml => return Ok(toml::from_str(content)?),
+            ConfigFormat::Json => serde_json::from_str(content)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(content)?,
+        };
+        match json_to_toml(document)? {
+            Some(value @ toml::Value::Table(_)) => Ok(value),
+            None => Ok(toml::Value::Table(toml::Table::new())),
+            Some(_) => anyhow::bail!("the document is not a mapping"),
+        }
+    }
+}
+
+/// Converts a JSON or YAML document to TOML values. TOML has no null, so a
+/// null is left out as if the key were absent.
+fn json_to_toml(value: serde_json::Value) -> Result<Option<toml::Value>> {
+    use serde_json::Value;
+    Ok(Some(match value {
+        Value::Null => return Ok(None),
+        Value::Bool(b) => toml::Value::Boolean(b),
+        Value::Number(n) => match (n.as_i64(), n.is_u64()) {
+            (Some(i), _) => toml::Value::Integer(i),
+            (None, true) => anyhow::bail!("{} is out of range", n),
+            (None, false) => toml::Value::Float(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => toml::Value::String(s),
+        Value::Array(items) => {
+            let mut array = Vec::new();
+            for item in items {
+                array.extend(json_to_toml(item)?);
+            }
+            toml::Value::Array(array)
+        }
+        Value::Object(map) => {
+            let mut table = toml::Table::new();
+            for (key, item) in map {
+                if let Some(item) = json_to_toml(item)? {
+                    table.insert(key, item);
+                }
+            }
+            toml::Value::Table(table)
+        }
+    }))
+}
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum MeasurementChannel {
     UnixSocket,
     HttpApi,
+    /// ttrpc over vsock to `aa_vsock_cid`:`aa_vsock_port`, for peer-pod and
+    /// confidential VM setups.
+    Vsock,
+    /// Extends a PCR of the (v)TPM at `[tpm].device` directly, for hosts
+    /// without an Attestation Agent. The local event log is always written.
+    Tpm,
+}
+
+/// One measurement backend, e.g. the primary or the standby Attestation Agent.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AaEndpoint {
+    /// Recorded with every measurement the backend took; defaults to the
+    /// address.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub channel: MeasurementChannel,
+    /// ttrpc socket, HTTP base URL, `vsock://<cid>:<port>`, local log path
+    /// or TPM device, depending on `channel`.
+    pub address: String,
+    /// TLS and authentication of an `http_api` backend; defaults to
+    /// `[aa_http]`.
+    #[serde(default)]
+    pub http: Option<AaHttpConfig>,
+    /// PCR and bank of a `tpm` backend; defaults to `[tpm]`.
+    #[serde(default)]
+    pub tpm: Option<TpmConfig>,
+    /// Request timeout of a `unix_socket` or `vsock` backend; defaults to
+    /// `aa_ttrpc_timeout_ms`.
+    #[serde(default)]
+    pub ttrpc_timeout_ms: Option<u64>,
+}
+
+impl AaEndpoint {
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.address)
+    }
+}
+
+/// The (v)TPM extended by `tpm` backends.
+#[derive(Debug, Deserialize, Clone)]
+pub struct TpmConfig {
+    /// TPM character device; the resource manager allows concurrent users.
+    #[serde(default = "default_tpm_device")]
+    pub device: String,
+    /// PCR extended by measurements without a `pcr_index` of their own.
+    #[serde(default = "default_tpm_pcr_index")]
+    pub pcr_index: u32,
+    /// PCR bank extended, `sha256` or `sha384`.
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+}
+
+/// TLS, authentication and timeouts of `http_api` backends, for reaching a
+/// trustiflux API server across a network boundary.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AaHttpConfig {
+    /// PEM CA bundle trusted for the server certificate, in addition to the
+    /// built-in roots.
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// PEM client certificate chain and private key presented for mutual
+    /// TLS; set both or neither.
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    /// File holding a bearer token sent as `Authorization` with every
+    /// request.
+    #[serde(default)]
+    pub token_path: Option<String>,
+    /// Limit on a whole request, response body included.
+    #[serde(default = "default_aa_http_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_aa_http_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     #[serde(default = "default_false")]
     pub one_shot: bool,
+    /// Compute every measurement and log what would be extended, without
+    /// connecting to the Attestation Agent.
+    #[serde(default = "default_false")]
+    pub dry_run: bool,
+    /// `json` writes every log line, audit records included, as a JSON
+    /// object.
+    #[serde(default = "default_log_format")]
+    pub log_format: LogFormat,
+    /// Treat measurers whose failure policy is `continue` as `fail_at_end`.
+    #[serde(default = "default_false")]
+    pub strict: bool,
+    /// Failure policy of each measurer, keyed by its config section, e.g.
+    /// `file_measurement = "fail_fast"`. Unlisted measurers use `continue`.
+    #[serde(default)]
+    pub failure_policies: BTreeMap<String, FailurePolicy>,
     #[serde(default = "default_attestation_agent_socket")]
     pub attestation_agent_socket: String,
     #[serde(default)]
     pub trustiflux_api_endpoint: Option<String>,
+    #[serde(default)]
+    pub aa_http: AaHttpConfig,
+    #[serde(default)]
+    pub tpm: TpmConfig,
     #[serde(default = "default_aa_channel")]
     pub aa_channel: MeasurementChannel,
+    /// Context ID of the VM running the Attestation Agent, used when
+    /// `aa_channel = "vsock"`.
+    #[serde(default)]
+    pub aa_vsock_cid: Option<u32>,
+    #[serde(default = "default_aa_vsock_port")]
+    pub aa_vsock_port: u32,
+    /// Limit on one ttrpc request to the Attestation Agent.
+    #[serde(default = "default_aa_ttrpc_timeout_ms")]
+    pub aa_ttrpc_timeout_ms: u64,
+    /// Backends tried in order, failing over when one cannot be reached.
+    /// When empty, the single backend selected by `aa_channel` is used.
+    #[serde(default)]
+    pub aa_endpoints: Vec<AaEndpoint>,
+    /// Tenant prefixed onto every measurement domain, e.g. `acme/file`.
+    #[serde(default)]
+    pub tenant: Option<String>,
+    /// Logical names substituted for filesystem prefixes in operations, e.g.
+    /// `"/mnt/nvme3/models" = "models"`, so that node-specific mount points
+    /// do not leak into verifier policies.
+    #[serde(default)]
+    pub path_aliases: BTreeMap<String, String>,
+    /// How many files or directories a measurer hashes at once.
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// How the config and content watchers learn about file changes.
+    #[serde(default = "default_watch_backend")]
+    pub watch_backend: WatchBackend,
+    #[serde(default = "default_watch_poll_interval_secs")]
+    pub watch_poll_interval_secs: u64,
+    /// Have the content watchers hash polled files instead of comparing
+    /// their size and modification time only. The config watcher always
+    /// does.
+    #[serde(default = "default_false")]
+    pub watch_poll_compare_contents: bool,
+    /// Register for the `measurement_policy` events extended on every
+    /// accepted config reload.
+    #[serde(default)]
+    pub policy_pcr_index: Option<u32>,
+    /// Hash structured content (container specs, OCI bundle configs, the
+    /// summary report bound into evidence) in canonical JSON.
+    #[serde(default = "default_false")]
+    pub structured_content: bool,
+    /// Where to write the JSON summary report after the initial run.
+    #[serde(default)]
+    pub summary_report_path: Option<String>,
+    /// Append-only journal of extensions, used by the `replay` subcommand.
+    #[serde(default)]
+    pub journal_path: Option<String>,
+    /// Remembers what was extended in this boot, so that a restart skips
+    /// measurements whose content has not changed.
+    #[serde(default)]
+    pub state_path: Option<String>,
+    /// Extend every measurement even if `state_path` shows it unchanged.
+    /// Set with `--force-remeasure`.
+    #[serde(skip)]
+    pub force_remeasure: bool,
+    #[serde(default)]
+    pub content_limits: ContentLimitsConfig,
+    #[serde(default)]
+    pub aa_retry: AaRetryConfig,
+    #[serde(default)]
+    pub aa_batch: AaBatchConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub local_event_log: LocalEventLogConfig,
+    /// Further sinks every measurement is recorded in, next to the
+    /// Attestation Agent.
+    #[serde(default)]
+    pub sinks: Vec<SinkConfig>,
+    #[serde(default)]
+    pub evidence_bundle: EvidenceBundleConfig,
     #[serde(default)]
     pub file_measurement: FileMeasurementConfig,
     #[serde(default)]
     pub model_dir_measurement: ModelDirMeasurementConfig,
-    // Add other measurement configs here as they are implemented
-    // pub process_measurement: ProcessMeasurementConfig,
+    #[serde(default)]
+    pub dataset_measurement: DatasetMeasurementConfig,
+    #[serde(default)]
+    pub dir_tree_measurement: DirTreeMeasurementConfig,
+    #[serde(default)]
+    pub object_measurement: ObjectMeasurementConfig,
+    #[serde(default)]
+    pub group_measurement: GroupMeasurementConfig,
+    #[serde(default)]
+    pub fs_image_measurement: FsImageMeasurementConfig,
+    #[serde(default)]
+    pub vmm_measurement: VmmMeasurementConfig,
+    #[serde(default)]
+    pub efi_measurement: EfiMeasurementConfig,
+    #[serde(default)]
+    pub grub_measurement: GrubMeasurementConfig,
+    #[serde(default)]
+    pub process_measurement: ProcessMeasurementConfig,
+    #[serde(default)]
+    pub kernel_state_measurement: KernelStateMeasurementConfig,
+    #[serde(default)]
+    pub device_measurement: DeviceMeasurementConfig,
+    #[serde(default)]
+    pub mount_measurement: MountMeasurementConfig,
+    #[serde(default)]
+    pub network_measurement: NetworkMeasurementConfig,
+    #[serde(default)]
+    pub sbom_measurement: SbomMeasurementConfig,
+    #[serde(default)]
+    pub exec_measurement: ExecMeasurementConfig,
+    #[serde(default)]
+    pub container_image_measurement: ContainerImageMeasurementConfig,
+    #[serde(default)]
+    pub nri_measurement: NriMeasurementConfig,
+    #[serde(default)]
+    pub hook_measurement: HookMeasurementConfig,
+    #[serde(default)]
+    pub docker_measurement: DockerMeasurementConfig,
+    #[serde(default)]
+    pub kubernetes: KubernetesConfig,
+    #[serde(default)]
+    pub kata: KataConfig,
+    #[serde(default)]
+    pub rvps: RvpsConfig,
+    #[serde(default)]
+    pub api: ApiConfig,
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
+    #[serde(default)]
+    pub reload: ReloadConfig,
+    #[serde(default)]
+    pub watcher_supervision: WatcherSupervisionConfig,
+    #[serde(default)]
+    pub status: StatusConfig,
+    #[serde(default)]
+    pub control: ControlConfig,
+}
+
+/// How the measurement run reacts to a measurer that fails.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Log the failure and carry on; only critical entries fail the run.
+    Continue,
+    /// Stop the run at this measurer and fail it.
+    FailFast,
+    /// Run the remaining measurers, then fail the run.
+    FailAtEnd,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceKind {
+    /// Raw TEE evidence from `GetEvidence`, bound to the summary report.
+    Evidence,
+    /// An attestation token from `GetToken`.
+    Token,
 }
 
+/// Content larger than `max_content_bytes` is stored under `store_dir` and
+/// extended as a digest-plus-locator descriptor.
 #[derive(Debug, Deserialize, Clone)]
+pub struct ContentLimitsConfig {
+    #[serde(default = "default_max_content_bytes")]
+    pub max_content_bytes: usize,
+    #[serde(default = "default_content_store_dir")]
+    pub store_dir: String,
+}
+
+/// Retrying extensions while the Attestation Agent is unreachable, and
+/// buffering them once retries are exhausted.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AaRetryConfig {
+    /// Further attempts after the first one failed to reach any backend.
+    #[serde(default = "default_aa_max_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each further retry.
+    #[serde(default = "default_aa_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    #[serde(default = "default_aa_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Extensions held while no backend is reachable; 0 disables queueing,
+    /// and extensions fail once retries are exhausted.
+    #[serde(default)]
+    pub queue_size: usize,
+    /// Keep the queue in this file so that it survives a restart.
+    #[serde(default)]
+    pub queue_path: Option<String>,
+    /// How often the daemon tries to flush a non-empty queue.
+    #[serde(default = "default_aa_flush_interval_secs")]
+    pub flush_interval_secs: u64,
+    /// How long startup keeps trying to reach a backend, for an Attestation
+    /// Agent that comes up after the tool at boot; 0 fails at once.
+    #[serde(default = "default_aa_startup_timeout_secs")]
+    pub startup_timeout_secs: u64,
+}
+
+/// Sending the measurements of one measurer run in batches instead of one
+/// round-trip at a time.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AaBatchConfig {
+    /// Measurements journaled and sent together.
+    #[serde(default = "default_aa_batch_size")]
+    pub batch_size: usize,
+    /// Extensions of different domains sent concurrently; those of one
+    /// domain are always sent in order. 1 sends everything in order.
+    #[serde(default = "default_aa_max_in_flight")]
+    pub max_in_flight: usize,
+    /// Send each batch to `http_api` backends as one request to
+    /// `/aa/aael/batch`, falling back to single requests when the backend
+    /// lacks it.
+    #[serde(default = "default_false")]
+    pub batch_api: bool,
+}
+
+/// Limits on how much is extended, protecting the Attestation Agent and its
+/// event log from a misconfigured glob.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RateLimitConfig {
+    /// Extensions per second, with bursts of up to one second's worth;
+    /// 0 is unlimited.
+    #[serde(default)]
+    pub max_events_per_sec: u32,
+    /// Extensions one measurement run may make before it is aborted; 0 is
+    /// unlimited.
+    #[serde(default = "default_max_events_per_run")]
+    pub max_events_per_run: u64,
+}
+
+/// A local copy of every measurement in the Attestation Agent event log
+/// (AAEL) format.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LocalEventLogConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default = "default_local_event_log_path")]
+    pub path: String,
+    /// Also extend measurements through the Attestation Agent; when false the
+    /// event log is the only sink.
+    #[serde(default = "default_true")]
+    pub forward_to_aa: bool,
+}
+
+/// An additional measurement sink, selected by `kind`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SinkConfig {
+    /// Appends each measurement to `path` as a TCG Canonical Event Log
+    /// record.
+    Cel {
+        path: String,
+        #[serde(default = "default_cel_format")]
+        format: CelFormat,
+        /// Bank of the event digest of each record.
+        #[serde(default = "default_hash_algorithm")]
+        hash_algorithm: String,
+        /// Register of measurements that do not name one.
+        #[serde(default = "default_pcr_index")]
+        pcr_index: u32,
+    },
+    /// Only logs each measurement.
+    DryRun,
+}
+
+/// Encoding of a Canonical Event Log sink.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CelFormat {
+    /// One CEL-JSON record per line.
+    Json,
+    /// A sequence of CBOR-encoded records.
+    Cbor,
+}
+
+/// Evidence bundle assembled after a successful initial run.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EvidenceBundleConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default = "default_evidence_kind")]
+    pub kind: EvidenceKind,
+    #[serde(default = "default_token_type")]
+    pub token_type: String,
+    #[serde(default)]
+    pub output_path: Option<String>,
+    /// The bundle is POSTed here as JSON.
+    #[serde(default)]
+    pub upload_url: Option<String>,
+    /// Number of trailing journal entries included as the audit log excerpt.
+    #[serde(default = "default_audit_log_entries")]
+    pub audit_log_entries: usize,
+}
+
+/// A file pattern or directory to measure, written either as a plain string
+/// or as `{ path = "...", critical = true }`. Failing to measure a critical
+/// entry fails the run; other entries are best-effort.
+///
+/// File measurement entries may also set `pcr_index` and `hash_algorithm`,
+/// overriding the `[file_measurement]` values for the files they match;
+/// `pattern` is accepted in place of `path`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum MeasurementEntry {
+    Path(String),
+    Detailed {
+        #[serde(alias = "pattern")]
+        path: String,
+        #[serde(default)]
+        critical: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pcr_index: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        hash_algorithm: Option<String>,
+    },
+}
+
+impl MeasurementEntry {
+    pub fn path(&self) -> &str {
+        match self {
+            MeasurementEntry::Path(path) | MeasurementEntry::Detailed { path, .. } => path,
+        }
+    }
+
+    pub fn is_critical(&self) -> bool {
+        matches!(self, MeasurementEntry::Detailed { critical: true, .. })
+    }
+
+    pub fn pcr_index(&self) -> Option<u32> {
+        match self {
+            MeasurementEntry::Detailed { pcr_index, .. } => *pcr_index,
+            MeasurementEntry::Path(_) => None,
+        }
+    }
+
+    pub fn hash_algorithm(&self) -> Option<&str> {
+        match self {
+            MeasurementEntry::Detailed { hash_algorithm, .. } => hash_algorithm.as_deref(),
+            MeasurementEntry::Path(_) => None,
+        }
+    }
+
+    /// The same entry for another pattern, keeping its settings.
+    pub fn with_path(&self, path: String) -> Self {
+        match self {
+            MeasurementEntry::Path(_) => MeasurementEntry::Path(path),
+            MeasurementEntry::Detailed {
+                critical,
+                pcr_index,
+                hash_algorithm,
+                ..
+            } => MeasurementEntry::Detailed {
+                path,
+                critical: *critical,
+                pcr_index: *pcr_index,
+                hash_algorithm: hash_algorithm.clone(),
+            },
+        }
+    }
+}
+
+impl From<String> for MeasurementEntry {
+    fn from(path: String) -> Self {
+        MeasurementEntry::Path(path)
+    }
+}
+
+/// What the file measurer does with matches reached through a symbolic
+/// link.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SymlinkPolicy {
+    /// Measure the target, recorded under the matched path.
+    Follow,
+    /// Skip the match; a critical entry fails.
+    Deny,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct FileMeasurementConfig {
     #[serde(default = "default_false")]
     pub enable: bool,
@@ -38,74 +588,2093 @@ pub struct FileMeasurementConfig {
     #[serde(default = "default_hash_algorithm")]
     pub hash_algorithm: String, // e.g., "sha256", "sha384"
     #[serde(default)]
-    pub files: Vec<String>,
+    pub files: Vec<MeasurementEntry>,
+    /// Extend canonical JSON with the digest and the `security.ima` /
+    /// `security.evm` signature type and key id instead of the bare digest.
+    #[serde(default = "default_false")]
+    pub ima_signatures: bool,
+    /// For `.tar`, `.tar.gz`/`.tgz` and `.zip` files, also extend the digest
+    /// of every member under the `archive_member` domain.
+    #[serde(default = "default_false")]
+    pub archive_members: bool,
+    /// Docker Engine socket used to resolve container names in
+    /// `container://<name-or-id>/<path>` entries.
+    #[serde(default = "default_docker_socket_path")]
+    pub docker_socket_path: String,
+    /// In daemon mode, re-extend a matched file whenever it is modified or
+    /// replaced after the initial run.
+    #[serde(default = "default_false")]
+    pub watch_content: bool,
+    /// Files are hashed in chunks of this many bytes.
+    #[serde(default = "default_read_buffer_bytes")]
+    pub read_buffer_bytes: usize,
+    #[serde(default = "default_symlink_policy")]
+    pub symlinks: SymlinkPolicy,
+    /// Matches whose resolved path is outside all of these directories are
+    /// skipped. Empty allows any path.
+    #[serde(default)]
+    pub allowed_roots: Vec<String>,
+    /// Skip matches that resolve into `/proc`, `/sys` or `/dev`.
+    #[serde(default = "default_true")]
+    pub skip_special_files: bool,
+    /// Reuse the digest of a file whose size, inode and timestamps are
+    /// unchanged since it was last hashed instead of hashing it again.
+    #[serde(default = "default_true")]
+    pub hash_cache: bool,
+    /// Keep the digests of `hash_cache` in this JSON file, so that they
+    /// survive restarts.
+    #[serde(default)]
+    pub hash_cache_path: Option<String>,
+    /// Glob patterns of matches that are skipped, e.g. `/etc/shadow` or
+    /// `/var/log/**`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Skip files larger than this many bytes. Unlimited when unset.
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// How the verity root hash of a model directory is computed.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerityBackend {
+    /// `cryptpilot verity format`, which needs the binary and root.
+    Cryptpilot,
+    /// An in-process hash tree over a deterministic image of the directory.
+    Native,
+}
+
+/// What is extended for a model directory.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelDirMode {
+    /// One root hash over the whole directory.
+    Verity,
+    /// The weight index of a sharded checkpoint and each shard it lists,
+    /// one by one; directories without an index are measured as `verity`.
+    Shards,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct ModelDirMeasurementConfig {
     #[serde(default = "default_false")]
     pub enable: bool,
     #[serde(default)]
     pub pcr_index: Option<u32>,
+    #[serde(default = "default_verity_backend")]
+    pub backend: VerityBackend,
+    #[serde(default = "default_model_dir_mode")]
+    pub mode: ModelDirMode,
     #[serde(default = "default_cryptpilot_binary")]
     pub cryptpilot_binary: String,
     #[serde(default)]
-    pub directories: Vec<String>,
+    pub directories: Vec<MeasurementEntry>,
+    /// Use `name@version:path` as the operation when version metadata is found.
+    #[serde(default = "default_false")]
+    pub versioned_operation: bool,
+    /// Version labels per directory, as `version` or `name@version`; these take
+    /// precedence over detected metadata.
+    #[serde(default)]
+    pub version_labels: BTreeMap<String, String>,
+    /// Measure directories on NFS, virtiofs, overlay and similar filesystems
+    /// by manifest instead of failing on verity formatting.
+    #[serde(default = "default_true")]
+    pub network_fs_fallback: bool,
+    /// After measuring, mount each directory over itself through dm-verity
+    /// with the measured root hash, enforcing integrity on every read.
+    #[serde(default = "default_false")]
+    pub activate_verity: bool,
+    /// Where hash trees of activated directories are kept.
+    #[serde(default = "default_verity_hash_dir")]
+    pub verity_hash_dir: String,
+    /// In daemon mode, re-measure a directory once its content changes.
+    #[serde(default = "default_false")]
+    pub watch_content: bool,
+    /// Seconds without writes after which a changed directory is measured.
+    #[serde(default = "default_model_dir_settle_secs")]
+    pub settle_secs: u64,
 }
 
-fn default_false() -> bool {
-    false
+/// Manifest-based measurement of large dataset directories.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DatasetMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default)]
+    pub directories: Vec<MeasurementEntry>,
+    /// File name patterns of index/metadata files, which are hashed fully.
+    #[serde(default = "default_dataset_index_patterns")]
+    pub index_patterns: Vec<String>,
+    /// Size of the start, middle and end windows hashed for other files.
+    #[serde(default = "default_dataset_shard_sample_bytes")]
+    pub shard_sample_bytes: u64,
+    /// Fraction (0 to 1) of shards, chosen by path, that are hashed fully.
+    #[serde(default)]
+    pub shard_sample_rate: f64,
+    /// Where to keep a copy of each manifest for verifiers.
+    #[serde(default)]
+    pub manifest_dir: Option<String>,
 }
 
-fn default_aa_channel() -> MeasurementChannel {
-    MeasurementChannel::UnixSocket
+/// Directory trees hashed file by file into one digest, for directories
+/// that do not need dm-verity.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DirTreeMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    #[serde(default)]
+    pub directories: Vec<MeasurementEntry>,
+    /// Glob patterns matched against paths relative to the measured
+    /// directory; a matching directory is skipped with its contents.
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
-fn default_attestation_agent_socket() -> String {
-    "unix:///run/confidential-containers/attestation-agent/attestation-agent.sock".to_string()
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectStorageProvider {
+    S3,
+    Oss,
 }
 
-fn default_pcr_index() -> u32 {
-    18 // Default PCR for this tool, distinct from AA's internal one
+/// Where object storage credentials come from.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialSource {
+    /// `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`/`AWS_SESSION_TOKEN`, or the
+    /// `OSS_` equivalents for OSS.
+    Env,
+    /// The role attached to the cloud instance.
+    InstanceMetadata,
 }
 
-fn default_hash_algorithm() -> String {
-    "sha256".to_string()
+#[derive(Debug, Deserialize, Clone)]
+pub struct ObjectMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_object_provider")]
+    pub provider: ObjectStorageProvider,
+    /// S3-compatible endpoint, e.g. `https://s3.us-east-1.amazonaws.com` or
+    /// `https://oss-cn-hangzhou.aliyuncs.com`.
+    #[serde(default)]
+    pub endpoint: String,
+    #[serde(default)]
+    pub region: String,
+    /// Address buckets as `endpoint/bucket` instead of `bucket.endpoint`.
+    #[serde(default = "default_false")]
+    pub path_style: bool,
+    #[serde(default = "default_credential_source")]
+    pub credentials: CredentialSource,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Objects to measure, as `bucket/key`.
+    #[serde(default)]
+    pub objects: Vec<String>,
 }
 
-fn default_cryptpilot_binary() -> String {
-    "cryptpilot".to_string()
+/// Named groups of files and directories measured as one composite digest.
+#[derive(Debug, Deserialize, Clone)]
+pub struct GroupMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    /// Hash algorithm for files; directories always use a SHA-256 tree digest.
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Also extend one `group_item` event per member before the composite.
+    #[serde(default = "default_false")]
+    pub emit_items: bool,
+    #[serde(default)]
+    pub groups: Vec<MeasurementGroup>,
 }
 
-impl Default for FileMeasurementConfig {
-    fn default() -> Self {
-        Self {
-            enable: default_false(),
-            pcr_index: default_pcr_index(),
-            hash_algorithm: default_hash_algorithm(),
-            files: Vec::new(),
-        }
-    }
+/// Read-only filesystem images (squashfs, EROFS).
+#[derive(Debug, Deserialize, Clone)]
+pub struct FsImageMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    #[serde(default)]
+    pub images: Vec<MeasurementEntry>,
+    /// Loop-mount each image read-only and include a manifest digest of its
+    /// content in the record.
+    #[serde(default = "default_false")]
+    pub mount_manifest: bool,
+    /// Where temporary mount points are created.
+    #[serde(default = "default_fs_image_mount_dir")]
+    pub mount_dir: String,
 }
 
-impl Default for ModelDirMeasurementConfig {
-    fn default() -> Self {
-        Self {
-            enable: default_false(),
-            pcr_index: None,
-            cryptpilot_binary: default_cryptpilot_binary(),
-            directories: Vec::new(),
-        }
-    }
+/// Virtualization stack of hosts running nested VMs. All lists take glob
+/// patterns.
+#[derive(Debug, Deserialize, Clone)]
+pub struct VmmMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// libvirt domain definitions, e.g. `/etc/libvirt/qemu/*.xml`.
+    #[serde(default)]
+    pub domain_xml: Vec<MeasurementEntry>,
+    /// Firmware binaries such as OVMF code and vars templates.
+    #[serde(default)]
+    pub firmware: Vec<MeasurementEntry>,
+    #[serde(default)]
+    pub disk_images: Vec<MeasurementEntry>,
+    /// Also measure the backing files of qcow2 disk images.
+    #[serde(default = "default_true")]
+    pub follow_backing_chain: bool,
 }
 
-impl Config {
-    pub fn load(config_path: Option<&Path>) -> Result<Self> {
+/// Unified Kernel Images and other EFI binaries, typically on the ESP.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EfiMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    /// Glob patterns, e.g. `/boot/efi/EFI/Linux/*.efi`.
+    #[serde(default)]
+    pub binaries: Vec<MeasurementEntry>,
+}
+
+/// GRUB configuration, environment block and modules on legacy-boot hosts.
+#[derive(Debug, Deserialize, Clone)]
+pub struct GrubMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Glob patterns of `grub.cfg` files; the modules they `insmod` are
+    /// measured as well.
+    #[serde(default = "default_grub_config_files")]
+    pub config_files: Vec<MeasurementEntry>,
+    #[serde(default = "default_grubenv_files")]
+    pub grubenv_files: Vec<MeasurementEntry>,
+    /// grubenv variables GRUB rewrites on every boot, left out of the
+    /// measurement.
+    #[serde(default = "default_grubenv_ignore")]
+    pub grubenv_ignore: Vec<String>,
+}
+
+/// Runtime kernel state: the command line, loaded modules and sysctls.
+#[derive(Debug, Deserialize, Clone)]
+pub struct KernelStateMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Measure `/proc/cmdline`.
+    #[serde(default = "default_true")]
+    pub cmdline: bool,
+    /// Measure the names of the loaded modules from `/proc/modules`.
+    #[serde(default = "default_true")]
+    pub modules: bool,
+    /// Sysctls in dotted form (`kernel.kptr_restrict`), read from
+    /// `/proc/sys`; an empty list measures none.
+    #[serde(default = "default_kernel_sysctls")]
+    pub sysctls: Vec<String>,
+}
+
+/// Executables of running processes, found through `/proc`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProcessMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Glob patterns matched against the process name (`comm`) and the file
+    /// name of its executable. A critical pattern fails when no running
+    /// process matches.
+    #[serde(default)]
+    pub processes: Vec<MeasurementEntry>,
+}
+
+/// Identity and firmware of PCI devices such as GPUs and accelerators.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DeviceMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Device classes to measure: `gpu`, `accelerator`, `network`,
+    /// `storage`, or a PCI class code prefix such as `0x0302`. A critical
+    /// class fails when no device of it is present.
+    #[serde(default = "default_device_classes")]
+    pub classes: Vec<MeasurementEntry>,
+    /// Queried for the VBIOS version of NVIDIA GPUs; empty skips it.
+    #[serde(default = "default_nvidia_smi_path")]
+    pub nvidia_smi_path: String,
+}
+
+/// The mount table, measured at startup and, in daemon mode, whenever it
+/// changes.
+#[derive(Debug, Deserialize, Clone)]
+pub struct MountMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Glob patterns of the mount points to measure; empty measures all.
+    #[serde(default)]
+    pub mount_points: Vec<String>,
+    /// Glob patterns of mount points left out, such as the per-container
+    /// mounts of container runtimes.
+    #[serde(default = "default_mount_exclude")]
+    pub exclude: Vec<String>,
+    /// Filesystem types left out, such as pseudo-filesystems.
+    #[serde(default = "default_mount_exclude_fstypes")]
+    pub exclude_fstypes: Vec<String>,
+    /// Re-measure in daemon mode when the mount table changes.
+    #[serde(default = "default_true")]
+    pub watch: bool,
+    /// How often the mount table is checked even without a change
+    /// notification.
+    #[serde(default = "default_mount_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+/// Firewall rulesets measured by the network state measurer.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FirewallBackend {
+    /// `nft --stateless list ruleset`.
+    Nftables,
+    /// `iptables-save` and `ip6tables-save`.
+    Iptables,
+}
+
+/// Listening sockets and firewall rules, so that the services a CVM exposes
+/// are attested.
+#[derive(Debug, Deserialize, Clone)]
+pub struct NetworkMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// Socket tables read from `/proc/net`: `tcp`, `tcp6`, `udp` and `udp6`.
+    #[serde(default = "default_network_protocols")]
+    pub protocols: Vec<String>,
+    /// Ports to include; empty includes every listening port.
+    #[serde(default)]
+    pub ports: Vec<u16>,
+    /// Leave out sockets bound to loopback addresses only.
+    #[serde(default = "default_false")]
+    pub exclude_loopback: bool,
+    /// Rulesets to measure next to the sockets.
+    #[serde(default)]
+    pub firewall: Vec<FirewallBackend>,
+    #[serde(default = "default_nft_binary")]
+    pub nft_binary: String,
+    #[serde(default = "default_iptables_save_binary")]
+    pub iptables_save_binary: String,
+    #[serde(default = "default_ip6tables_save_binary")]
+    pub ip6tables_save_binary: String,
+}
+
+/// SPDX and CycloneDX documents, so that the supply-chain metadata of a
+/// workload is bound into the event log.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SbomMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    /// SBOM files in SPDX or CycloneDX JSON; glob patterns.
+    #[serde(default)]
+    pub sboms: Vec<MeasurementEntry>,
+    /// Check the files an SBOM lists with hashes against the disk.
+    #[serde(default = "default_false")]
+    pub verify_components: bool,
+    /// Directory the listed file names are resolved against.
+    #[serde(default = "default_sbom_root")]
+    pub root: String,
+    /// Fail the measurer when a listed file is missing or differs, instead
+    /// of only logging it.
+    #[serde(default = "default_false")]
+    pub fail_on_mismatch: bool,
+}
+
+/// How file changes are detected.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchBackend {
+    /// inotify, polling paths on NFS, 9p, virtiofs, overlay and CIFS mounts,
+    /// where inotify misses changes, and everything when inotify is
+    /// unavailable.
+    Auto,
+    /// inotify only.
+    Inotify,
+    /// Polling only.
+    Poll,
+}
+
+/// How log lines are written.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// env_logger's plain text.
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+/// How the exec measurer learns about executed binaries.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecMonitor {
+    /// fanotify exec notifications on the mounts of `include`; needs
+    /// CAP_SYS_ADMIN.
+    Fanotify,
+    /// fanotify exec permission events: each execution waits until its
+    /// binary has been extended.
+    FanotifyPermission,
+    /// inotify on the `include` directories: executables are measured when
+    /// they are created or modified, not when they run.
+    Inotify,
+}
+
+/// Binaries executed at runtime, measured by the daemon as they run.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ExecMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_hash_algorithm")]
+    pub hash_algorithm: String,
+    #[serde(default = "default_exec_monitor")]
+    pub monitor: ExecMonitor,
+    /// Path prefixes of the binaries to measure.
+    #[serde(default = "default_exec_include")]
+    pub include: Vec<String>,
+    /// Path prefixes left out even when under `include`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Number of binaries whose digest is remembered; a binary executed
+    /// again unchanged is neither re-hashed nor re-extended.
+    #[serde(default = "default_exec_cache_size")]
+    pub cache_size: usize,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRuntime {
+    /// The Docker Engine API.
+    Docker,
+    /// A CRI runtime such as containerd or CRI-O, queried with `crictl`.
+    Cri,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ContainerImageMeasurementConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_container_runtime")]
+    pub runtime: ContainerRuntime,
+    /// Docker Engine socket or CRI endpoint; defaults to the usual one for
+    /// `runtime`.
+    #[serde(default)]
+    pub socket_path: Option<String>,
+    #[serde(default = "default_crictl_path")]
+    pub crictl_path: String,
+    /// Glob patterns matched against the Kubernetes namespace of each
+    /// container; when empty, containers of every namespace (or none) match.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Glob patterns matched against container names; when empty, every
+    /// container matches.
+    #[serde(default)]
+    pub names: Vec<String>,
+}
+
+impl ContainerImageMeasurementConfig {
+    pub fn resolved_socket_path(&self) -> String {
+        match (&self.socket_path, &self.runtime) {
+            (Some(path), _) => path.clone(),
+            (None, ContainerRuntime::Docker) => default_docker_socket_path(),
+            (None, ContainerRuntime::Cri) => default_cri_endpoint(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MeasurementGroup {
+    /// Used as the operation of the composite event.
+    pub name: String,
+    /// Glob patterns of member files and directories.
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+/// Settings for the `nri` subcommand, which measures containers as containerd
+/// creates them.
+#[derive(Debug, Deserialize, Clone)]
+pub struct NriMeasurementConfig {
+    #[serde(default = "default_nri_socket_path")]
+    pub socket_path: String,
+    #[serde(default = "default_nri_plugin_name")]
+    pub plugin_name: String,
+    /// Two-digit index that orders this plugin among other NRI plugins.
+    #[serde(default = "default_nri_plugin_index")]
+    pub plugin_index: String,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    /// Fail container creation, or start, when the measurement cannot be
+    /// extended.
+    #[serde(default = "default_false")]
+    pub block_on_failure: bool,
+    /// CRI endpoint asked for the image digest of each container.
+    #[serde(default = "default_cri_endpoint")]
+    pub runtime_endpoint: String,
+    #[serde(default = "default_crictl_path")]
+    pub crictl_path: String,
+    /// Hash the rootfs of each container before it starts.
+    #[serde(default = "default_true")]
+    pub measure_rootfs: bool,
+    /// containerd's runtime v2 task directory of the CRI namespace, under
+    /// which the rootfs of a starting container is mounted at `<id>/rootfs`.
+    #[serde(default = "default_nri_task_dir")]
+    pub task_dir: String,
+}
+
+/// Settings for the `hook` subcommand, run by the OCI runtime per container.
+#[derive(Debug, Deserialize, Clone)]
+pub struct HookMeasurementConfig {
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    /// Hash the whole rootfs; disable for large images if hook latency matters.
+    #[serde(default = "default_true")]
+    pub measure_rootfs: bool,
+    /// Also measure each overlayfs lower layer and the upper layer of the
+    /// rootfs separately, so that a change can be traced to its layer.
+    #[serde(default = "default_false")]
+    pub rootfs_layers: bool,
+    /// Docker layer database used to look up the diff-id of overlay2 layers.
+    #[serde(default = "default_docker_layerdb_dir")]
+    pub docker_layerdb_dir: String,
+    /// Exit non-zero (aborting container creation) when measuring fails.
+    #[serde(default = "default_false")]
+    pub block_on_failure: bool,
+}
+
+/// Settings for the `docker` subcommand, which follows the Docker engine's
+/// event stream on hosts without containerd.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DockerMeasurementConfig {
+    #[serde(default = "default_docker_socket_path")]
+    pub socket_path: String,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+}
+
+/// Local HTTP API through which co-located services request a measurement
+/// right after writing an artifact (`POST /measure`).
+#[derive(Debug, Deserialize, Clone)]
+pub struct ApiConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default = "default_api_listen")]
+    pub listen: String,
+    /// File holding the bearer token clients must present; required.
+    #[serde(default)]
+    pub token_file: Option<String>,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    /// Domains clients may extend; the first is used when a request names
+    /// none. Domains of the built-in measurers are refused.
+    #[serde(default = "default_api_domains")]
+    pub allowed_domains: Vec<String>,
+}
+
+/// What happens to re-measurement triggered during a blackout window.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlackoutAction {
+    /// Queue the work and run it once the window closes.
+    Defer,
+    /// Drop the work.
+    Skip,
+}
+
+/// Time windows during which heavy re-measurement is held back, e.g. during
+/// inference peak hours.
+#[derive(Debug, Deserialize, Clone)]
+pub struct MaintenanceConfig {
+    /// UTC windows such as `"Mon-Fri 08:00-20:00"` or `"22:00-02:00"`.
+    #[serde(default)]
+    pub blackout_windows: Vec<String>,
+    #[serde(default = "default_blackout_action")]
+    pub blackout_action: BlackoutAction,
+}
+
+/// What a config reload re-measures or records besides newly added entries.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReloadConfig {
+    /// Re-measure every entry of `file_measurement` or `model_dir_measurement`
+    /// when a setting that changes their measurements does (the PCR index,
+    /// the hash algorithm, ...), not only the added entries.
+    #[serde(default = "default_true")]
+    pub remeasure_on_settings_change: bool,
+    /// Extend a `measurement_policy` event for every entry removed from
+    /// `file_measurement` or `model_dir_measurement` and for every measurer
+    /// section that gets disabled, so the event log shows they are no longer
+    /// measured.
+    #[serde(default = "default_false")]
+    pub tombstones: bool,
+}
+
+/// Restarting runtime watchers that fail, so that the daemon keeps reacting
+/// to changes.
+#[derive(Debug, Deserialize, Clone)]
+pub struct WatcherSupervisionConfig {
+    /// Restart a watcher that returned an error or panicked; otherwise it
+    /// stays stopped.
+    #[serde(default = "default_true")]
+    pub restart: bool,
+    /// Delay before the first restart; doubled for each further failure.
+    #[serde(default = "default_watcher_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// Also how long a restarted watcher has to run before its failures
+    /// are forgotten.
+    #[serde(default = "default_watcher_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Failures in a row after which a watcher is logged as an error and
+    /// the daemon reported unhealthy.
+    #[serde(default = "default_watcher_failure_threshold")]
+    pub failure_threshold: u32,
+}
+
+/// Unauthenticated HTTP endpoints reporting daemon health (`GET /healthz`)
+/// and details (`GET /status`) for probes and monitoring.
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatusConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default = "default_status_listen")]
+    pub listen: String,
+}
+
+/// ttrpc service on a unix socket through which guest components request
+/// measurements from the daemon.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ControlConfig {
+    #[serde(default = "default_false")]
+    pub enable: bool,
+    #[serde(default = "default_control_socket")]
+    pub socket: String,
+    /// Permissions of the socket, which together with its owner and group
+    /// are the only access control.
+    #[serde(default = "default_control_socket_mode")]
+    pub socket_mode: u32,
+    /// User, by name or uid, that owns the socket; the daemon's by default.
+    #[serde(default)]
+    pub socket_owner: Option<String>,
+    /// Group, by name or gid, of the socket; the daemon's by default.
+    #[serde(default)]
+    pub socket_group: Option<String>,
+}
+
+/// Where the node identity is added to each measurement.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeIdentityPlacement {
+    None,
+    /// `<domain>@<identity>`
+    Domain,
+    /// `<identity>:<operation>`
+    Operation,
+}
+
+/// Settings shared by the Kubernetes integrations.
+#[derive(Debug, Deserialize, Clone)]
+pub struct KubernetesConfig {
+    /// Name of the node the tool runs on; defaults to the NODE_NAME environment
+    /// variable, normally injected through the downward API.
+    #[serde(default)]
+    pub node_name: Option<String>,
+    /// Namespace watched for MeasurementPolicy objects; defaults to the pod's own.
+    #[serde(default)]
+    pub policy_namespace: Option<String>,
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Where the controller writes the config it reconciled from policies.
+    #[serde(default)]
+    pub effective_config_path: Option<String>,
+    #[serde(default = "default_node_identity")]
+    pub node_identity: NodeIdentityPlacement,
+    /// Labels from the downward API labels file appended to the identity.
+    #[serde(default)]
+    pub identity_labels: Vec<String>,
+    #[serde(default = "default_labels_path")]
+    pub labels_path: String,
+    /// Measure paths listed in the `measurement.io/paths` pod annotation.
+    #[serde(default = "default_false")]
+    pub pod_annotations: bool,
+    /// Extend the UID and normalized spec digest of every pod on the node.
+    #[serde(default = "default_false")]
+    pub pod_specs: bool,
+    /// Register for pod path and pod spec measurements.
+    #[serde(default)]
+    pub pod_pcr_index: Option<u32>,
+    #[serde(default = "default_kubelet_root")]
+    pub kubelet_root: String,
+    /// Where the host filesystem is mounted when running in a container.
+    #[serde(default = "default_host_root")]
+    pub host_root: String,
+    /// Publish the `RuntimeMeasurementHealthy` node condition and node Events.
+    #[serde(default = "default_true")]
+    pub node_condition: bool,
+}
+
+/// Settings for the `kata` guest profile.
+#[derive(Debug, Deserialize, Clone)]
+pub struct KataConfig {
+    /// Directory where the Kata agent exposes the sandbox's shared volumes.
+    #[serde(default = "default_kata_volumes_dir")]
+    pub volumes_dir: String,
+    /// ttrpc vsock addresses probed when no AA unix socket exists.
+    #[serde(default = "default_kata_aa_vsock_addresses")]
+    pub aa_vsock_addresses: Vec<String>,
+    #[serde(default)]
+    pub pcr_index: Option<u32>,
+    #[serde(default = "default_kata_volume_poll_interval_secs")]
+    pub volume_poll_interval_secs: u64,
+}
+
+/// Trustee endpoint used by `provision-rvps`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RvpsConfig {
+    /// Base URL of the KBS fronting the RVPS, e.g. `https://kbs.example.com:8080`.
+    #[serde(default)]
+    pub kbs_url: String,
+    /// File holding the KBS admin bearer token; defaults to `KBS_ADMIN_TOKEN`.
+    #[serde(default)]
+    pub admin_token_path: Option<String>,
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// Reference values are named `<name_prefix>.<domain>.<operation>`.
+    #[serde(default = "default_rvps_name_prefix")]
+    pub name_prefix: String,
+}
+
+fn default_false() -> bool {
+    false
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_aa_http_timeout_secs() -> u64 {
+    30
+}
+
+fn default_aa_http_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_aa_channel() -> MeasurementChannel {
+    MeasurementChannel::UnixSocket
+}
+
+fn default_container_runtime() -> ContainerRuntime {
+    ContainerRuntime::Cri
+}
+
+fn default_crictl_path() -> String {
+    "crictl".to_string()
+}
+
+fn default_cri_endpoint() -> String {
+    "unix:///run/containerd/containerd.sock".to_string()
+}
+
+fn default_aa_vsock_port() -> u32 {
+    50002
+}
+
+fn default_aa_ttrpc_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_attestation_agent_socket() -> String {
+    "unix:///run/confidential-containers/attestation-agent/attestation-agent.sock".to_string()
+}
+
+fn default_evidence_kind() -> EvidenceKind {
+    EvidenceKind::Evidence
+}
+
+fn default_token_type() -> String {
+    "kbs".to_string()
+}
+
+fn default_fs_image_mount_dir() -> String {
+    "/run/measurement_tool/images".to_string()
+}
+
+fn default_max_content_bytes() -> usize {
+    4096
+}
+
+fn default_content_store_dir() -> String {
+    "/var/lib/measurement_tool/content".to_string()
+}
+
+fn default_audit_log_entries() -> usize {
+    100
+}
+
+fn default_cel_format() -> CelFormat {
+    CelFormat::Json
+}
+
+fn default_pcr_index() -> u32 {
+    18 // Default PCR for this tool, distinct from AA's internal one
+}
+
+fn default_hash_algorithm() -> String {
+    "sha256".to_string()
+}
+
+fn default_aa_max_retries() -> u32 {
+    3
+}
+
+fn default_aa_initial_backoff_ms() -> u64 {
+    200
+}
+
+fn default_aa_max_backoff_ms() -> u64 {
+    5000
+}
+
+fn default_aa_flush_interval_secs() -> u64 {
+    10
+}
+
+fn default_aa_startup_timeout_secs() -> u64 {
+    60
+}
+
+fn default_watcher_initial_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_watcher_max_backoff_ms() -> u64 {
+    300_000
+}
+
+fn default_watcher_failure_threshold() -> u32 {
+    3
+}
+
+fn default_tpm_device() -> String {
+    "/dev/tpmrm0".to_string()
+}
+
+fn default_tpm_pcr_index() -> u32 {
+    23
+}
+
+fn default_log_format() -> LogFormat {
+    LogFormat::Text
+}
+
+fn default_aa_batch_size() -> usize {
+    64
+}
+
+fn default_aa_max_in_flight() -> usize {
+    1
+}
+
+fn default_max_events_per_run() -> u64 {
+    100_000
+}
+
+fn default_local_event_log_path() -> String {
+    "/var/lib/measurement_tool/eventlog".to_string()
+}
+
+fn default_max_concurrency() -> usize {
+    4
+}
+
+fn default_symlink_policy() -> SymlinkPolicy {
+    SymlinkPolicy::Follow
+}
+
+fn default_read_buffer_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_grub_config_files() -> Vec<MeasurementEntry> {
+    vec![
+        "/boot/grub/grub.cfg".to_string().into(),
+        "/boot/grub2/grub.cfg".to_string().into(),
+    ]
+}
+
+fn default_grubenv_files() -> Vec<MeasurementEntry> {
+    vec![
+        "/boot/grub/grubenv".to_string().into(),
+        "/boot/grub2/grubenv".to_string().into(),
+    ]
+}
+
+fn default_grubenv_ignore() -> Vec<String> {
+    vec!["boot_success".to_string(), "boot_indeterminate".to_string()]
+}
+
+fn default_kernel_sysctls() -> Vec<String> {
+    [
+        "kernel.kexec_load_disabled",
+        "kernel.modules_disabled",
+        "kernel.kptr_restrict",
+        "kernel.dmesg_restrict",
+        "kernel.unprivileged_bpf_disabled",
+        "kernel.yama.ptrace_scope",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+fn default_device_classes() -> Vec<MeasurementEntry> {
+    vec![
+        MeasurementEntry::Path("gpu".to_string()),
+        MeasurementEntry::Path("accelerator".to_string()),
+    ]
+}
+
+fn default_nvidia_smi_path() -> String {
+    "nvidia-smi".to_string()
+}
+
+fn default_mount_exclude() -> Vec<String> {
+    [
+        "/run/containerd/**",
+        "/run/netns/**",
+        "/var/lib/containerd/**",
+        "/var/lib/docker/**",
+        "/var/lib/kubelet/pods/**",
+    ]
+    .iter()
+    .map(|p| p.to_string())
+    .collect()
+}
+
+fn default_mount_exclude_fstypes() -> Vec<String> {
+    [
+        "autofs",
+        "binfmt_misc",
+        "bpf",
+        "cgroup",
+        "cgroup2",
+        "configfs",
+        "debugfs",
+        "devpts",
+        "fusectl",
+        "hugetlbfs",
+        "mqueue",
+        "nsfs",
+        "proc",
+        "pstore",
+        "securityfs",
+        "sysfs",
+        "tracefs",
+    ]
+    .iter()
+    .map(|t| t.to_string())
+    .collect()
+}
+
+fn default_mount_poll_interval_secs() -> u64 {
+    60
+}
+
+fn default_network_protocols() -> Vec<String> {
+    ["tcp", "tcp6", "udp", "udp6"]
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
+fn default_watch_backend() -> WatchBackend {
+    WatchBackend::Auto
+}
+
+fn default_watch_poll_interval_secs() -> u64 {
+    5
+}
+
+fn default_sbom_root() -> String {
+    "/".to_string()
+}
+
+fn default_nft_binary() -> String {
+    "nft".to_string()
+}
+
+fn default_iptables_save_binary() -> String {
+    "iptables-save".to_string()
+}
+
+fn default_ip6tables_save_binary() -> String {
+    "ip6tables-save".to_string()
+}
+
+fn default_exec_monitor() -> ExecMonitor {
+    ExecMonitor::Fanotify
+}
+
+fn default_exec_include() -> Vec<String> {
+    ["/usr/bin", "/usr/sbin", "/usr/local/bin", "/opt"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_exec_cache_size() -> usize {
+    4096
+}
+
+fn default_verity_backend() -> VerityBackend {
+    VerityBackend::Cryptpilot
+}
+
+fn default_model_dir_mode() -> ModelDirMode {
+    ModelDirMode::Verity
+}
+
+fn default_cryptpilot_binary() -> String {
+    "cryptpilot".to_string()
+}
+
+fn default_verity_hash_dir() -> String {
+    "/var/lib/measurement_tool/verity".to_string()
+}
+
+fn default_model_dir_settle_secs() -> u64 {
+    30
+}
+
+fn default_dataset_index_patterns() -> Vec<String> {
+    ["*.json", "*.jsonl", "*.yaml", "*.yml", "*.txt", "*.idx", "*.index"]
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
+fn default_dataset_shard_sample_bytes() -> u64 {
+    1024 * 1024
+}
+
+fn default_object_provider() -> ObjectStorageProvider {
+    ObjectStorageProvider::S3
+}
+
+fn default_credential_source() -> CredentialSource {
+    CredentialSource::Env
+}
+
+fn default_nri_socket_path() -> String {
+    "/var/run/nri/nri.sock".to_string()
+}
+
+fn default_nri_plugin_name() -> String {
+    "measurement_tool".to_string()
+}
+
+fn default_nri_plugin_index() -> String {
+    "50".to_string()
+}
+
+fn default_nri_task_dir() -> String {
+    "/run/containerd/io.containerd.runtime.v2.task/k8s.io".to_string()
+}
+
+fn default_docker_socket_path() -> String {
+    "/var/run/docker.sock".to_string()
+}
+
+fn default_docker_layerdb_dir() -> String {
+    "/var/lib/docker/image/overlay2/layerdb/sha256".to_string()
+}
+
+fn default_blackout_action() -> BlackoutAction {
+    BlackoutAction::Defer
+}
+
+fn default_api_domains() -> Vec<String> {
+    vec!["api".to_string()]
+}
+
+fn default_api_listen() -> String {
+    "127.0.0.1:8007".to_string()
+}
+
+fn default_status_listen() -> String {
+    "127.0.0.1:8008".to_string()
+}
+
+fn default_control_socket() -> String {
+    "/run/measurement_tool/control.sock".to_string()
+}
+
+fn default_control_socket_mode() -> u32 {
+    0o600
+}
+
+fn default_poll_interval_secs() -> u64 {
+    30
+}
+
+fn default_node_identity() -> NodeIdentityPlacement {
+    NodeIdentityPlacement::None
+}
+
+fn default_labels_path() -> String {
+    "/etc/podinfo/labels".to_string()
+}
+
+fn default_kubelet_root() -> String {
+    "/var/lib/kubelet".to_string()
+}
+
+fn default_host_root() -> String {
+    "/".to_string()
+}
+
+fn default_rvps_name_prefix() -> String {
+    "measurement".to_string()
+}
+
+fn default_kata_volumes_dir() -> String {
+    "/run/kata-containers/shared/containers".to_string()
+}
+
+fn default_kata_aa_vsock_addresses() -> Vec<String> {
+    vec!["vsock://1:50002".to_string(), "vsock://2:50002".to_string()]
+}
+
+fn default_kata_volume_poll_interval_secs() -> u64 {
+    5
+}
+
+impl Default for ContentLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_content_bytes: default_max_content_bytes(),
+            store_dir: default_content_store_dir(),
+        }
+    }
+}
+
+impl Default for AaRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_aa_max_retries(),
+            initial_backoff_ms: default_aa_initial_backoff_ms(),
+            max_backoff_ms: default_aa_max_backoff_ms(),
+            queue_size: 0,
+            queue_path: None,
+            flush_interval_secs: default_aa_flush_interval_secs(),
+            startup_timeout_secs: default_aa_startup_timeout_secs(),
+        }
+    }
+}
+
+impl Default for AaBatchConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: default_aa_batch_size(),
+            max_in_flight: default_aa_max_in_flight(),
+            batch_api: default_false(),
+        }
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_events_per_sec: 0,
+            max_events_per_run: default_max_events_per_run(),
+        }
+    }
+}
+
+impl Default for LocalEventLogConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            path: default_local_event_log_path(),
+            forward_to_aa: default_true(),
+        }
+    }
+}
+
+impl Default for EvidenceBundleConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            kind: default_evidence_kind(),
+            token_type: default_token_type(),
+            output_path: None,
+            upload_url: None,
+            audit_log_entries: default_audit_log_entries(),
+        }
+    }
+}
+
+impl Default for FileMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: default_pcr_index(),
+            hash_algorithm: default_hash_algorithm(),
+            files: Vec::new(),
+            ima_signatures: default_false(),
+            archive_members: default_false(),
+            docker_socket_path: default_docker_socket_path(),
+            watch_content: default_false(),
+            read_buffer_bytes: default_read_buffer_bytes(),
+            symlinks: default_symlink_policy(),
+            allowed_roots: Vec::new(),
+            skip_special_files: default_true(),
+            hash_cache: default_true(),
+            hash_cache_path: None,
+            exclude: Vec::new(),
+            max_file_size: None,
+        }
+    }
+}
+
+impl Default for ModelDirMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            backend: default_verity_backend(),
+            mode: default_model_dir_mode(),
+            cryptpilot_binary: default_cryptpilot_binary(),
+            directories: Vec::new(),
+            versioned_operation: default_false(),
+            version_labels: BTreeMap::new(),
+            network_fs_fallback: default_true(),
+            activate_verity: default_false(),
+            verity_hash_dir: default_verity_hash_dir(),
+            watch_content: default_false(),
+            settle_secs: default_model_dir_settle_secs(),
+        }
+    }
+}
+
+impl Default for NriMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            socket_path: default_nri_socket_path(),
+            plugin_name: default_nri_plugin_name(),
+            plugin_index: default_nri_plugin_index(),
+            pcr_index: None,
+            block_on_failure: default_false(),
+            runtime_endpoint: default_cri_endpoint(),
+            crictl_path: default_crictl_path(),
+            measure_rootfs: default_true(),
+            task_dir: default_nri_task_dir(),
+        }
+    }
+}
+
+impl Default for DatasetMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            directories: Vec::new(),
+            index_patterns: default_dataset_index_patterns(),
+            shard_sample_bytes: default_dataset_shard_sample_bytes(),
+            shard_sample_rate: 0.0,
+            manifest_dir: None,
+        }
+    }
+}
+
+impl Default for DirTreeMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            directories: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+impl Default for ObjectMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            provider: default_object_provider(),
+            endpoint: String::new(),
+            region: String::new(),
+            path_style: default_false(),
+            credentials: default_credential_source(),
+            hash_algorithm: default_hash_algorithm(),
+            objects: Vec::new(),
+        }
+    }
+}
+
+impl Default for GroupMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            emit_items: default_false(),
+            groups: Vec::new(),
+        }
+    }
+}
+
+impl Default for FsImageMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            images: Vec::new(),
+            mount_manifest: default_false(),
+            mount_dir: default_fs_image_mount_dir(),
+        }
+    }
+}
+
+impl Default for VmmMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            domain_xml: Vec::new(),
+            firmware: Vec::new(),
+            disk_images: Vec::new(),
+            follow_backing_chain: default_true(),
+        }
+    }
+}
+
+impl Default for EfiMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            binaries: Vec::new(),
+        }
+    }
+}
+
+impl Default for GrubMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            config_files: default_grub_config_files(),
+            grubenv_files: default_grubenv_files(),
+            grubenv_ignore: default_grubenv_ignore(),
+        }
+    }
+}
+
+impl Default for KernelStateMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            cmdline: default_true(),
+            modules: default_true(),
+            sysctls: default_kernel_sysctls(),
+        }
+    }
+}
+
+impl Default for ProcessMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            processes: Vec::new(),
+        }
+    }
+}
+
+impl Default for DeviceMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            classes: default_device_classes(),
+            nvidia_smi_path: default_nvidia_smi_path(),
+        }
+    }
+}
+
+impl Default for TpmConfig {
+    fn default() -> Self {
+        Self {
+            device: default_tpm_device(),
+            pcr_index: default_tpm_pcr_index(),
+            hash_algorithm: default_hash_algorithm(),
+        }
+    }
+}
+
+impl Default for MountMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            mount_points: Vec::new(),
+            exclude: default_mount_exclude(),
+            exclude_fstypes: default_mount_exclude_fstypes(),
+            watch: default_true(),
+            poll_interval_secs: default_mount_poll_interval_secs(),
+        }
+    }
+}
+
+impl Default for NetworkMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            protocols: default_network_protocols(),
+            ports: Vec::new(),
+            exclude_loopback: default_false(),
+            firewall: Vec::new(),
+            nft_binary: default_nft_binary(),
+            iptables_save_binary: default_iptables_save_binary(),
+            ip6tables_save_binary: default_ip6tables_save_binary(),
+        }
+    }
+}
+
+impl Default for SbomMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            sboms: Vec::new(),
+            verify_components: default_false(),
+            root: default_sbom_root(),
+            fail_on_mismatch: default_false(),
+        }
+    }
+}
+
+impl Default for ExecMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            hash_algorithm: default_hash_algorithm(),
+            monitor: default_exec_monitor(),
+            include: default_exec_include(),
+            exclude: Vec::new(),
+            cache_size: default_exec_cache_size(),
+        }
+    }
+}
+
+impl Default for ContainerImageMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            pcr_index: None,
+            runtime: default_container_runtime(),
+            socket_path: None,
+            crictl_path: default_crictl_path(),
+            namespaces: Vec::new(),
+            names: Vec::new(),
+        }
+    }
+}
+
+impl Default for HookMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            pcr_index: None,
+            measure_rootfs: default_true(),
+            rootfs_layers: default_false(),
+            docker_layerdb_dir: default_docker_layerdb_dir(),
+            block_on_failure: default_false(),
+        }
+    }
+}
+
+impl Default for DockerMeasurementConfig {
+    fn default() -> Self {
+        Self {
+            socket_path: default_docker_socket_path(),
+            pcr_index: None,
+        }
+    }
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            listen: default_api_listen(),
+            token_file: None,
+            pcr_index: None,
+            allowed_domains: default_api_domains(),
+        }
+    }
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            blackout_windows: Vec::new(),
+            blackout_action: default_blackout_action(),
+        }
+    }
+}
+
+impl Default for AaHttpConfig {
+    fn default() -> Self {
+        Self {
+            ca_cert_path: None,
+            client_cert_path: None,
+            client_key_path: None,
+            token_path: None,
+            timeout_secs: default_aa_http_timeout_secs(),
+            connect_timeout_secs: default_aa_http_connect_timeout_secs(),
+        }
+    }
+}
+
+impl Default for ReloadConfig {
+    fn default() -> Self {
+        Self {
+            remeasure_on_settings_change: default_true(),
+            tombstones: default_false(),
+        }
+    }
+}
+
+impl Default for WatcherSupervisionConfig {
+    fn default() -> Self {
+        Self {
+            restart: default_true(),
+            initial_backoff_ms: default_watcher_initial_backoff_ms(),
+            max_backoff_ms: default_watcher_max_backoff_ms(),
+            failure_threshold: default_watcher_failure_threshold(),
+        }
+    }
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            listen: default_status_listen(),
+        }
+    }
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            enable: default_false(),
+            socket: default_control_socket(),
+            socket_mode: default_control_socket_mode(),
+            socket_owner: None,
+            socket_group: None,
+        }
+    }
+}
+
+impl Default for KubernetesConfig {
+    fn default() -> Self {
+        Self {
+            node_name: None,
+            policy_namespace: None,
+            poll_interval_secs: default_poll_interval_secs(),
+            effective_config_path: None,
+            node_identity: default_node_identity(),
+            identity_labels: Vec::new(),
+            labels_path: default_labels_path(),
+            pod_annotations: default_false(),
+            pod_specs: default_false(),
+            pod_pcr_index: None,
+            kubelet_root: default_kubelet_root(),
+            host_root: default_host_root(),
+            node_condition: default_true(),
+        }
+    }
+}
+
+impl Default for RvpsConfig {
+    fn default() -> Self {
+        Self {
+            kbs_url: String::new(),
+            admin_token_path: None,
+            ca_cert_path: None,
+            name_prefix: default_rvps_name_prefix(),
+        }
+    }
+}
+
+impl Default for KataConfig {
+    fn default() -> Self {
+        Self {
+            volumes_dir: default_kata_volumes_dir(),
+            aa_vsock_addresses: default_kata_aa_vsock_addresses(),
+            pcr_index: None,
+            volume_poll_interval_secs: default_kata_volume_poll_interval_secs(),
+        }
+    }
+}
+
+impl KubernetesConfig {
+    pub fn resolve_node_name(&self) -> Option<String> {
+        self.node_name
+            .clone()
+            .or_else(|| std::env::var("NODE_NAME").ok())
+    }
+}
+
+/// A configuration together with the files it was assembled from.
+pub struct LoadedConfig {
+    pub config: Config,
+    /// The main file followed by the included fragments, in merge order.
+    pub sources: Vec<PathBuf>,
+    /// Include patterns resolved against the main file's directory.
+    pub include_patterns: Vec<String>,
+    /// SHA-256 over the content of every source, used to detect changes.
+    pub digest: String,
+    /// The keys set by environment variables and `--set`, with where each
+    /// came from, in the order they were applied.
+    pub overrides: Vec<String>,
+}
+
+impl Config {
+    /// The failure policy of the measurer configured by `section`, with
+    /// `strict` applied.
+    pub fn failure_policy(&self, section: &str) -> FailurePolicy {
+        match self.failure_policies.get(section) {
+            Some(&policy) if policy != FailurePolicy::Continue => policy,
+            _ if self.strict => FailurePolicy::FailAtEnd,
+            _ => FailurePolicy::Continue,
+        }
+    }
+
+    /// The configured backends in failover order: `aa_endpoints`, or the
+    /// single backend described by `aa_channel` and its address setting.
+    pub fn resolved_aa_endpoints(&self) -> Vec<AaEndpoint> {
+        if !self.aa_endpoints.is_empty() {
+            return self
+                .aa_endpoints
+                .iter()
+                .map(|endpoint| AaEndpoint {
+                    http: endpoint.http.clone().or_else(|| Some(self.aa_http.clone())),
+                    tpm: endpoint.tpm.clone().or_else(|| Some(self.tpm.clone())),
+                    ttrpc_timeout_ms: endpoint.ttrpc_timeout_ms.or(Some(self.aa_ttrpc_timeout_ms)),
+                    ..endpoint.clone()
+                })
+                .collect();
+        }
+        let address = match self.aa_channel {
+            MeasurementChannel::UnixSocket => self.attestation_agent_socket.clone(),
+            MeasurementChannel::HttpApi => self.trustiflux_api_endpoint.clone().unwrap_or_default(),
+            MeasurementChannel::Vsock => self
+                .aa_vsock_cid
+                .map(|cid| format!("vsock://{}:{}", cid, self.aa_vsock_port))
+                .unwrap_or_default(),
+            MeasurementChannel::Tpm => self.tpm.device.clone(),
+        };
+        vec![AaEndpoint {
+            name: None,
+            channel: self.aa_channel.clone(),
+            address,
+            http: Some(self.aa_http.clone()),
+            tpm: Some(self.tpm.clone()),
+            ttrpc_timeout_ms: Some(self.aa_ttrpc_timeout_ms),
+        }]
+    }
+
+    pub fn load(config_path: Option<&Path>) -> Result<Self> {
         let path = config_path.unwrap_or_else(|| Path::new("runtime-measurer-config.toml"));
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read configuration file: {:?}", path))?;
-        let config: Config = toml::from_str(&content)
-            .with_context(|| format!("Failed to parse TOML from config file: {:?}", path))?;
-        Ok(config)
+        Ok(Self::load_with_sources(path)?.config)
+    }
+
+    /// Loads `path` and merges the fragments matched by its top-level
+    /// `include` glob patterns (e.g. a `conf.d` directory) over it, in path
+    /// order: tables are merged key by key, any other value is replaced.
+    /// Relative patterns are resolved against the directory of `path`, and
+    /// only the main file's `include` is honored.
+    ///
+    /// When `path` is a directory (a `conf.d`), its `.toml`, `.json`,
+    /// `.yaml` and `.yml` files are merged in lexical order instead, and
+    /// their `include` keys are ignored.
+    ///
+    /// Files ending in `.json`, `.yaml` or `.yml` are read as JSON or YAML
+    /// and any other as TOML, unless `--config-format` names the format of
+    /// the main file.
+    ///
+    /// Environment variables and `--set` flags are applied last (see
+    /// [`config_overrides`]). Without a file at `path`, the configuration is
+    /// built from them and the defaults, provided any is set.
+    pub fn load_with_sources(path: &Path) -> Result<LoadedConfig> {
+        let overrides = config_overrides()?;
+        let is_dir = path.is_dir();
+        let from_file = !is_dir && (path.exists() || overrides.is_empty());
+        let content = if from_file {
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read configuration file: {:?}", path))?
+        } else {
+            String::new()
+        };
+        let format = CONFIG_FORMAT
+            .get()
+            .copied()
+            .unwrap_or_else(|| ConfigFormat::from_path(path));
+        let mut value = if from_file {
+            format.parse(&content).with_context(|| {
+                format!("Failed to parse {:?} from config file: {:?}", format, path)
+            })?
+        } else {
+            toml::Value::Table(toml::Table::new())
+        };
+
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        let mut sources = Vec::new();
+        if from_file {
+            sources.push(path.to_path_buf());
+        }
+
+        // A directory is read as its fragments in lexical order, the
+        // patterns only telling the watcher what to follow.
+        let (include_patterns, fragment_groups) = if is_dir {
+            let patterns = CONFIG_EXTENSIONS
+                .iter()
+                .map(|ext| {
+                    path.join(format!("*.{}", ext))
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+            (patterns, vec![config_dir_fragments(path)?])
+        } else {
+            let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+            let patterns: Vec<String> = value
+                .get("include")
+                .and_then(toml::Value::as_array)
+                .map(|patterns| {
+                    patterns
+                        .iter()
+                        .filter_map(toml::Value::as_str)
+                        .map(|p| base_dir.join(p).to_string_lossy().into_owned())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let mut groups = Vec::new();
+            for pattern in &patterns {
+                let mut fragments: Vec<PathBuf> = glob::glob(pattern)
+                    .with_context(|| format!("Invalid include pattern: {}", pattern))?
+                    .flatten()
+                    .filter(|p| p.is_file())
+                    .collect();
+                fragments.sort();
+                groups.push(fragments);
+            }
+            (patterns, groups)
+        };
+
+        for fragment in fragment_groups.into_iter().flatten() {
+            let content = fs::read_to_string(&fragment)
+                .with_context(|| format!("Failed to read config fragment: {:?}", fragment))?;
+            let format = ConfigFormat::from_path(&fragment);
+            let mut overlay = format.parse(&content).with_context(|| {
+                format!(
+                    "Failed to parse {:?} from config fragment: {:?}",
+                    format, fragment
+                )
+            })?;
+            if let Some(table) = overlay.as_table_mut() {
+                table.remove("include");
+            }
+            merge_toml(&mut value, overlay);
+            hasher.update(fragment.to_string_lossy().as_bytes());
+            hasher.update(content.as_bytes());
+            sources.push(fragment);
+        }
+
+        let mut applied = Vec::new();
+        for (origin, key, raw) in overrides {
+            let typed = override_value(&value, &key, &raw)
+                .with_context(|| format!("Cannot apply {}", origin))?;
+            set_key(&mut value, &key, typed).with_context(|| format!("Cannot apply {}", origin))?;
+            hasher.update(origin.as_bytes());
+            hasher.update(raw.as_bytes());
+            applied.push(format!("{} (from {})", key, origin));
+        }
+
+        let config: Config = value
+            .try_into()
+            .with_context(|| format!("Invalid configuration in {:?}", sources))?;
+        if let Some(ref tenant) = config.tenant {
+            if tenant.is_empty() || tenant.contains('/') {
+                anyhow::bail!("tenant must be non-empty and must not contain '/'");
+            }
+        }
+        for (prefix, alias) in &config.path_aliases {
+            if !prefix.starts_with('/')
+                || prefix.trim_end_matches('/').is_empty()
+                || alias.is_empty()
+            {
+                anyhow::bail!(
+                    "path_aliases entry {:?} = {:?} must map an absolute path below / to a name",
+                    prefix,
+                    alias
+                );
+            }
+        }
+        let sections: Vec<String> = crate::modules::registered_measurers()
+            .iter()
+            .map(|m| m.config_section().to_string())
+            .collect();
+        for section in config.failure_policies.keys() {
+            if !sections.contains(section) {
+                anyhow::bail!(
+                    "failure_policies names {:?}, which is not a measurer section",
+                    section
+                );
+            }
+        }
+        if config.local_event_log.enable && config.local_event_log.path.is_empty() {
+            anyhow::bail!("local_event_log.path must be set when local_event_log is enabled");
+        }
+        BlackoutSchedule::from_config(&config.maintenance)?;
+        Ok(LoadedConfig {
+            config,
+            sources,
+            include_patterns,
+            digest: hex::encode(hasher.finalize()),
+            overrides: applied,
+        })
+    }
+}
+
+/// The config files of a config directory, in lexical order. Files of
+/// other types and hidden files, such as editor backups, are skipped.
+fn config_dir_fragments(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut fragments = Vec::new();
+    for entry in fs::read_dir(dir)
+        .with_context(|| format!("Failed to read configuration directory: {:?}", dir))?
+    {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        let known = path
+            .extension()
+            .is_some_and(|ext| CONFIG_EXTENSIONS.contains(&ext.to_string_lossy().as_ref()));
+        if path.is_file() && known && !hidden {
+            fragments.push(path);
+        }
+    }
+    fragments.sort();
+    Ok(fragments)
+}
+
+/// Sets the format of the main config file instead of detecting it by
+/// extension. Call once, before loading.
+pub fn set_config_format(format: ConfigFormat) -> Result<()> {
+    CONFIG_FORMAT
+        .set(format)
+        .map_err(|_| anyhow::anyhow!("the config format is already set"))
+}
+
+/// Registers the `key=value` pairs of `--set` flags, with dotted keys such
+/// as `file_measurement.enable`. Call once, before loading.
+pub fn set_cli_overrides(pairs: &[String]) -> Result<()> {
+    let mut overrides = Vec::new();
+    for pair in pairs {
+        let Some((key, value)) = pair.split_once('=') else {
+            anyhow::bail!("--set expects KEY=VALUE, got {:?}", pair);
+        };
+        overrides.push((key.trim().to_string(), value.to_string()));
+    }
+    CLI_OVERRIDES
+        .set(overrides)
+        .map_err(|_| anyhow::anyhow!("config overrides are already set"))
+}
+
+/// The overrides applied over the config file, lowest precedence first, as
+/// `(origin, dotted key, raw value)`: `MEASURER_*` environment variables in
+/// name order, then `--set` flags in command-line order.
+///
+/// In variable names, `__` separates nested keys and names are lowercased,
+/// so `MEASURER_FILE_MEASUREMENT__ENABLE` sets `file_measurement.enable`.
+fn config_overrides() -> Result<Vec<(String, String, String)>> {
+    let mut from_env: Vec<(String, String)> = env::vars()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+        .collect();
+    from_env.sort();
+
+    let mut overrides = Vec::new();
+    for (name, value) in from_env {
+        let suffix = &name[ENV_PREFIX.len()..];
+        let key = match ENV_ALIASES.iter().find(|(alias, _)| *alias == suffix) {
+            Some((_, key)) => key.to_string(),
+            None => suffix
+                .split("__")
+                .map(|part| part.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("."),
+        };
+        overrides.push((name, key, value));
+    }
+    for (key, value) in CLI_OVERRIDES.get().into_iter().flatten() {
+        overrides.push((format!("--set {}", key), key.clone(), value.clone()));
+    }
+    for (origin, key, _) in &overrides {
+        if key.is_empty() || key.split('.').any(str::is_empty) {
+            anyhow::bail!("{} does not name a config key", origin);
+        }
+    }
+    Ok(overrides)
+}
+
+/// Reads an override as a TOML value (`true`, `17`, `["a", "b"]`,
+/// `"quoted"`) for the `key` it sets in `root`. It is taken as a plain string
+/// when it is not TOML, or when the field only accepts a string, so that
+/// `MEASURER_TENANT=123` sets the tenant `"123"`.
+fn override_value(root: &toml::Value, key: &str, raw: &str) -> Result<toml::Value> {
+    let string = toml::Value::String(raw.to_string());
+    let Some(parsed) = toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+    else {
+        return Ok(string);
+    };
+    if parsed.is_str() {
+        return Ok(parsed);
+    }
+    let accepted = |value: &toml::Value| -> Result<bool> {
+        let mut probe = root.clone();
+        set_key(&mut probe, key, value.clone())?;
+        Ok(probe.try_into::<Config>().is_ok())
+    };
+    if !accepted(&parsed)? && accepted(&string)? {
+        return Ok(string);
+    }
+    Ok(parsed)
+}
+
+/// Sets the dotted `key` in `root`, creating the tables on the way.
+fn set_key(root: &mut toml::Value, key: &str, value: toml::Value) -> Result<()> {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let last = parts.pop().unwrap_or_default();
+    let mut table = root
+        .as_table_mut()
+        .ok_or_else(|| anyhow::anyhow!("the configuration is not a table"))?;
+    for part in parts {
+        table = table
+            .entry(part)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("{:?} in {:?} is not a table", part, key))?;
+    }
+    table.insert(last.to_string(), value);
+    Ok(())
+}
+
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_take_the_type_of_their_field() {
+        let root = toml::Value::Table(toml::Table::new());
+        let value = |key: &str, raw: &str| override_value(&root, key, raw).unwrap();
+        assert_eq!(
+            value("tenant", "123"),
+            toml::Value::String("123".to_string())
+        );
+        assert_eq!(
+            value("tenant", "\"acme\""),
+            toml::Value::String("acme".to_string())
+        );
+        assert_eq!(
+            value("file_measurement.enable", "true"),
+            toml::Value::Boolean(true)
+        );
+        assert_eq!(
+            value("file_measurement.pcr_index", "17"),
+            toml::Value::Integer(17)
+        );
     }
 }
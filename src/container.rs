@@ -0,0 +1,83 @@
+// src/container.rs
+use crate::docker;
+use crate::error::{MeasurementError, Result};
+use log::debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SCHEME: &str = "container://";
+
+/// Shortest id prefix accepted without asking the container engine.
+const MIN_ID_PREFIX: usize = 12;
+
+/// A `container://<name-or-id>/<path>` entry, naming a file inside the mount
+/// namespace of a running container.
+#[derive(Debug)]
+pub struct ContainerPath<'a> {
+    pub container: &'a str,
+    /// Absolute path (or glob pattern) inside the container.
+    pub path: &'a str,
+}
+
+impl<'a> ContainerPath<'a> {
+    pub fn is_container_entry(entry: &str) -> bool {
+        entry.starts_with(SCHEME)
+    }
+
+    pub fn parse(entry: &'a str) -> Result<Self> {
+        let invalid = || {
+            MeasurementError::Config(format!(
+                "Invalid container entry '{}', expected container://<name-or-id>/<path>",
+                entry
+            ))
+        };
+        let rest = entry.strip_prefix(SCHEME).ok_or_else(invalid)?;
+        let (container, path) = rest.split_at(rest.find('/').ok_or_else(invalid)?);
+        let valid_name = !container.is_empty()
+            && container
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+        if !valid_name || path.len() < 2 {
+            return Err(invalid());
+        }
+        Ok(Self { container, path })
+    }
+
+    /// The operation recorded for a file found at `host_path` under `root`.
+    pub fn operation(&self, root: &Path, host_path: &Path) -> String {
+        let inner = host_path.strip_prefix(root).unwrap_or(host_path);
+        format!("{}{}/{}", SCHEME, self.container, inner.to_string_lossy())
+    }
+}
+
+/// Returns `/proc/<pid>/root` of a process in the container, through which
+/// the container's filesystem is reachable from the host.
+///
+/// Full or abbreviated ids are matched against process cgroups, which works
+/// for any engine; names, and ids no process matches, are resolved through
+/// the Docker Engine API.
+pub async fn root_dir(container: &str, docker_socket: &Path) -> Result<PathBuf> {
+    let pid = match pid_from_cgroups(container) {
+        Some(pid) => pid,
+        None => docker::container_pid(docker_socket, container).await?,
+    };
+    debug!("Container {} resolved to pid {}", container, pid);
+    Ok(PathBuf::from(format!("/proc/{}/root", pid)))
+}
+
+/// Finds the lowest pid whose cgroup path contains `id`.
+fn pid_from_cgroups(id: &str) -> Option<u32> {
+    if id.len() < MIN_ID_PREFIX || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    fs::read_dir("/proc")
+        .ok()?
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .filter(|pid| {
+            fs::read_to_string(format!("/proc/{}/cgroup", pid))
+                .map(|cgroup| cgroup.contains(id))
+                .unwrap_or(false)
+        })
+        .min()
+}
@@ -0,0 +1,259 @@
+// src/control.rs
+use crate::config::{Config, ControlConfig, MeasurementEntry};
+use crate::error::{MeasurementError, Result};
+use crate::listener;
+use crate::modules::{FileMeasurer, ModelDirMeasurer};
+use crate::rpc_client::AAClient;
+use crate::rpc_generated::measurement_control::{
+    MeasureDirRequest, MeasureFileRequest, MeasureResponse, Measurement, RemeasureRequest,
+    RemeasureResponse, StatusRequest, StatusResponse,
+};
+use crate::rpc_generated::measurement_control_ttrpc::{
+    create_measurement_control_service, MeasurementControlService,
+};
+use crate::sink::{Extension, MeasurementSink};
+use crate::status::DaemonStatus;
+use async_trait::async_trait;
+use glob::Pattern;
+use log::{info, warn};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{Notify, RwLock};
+use ttrpc::asynchronous::{Server, TtrpcContext};
+
+/// Passes measurements on to the daemon's sink and keeps those of one
+/// request for its response.
+struct RecordingSink {
+    inner: Arc<dyn MeasurementSink>,
+    extended: Mutex<Vec<Measurement>>,
+}
+
+impl RecordingSink {
+    fn record(&self, domain: &str, operation: &str, content: &str) {
+        let mut measurement = Measurement::new();
+        measurement.Domain = domain.to_string();
+        measurement.Operation = operation.to_string();
+        measurement.Content = content.to_string();
+        self.extended.lock().unwrap().push(measurement);
+    }
+}
+
+#[async_trait]
+impl MeasurementSink for RecordingSink {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn extend(
+        &self,
+        domain: &str,
+        operation: &str,
+        content: &str,
+        pcr_index: Option<u64>,
+    ) -> Result<()> {
+        self.inner
+            .extend(domain, operation, content, pcr_index)
+            .await?;
+        self.record(domain, operation, content);
+        Ok(())
+    }
+
+    async fn extend_batch(&self, extensions: &[Extension]) -> Vec<Result<()>> {
+        let results = self.inner.extend_batch(extensions).await;
+        for (extension, result) in extensions.iter().zip(&results) {
+            if result.is_ok() {
+                self.record(&extension.domain, &extension.operation, &extension.content);
+            }
+        }
+        results
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.inner.is_dry_run()
+    }
+}
+
+/// Serves `MeasureFile`, `MeasureDir`, `Remeasure` and `Status`. Files and
+/// directories are measured with the current settings of
+/// `[file_measurement]` and `[model_dir_measurement]`, enabled or not.
+struct ControlService {
+    config: Arc<RwLock<Config>>,
+    sink: Arc<dyn MeasurementSink>,
+    reload_requests: Arc<Notify>,
+    status: Arc<DaemonStatus>,
+    aa_client: Arc<AAClient>,
+}
+
+impl ControlService {
+    /// Measures one file or directory as a critical entry and returns what
+    /// was extended.
+    async fn measure(&self, path: &str, dir: bool) -> ttrpc::Result<MeasureResponse> {
+        if !Path::new(path).is_absolute() {
+            return Err(rpc_error(
+                ttrpc::Code::INVALID_ARGUMENT,
+                format!("{} is not an absolute path", path),
+            ));
+        }
+        let mut config = self.config.read().await.clone();
+        if dir
+            && config.model_dir_measurement.activate_verity
+            && !is_configured_dir(path, &config.model_dir_measurement.directories)
+        {
+            // Remounting is reserved for the directories the daemon measures
+            // itself; any other directory is only measured.
+            info!(
+                "{} is not a configured model directory; measuring it without activating verity",
+                path
+            );
+            config.model_dir_measurement.activate_verity = false;
+        }
+        let sink = Arc::new(RecordingSink {
+            inner: self.sink.clone(),
+            extended: Mutex::new(Vec::new()),
+        });
+        let entry = MeasurementEntry::Detailed {
+            path: if dir {
+                path.to_string()
+            } else {
+                Pattern::escape(path)
+            },
+            critical: true,
+            pcr_index: None,
+            hash_algorithm: None,
+        };
+        let result = if dir {
+            ModelDirMeasurer::new()
+                .measure_specific_dirs(
+                    &[entry],
+                    &config.model_dir_measurement,
+                    config.max_concurrency,
+                    sink.clone(),
+                )
+                .await
+        } else {
+            FileMeasurer::new()
+                .measure_patterns(
+                    &[entry],
+                    &config.file_measurement,
+                    config.max_concurrency,
+                    sink.clone(),
+                )
+                .await
+        };
+        if let Err(e) = result {
+            warn!("Requested measurement of {} failed: {}", path, e);
+            return Err(rpc_error(ttrpc::Code::FAILED_PRECONDITION, e.to_string()));
+        }
+        info!("Measured {} on request", path);
+
+        let mut response = MeasureResponse::new();
+        response.Measurements = std::mem::take(&mut *sink.extended.lock().unwrap());
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl MeasurementControlService for ControlService {
+    async fn measure_file(
+        &self,
+        _ctx: &TtrpcContext,
+        req: MeasureFileRequest,
+    ) -> ttrpc::Result<MeasureResponse> {
+        self.measure(&req.Path, false).await
+    }
+
+    async fn measure_dir(
+        &self,
+        _ctx: &TtrpcContext,
+        req: MeasureDirRequest,
+    ) -> ttrpc::Result<MeasureResponse> {
+        self.measure(&req.Path, true).await
+    }
+
+    /// Reloads the config and re-runs every enabled measurer in the
+    /// background, like SIGHUP.
+    async fn remeasure(
+        &self,
+        _ctx: &TtrpcContext,
+        _: RemeasureRequest,
+    ) -> ttrpc::Result<RemeasureResponse> {
+        info!("Re-measurement requested over the control socket");
+        self.reload_requests.notify_one();
+        Ok(RemeasureResponse::new())
+    }
+
+    async fn status(&self, _ctx: &TtrpcContext, _: StatusRequest) -> ttrpc::Result<StatusResponse> {
+        let mut response = StatusResponse::new();
+        response.Healthy = self.status.is_healthy(&self.aa_client);
+        response.StatusJson =
+            String::from_utf8_lossy(&self.status.report_json(&self.aa_client)).into_owned();
+        Ok(response)
+    }
+}
+
+/// Whether `path` is one of `directories`, compared by canonical path.
+fn is_configured_dir(path: &str, directories: &[MeasurementEntry]) -> bool {
+    let Ok(path) = fs::canonicalize(path) else {
+        return false;
+    };
+    directories
+        .iter()
+        .any(|entry| fs::canonicalize(entry.path()).is_ok_and(|dir| dir == path))
+}
+
+fn rpc_error(code: ttrpc::Code, message: String) -> ttrpc::Error {
+    ttrpc::Error::RpcStatus(ttrpc::get_status(code, message))
+}
+
+fn control_error(e: ttrpc::Error) -> MeasurementError {
+    MeasurementError::Other(anyhow::anyhow!("Control socket failed: {}", e))
+}
+
+/// Serves the control API on `config.socket`, or on the socket named
+/// `control` that systemd passed, until the daemon exits.
+pub async fn serve(
+    config: ControlConfig,
+    shared_config: Arc<RwLock<Config>>,
+    sink: Arc<dyn MeasurementSink>,
+    reload_requests: Arc<Notify>,
+    status: Arc<DaemonStatus>,
+    aa_client: Arc<AAClient>,
+) -> Result<()> {
+    let service = ControlService {
+        config: shared_config,
+        sink,
+        reload_requests,
+        status,
+        aa_client,
+    };
+    let server = match listener::activated_fd("control") {
+        Some(fd) => {
+            info!("Control API listening on the socket passed by systemd");
+            Server::new().add_listener(fd).map_err(control_error)?
+        }
+        None => {
+            let server = listener::bind_unix_socket(
+                Path::new(&config.socket),
+                config.socket_owner.as_deref(),
+                config.socket_group.as_deref(),
+                config.socket_mode,
+                |socket| {
+                    Server::new()
+                        .bind(&format!("unix://{}", socket.display()))
+                        .map_err(control_error)
+                },
+            )?;
+            info!(
+                "Control API listening on {} (mode {:o})",
+                config.socket, config.socket_mode
+            );
+            server
+        }
+    };
+    let mut server = server.register_service(create_measurement_control_service(Arc::new(service)));
+    server.start().await.map_err(control_error)?;
+
+    std::future::pending::<()>().await;
+    Ok(())
+}
@@ -0,0 +1,160 @@
+// src/daemon.rs
+use anyhow::{bail, Context, Result};
+use log::{debug, warn};
+use std::env;
+use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::UnixDatagram;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A pidfile owned by the running daemon, removed again on drop.
+pub struct Pidfile {
+    path: PathBuf,
+    pid: u32,
+}
+
+impl Pidfile {
+    /// Fails if the pidfile names a process that is still alive. A stale
+    /// pidfile left behind by a crashed instance is removed.
+    pub fn check(path: &Path) -> Result<()> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read pidfile {:?}", path)),
+        };
+        if let Ok(pid) = content.trim().parse::<i32>() {
+            if pid > 0 && process_alive(pid) {
+                bail!(
+                    "measurement tool is already running with pid {} (pidfile {:?})",
+                    pid,
+                    path
+                );
+            }
+        }
+        warn!("Removing stale pidfile {:?}", path);
+        fs::remove_file(path).with_context(|| format!("Failed to remove stale pidfile {:?}", path))
+    }
+
+    /// Writes the current process id. Creation is exclusive, so two instances
+    /// racing for the same pidfile cannot both succeed.
+    pub fn acquire(path: &Path) -> Result<Self> {
+        Self::check(path)?;
+        let pid = std::process::id();
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .with_context(|| format!("Failed to create pidfile {:?}", path))?;
+        writeln!(file, "{}", pid)?;
+        debug!("Wrote pid {} to {:?}", pid, path);
+        Ok(Self {
+            path: path.to_path_buf(),
+            pid,
+        })
+    }
+}
+
+impl Drop for Pidfile {
+    fn drop(&mut self) {
+        // Only remove the file if it still names us.
+        let ours = fs::read_to_string(&self.path)
+            .map(|c| c.trim() == self.pid.to_string())
+            .unwrap_or(false);
+        if ours {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+fn process_alive(pid: i32) -> bool {
+    // SAFETY: signal 0 performs the permission and existence checks only.
+    let ret = unsafe { libc::kill(pid, 0) };
+    ret == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Detaches from the controlling terminal with the classic double fork.
+///
+/// Must be called before any threads (including the tokio runtime) exist.
+/// stdin and stdout are redirected to /dev/null; stderr is kept so that the
+/// init system can still capture logs.
+pub fn daemonize() -> Result<()> {
+    fork_and_exit_parent()?;
+    // SAFETY: setsid has no memory-safety preconditions.
+    if unsafe { libc::setsid() } < 0 {
+        return Err(std::io::Error::last_os_error()).context("setsid failed");
+    }
+    fork_and_exit_parent()?;
+
+    let dev_null = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/null")
+        .context("Failed to open /dev/null")?;
+    for fd in [libc::STDIN_FILENO, libc::STDOUT_FILENO] {
+        // SAFETY: both descriptors are valid for the lifetime of the call.
+        if unsafe { libc::dup2(dev_null.as_raw_fd(), fd) } < 0 {
+            return Err(std::io::Error::last_os_error()).context("dup2 failed");
+        }
+    }
+    Ok(())
+}
+
+fn fork_and_exit_parent() -> Result<()> {
+    // SAFETY: called while the process is still single-threaded.
+    match unsafe { libc::fork() } {
+        -1 => Err(std::io::Error::last_os_error()).context("fork failed"),
+        0 => Ok(()),
+        _ => std::process::exit(0),
+    }
+}
+
+/// Sends `state` (e.g. `READY=1`) to the service manager when it passed a
+/// notification socket, as for systemd's `Type=notify`; a no-op otherwise.
+pub fn sd_notify(state: &str) -> Result<()> {
+    let Some(socket_path) = env::var_os("NOTIFY_SOCKET") else {
+        return Ok(());
+    };
+    let socket = UnixDatagram::unbound().context("Failed to create notify socket")?;
+    let socket_path = socket_path.into_vec();
+    if let Some(name) = socket_path.strip_prefix(b"@") {
+        send_abstract(&socket, name, state)?;
+    } else {
+        socket
+            .send_to(state.as_bytes(), OsStr::from_bytes(&socket_path))
+            .context("Failed to notify the service manager")?;
+    }
+    debug!("Notified the service manager: {}", state);
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn send_abstract(socket: &UnixDatagram, name: &[u8], state: &str) -> Result<()> {
+    use std::os::linux::net::SocketAddrExt;
+    let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+    socket
+        .send_to_addr(state.as_bytes(), &addr)
+        .context("Failed to notify the service manager")?;
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send_abstract(_socket: &UnixDatagram, _name: &[u8], _state: &str) -> Result<()> {
+    bail!("Abstract notify sockets are only supported on Linux")
+}
+
+/// How often to send `WATCHDOG=1`: half the interval the service manager
+/// set in `WATCHDOG_USEC`, or `None` when the watchdog is not enabled for
+/// this process.
+pub fn watchdog_interval() -> Option<Duration> {
+    let usec: u64 = env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+    if let Ok(pid) = env::var("WATCHDOG_PID") {
+        if pid.parse::<u32>().ok()? != std::process::id() {
+            return None;
+        }
+    }
+    (usec > 0).then(|| Duration::from_micros(usec / 2))
+}
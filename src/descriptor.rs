@@ -0,0 +1,70 @@
+// src/descriptor.rs
+use crate::canonical_json;
+use crate::config::ContentLimitsConfig;
+use crate::error::{MeasurementError, Result};
+use log::{info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extended in place of content larger than `content_limits.max_content_bytes`.
+/// The full payload is kept at `locator`, named by its digest.
+#[derive(Debug, Serialize)]
+pub struct ContentDescriptor {
+    pub digest: String,
+    pub size: usize,
+    pub locator: String,
+}
+
+/// Replaces oversized content with a digest-plus-locator descriptor.
+pub struct ContentLimiter {
+    max_content_bytes: usize,
+    store_dir: PathBuf,
+}
+
+impl ContentLimiter {
+    pub fn from_config(config: &ContentLimitsConfig) -> Self {
+        Self {
+            max_content_bytes: config.max_content_bytes,
+            store_dir: PathBuf::from(&config.store_dir),
+        }
+    }
+
+    /// Returns `content` unchanged if it fits, otherwise stores it and returns
+    /// the canonical JSON encoding of its descriptor.
+    pub fn limit(&self, domain: &str, operation: &str, content: &str) -> Result<String> {
+        if content.len() <= self.max_content_bytes {
+            return Ok(content.to_string());
+        }
+        let hex_digest = hex::encode(Sha256::digest(content.as_bytes()));
+        let path = self.store_dir.join(&hex_digest);
+        // The descriptor binds the payload through its digest, so a payload
+        // that cannot be stored is reported but does not block the extension.
+        if let Err(e) = store(&self.store_dir, &path, content) {
+            warn!("Failed to store oversized content at {:?}: {}", path, e);
+        }
+        info!(
+            "Content of {} {} is {} bytes; extending a descriptor instead",
+            domain,
+            operation,
+            content.len()
+        );
+        let descriptor = ContentDescriptor {
+            digest: format!("sha256:{}", hex_digest),
+            size: content.len(),
+            locator: format!("file://{}", path.to_string_lossy()),
+        };
+        let encoded = canonical_json::to_vec(&descriptor)
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        Ok(String::from_utf8_lossy(&encoded).into_owned())
+    }
+}
+
+fn store(dir: &Path, path: &Path, content: &str) -> std::io::Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    fs::create_dir_all(dir)?;
+    fs::write(path, content)
+}
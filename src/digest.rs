@@ -0,0 +1,176 @@
+// src/digest.rs
+//! Registry of the hash algorithms measurers accept as `hash_algorithm`.
+//! A new algorithm is added to [`ALGORITHMS`] and is then available to
+//! every measurer hashing through [`Hasher`].
+use crate::error::{MeasurementError, Result};
+use sha2::digest::{DynDigest, InvalidBufferSize};
+use sha2::{Sha256, Sha384, Sha512};
+use std::io::{self, Read};
+
+struct Algorithm {
+    /// Name in the configuration and prefix of the extended digests.
+    name: &'static str,
+    new: fn() -> Box<dyn DynDigest + Send>,
+}
+
+fn sha256() -> Box<dyn DynDigest + Send> {
+    Box::new(Sha256::default())
+}
+
+fn sha384() -> Box<dyn DynDigest + Send> {
+    Box::new(Sha384::default())
+}
+
+fn sha512() -> Box<dyn DynDigest + Send> {
+    Box::new(Sha512::default())
+}
+
+/// Several times faster than SHA-2 on large files, such as model weights.
+fn blake3() -> Box<dyn DynDigest + Send> {
+    Box::new(Blake3::default())
+}
+
+/// BLAKE3 behind the `DynDigest` of `sha2`, whichever `digest` version the
+/// `blake3` crate itself implements.
+#[derive(Clone, Default)]
+struct Blake3(blake3::Hasher);
+
+impl DynDigest for Blake3 {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize_into(mut self, buf: &mut [u8]) -> std::result::Result<(), InvalidBufferSize> {
+        self.finalize_into_reset(buf)
+    }
+
+    fn finalize_into_reset(
+        &mut self,
+        out: &mut [u8],
+    ) -> std::result::Result<(), InvalidBufferSize> {
+        if out.len() != blake3::OUT_LEN {
+            return Err(InvalidBufferSize);
+        }
+        out.copy_from_slice(self.0.finalize().as_bytes());
+        self.0.reset();
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    fn output_size(&self) -> usize {
+        blake3::OUT_LEN
+    }
+
+    fn box_clone(&self) -> Box<dyn DynDigest> {
+        Box::new(self.clone())
+    }
+}
+
+/// GB/T 32905, required by some regulated deployments.
+fn sm3() -> Box<dyn DynDigest + Send> {
+    Box::new(sm3::Sm3::default())
+}
+
+const ALGORITHMS: &[Algorithm] = &[
+    Algorithm {
+        name: "sha256",
+        new: sha256,
+    },
+    Algorithm {
+        name: "sha384",
+        new: sha384,
+    },
+    Algorithm {
+        name: "sha512",
+        new: sha512,
+    },
+    Algorithm {
+        name: "blake3",
+        new: blake3,
+    },
+    Algorithm {
+        name: "sm3",
+        new: sm3,
+    },
+];
+
+fn find(name: &str) -> Option<&'static Algorithm> {
+    ALGORITHMS.iter().find(|algorithm| algorithm.name == name)
+}
+
+/// The registered name of the algorithm `name` names, in any case and with
+/// or without dashes (`SHA-256`, `sha256`).
+pub fn canonical_name(name: &str) -> Option<&'static str> {
+    find(&name.to_lowercase().replace('-', "")).map(|algorithm| algorithm.name)
+}
+
+/// An incremental hash of a registered algorithm.
+pub struct Hasher {
+    name: &'static str,
+    inner: Box<dyn DynDigest + Send>,
+}
+
+impl Hasher {
+    /// A hasher of the algorithm named `name`, in any case.
+    pub fn new(name: &str) -> Result<Self> {
+        let lowercase = name.to_lowercase();
+        find(&lowercase)
+            .map(|algorithm| Self {
+                name: algorithm.name,
+                inner: (algorithm.new)(),
+            })
+            .ok_or(MeasurementError::UnsupportedHashAlgorithm(lowercase))
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Hashes everything `reader` yields.
+    pub fn update_reader(&mut self, reader: &mut impl Read) -> Result<()> {
+        io::copy(reader, self)?;
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        self.inner.finalize().to_vec()
+    }
+
+    pub fn finalize_hex(self) -> String {
+        hex::encode(self.finalize())
+    }
+
+    /// The digest as extended: `<algorithm>:<hex>`.
+    pub fn finalize_prefixed(self) -> String {
+        let name = self.name;
+        format!("{}:{}", name, self.finalize_hex())
+    }
+}
+
+impl io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `<algorithm>:<hex>` of `content`.
+pub fn digest_bytes(content: &[u8], hash_algorithm: &str) -> Result<String> {
+    let mut hasher = Hasher::new(hash_algorithm)?;
+    hasher.update(content);
+    Ok(hasher.finalize_prefixed())
+}
+
+/// `<algorithm>:<hex>` of everything `reader` yields.
+pub fn digest_reader(reader: &mut impl Read, hash_algorithm: &str) -> Result<String> {
+    let mut hasher = Hasher::new(hash_algorithm)?;
+    hasher.update_reader(reader)?;
+    Ok(hasher.finalize_prefixed())
+}
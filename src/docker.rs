@@ -0,0 +1,291 @@
+// src/docker.rs
+use crate::canonical_json;
+use crate::config::Config;
+use crate::error::{MeasurementError, Result};
+use crate::sink::MeasurementSink;
+use log::{debug, info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tokio::time::sleep;
+
+const SPEC_DOMAIN: &str = "container_spec";
+const IMAGE_DOMAIN: &str = "container_image";
+
+/// `filters={"type":["container"],"event":["create","start","destroy"]}`
+const EVENTS_PATH: &str = "/events?filters=%7B%22type%22%3A%5B%22container%22%5D%2C%22event%22%3A\
+%5B%22create%22%2C%22start%22%2C%22destroy%22%5D%7D";
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// The parts of a Docker container config that are measured, serialized with
+/// sorted keys so that the digest does not depend on map iteration order.
+#[derive(Serialize)]
+struct ContainerConfigDigestInput<'a> {
+    path: &'a str,
+    args: Vec<&'a str>,
+    env: Vec<&'a str>,
+    mounts: Vec<MountDigestInput<'a>>,
+    labels: BTreeMap<&'a str, &'a str>,
+    privileged: bool,
+}
+
+#[derive(Serialize)]
+struct MountDigestInput<'a> {
+    destination: &'a str,
+    #[serde(rename = "type")]
+    mount_type: &'a str,
+    source: &'a str,
+    rw: bool,
+}
+
+/// Minimal Docker Engine API client over the unix socket.
+///
+/// Requests are sent as HTTP/1.0 so that dockerd answers without chunked
+/// encoding and ends every response by closing the connection.
+pub(crate) struct DockerClient {
+    socket_path: PathBuf,
+}
+
+impl DockerClient {
+    pub(crate) fn new(socket_path: &Path) -> Self {
+        Self {
+            socket_path: socket_path.to_path_buf(),
+        }
+    }
+
+    async fn request(&self, path: &str) -> Result<BufReader<UnixStream>> {
+        let mut stream = UnixStream::connect(&self.socket_path).await?;
+        stream
+            .write_all(format!("GET {} HTTP/1.0\r\nHost: docker\r\n\r\n", path).as_bytes())
+            .await?;
+        let mut reader = BufReader::new(stream);
+
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line).await?;
+        let status = status_line.split_whitespace().nth(1).unwrap_or_default();
+        if !status.starts_with('2') {
+            let mut body = String::new();
+            reader.read_to_string(&mut body).await.ok();
+            let body = body.rsplit("\r\n\r\n").next().unwrap_or_default();
+            return Err(MeasurementError::Docker(format!(
+                "GET {} returned {}: {}",
+                path,
+                status_line.trim(),
+                body.trim()
+            )));
+        }
+        // Skip the headers.
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await? == 0 || line == "\r\n" {
+                break;
+            }
+        }
+        Ok(reader)
+    }
+
+    pub(crate) async fn get_json(&self, path: &str) -> Result<Value> {
+        let mut body = Vec::new();
+        self.request(path).await?.read_to_end(&mut body).await?;
+        serde_json::from_slice(&body)
+            .map_err(|e| MeasurementError::Docker(format!("Invalid response to {}: {}", path, e)))
+    }
+}
+
+struct DockerWatcher {
+    client: DockerClient,
+    pcr_index: Option<u64>,
+    structured_content: bool,
+    sink: Arc<dyn MeasurementSink>,
+    measured: HashSet<String>,
+}
+
+impl DockerWatcher {
+    /// Measures containers that are already running, e.g. those started
+    /// before the tool or while the event stream was disconnected.
+    async fn measure_running(&mut self) -> Result<()> {
+        let containers = self.client.get_json("/containers/json").await?;
+        for container in containers.as_array().into_iter().flatten() {
+            if let Some(id) = container["Id"].as_str() {
+                self.measure_once(id).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes the event stream until dockerd closes it.
+    async fn follow_events(&mut self, stream: BufReader<UnixStream>) -> Result<()> {
+        let mut events = stream.lines();
+        while let Some(line) = events.next_line().await? {
+            let event: Value = match serde_json::from_str(&line) {
+                Ok(event) => event,
+                Err(e) => {
+                    debug!("Skipping malformed Docker event: {}", e);
+                    continue;
+                }
+            };
+            let id = event["Actor"]["ID"].as_str().unwrap_or_default();
+            match event["Action"].as_str().unwrap_or_default() {
+                // A container is measured when created; `start` covers
+                // containers created before the tool was watching.
+                "create" | "start" => self.measure_once(id).await,
+                "destroy" => {
+                    self.measured.remove(id);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    async fn measure_once(&mut self, id: &str) {
+        if id.is_empty() || self.measured.contains(id) {
+            return;
+        }
+        match self.measure_container(id).await {
+            Ok(()) => {
+                self.measured.insert(id.to_string());
+            }
+            Err(e) => warn!("Failed to measure Docker container {}: {}", id, e),
+        }
+    }
+
+    async fn measure_container(&self, id: &str) -> Result<()> {
+        let container = self
+            .client
+            .get_json(&format!("/containers/{}/json", id))
+            .await?;
+        let name = container["Name"]
+            .as_str()
+            .unwrap_or(id)
+            .trim_start_matches('/');
+        let operation = format!("{}/{}", name, short_id(id));
+
+        let config_digest = container_config_digest(&container, self.structured_content)?;
+        debug!("Container {} config digest: {}", operation, config_digest);
+        self.sink
+            .extend(SPEC_DOMAIN, &operation, &config_digest, self.pcr_index)
+            .await?;
+
+        // The image id is the digest of the image config, which pins the
+        // layers; repo digests are recorded by dockerd only for pulled images.
+        let image_id = container["Image"]
+            .as_str()
+            .ok_or_else(|| MeasurementError::Docker(format!("Container {} has no image id", id)))?;
+        self.sink
+            .extend(IMAGE_DOMAIN, &operation, image_id, self.pcr_index)
+            .await?;
+        info!("Measured Docker container {}", operation);
+        Ok(())
+    }
+}
+
+/// Returns `sha256:<hex>` over the container's command, env, mounts, labels
+/// and privileged flag.
+fn container_config_digest(container: &Value, canonical: bool) -> Result<String> {
+    let args = string_array(&container["Args"]);
+    let env = string_array(&container["Config"]["Env"]);
+    let labels: BTreeMap<&str, &str> = container["Config"]["Labels"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(k, v)| (k.as_str(), v.as_str().unwrap_or_default()))
+        .collect();
+    let input = ContainerConfigDigestInput {
+        path: container["Path"].as_str().unwrap_or_default(),
+        args,
+        env,
+        mounts: container["Mounts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|m| MountDigestInput {
+                destination: m["Destination"].as_str().unwrap_or_default(),
+                mount_type: m["Type"].as_str().unwrap_or_default(),
+                source: m["Source"].as_str().unwrap_or_default(),
+                rw: m["RW"].as_bool().unwrap_or_default(),
+            })
+            .collect(),
+        labels,
+        privileged: container["HostConfig"]["Privileged"]
+            .as_bool()
+            .unwrap_or_default(),
+    };
+    let encoded = canonical_json::encode(&input, canonical)
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    Ok(format!("sha256:{}", hex::encode(Sha256::digest(&encoded))))
+}
+
+fn string_array(value: &Value) -> Vec<&str> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..id.len().min(12)]
+}
+
+/// Returns the pid of a running container's main process, looked up by name
+/// or id through the Docker Engine API.
+pub async fn container_pid(socket_path: &Path, name_or_id: &str) -> Result<u32> {
+    let client = DockerClient {
+        socket_path: socket_path.to_path_buf(),
+    };
+    let container = client
+        .get_json(&format!("/containers/{}/json", name_or_id))
+        .await?;
+    match container["State"]["Pid"].as_u64() {
+        Some(pid) if pid > 0 => Ok(pid as u32),
+        _ => Err(MeasurementError::Docker(format!(
+            "Container {} is not running",
+            name_or_id
+        ))),
+    }
+}
+
+/// Measures existing containers, then follows the Docker event stream,
+/// reconnecting whenever dockerd restarts.
+pub async fn watch_events(config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+    let docker = &config.docker_measurement;
+    let socket_path = Path::new(&docker.socket_path);
+    info!("Watching Docker engine at {:?}", socket_path);
+    let mut watcher = DockerWatcher {
+        client: DockerClient {
+            socket_path: socket_path.to_path_buf(),
+        },
+        pcr_index: docker.pcr_index.map(|v| v as u64),
+        structured_content: config.structured_content,
+        sink,
+        measured: HashSet::new(),
+    };
+
+    loop {
+        // Subscribe before listing so that no container created in between
+        // is missed; measuring twice is prevented by the measured set.
+        match watcher.client.request(EVENTS_PATH).await {
+            Ok(stream) => {
+                info!("Subscribed to Docker container events");
+                if let Err(e) = watcher.measure_running().await {
+                    warn!("Failed to list Docker containers: {}", e);
+                }
+                match watcher.follow_events(stream).await {
+                    Ok(()) => warn!("Docker event stream closed"),
+                    Err(e) => warn!("Docker event stream failed: {}", e),
+                }
+            }
+            Err(e) => warn!("Cannot subscribe to Docker events: {}", e),
+        }
+        sleep(RECONNECT_DELAY).await;
+    }
+}
@@ -0,0 +1,452 @@
+// src/doctor.rs
+use crate::config::{AaEndpoint, Config, MeasurementChannel, VerityBackend};
+use crate::container::ContainerPath;
+use glob::glob;
+use std::ffi::CString;
+use std::fs::{self, File, OpenOptions};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::process::Command;
+use ttrpc::asynchronous::Client;
+
+/// inotify limits below these are likely to break config watching on busy hosts.
+#[cfg(target_os = "linux")]
+const MIN_INOTIFY_WATCHES: u64 = 8192;
+#[cfg(target_os = "linux")]
+const MIN_INOTIFY_INSTANCES: u64 = 128;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// One diagnostic result, with a suggested fix when it is not `Ok`.
+struct Finding {
+    severity: Severity,
+    message: String,
+    hint: Option<String>,
+}
+
+impl Finding {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Ok,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Fail,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Runs the `doctor` subcommand and returns the process exit code: 0 when no
+/// check failed (warnings are allowed), 1 otherwise.
+pub fn run_doctor(config_path: &Path) -> i32 {
+    let mut findings = Vec::new();
+
+    let config = match Config::load(Some(config_path)) {
+        Ok(cfg) => {
+            findings.push(Finding::ok(format!(
+                "Loaded configuration {:?}",
+                config_path
+            )));
+            Some(cfg)
+        }
+        Err(e) => {
+            findings.push(Finding::fail(
+                format!("Cannot load configuration: {:#}", e),
+                "Pass the config path explicitly or fix the reported TOML error",
+            ));
+            None
+        }
+    };
+
+    if let Some(ref config) = config {
+        let event_log = &config.local_event_log;
+        if event_log.enable && !event_log.forward_to_aa {
+            findings.push(Finding::warn(
+                format!(
+                    "Measurements go to the local event log {} only, not an Attestation Agent",
+                    event_log.path
+                ),
+                "Set [local_event_log].forward_to_aa = true outside development",
+            ));
+        } else {
+            findings.extend(config.resolved_aa_endpoints().iter().map(check_backend));
+        }
+    }
+    findings.push(check_tee());
+    if let Some(ref config) = config {
+        findings.push(check_cryptpilot(config));
+    }
+    #[cfg(target_os = "linux")]
+    findings.extend(check_inotify());
+    if let Some(ref config) = config {
+        findings.extend(check_paths(config));
+    }
+
+    for finding in &findings {
+        let tag = match finding.severity {
+            Severity::Ok => " OK ",
+            Severity::Warn => "WARN",
+            Severity::Fail => "FAIL",
+        };
+        println!("[{}] {}", tag, finding.message);
+        if let Some(ref hint) = finding.hint {
+            println!("       -> {}", hint);
+        }
+    }
+
+    let failures = count(&findings, Severity::Fail);
+    println!(
+        "{} ok, {} warnings, {} failures",
+        count(&findings, Severity::Ok),
+        count(&findings, Severity::Warn),
+        failures
+    );
+    if failures == 0 {
+        0
+    } else {
+        1
+    }
+}
+
+fn count(findings: &[Finding], severity: Severity) -> usize {
+    findings.iter().filter(|f| f.severity == severity).count()
+}
+
+fn check_backend(endpoint: &AaEndpoint) -> Finding {
+    match endpoint.channel {
+        MeasurementChannel::UnixSocket => {
+            let socket = &endpoint.address;
+            let path = socket.strip_prefix("unix://").unwrap_or(socket);
+            if path.starts_with('@') {
+                return Finding::ok(format!("Using abstract AA socket {}", path));
+            }
+            match fs::metadata(path) {
+                Ok(meta) if !meta.file_type().is_socket() => Finding::fail(
+                    format!("{} exists but is not a socket", path),
+                    "Point attestation_agent_socket at the Attestation Agent ttrpc socket",
+                ),
+                Ok(_) => match UnixStream::connect(path) {
+                    Ok(_) => Finding::ok(format!("AA socket {} accepts connections", path)),
+                    Err(e) => Finding::fail(
+                        format!("Cannot connect to AA socket {}: {}", path, e),
+                        "Check that attestation-agent runs and this user may use the socket",
+                    ),
+                },
+                Err(e) => Finding::fail(
+                    format!("AA socket {} not found: {}", path, e),
+                    "Start attestation-agent or correct attestation_agent_socket",
+                ),
+            }
+        }
+        MeasurementChannel::HttpApi if endpoint.address.is_empty() => Finding::fail(
+            "aa_channel is http_api but trustiflux_api_endpoint is not set",
+            "Set trustiflux_api_endpoint in the configuration",
+        ),
+        MeasurementChannel::HttpApi => {
+            let http = endpoint.http.clone().unwrap_or_default();
+            let unreadable: Vec<String> = [
+                http.ca_cert_path,
+                http.client_cert_path,
+                http.client_key_path,
+                http.token_path,
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|path| File::open(path).is_err())
+            .collect();
+            if unreadable.is_empty() {
+                Finding::ok(format!(
+                    "Using trustiflux API endpoint {}",
+                    endpoint.address
+                ))
+            } else {
+                Finding::fail(
+                    format!(
+                        "Cannot read {} for trustiflux API endpoint {}",
+                        unreadable.join(", "),
+                        endpoint.address
+                    ),
+                    "Fix the paths in [aa_http] or the file permissions",
+                )
+            }
+        }
+        MeasurementChannel::Vsock if endpoint.address.is_empty() => Finding::fail(
+            "aa_channel is vsock but aa_vsock_cid is not set",
+            "Set aa_vsock_cid to the context ID of the VM running the Attestation Agent",
+        ),
+        MeasurementChannel::Vsock => match Client::connect(&endpoint.address) {
+            Ok(_) => Finding::ok(format!("AA vsock {} accepts connections", endpoint.address)),
+            Err(e) => Finding::fail(
+                format!("Cannot connect to AA vsock {}: {}", endpoint.address, e),
+                "Check aa_vsock_cid and aa_vsock_port and that attestation-agent listens on vsock",
+            ),
+        },
+        MeasurementChannel::Tpm => match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&endpoint.address)
+        {
+            Ok(_) => Finding::ok(format!("TPM device {} can be opened", endpoint.address)),
+            Err(e) => Finding::fail(
+                format!("Cannot open TPM device {}: {}", endpoint.address, e),
+                "Check that the (v)TPM driver is loaded and this user may use the device, or correct [tpm].device",
+            ),
+        },
+    }
+}
+
+fn check_tee() -> Finding {
+    let candidates = [
+        ("/dev/tdx_guest", "Intel TDX"),
+        ("/dev/tdx-guest", "Intel TDX"),
+        ("/dev/sev-guest", "AMD SEV-SNP"),
+        ("/dev/tpmrm0", "TPM"),
+        ("/dev/tpm0", "TPM"),
+    ];
+    for (device, tee) in candidates {
+        if Path::new(device).exists() {
+            return Finding::ok(format!("Detected {} ({})", tee, device));
+        }
+    }
+    Finding::warn(
+        "No TEE or TPM device found",
+        "Measurements can only be extended inside a confidential guest or with a (v)TPM",
+    )
+}
+
+fn check_cryptpilot(config: &Config) -> Finding {
+    let md_config = &config.model_dir_measurement;
+    let binary = &md_config.cryptpilot_binary;
+    let output = Command::new(binary).arg("--version").output();
+    let severity_if_missing = if md_config.enable && md_config.backend == VerityBackend::Cryptpilot
+    {
+        Severity::Fail
+    } else {
+        Severity::Warn
+    };
+    match output {
+        Ok(out) if out.status.success() => {
+            let version = String::from_utf8_lossy(&out.stdout);
+            Finding::ok(format!("cryptpilot available: {}", version.trim()))
+        }
+        Ok(out) => missing(
+            severity_if_missing,
+            format!("'{} --version' exited with {}", binary, out.status),
+            "Reinstall cryptpilot or set model_dir_measurement.cryptpilot_binary",
+        ),
+        Err(e) => missing(
+            severity_if_missing,
+            format!("Cannot run cryptpilot binary '{}': {}", binary, e),
+            "Install cryptpilot, set model_dir_measurement.cryptpilot_binary or use backend = \"native\"",
+        ),
+    }
+}
+
+fn missing(severity: Severity, message: String, hint: &str) -> Finding {
+    match severity {
+        Severity::Fail => Finding::fail(message, hint),
+        _ => Finding::warn(message, hint),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn check_inotify() -> Vec<Finding> {
+    [
+        ("max_user_watches", MIN_INOTIFY_WATCHES),
+        ("max_user_instances", MIN_INOTIFY_INSTANCES),
+    ]
+    .into_iter()
+    .map(|(name, minimum)| {
+        let path = format!("/proc/sys/fs/inotify/{}", name);
+        match fs::read_to_string(&path).map(|s| s.trim().parse::<u64>()) {
+            Ok(Ok(value)) if value >= minimum => {
+                Finding::ok(format!("inotify {} = {}", name, value))
+            }
+            Ok(Ok(value)) => Finding::warn(
+                format!("inotify {} is low ({} < {})", name, value, minimum),
+                format!("Raise it with: sysctl fs.inotify.{}={}", name, minimum),
+            ),
+            _ => Finding::warn(
+                format!("Cannot read {}", path),
+                "Config watching relies on inotify; check that /proc is mounted",
+            ),
+        }
+    })
+    .collect()
+}
+
+fn check_paths(config: &Config) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if config.file_measurement.enable {
+        for entry in &config.file_measurement.files {
+            let pattern = entry.path();
+            if ContainerPath::is_container_entry(pattern) {
+                // The container may not be running yet; only the form is checked.
+                findings.push(match ContainerPath::parse(pattern) {
+                    Ok(_) => Finding::ok(format!(
+                        "Container entry '{}' is resolved at measurement time",
+                        pattern
+                    )),
+                    Err(e) => {
+                        Finding::fail(e.to_string(), "Fix the entry in file_measurement.files")
+                    }
+                });
+                continue;
+            }
+            let entries = match glob(pattern) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    findings.push(Finding::fail(
+                        format!("Invalid glob pattern '{}': {}", pattern, e),
+                        "Fix the pattern in file_measurement.files",
+                    ));
+                    continue;
+                }
+            };
+            let mut matched = 0;
+            let mut unreadable = Vec::new();
+            for path in entries.flatten().filter(|p| p.is_file()) {
+                matched += 1;
+                if File::open(&path).is_err() {
+                    unreadable.push(path.to_string_lossy().to_string());
+                }
+            }
+            findings.push(if matched == 0 && entry.is_critical() {
+                Finding::fail(
+                    format!("Critical pattern '{}' matches no files", pattern),
+                    "Restore the file; measuring it is required for the run to succeed",
+                )
+            } else if matched == 0 {
+                Finding::warn(
+                    format!("Pattern '{}' matches no files", pattern),
+                    "Check the path, or remove the pattern if it is no longer needed",
+                )
+            } else if !unreadable.is_empty() {
+                Finding::fail(
+                    format!(
+                        "Pattern '{}': cannot read {}",
+                        pattern,
+                        unreadable.join(", ")
+                    ),
+                    "Run as a user that can read the measured files",
+                )
+            } else {
+                Finding::ok(format!(
+                    "Pattern '{}' matches {} readable files",
+                    pattern, matched
+                ))
+            });
+        }
+    }
+
+    if config.model_dir_measurement.enable {
+        for entry in &config.model_dir_measurement.directories {
+            let dir = entry.path();
+            findings.push(match fs::read_dir(dir) {
+                Ok(_) => Finding::ok(format!("Model directory {} is readable", dir)),
+                Err(e) => Finding::fail(
+                    format!("Cannot read model directory {}: {}", dir, e),
+                    "Fix model_dir_measurement.directories or the directory permissions",
+                ),
+            });
+        }
+    }
+
+    if config.dataset_measurement.enable {
+        for entry in &config.dataset_measurement.directories {
+            let dir = entry.path();
+            findings.push(match fs::read_dir(dir) {
+                Ok(_) => Finding::ok(format!("Dataset directory {} is readable", dir)),
+                Err(e) => Finding::fail(
+                    format!("Cannot read dataset directory {}: {}", dir, e),
+                    "Fix dataset_measurement.directories or the directory permissions",
+                ),
+            });
+        }
+    }
+
+    if config.dir_tree_measurement.enable {
+        for entry in &config.dir_tree_measurement.directories {
+            let dir = entry.path();
+            findings.push(match fs::read_dir(dir) {
+                Ok(_) => Finding::ok(format!("Directory tree {} is readable", dir)),
+                Err(e) => Finding::fail(
+                    format!("Cannot read directory tree {}: {}", dir, e),
+                    "Fix dir_tree_measurement.directories or the directory permissions",
+                ),
+            });
+        }
+    }
+
+    let outputs = [
+        ("summary_report_path", &config.summary_report_path),
+        ("journal_path", &config.journal_path),
+        ("state_path", &config.state_path),
+        (
+            "file_measurement.hash_cache_path",
+            &config.file_measurement.hash_cache_path,
+        ),
+    ];
+    for (key, value) in outputs {
+        if let Some(path) = value {
+            findings.push(check_writable_parent(key, Path::new(path)));
+        }
+    }
+    if config.control.enable {
+        findings.push(check_writable_parent(
+            "control.socket",
+            Path::new(&config.control.socket),
+        ));
+    }
+
+    findings
+}
+
+fn check_writable_parent(key: &str, path: &Path) -> Finding {
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let Ok(c_parent) = CString::new(parent.as_os_str().as_bytes()) else {
+        return Finding::fail(
+            format!("{} {:?} is not a valid path", key, path),
+            "Fix the path",
+        );
+    };
+    // SAFETY: c_parent is a valid NUL-terminated string for the duration of the call.
+    if unsafe { libc::access(c_parent.as_ptr(), libc::W_OK) } == 0 {
+        Finding::ok(format!("{} directory {:?} is writable", key, parent))
+    } else {
+        Finding::fail(
+            format!(
+                "{} directory {:?} is not writable: {}",
+                key,
+                parent,
+                std::io::Error::last_os_error()
+            ),
+            format!("Create {:?} or grant this user write access", parent),
+        )
+    }
+}
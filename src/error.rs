@@ -18,12 +18,33 @@ pub enum MeasurementError {
     #[error("Invalid directory for measurement: {0}")]
     InvalidDirectory(String),
 
+    #[error("Path rejected by the measurement policy: {0}")]
+    PathPolicy(String),
+
     #[error("Command execution failed: {0}")]
     CommandExecution(String),
 
     #[error("HTTP request failed: {0}")]
     Http(String),
 
+    #[error("NRI plugin error: {0}")]
+    Nri(String),
+
+    #[error("Docker engine error: {0}")]
+    Docker(String),
+
+    #[error("Kubernetes API error: {0}")]
+    Kubernetes(String),
+
+    #[error("TPM error: {0}")]
+    Tpm(String),
+
+    #[error("Measurement limit reached: {0}")]
+    EventLimit(String),
+
+    #[error("Critical entries could not be measured: {0}")]
+    CriticalEntry(String),
+
     #[error("Configuration error: {0}")]
     Config(String),
 
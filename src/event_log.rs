@@ -0,0 +1,61 @@
+// src/event_log.rs
+use crate::error::{MeasurementError, Result};
+use crate::report::unix_timestamp;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// The AAEL event `<domain> <operation> <content>`: what the Attestation
+/// Agent extends, and what a TPM PCR or CEL digest is taken over.
+pub fn aael_event(domain: &str, operation: &str, content: &str) -> String {
+    format!("{} {} {}", domain, operation, content)
+}
+
+/// The `[local_event_log]`: appends each measurement to a local file as a
+/// line of the Unix time followed by its AAEL event.
+pub struct EventLog {
+    file: Mutex<File>,
+    /// Set once a write failed; the log may then miss a line.
+    failed: AtomicBool,
+}
+
+impl EventLog {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+            failed: AtomicBool::new(false),
+        })
+    }
+
+    /// Fails once a write failed, or when the data written so far cannot
+    /// be synced to disk.
+    pub fn check_writable(&self) -> Result<()> {
+        if self.failed.load(Ordering::Relaxed) {
+            return Err(MeasurementError::Other(anyhow::anyhow!(
+                "an earlier write to the event log failed"
+            )));
+        }
+        self.file.lock().unwrap().sync_data()?;
+        Ok(())
+    }
+
+    pub fn append(&self, domain: &str, operation: &str, content: &str) -> Result<()> {
+        let line = format!(
+            "{} {}",
+            unix_timestamp(),
+            aael_event(domain, operation, content)
+        );
+        let mut file = self.file.lock().unwrap();
+        if let Err(e) = writeln!(file, "{}", line).and_then(|_| file.flush()) {
+            self.failed.store(true, Ordering::Relaxed);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
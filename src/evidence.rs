@@ -0,0 +1,104 @@
+// src/evidence.rs
+use crate::canonical_json;
+use crate::config::{EvidenceBundleConfig, EvidenceKind};
+use crate::journal::JournalEntry;
+use crate::report::{unix_timestamp, SummaryReport};
+use crate::rpc_client::AAClient;
+use anyhow::{bail, Context, Result};
+use log::info;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+/// A single artifact proving the measured state at a point in time: the
+/// summary report, the tail of the local journal, and TEE evidence (or an
+/// attestation token) obtained right after the run.
+#[derive(Debug, Serialize)]
+pub struct EvidenceBundle {
+    /// Seconds since the UNIX epoch at which the bundle was assembled.
+    pub generated_at: u64,
+    /// `sha256:<hex>` of the serialized summary report (canonical JSON with
+    /// `structured_content`). It is passed to the Attestation Agent as runtime
+    /// data, binding the evidence to the report.
+    pub report_digest: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    pub summary: SummaryReport,
+    pub audit_log: Vec<JournalEntry>,
+}
+
+impl EvidenceBundle {
+    pub async fn collect(
+        config: &EvidenceBundleConfig,
+        summary: SummaryReport,
+        aa_client: &AAClient,
+        structured_content: bool,
+    ) -> Result<Self> {
+        let report_json = canonical_json::encode(&summary, structured_content)?;
+        let report_hash = Sha256::digest(&report_json);
+
+        let (evidence, token) = match config.kind {
+            EvidenceKind::Evidence => {
+                let evidence = aa_client
+                    .get_evidence(&report_hash)
+                    .await
+                    .context("GetEvidence failed")?;
+                (Some(printable(evidence)), None)
+            }
+            EvidenceKind::Token => {
+                let token = aa_client
+                    .get_token(&config.token_type)
+                    .await
+                    .context("GetToken failed")?;
+                (None, Some(printable(token)))
+            }
+        };
+
+        let audit_log = match aa_client.journal() {
+            Some(journal) => journal.tail(config.audit_log_entries)?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            generated_at: unix_timestamp(),
+            report_digest: format!("sha256:{}", hex::encode(report_hash)),
+            evidence,
+            token,
+            summary,
+            audit_log,
+        })
+    }
+
+    /// Writes the bundle to `output_path` and/or POSTs it to `upload_url`.
+    pub async fn publish(&self, config: &EvidenceBundleConfig) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        if let Some(ref path) = config.output_path {
+            fs::write(Path::new(path), &content)
+                .with_context(|| format!("Failed to write evidence bundle: {}", path))?;
+            info!("Evidence bundle written to {}", path);
+        }
+        if let Some(ref url) = config.upload_url {
+            let resp = reqwest::Client::new()
+                .post(url)
+                .header("content-type", "application/json")
+                .body(content)
+                .send()
+                .await
+                .with_context(|| format!("Failed to upload evidence bundle to {}", url))?;
+            if !resp.status().is_success() {
+                bail!("Evidence bundle upload to {} returned {}", url, resp.status());
+            }
+            info!("Evidence bundle uploaded to {}", url);
+        }
+        Ok(())
+    }
+}
+
+/// Evidence and tokens are JSON or JWT text on every supported TEE; anything
+/// else is kept as hex.
+fn printable(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap_or_else(|e| hex::encode(e.into_bytes()))
+}
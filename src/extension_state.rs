@@ -0,0 +1,180 @@
+// src/extension_state.rs
+use crate::error::{MeasurementError, Result};
+use crate::report::MeasurementRecord;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+const BOOT_ID_PATH: &str = "/proc/sys/kernel/random/boot_id";
+
+/// The last content extended for a domain, operation and register.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StateEntry {
+    boot_id: String,
+    domain: String,
+    operation: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    register_index: Option<u64>,
+    /// SHA-256 of the extended content, so the state file does not copy
+    /// large or sensitive content.
+    content_digest: String,
+}
+
+type StateKey = (String, String, Option<u64>);
+
+struct StateFile {
+    file: File,
+    entries: HashMap<StateKey, String>,
+}
+
+/// JSON-lines record of what has been extended since boot, so a restarted
+/// daemon skips measurements whose content has not changed.
+///
+/// Registers reset when the TEE reboots, so entries are tagged with the
+/// kernel's boot id and entries of earlier boots are ignored.
+pub struct ExtensionState {
+    boot_id: String,
+    state: Mutex<StateFile>,
+}
+
+impl ExtensionState {
+    /// Loads the entries of the current boot from `path` and rewrites the
+    /// file with only the latest entry of each measurement.
+    pub fn open(path: &Path) -> Result<Self> {
+        let boot_id = fs::read_to_string(BOOT_ID_PATH)
+            .map_err(|e| {
+                MeasurementError::Config(format!(
+                    "Cannot read the boot id from {} for state_path: {}",
+                    BOOT_ID_PATH, e
+                ))
+            })?
+            .trim()
+            .to_string();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut entries = HashMap::new();
+        let mut stale = 0;
+        if path.exists() {
+            for entry in read_entries(path)? {
+                if entry.boot_id != boot_id {
+                    stale += 1;
+                    continue;
+                }
+                let key = (entry.domain, entry.operation, entry.register_index);
+                entries.insert(key, entry.content_digest);
+            }
+        }
+        if stale > 0 {
+            info!(
+                "Ignoring {} state entries of an earlier boot in {:?}",
+                stale, path
+            );
+        }
+        if !entries.is_empty() {
+            info!(
+                "Loaded {} already extended measurements from {:?}",
+                entries.len(),
+                path
+            );
+        }
+
+        let tmp = path.with_extension("tmp");
+        let mut compacted = File::create(&tmp)?;
+        for ((domain, operation, register_index), content_digest) in &entries {
+            let entry = StateEntry {
+                boot_id: boot_id.clone(),
+                domain: domain.clone(),
+                operation: operation.clone(),
+                register_index: *register_index,
+                content_digest: content_digest.clone(),
+            };
+            write_entry(&mut compacted, &entry)?;
+        }
+        compacted.sync_all()?;
+        fs::rename(&tmp, path)?;
+
+        let file = OpenOptions::new().append(true).open(path)?;
+        Ok(Self {
+            boot_id,
+            state: Mutex::new(StateFile { file, entries }),
+        })
+    }
+
+    /// Whether `record` was already extended with the same content in this
+    /// boot.
+    pub fn is_unchanged(&self, record: &MeasurementRecord) -> bool {
+        let state = self.state.lock().unwrap();
+        state.entries.get(&key(record)) == Some(&content_digest(record))
+    }
+
+    /// Remembers the content `record` was extended with. A state that
+    /// cannot be written is still kept in memory.
+    pub fn record(&self, record: &MeasurementRecord) {
+        let entry = StateEntry {
+            boot_id: self.boot_id.clone(),
+            domain: record.domain.clone(),
+            operation: record.operation.clone(),
+            register_index: record.register_index,
+            content_digest: content_digest(record),
+        };
+        let mut state = self.state.lock().unwrap();
+        if state.entries.get(&key(record)) == Some(&entry.content_digest) {
+            return;
+        }
+        if let Err(e) = write_entry(&mut state.file, &entry) {
+            warn!(
+                "Failed to write extension state of {}: {}",
+                record.operation, e
+            );
+        }
+        state.entries.insert(key(record), entry.content_digest);
+    }
+}
+
+fn key(record: &MeasurementRecord) -> StateKey {
+    (
+        record.domain.clone(),
+        record.operation.clone(),
+        record.register_index,
+    )
+}
+
+fn content_digest(record: &MeasurementRecord) -> String {
+    format!(
+        "sha256:{}",
+        hex::encode(Sha256::digest(record.content.as_bytes()))
+    )
+}
+
+fn write_entry(file: &mut File, entry: &StateEntry) -> Result<()> {
+    let line =
+        serde_json::to_string(entry).map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    writeln!(file, "{}", line)?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Reads the state entries, skipping lines that cannot be parsed, such as
+/// one cut short by a crash.
+fn read_entries(path: &Path) -> Result<Vec<StateEntry>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => warn!("Skipping malformed state entry in {:?}: {}", path, e),
+        }
+    }
+    Ok(entries)
+}
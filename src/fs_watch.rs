@@ -0,0 +1,91 @@
+// src/fs_watch.rs
+use crate::config::{Config, WatchBackend};
+use log::{info, warn};
+use notify::{recommended_watcher, Event, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+type Handler = Arc<dyn Fn(notify::Result<Event>) + Send + Sync>;
+
+/// Watches paths with the configured `watch_backend`, shared by the config
+/// watcher and the content watchers. The poll watcher is only started once
+/// a path needs it.
+pub struct PathWatcher {
+    backend: WatchBackend,
+    poll_interval: Duration,
+    compare_contents: bool,
+    handler: Handler,
+    native: Option<RecommendedWatcher>,
+    poll: Option<PollWatcher>,
+}
+
+impl PathWatcher {
+    /// Delivers the events of every watched path to `handler`. Polled paths
+    /// are compared every `watch_poll_interval_secs`, by content when
+    /// `compare_contents` is set and by size and modification time
+    /// otherwise.
+    pub fn new<F>(config: &Config, compare_contents: bool, handler: F) -> notify::Result<Self>
+    where
+        F: Fn(notify::Result<Event>) + Send + Sync + 'static,
+    {
+        let mut watcher = Self {
+            backend: config.watch_backend,
+            poll_interval: Duration::from_secs(config.watch_poll_interval_secs.max(1)),
+            compare_contents,
+            handler: Arc::new(handler),
+            native: None,
+            poll: None,
+        };
+        if watcher.backend != WatchBackend::Poll {
+            match recommended_watcher(watcher.forward()) {
+                Ok(native) => watcher.native = Some(native),
+                Err(e) if watcher.backend == WatchBackend::Auto => warn!(
+                    "File change notifications are unavailable ({}); polling every {:?} instead",
+                    e, watcher.poll_interval
+                ),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(watcher)
+    }
+
+    fn forward(&self) -> impl Fn(notify::Result<Event>) + Send + 'static {
+        let handler = self.handler.clone();
+        move |res| handler(res)
+    }
+
+    pub fn watch(&mut self, path: &Path, mode: RecursiveMode) -> notify::Result<()> {
+        if let Some(native) = self.native.as_mut() {
+            match (self.backend, unwatchable_filesystem(path)) {
+                (WatchBackend::Auto, Some(fs_type)) => info!(
+                    "{:?} is on {}, where changes may not be notified; polling it every {:?}",
+                    path, fs_type, self.poll_interval
+                ),
+                _ => return native.watch(path, mode),
+            }
+        }
+        let poll = match self.poll.take() {
+            Some(poll) => poll,
+            None => {
+                let config = notify::Config::default()
+                    .with_poll_interval(self.poll_interval)
+                    .with_compare_contents(self.compare_contents);
+                PollWatcher::new(self.forward(), config)?
+            }
+        };
+        self.poll.insert(poll).watch(path, mode)
+    }
+}
+
+/// The filesystem holding `path` if inotify misses changes made to it
+/// elsewhere, such as on another NFS client or the host of a 9p share.
+#[cfg(target_os = "linux")]
+fn unwatchable_filesystem(path: &Path) -> Option<&'static str> {
+    crate::modules::model_dir_measurer::network_filesystem(path)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn unwatchable_filesystem(_path: &Path) -> Option<&'static str> {
+    None
+}
@@ -0,0 +1,161 @@
+// src/hash_cache.rs
+use crate::error::{MeasurementError, Result};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// What a file looked like when it was hashed. A file written in place keeps
+/// its inode but changes its modification and change times.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    size: u64,
+    dev: u64,
+    inode: u64,
+    mtime: (i64, i64),
+    ctime: (i64, i64),
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> Result<Self> {
+        let metadata = fs::metadata(path)?;
+        Ok(Self {
+            size: metadata.size(),
+            dev: metadata.dev(),
+            inode: metadata.ino(),
+            mtime: (metadata.mtime(), metadata.mtime_nsec()),
+            ctime: (metadata.ctime(), metadata.ctime_nsec()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedDigest {
+    stamp: FileStamp,
+    hash_algorithm: String,
+    digest: String,
+}
+
+/// File digests keyed by path, shared by every file measurement of the
+/// process and optionally persisted as JSON.
+#[derive(Default)]
+struct HashCache {
+    /// The file the entries were loaded from and are saved to.
+    path: Option<PathBuf>,
+    entries: HashMap<String, CachedDigest>,
+    dirty: bool,
+}
+
+static CACHE: OnceLock<Mutex<HashCache>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<HashCache> {
+    CACHE.get_or_init(|| Mutex::new(HashCache::default()))
+}
+
+impl HashCache {
+    /// Switches to the entries persisted at `path`, loading them the first
+    /// time the path is used.
+    fn use_path(&mut self, path: Option<&str>) {
+        let path = path.map(PathBuf::from);
+        if self.path == path {
+            return;
+        }
+        self.entries.clear();
+        self.dirty = false;
+        if let Some(path) = &path {
+            match load(path) {
+                Ok(entries) => {
+                    info!(
+                        "Loaded {} cached file digests from {:?}",
+                        entries.len(),
+                        path
+                    );
+                    self.entries = entries;
+                }
+                Err(e) => warn!("Ignoring hash cache {:?}: {}", path, e),
+            }
+        }
+        self.path = path;
+    }
+}
+
+fn load(path: &Path) -> Result<HashMap<String, CachedDigest>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    serde_json::from_reader(BufReader::new(File::open(path)?))
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))
+}
+
+/// The digest `file_path` had when it was last hashed with
+/// `hash_algorithm`, if it still has the same `stamp`.
+pub fn lookup(
+    file_path: &str,
+    hash_algorithm: &str,
+    stamp: &FileStamp,
+    persist_path: Option<&str>,
+) -> Option<String> {
+    let mut cache = cache().lock().unwrap();
+    cache.use_path(persist_path);
+    let cached = cache.entries.get(file_path)?;
+    if cached.stamp != *stamp || !cached.hash_algorithm.eq_ignore_ascii_case(hash_algorithm) {
+        return None;
+    }
+    debug!("{} is unchanged since it was last hashed", file_path);
+    Some(cached.digest.clone())
+}
+
+/// Remembers the digest of `file_path` as it was at `stamp`.
+pub fn store(
+    file_path: &str,
+    hash_algorithm: &str,
+    stamp: FileStamp,
+    digest: &str,
+    persist_path: Option<&str>,
+) {
+    let mut cache = cache().lock().unwrap();
+    cache.use_path(persist_path);
+    cache.entries.insert(
+        file_path.to_string(),
+        CachedDigest {
+            stamp,
+            hash_algorithm: hash_algorithm.to_lowercase(),
+            digest: digest.to_string(),
+        },
+    );
+    cache.dirty = true;
+}
+
+/// Writes the entries to the persisted cache file, if there is one and
+/// anything changed since the last save.
+pub fn save() {
+    let mut cache = cache().lock().unwrap();
+    let Some(path) = cache.path.clone() else {
+        return;
+    };
+    if !cache.dirty {
+        return;
+    }
+    match write(&path, &cache.entries) {
+        Ok(()) => cache.dirty = false,
+        Err(e) => warn!("Failed to save the hash cache to {:?}: {}", path, e),
+    }
+}
+
+fn write(path: &Path, entries: &HashMap<String, CachedDigest>) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    let mut writer = BufWriter::new(File::create(&tmp)?);
+    serde_json::to_writer(&mut writer, entries)
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
@@ -0,0 +1,394 @@
+// src/hook.rs
+use crate::canonical_json;
+use crate::config::{Config, HookMeasurementConfig};
+use crate::digest::Hasher;
+use crate::error::{MeasurementError, Result};
+use crate::nri::IMAGE_ANNOTATIONS;
+use crate::overlay::{self, OverlayLayers};
+use crate::sink::MeasurementSink;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Everything the hook measures is extended under this domain, with the
+/// container id and what was measured as the operation.
+const DOMAIN: &str = "container";
+
+/// The container state an OCI runtime passes to hooks on stdin.
+#[derive(Debug, Deserialize)]
+pub struct ContainerState {
+    pub id: String,
+    pub bundle: PathBuf,
+    #[serde(default)]
+    pub annotations: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BundleConfig {
+    root: Option<BundleRoot>,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BundleRoot {
+    path: PathBuf,
+}
+
+/// Content extended for one overlayfs layer of a rootfs.
+#[derive(Debug, Serialize)]
+struct LayerRecord {
+    digest: String,
+    /// Digest of the uncompressed layer tar, when the image store records it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff_id: Option<String>,
+}
+
+impl ContainerState {
+    pub fn from_reader(reader: impl Read) -> Result<Self> {
+        serde_json::from_reader(reader).map_err(|e| {
+            MeasurementError::Config(format!("Invalid OCI container state on stdin: {}", e))
+        })
+    }
+}
+
+/// Measures the bundle's config.json, the image reference and, if enabled,
+/// the container rootfs, with the operations `<id>/config`,
+/// `<id>/image_ref` and `<id>/rootfs`.
+///
+/// The image reference is taken from the annotations CRI runtimes set, in
+/// the container state or else in config.json. It is the name the image was
+/// pulled by, which may be a mutable tag, so it is extended as a reference
+/// and not as an image digest.
+pub async fn run_hook(
+    state: &ContainerState,
+    config: &Config,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    let hook_config = &config.hook_measurement;
+    let pcr_index = hook_config.pcr_index.map(|v| v as u64);
+    let config_path = state.bundle.join("config.json");
+
+    let raw_config = fs::read(&config_path)?;
+    let config_digest = if config.structured_content {
+        // Runtimes differ in key order and whitespace when writing config.json.
+        let value: serde_json::Value = serde_json::from_slice(&raw_config).map_err(|e| {
+            MeasurementError::Config(format!("Invalid bundle config {:?}: {}", config_path, e))
+        })?;
+        let encoded = canonical_json::to_vec(&value)
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        format!("sha256:{}", hex::encode(Sha256::digest(&encoded)))
+    } else {
+        format!("sha256:{}", hex::encode(Sha256::digest(&raw_config)))
+    };
+    debug!("Bundle config {:?} digest: {}", config_path, config_digest);
+    sink.extend(
+        DOMAIN,
+        &format!("{}/config", state.id),
+        &config_digest,
+        pcr_index,
+    )
+    .await?;
+
+    let bundle_config: BundleConfig = serde_json::from_slice(&raw_config).map_err(|e| {
+        MeasurementError::Config(format!("Invalid bundle config {:?}: {}", config_path, e))
+    })?;
+    let image = IMAGE_ANNOTATIONS.iter().find_map(|key| {
+        state
+            .annotations
+            .get(*key)
+            .or_else(|| bundle_config.annotations.get(*key))
+    });
+    match image {
+        Some(image) => {
+            sink.extend(DOMAIN, &format!("{}/image_ref", state.id), image, pcr_index)
+                .await?
+        }
+        None => warn!("Container {} has no image annotation", state.id),
+    }
+
+    if hook_config.measure_rootfs {
+        let rootfs = match bundle_config.root {
+            Some(root) if root.path.is_absolute() => root.path,
+            Some(root) => state.bundle.join(root.path),
+            None => state.bundle.join("rootfs"),
+        };
+        let rootfs_digest = tree_digest(&rootfs)?;
+        debug!("Rootfs {:?} digest: {}", rootfs, rootfs_digest);
+        sink.extend(
+            DOMAIN,
+            &format!("{}/rootfs", state.id),
+            &rootfs_digest,
+            pcr_index,
+        )
+        .await?;
+
+        if hook_config.rootfs_layers {
+            match overlay::overlay_layers(&rootfs)? {
+                Some(layers) => {
+                    measure_layers(state, &layers, hook_config, sink).await?;
+                }
+                None => warn!(
+                    "Rootfs {:?} is not an overlayfs mount; measured the merged view only",
+                    rootfs
+                ),
+            }
+        }
+    }
+
+    info!(
+        "Measured container {} from bundle {:?}",
+        state.id, state.bundle
+    );
+    Ok(())
+}
+
+/// Extends one event per lower layer, with operation
+/// `<id>/lower/<n>` counting from the base layer, and one for the upper
+/// layer with operation `<id>/upper`.
+async fn measure_layers(
+    state: &ContainerState,
+    layers: &OverlayLayers,
+    hook_config: &HookMeasurementConfig,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    let pcr_index = hook_config.pcr_index.map(|v| v as u64);
+    let layerdb = Path::new(&hook_config.docker_layerdb_dir);
+    let lower = layers
+        .lower
+        .iter()
+        .enumerate()
+        .map(|(n, dir)| (format!("{}/lower/{}", state.id, n), dir, true));
+    let upper = layers
+        .upper
+        .iter()
+        .map(|dir| (format!("{}/upper", state.id), dir, false));
+
+    for (operation, dir, is_lower) in lower.chain(upper) {
+        let record = LayerRecord {
+            digest: tree_digest(dir)?,
+            diff_id: if is_lower {
+                overlay::docker_diff_id(dir, layerdb)
+            } else {
+                None
+            },
+        };
+        debug!("Layer {} ({:?}): {:?}", operation, dir, record);
+        let content = canonical_json::to_vec(&record)
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        sink.extend(
+            DOMAIN,
+            &operation,
+            &String::from_utf8_lossy(&content),
+            pcr_index,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Digest of a directory tree: one line per entry (relative path, type, mode
+/// and content or link target digest), sorted by path. Symlinks are not
+/// followed and special files contribute only their metadata.
+pub fn tree_digest(root: &Path) -> Result<String> {
+    tree_digest_with(root, "sha256")
+}
+
+/// [`tree_digest`] with `hash_algorithm` for the listing and the file
+/// contents.
+pub fn tree_digest_with(root: &Path, hash_algorithm: &str) -> Result<String> {
+    let mut entries = Vec::new();
+    collect_entries(root, root, hash_algorithm, &mut entries)?;
+    entries.sort();
+
+    let mut hasher = Hasher::new(hash_algorithm)?;
+    for entry in &entries {
+        hasher.update(entry.as_bytes());
+        hasher.update(b"\n");
+    }
+    Ok(hasher.finalize_prefixed())
+}
+
+fn collect_entries(
+    root: &Path,
+    dir: &Path,
+    hash_algorithm: &str,
+    entries: &mut Vec<String>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let meta = fs::symlink_metadata(&path)?;
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy();
+        let mode = meta.permissions().mode() & 0o7777;
+        let file_type = meta.file_type();
+
+        if file_type.is_dir() {
+            entries.push(format!("{}\0d\0{:o}", relative, mode));
+            collect_entries(root, &path, hash_algorithm, entries)?;
+        } else if file_type.is_symlink() {
+            let target = fs::read_link(&path)?;
+            entries.push(format!(
+                "{}\0l\0{:o}\0{}",
+                relative,
+                mode,
+                target.to_string_lossy()
+            ));
+        } else if file_type.is_file() {
+            let mut hasher = Hasher::new(hash_algorithm)?;
+            hasher.update_reader(&mut fs::File::open(&path)?)?;
+            entries.push(format!(
+                "{}\0f\0{:o}\0{}",
+                relative,
+                mode,
+                hasher.finalize_hex()
+            ));
+        } else {
+            entries.push(format!("{}\0s\0{:o}", relative, mode));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc_client::AAClient;
+    use std::os::unix::fs::symlink;
+
+    fn set_mode(path: &Path, mode: u32) {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn tree_digest_lists_paths_modes_contents_and_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join("etc")).unwrap();
+        set_mode(&root.join("etc"), 0o755);
+        fs::write(root.join("etc/app.conf"), b"x").unwrap();
+        set_mode(&root.join("etc/app.conf"), 0o644);
+        symlink("etc/app.conf", root.join("app.conf")).unwrap();
+
+        let listing = [
+            "app.conf\0l\x00777\0etc/app.conf".to_string(),
+            "etc\0d\x00755".to_string(),
+            format!(
+                "etc/app.conf\0f\x00644\0{}",
+                hex::encode(Sha256::digest(b"x"))
+            ),
+        ]
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+        let expected = format!("sha256:{}", hex::encode(Sha256::digest(listing)));
+        assert_eq!(tree_digest(root).unwrap(), expected);
+
+        set_mode(&root.join("etc/app.conf"), 0o600);
+        assert_ne!(tree_digest(root).unwrap(), expected);
+        set_mode(&root.join("etc/app.conf"), 0o644);
+        fs::write(root.join("etc/app.conf"), b"y").unwrap();
+        assert_ne!(tree_digest(root).unwrap(), expected);
+    }
+
+    #[test]
+    fn tree_digest_does_not_depend_on_where_the_tree_is() {
+        let digests: Vec<String> = (0..2)
+            .map(|_| {
+                let dir = tempfile::tempdir().unwrap();
+                fs::create_dir(dir.path().join("bin")).unwrap();
+                set_mode(&dir.path().join("bin"), 0o755);
+                fs::write(dir.path().join("bin/sh"), b"#!").unwrap();
+                set_mode(&dir.path().join("bin/sh"), 0o755);
+                tree_digest(dir.path()).unwrap()
+            })
+            .collect();
+        assert_eq!(digests[0], digests[1]);
+    }
+
+    async fn hook_records(bundle: &Path, state_json: &str) -> Vec<(String, String, String)> {
+        let log_path = bundle.join("events.log");
+        let config: Config = toml::from_str(&format!(
+            r#"
+            [local_event_log]
+            enable = true
+            path = {:?}
+            forward_to_aa = false
+            "#,
+            log_path
+        ))
+        .unwrap();
+        let state = ContainerState::from_reader(state_json.as_bytes()).unwrap();
+        let aa_client = AAClient::from_config(&config).await.unwrap();
+        run_hook(&state, &config, &aa_client).await.unwrap();
+        aa_client
+            .records()
+            .into_iter()
+            .map(|r| (r.domain, r.operation, r.content))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn run_hook_extends_config_image_ref_and_rootfs() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path();
+        let config_json = r#"{"root":{"path":"rootfs"},"annotations":{"io.kubernetes.cri.image-name":"docker.io/library/busybox:1.36"}}"#;
+        fs::write(bundle.join("config.json"), config_json).unwrap();
+        fs::create_dir(bundle.join("rootfs")).unwrap();
+        fs::write(bundle.join("rootfs/hello"), b"hello").unwrap();
+
+        let records =
+            hook_records(bundle, &format!(r#"{{"id":"c1","bundle":{:?}}}"#, bundle)).await;
+        let config_digest = format!(
+            "sha256:{}",
+            hex::encode(Sha256::digest(config_json.as_bytes()))
+        );
+        let rootfs_digest = tree_digest(&bundle.join("rootfs")).unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (
+                    "container".to_string(),
+                    "c1/config".to_string(),
+                    config_digest
+                ),
+                (
+                    "container".to_string(),
+                    "c1/image_ref".to_string(),
+                    "docker.io/library/busybox:1.36".to_string()
+                ),
+                (
+                    "container".to_string(),
+                    "c1/rootfs".to_string(),
+                    rootfs_digest
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_hook_prefers_the_state_annotation_and_skips_a_missing_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path();
+        fs::write(bundle.join("config.json"), r#"{"root":{"path":"rootfs"}}"#).unwrap();
+        fs::create_dir(bundle.join("rootfs")).unwrap();
+
+        let with_state_annotation = format!(
+            r#"{{"id":"c2","bundle":{:?},"annotations":{{"io.kubernetes.cri-o.ImageName":"quay.io/app@sha256:ab"}}}}"#,
+            bundle
+        );
+        let records = hook_records(bundle, &with_state_annotation).await;
+        assert_eq!(records[1].1, "c2/image_ref");
+        assert_eq!(records[1].2, "quay.io/app@sha256:ab");
+
+        let records =
+            hook_records(bundle, &format!(r#"{{"id":"c3","bundle":{:?}}}"#, bundle)).await;
+        let operations: Vec<&str> = records.iter().map(|r| r.1.as_str()).collect();
+        assert_eq!(operations, ["c3/config", "c3/rootfs"]);
+    }
+}
@@ -0,0 +1,119 @@
+// src/ima.rs
+use serde::Serialize;
+use std::io;
+use std::path::Path;
+
+const IMA_XATTR: &str = "security.ima";
+const EVM_XATTR: &str = "security.evm";
+
+// Leading type byte of the xattr values (see `enum evm_ima_xattr_type` in the
+// kernel's security/integrity/integrity.h).
+const IMA_XATTR_DIGEST: u8 = 0x01;
+const EVM_XATTR_HMAC: u8 = 0x02;
+const EVM_IMA_XATTR_DIGSIG: u8 = 0x03;
+const IMA_XATTR_DIGEST_NG: u8 = 0x04;
+const EVM_XATTR_PORTABLE_DIGSIG: u8 = 0x05;
+const IMA_VERITY_DIGSIG: u8 = 0x06;
+
+/// Version of `struct signature_v2_hdr`, whose 4-byte key id follows the
+/// type, version and hash algorithm bytes.
+const DIGSIG_VERSION_2: u8 = 2;
+
+/// What a `security.ima` or `security.evm` attribute holds.
+#[derive(Debug, Serialize)]
+pub struct XattrSummary {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Key id of a v2 signature, as 8 hex digits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+}
+
+/// IMA and EVM attributes of a file; `None` where the attribute is absent.
+#[derive(Debug, Serialize)]
+pub struct IntegrityXattrs {
+    pub ima: Option<XattrSummary>,
+    pub evm: Option<XattrSummary>,
+}
+
+impl IntegrityXattrs {
+    pub fn read(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            ima: read_xattr(path, IMA_XATTR)?.map(|v| summarize(&v)),
+            evm: read_xattr(path, EVM_XATTR)?.map(|v| summarize(&v)),
+        })
+    }
+}
+
+fn summarize(value: &[u8]) -> XattrSummary {
+    let kind = match value.first() {
+        Some(&IMA_XATTR_DIGEST) => "digest",
+        Some(&EVM_XATTR_HMAC) => "hmac",
+        Some(&EVM_IMA_XATTR_DIGSIG) => "signature",
+        Some(&IMA_XATTR_DIGEST_NG) => "digest_ng",
+        Some(&EVM_XATTR_PORTABLE_DIGSIG) => "portable_signature",
+        Some(&IMA_VERITY_DIGSIG) => "verity_signature",
+        _ => "unknown",
+    };
+    let is_signature = matches!(
+        value.first(),
+        Some(&EVM_IMA_XATTR_DIGSIG) | Some(&EVM_XATTR_PORTABLE_DIGSIG) | Some(&IMA_VERITY_DIGSIG)
+    );
+    let key_id = match value {
+        [_, DIGSIG_VERSION_2, _, id @ ..] if is_signature && id.len() >= 4 => {
+            Some(hex::encode(&id[..4]))
+        }
+        _ => None,
+    };
+    XattrSummary { kind, key_id }
+}
+
+/// Returns the value of extended attribute `name`, or `None` if the file
+/// does not have it or the filesystem does not support xattrs.
+#[cfg(target_os = "linux")]
+fn read_xattr(path: &Path, name: &str) -> io::Result<Option<Vec<u8>>> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())?;
+    let c_name = CString::new(name)?;
+    loop {
+        let size =
+            unsafe { libc::getxattr(c_path.as_ptr(), c_name.as_ptr(), std::ptr::null_mut(), 0) };
+        if size < 0 {
+            return absent_or_error(io::Error::last_os_error());
+        }
+        let mut buf = vec![0u8; size as usize];
+        let read = unsafe {
+            libc::getxattr(
+                c_path.as_ptr(),
+                c_name.as_ptr(),
+                buf.as_mut_ptr().cast(),
+                buf.len(),
+            )
+        };
+        if read >= 0 {
+            buf.truncate(read as usize);
+            return Ok(Some(buf));
+        }
+        let err = io::Error::last_os_error();
+        // The attribute grew between the two calls.
+        if err.raw_os_error() != Some(libc::ERANGE) {
+            return absent_or_error(err);
+        }
+    }
+}
+
+/// IMA and EVM are Linux-only; files elsewhere never carry their attributes.
+#[cfg(not(target_os = "linux"))]
+fn read_xattr(_path: &Path, _name: &str) -> io::Result<Option<Vec<u8>>> {
+    Ok(None)
+}
+
+#[cfg(target_os = "linux")]
+fn absent_or_error(err: io::Error) -> io::Result<Option<Vec<u8>>> {
+    match err.raw_os_error() {
+        Some(libc::ENODATA) | Some(libc::ENOTSUP) => Ok(None),
+        _ => Err(err),
+    }
+}
@@ -0,0 +1,268 @@
+// src/journal.rs
+use crate::error::{MeasurementError, Result};
+use crate::report::{unix_timestamp, MeasurementRecord};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStatus {
+    /// Written before the extension is attempted.
+    Pending,
+    /// Written once the backend acknowledged the extension.
+    Extended,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub seq: u64,
+    pub timestamp: u64,
+    pub status: JournalStatus,
+    #[serde(flatten)]
+    pub record: MeasurementRecord,
+}
+
+/// Append-only JSON-lines journal of measurement extensions.
+///
+/// Every extension is journaled as `pending` before it is sent and as
+/// `extended` (with the same sequence number) once it succeeded, so entries
+/// that never reached the backend can be replayed later without duplicating
+/// the ones that did.
+///
+/// Every process that has the journal open holds a shared `flock` on it, so
+/// that [`Journal::compact`] only rewrites a journal nobody else appends to.
+pub struct Journal {
+    path: PathBuf,
+    state: Mutex<JournalState>,
+}
+
+struct JournalState {
+    file: File,
+    next_seq: u64,
+}
+
+impl Journal {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = open_locked(path)?;
+        let next_seq = read_entries(path)?
+            .iter()
+            .map(|e| e.seq + 1)
+            .max()
+            .unwrap_or(0);
+        Ok(Self {
+            path: path.to_path_buf(),
+            state: Mutex::new(JournalState { file, next_seq }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records a measurement that is about to be extended and returns its
+    /// sequence number.
+    pub fn append_pending(&self, record: &MeasurementRecord) -> Result<u64> {
+        let mut state = self.state.lock().unwrap();
+        let seq = state.next_seq;
+        write_entry(
+            &mut state.file,
+            &new_entry(seq, JournalStatus::Pending, record),
+        )?;
+        state.next_seq += 1;
+        Ok(seq)
+    }
+
+    pub fn mark_extended(&self, seq: u64, record: &MeasurementRecord) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        write_entry(
+            &mut state.file,
+            &new_entry(seq, JournalStatus::Extended, record),
+        )
+    }
+
+    /// Returns the last `count` entries, oldest first.
+    pub fn tail(&self, count: usize) -> Result<Vec<JournalEntry>> {
+        let entries = read_entries(&self.path)?;
+        let skip = entries.len().saturating_sub(count);
+        Ok(entries.into_iter().skip(skip).collect())
+    }
+
+    /// Returns the pending entries that have no matching `extended` entry, in
+    /// sequence order.
+    pub fn unextended(&self) -> Result<Vec<JournalEntry>> {
+        let entries = read_entries(&self.path)?;
+        let extended: HashSet<u64> = entries
+            .iter()
+            .filter(|e| e.status == JournalStatus::Extended)
+            .map(|e| e.seq)
+            .collect();
+        let mut pending: Vec<JournalEntry> = entries
+            .into_iter()
+            .filter(|e| e.status == JournalStatus::Pending && !extended.contains(&e.seq))
+            .collect();
+        pending.sort_by_key(|e| e.seq);
+        pending.dedup_by_key(|e| e.seq);
+        Ok(pending)
+    }
+
+    /// Rewrites the journal without the entries the backend acknowledged,
+    /// keeping only those still pending. Returns the number of lines
+    /// dropped, or `None` when another process has the journal open and it
+    /// is left as it is.
+    pub fn compact(&self) -> Result<Option<usize>> {
+        let mut state = self.state.lock().unwrap();
+        if !try_lock_exclusive(&state.file)? {
+            return Ok(None);
+        }
+        let entries = read_entries(&self.path)?;
+        let total = entries.len();
+        let extended: HashSet<u64> = entries
+            .iter()
+            .filter(|e| e.status == JournalStatus::Extended)
+            .map(|e| e.seq)
+            .collect();
+
+        let mut compacted = self.path.clone().into_os_string();
+        compacted.push(".compact");
+        let compacted = PathBuf::from(compacted);
+        let mut kept = 0;
+        {
+            let mut file = File::create(&compacted)?;
+            for entry in entries.iter().filter(|e| !extended.contains(&e.seq)) {
+                write_entry(&mut file, entry)?;
+                kept += 1;
+            }
+            file.sync_all()?;
+        }
+        fs::rename(&compacted, &self.path)?;
+        // Replacing the file releases the exclusive lock held on the old one.
+        state.file = open_locked(&self.path)?;
+        Ok(Some(total - kept))
+    }
+}
+
+/// Opens `path` for appending under a shared lock. A file that was replaced
+/// by [`Journal::compact`] while waiting for the lock is opened again.
+fn open_locked(path: &Path) -> Result<File> {
+    loop {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_SH) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        let opened = file.metadata()?;
+        match fs::metadata(path) {
+            Ok(current) if current.dev() == opened.dev() && current.ino() == opened.ino() => {
+                return Ok(file)
+            }
+            _ => continue,
+        }
+    }
+}
+
+/// Converts the shared lock on `file` into an exclusive one, unless another
+/// process holds the file open.
+fn try_lock_exclusive(file: &File) -> Result<bool> {
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(true);
+    }
+    let error = std::io::Error::last_os_error();
+    if error.raw_os_error() != Some(libc::EWOULDBLOCK) {
+        return Err(error.into());
+    }
+    // A failed conversion drops the shared lock; take it again.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_SH) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(false)
+}
+
+fn new_entry(seq: u64, status: JournalStatus, record: &MeasurementRecord) -> JournalEntry {
+    JournalEntry {
+        seq,
+        timestamp: unix_timestamp(),
+        status,
+        record: record.clone(),
+    }
+}
+
+fn write_entry(file: &mut File, entry: &JournalEntry) -> Result<()> {
+    let line =
+        serde_json::to_string(entry).map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    writeln!(file, "{}", line)?;
+    file.flush()?;
+    Ok(())
+}
+
+fn read_entries(path: &Path) -> Result<Vec<JournalEntry>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+    for (lineno, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|e| {
+            MeasurementError::Config(format!(
+                "Malformed journal entry at {:?}:{}: {}",
+                path,
+                lineno + 1,
+                e
+            ))
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(operation: &str) -> MeasurementRecord {
+        MeasurementRecord::new("file", operation, "sha256:00", None)
+    }
+
+    #[test]
+    fn compact_keeps_only_pending_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::open(&dir.path().join("journal.jsonl")).unwrap();
+        let first = journal.append_pending(&record("/a")).unwrap();
+        journal.mark_extended(first, &record("/a")).unwrap();
+        let second = journal.append_pending(&record("/b")).unwrap();
+
+        assert_eq!(journal.compact().unwrap(), Some(2));
+        let entries = journal.tail(10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].seq, second);
+
+        // Appends after compaction go to the rewritten file.
+        journal.mark_extended(second, &record("/b")).unwrap();
+        assert!(journal.unextended().unwrap().is_empty());
+        assert_eq!(journal.compact().unwrap(), Some(2));
+        assert!(journal.tail(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn compact_leaves_a_journal_open_elsewhere_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let journal = Journal::open(&path).unwrap();
+        let seq = journal.append_pending(&record("/a")).unwrap();
+        journal.mark_extended(seq, &record("/a")).unwrap();
+
+        let other = Journal::open(&path).unwrap();
+        assert_eq!(journal.compact().unwrap(), None);
+        assert_eq!(journal.tail(10).unwrap().len(), 2);
+        drop(other);
+        assert_eq!(journal.compact().unwrap(), Some(2));
+    }
+}
@@ -0,0 +1,129 @@
+// src/kata.rs
+use crate::config::{Config, KataConfig, MeasurementChannel};
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use crate::hook::tree_digest;
+use crate::sink::MeasurementSink;
+use log::{debug, info, warn};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use ttrpc::asynchronous::Client;
+
+const VOLUME_DOMAIN: &str = "kata_volume";
+
+/// Policy file provided through the Kata agent's config mount.
+pub const DEFAULT_POLICY_PATH: &str = "/etc/kata-containers/measurement.toml";
+
+/// Unix sockets the Attestation Agent is known to listen on inside Kata and
+/// peer-pod guests, tried before any vsock endpoint.
+const GUEST_AA_SOCKETS: &[&str] = &[
+    "/run/confidential-containers/attestation-agent/attestation-agent.sock",
+    "/run/attestation-agent/attestation-agent.sock",
+];
+
+/// Points the config at the first reachable Attestation Agent: the configured
+/// socket, then the well-known guest sockets, then the configured vsock
+/// addresses.
+pub fn discover_attestation_agent(config: &mut Config) -> Result<()> {
+    if config.aa_channel != MeasurementChannel::UnixSocket {
+        return Ok(());
+    }
+
+    let configured = config.attestation_agent_socket.clone();
+    let unix_candidates = std::iter::once(configured.as_str())
+        .filter_map(|addr| addr.strip_prefix("unix://"))
+        .chain(GUEST_AA_SOCKETS.iter().copied());
+    for path in unix_candidates {
+        if Path::new(path).exists() {
+            config.attestation_agent_socket = format!("unix://{}", path);
+            info!("Using Attestation Agent socket {}", path);
+            return Ok(());
+        }
+    }
+
+    for addr in &config.kata.aa_vsock_addresses {
+        match Client::connect(addr) {
+            Ok(_) => {
+                info!("Using Attestation Agent at {}", addr);
+                config.attestation_agent_socket = addr.clone();
+                return Ok(());
+            }
+            Err(e) => debug!("Attestation Agent not reachable at {}: {}", addr, e),
+        }
+    }
+
+    Err(MeasurementError::RpcClient(
+        "No Attestation Agent found on the guest sockets or vsock addresses".to_string(),
+    ))
+}
+
+/// Measures every volume the Kata agent has shared into the sandbox, then
+/// keeps polling so that volumes of containers started later are measured
+/// once as well.
+pub async fn watch_volumes(config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+    let kata = &config.kata;
+    let volumes_dir = Path::new(&kata.volumes_dir);
+    let mut measured = HashSet::new();
+    info!("Measuring pod volumes under {:?}", volumes_dir);
+
+    loop {
+        match fs::read_dir(volumes_dir) {
+            Ok(entries) => {
+                for entry in entries.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if is_container_dir(&name) || measured.contains(&name) {
+                        continue;
+                    }
+                    match measure_volume(&entry.path(), &name, kata, &sink).await {
+                        Ok(()) => {
+                            measured.insert(name);
+                        }
+                        Err(e) => warn!("Failed to measure volume {}: {}", name, e),
+                    }
+                }
+            }
+            Err(e) => debug!("Cannot read {:?} yet: {}", volumes_dir, e),
+        }
+        sleep(Duration::from_secs(kata.volume_poll_interval_secs)).await;
+    }
+}
+
+/// Container directories are named after the 64-character container id and
+/// hold the rootfs; everything else in the shared directory is a volume.
+fn is_container_dir(name: &str) -> bool {
+    name.len() == 64 && name.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+async fn measure_volume(
+    path: &Path,
+    name: &str,
+    kata: &KataConfig,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    let digest = {
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            if path.is_dir() {
+                tree_digest(&path)
+            } else {
+                digest_reader(&mut File::open(&path)?, "sha256")
+            }
+        })
+        .await
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+    };
+    debug!("Volume {} digest: {}", name, digest);
+    sink.extend(
+        VOLUME_DOMAIN,
+        name,
+        &digest,
+        kata.pcr_index.map(|v| v as u64),
+    )
+    .await?;
+    info!("Measured volume {}", name);
+    Ok(())
+}
@@ -0,0 +1,171 @@
+// src/kubernetes/client.rs
+use crate::error::{MeasurementError, Result};
+use log::debug;
+use reqwest::header::CONTENT_TYPE;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+
+/// How long the API server keeps a watch open before the client starts a
+/// new one.
+const WATCH_TIMEOUT_SECS: u64 = 300;
+
+/// Minimal Kubernetes API client using the pod's service account.
+///
+/// Only the handful of JSON calls the tool needs are implemented; the token is
+/// re-read for every request because projected tokens are rotated.
+pub struct KubeClient {
+    http_client: reqwest::Client,
+    base_url: String,
+    token_path: PathBuf,
+    namespace: String,
+}
+
+impl KubeClient {
+    pub fn in_cluster() -> Result<Self> {
+        let host = env::var("KUBERNETES_SERVICE_HOST").map_err(|_| {
+            MeasurementError::Kubernetes("KUBERNETES_SERVICE_HOST is not set".to_string())
+        })?;
+        let port = env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".to_string());
+        let sa_dir = Path::new(SERVICE_ACCOUNT_DIR);
+
+        let ca_pem = fs::read(sa_dir.join("ca.crt"))?;
+        let ca = reqwest::Certificate::from_pem(&ca_pem).map_err(|e| {
+            MeasurementError::Kubernetes(format!("Invalid service account CA: {}", e))
+        })?;
+        let http_client = reqwest::Client::builder()
+            .user_agent("measurement-tool/0.1.0")
+            .add_root_certificate(ca)
+            .build()
+            .map_err(|e| MeasurementError::Http(format!("Failed to build HTTP client: {}", e)))?;
+        let namespace = fs::read_to_string(sa_dir.join("namespace"))?
+            .trim()
+            .to_string();
+
+        Ok(Self {
+            http_client,
+            base_url: format!("https://{}:{}", host, port),
+            token_path: sa_dir.join("token"),
+            namespace,
+        })
+    }
+
+    /// The namespace the tool's own pod runs in.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub async fn get(&self, path: &str) -> Result<Value> {
+        let request = self.http_client.get(&self.url(path));
+        self.send(request).await
+    }
+
+    pub async fn post(&self, path: &str, body: &Value) -> Result<Value> {
+        let request = self.http_client.post(&self.url(path)).json(body);
+        self.send(request).await
+    }
+
+    /// Applies a JSON merge patch (RFC 7386).
+    pub async fn merge_patch(&self, path: &str, patch: &Value) -> Result<Value> {
+        let request = self
+            .http_client
+            .patch(&self.url(path))
+            .header(CONTENT_TYPE, "application/merge-patch+json")
+            .body(patch.to_string());
+        self.send(request).await
+    }
+
+    /// Applies a strategic merge patch, which merges lists such as node
+    /// conditions by key instead of replacing them.
+    pub async fn strategic_merge_patch(&self, path: &str, patch: &Value) -> Result<Value> {
+        let request = self
+            .http_client
+            .patch(&self.url(path))
+            .header(CONTENT_TYPE, "application/strategic-merge-patch+json")
+            .body(patch.to_string());
+        self.send(request).await
+    }
+
+    /// Watches the collection at `path`, which may already carry a query,
+    /// for changes after `resource_version`.
+    pub async fn watch(&self, path: &str, resource_version: &str) -> Result<WatchEvents> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let url = self.url(&format!(
+            "{}{}watch=true&resourceVersion={}&timeoutSeconds={}",
+            path, separator, resource_version, WATCH_TIMEOUT_SECS
+        ));
+        let response = self.send_raw(self.http_client.get(&url)).await?;
+        Ok(WatchEvents {
+            response,
+            buffer: Vec::new(),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        self.send_raw(request)
+            .await?
+            .json::<Value>()
+            .await
+            .map_err(|e| MeasurementError::Kubernetes(format!("Invalid response body: {}", e)))
+    }
+
+    async fn send_raw(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let token = fs::read_to_string(&self.token_path)?;
+        let response = request
+            .bearer_auth(token.trim())
+            .send()
+            .await
+            .map_err(|e| MeasurementError::Kubernetes(format!("Request failed: {}", e)))?;
+        let status = response.status();
+        debug!("Kubernetes API {} {}", response.url(), status);
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(MeasurementError::Kubernetes(format!(
+                "API returned {}: {}",
+                status,
+                body.trim()
+            )));
+        }
+        Ok(response)
+    }
+}
+
+/// The events of a watch, one JSON object `{"type": ..., "object": ...}`
+/// per line.
+pub struct WatchEvents {
+    response: reqwest::Response,
+    buffer: Vec<u8>,
+}
+
+impl WatchEvents {
+    /// Returns the next event, or `None` once the server ended the watch.
+    pub async fn next(&mut self) -> Result<Option<Value>> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).collect();
+                if line.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+                return serde_json::from_slice(&line).map(Some).map_err(|e| {
+                    MeasurementError::Kubernetes(format!("Invalid watch event: {}", e))
+                });
+            }
+            let chunk = self
+                .response
+                .chunk()
+                .await
+                .map_err(|e| MeasurementError::Kubernetes(format!("Watch failed: {}", e)))?;
+            match chunk {
+                Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                None => return Ok(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// src/kubernetes/health.rs
+use crate::error::Result;
+use crate::kubernetes::KubeClient;
+use crate::report::now_rfc3339;
+use log::warn;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+/// Node condition reflecting whether the last measurement run succeeded.
+pub const CONDITION_TYPE: &str = "RuntimeMeasurementHealthy";
+
+const COMPONENT: &str = "measurement-tool";
+
+/// Publishes the outcome of measurement runs as a node condition and as
+/// Events on the node, so that schedulers and dashboards can spot nodes whose
+/// measurement pipeline is broken.
+pub struct NodeHealthReporter {
+    client: Arc<KubeClient>,
+    node_name: String,
+    /// Health reported last, used to keep `lastTransitionTime` stable.
+    last_healthy: Mutex<Option<bool>>,
+}
+
+impl NodeHealthReporter {
+    pub fn new(client: Arc<KubeClient>, node_name: String) -> Self {
+        Self {
+            client,
+            node_name,
+            last_healthy: Mutex::new(None),
+        }
+    }
+
+    /// Reports a run with the given failures. Errors are logged, never
+    /// returned, since reporting must not interfere with measuring.
+    pub async fn report(&self, failures: &[String]) {
+        let healthy = failures.is_empty();
+        let (reason, message) = if healthy {
+            (
+                "MeasurementSucceeded",
+                "All enabled measurers succeeded".to_string(),
+            )
+        } else {
+            (
+                "MeasurementFailed",
+                format!(
+                    "{} measurer(s) failed: {}",
+                    failures.len(),
+                    failures.join("; ")
+                ),
+            )
+        };
+        let transitioned = {
+            let mut last = self.last_healthy.lock().unwrap();
+            last.replace(healthy) != Some(healthy)
+        };
+
+        if let Err(e) = self
+            .patch_condition(healthy, reason, &message, transitioned)
+            .await
+        {
+            warn!("Failed to update node condition {}: {}", CONDITION_TYPE, e);
+        }
+        if let Err(e) = self.emit_event(healthy, reason, &message).await {
+            warn!("Failed to emit node event: {}", e);
+        }
+    }
+
+    async fn patch_condition(
+        &self,
+        healthy: bool,
+        reason: &str,
+        message: &str,
+        transitioned: bool,
+    ) -> Result<()> {
+        let now = now_rfc3339();
+        let mut condition = json!({
+            "type": CONDITION_TYPE,
+            "status": if healthy { "True" } else { "False" },
+            "reason": reason,
+            "message": message,
+            "lastHeartbeatTime": now,
+        });
+        if transitioned {
+            condition["lastTransitionTime"] = json!(now);
+        }
+        // Node conditions are merged by type, leaving the kubelet's untouched.
+        let patch = json!({ "status": { "conditions": [condition] } });
+        self.client
+            .strategic_merge_patch(&format!("/api/v1/nodes/{}/status", self.node_name), &patch)
+            .await?;
+        Ok(())
+    }
+
+    async fn emit_event(&self, healthy: bool, reason: &str, message: &str) -> Result<()> {
+        let now = now_rfc3339();
+        let namespace = self.client.namespace();
+        let event = json!({
+            "metadata": {
+                "generateName": format!("{}.", self.node_name),
+                "namespace": namespace,
+            },
+            "involvedObject": {
+                "apiVersion": "v1",
+                "kind": "Node",
+                "name": self.node_name,
+                "uid": self.node_name,
+            },
+            "type": if healthy { "Normal" } else { "Warning" },
+            "reason": reason,
+            "message": message,
+            "source": { "component": COMPONENT, "host": self.node_name },
+            "reportingComponent": COMPONENT,
+            "reportingInstance": self.node_name,
+            "firstTimestamp": now,
+            "lastTimestamp": now,
+            "count": 1,
+        });
+        self.client
+            .post(&format!("/api/v1/namespaces/{}/events", namespace), &event)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+// src/kubernetes/identity.rs
+use crate::config::{KubernetesConfig, NodeIdentityPlacement};
+use crate::error::{MeasurementError, Result};
+use log::{info, warn};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Tags every measurement with the node it was taken on, so that event logs
+/// from a DaemonSet spread over many nodes stay attributable.
+#[derive(Debug, Clone)]
+pub struct NodeIdentity {
+    identity: String,
+    placement: NodeIdentityPlacement,
+}
+
+impl NodeIdentity {
+    /// Returns `None` when node identity is disabled. The node name is required
+    /// once enabled; missing identity labels are skipped with a warning.
+    pub fn from_config(config: &KubernetesConfig) -> Result<Option<Self>> {
+        if config.node_identity == NodeIdentityPlacement::None {
+            return Ok(None);
+        }
+        let node_name = config.resolve_node_name().ok_or_else(|| {
+            MeasurementError::Config(
+                "node_identity needs kubernetes.node_name or the NODE_NAME environment variable"
+                    .to_string(),
+            )
+        })?;
+
+        let mut identity = node_name;
+        if !config.identity_labels.is_empty() {
+            let labels = read_downward_labels(Path::new(&config.labels_path));
+            for key in &config.identity_labels {
+                match labels.get(key) {
+                    Some(value) => identity.push_str(&format!(",{}={}", key, value)),
+                    None => warn!("Identity label {} not found in {}", key, config.labels_path),
+                }
+            }
+        }
+        info!("Tagging measurements with node identity {}", identity);
+        Ok(Some(Self {
+            identity,
+            placement: config.node_identity.clone(),
+        }))
+    }
+
+    /// Returns the (domain, operation) pair to extend.
+    pub fn apply(&self, domain: &str, operation: &str) -> (String, String) {
+        match self.placement {
+            NodeIdentityPlacement::Domain => (
+                format!("{}@{}", domain, self.identity),
+                operation.to_string(),
+            ),
+            NodeIdentityPlacement::Operation => (
+                domain.to_string(),
+                format!("{}:{}", self.identity, operation),
+            ),
+            NodeIdentityPlacement::None => (domain.to_string(), operation.to_string()),
+        }
+    }
+}
+
+/// Parses a downward API labels file (`key="value"` per line). A missing file
+/// yields no labels.
+fn read_downward_labels(path: &Path) -> BTreeMap<String, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            warn!("Failed to read downward API labels {:?}: {}", path, e);
+            return BTreeMap::new();
+        }
+    };
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().to_string(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect()
+}
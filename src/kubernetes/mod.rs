@@ -0,0 +1,15 @@
+// src/kubernetes/mod.rs
+
+pub mod client;
+pub mod health;
+pub mod identity;
+pub mod pod_specs;
+pub mod pods;
+pub mod policy;
+
+pub use client::KubeClient;
+pub use health::NodeHealthReporter;
+pub use identity::NodeIdentity;
+pub use pod_specs::K8sPodWatcher;
+pub use pods::PodAnnotationWatcher;
+pub use policy::PolicyController;
@@ -0,0 +1,173 @@
+// src/kubernetes/pod_specs.rs
+use crate::canonical_json;
+use crate::config::Config;
+use crate::error::{MeasurementError, Result};
+use crate::kubernetes::KubeClient;
+use crate::modules::ConfigWatcher;
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+
+const DOMAIN: &str = "k8s_pod";
+
+/// Prefix of the projected service account token volume, which the API
+/// server adds to every pod under a random name.
+const TOKEN_VOLUME_PREFIX: &str = "kube-api-access-";
+
+/// Extends the identity of every pod on this node, its UID and a digest of
+/// its normalized spec, once per pod.
+#[derive(Default)]
+pub struct K8sPodWatcher;
+
+/// Content extended for each pod, as canonical JSON.
+#[derive(Serialize)]
+struct PodRecord<'a> {
+    uid: &'a str,
+    spec_digest: String,
+}
+
+impl K8sPodWatcher {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn is_token_volume(name: &Value) -> bool {
+    name.as_str()
+        .is_some_and(|name| name.starts_with(TOKEN_VOLUME_PREFIX))
+}
+
+/// Drops what the control plane fills in differently for each replica (the
+/// node name and the service account token volume), so that the pods of one
+/// workload share a spec digest.
+fn normalized_spec(spec: &Value) -> Value {
+    let mut spec = spec.clone();
+    if let Some(fields) = spec.as_object_mut() {
+        fields.remove("nodeName");
+        if let Some(volumes) = fields.get_mut("volumes").and_then(Value::as_array_mut) {
+            volumes.retain(|volume| !is_token_volume(&volume["name"]));
+        }
+        for key in ["initContainers", "containers", "ephemeralContainers"] {
+            let containers = fields.get_mut(key).and_then(Value::as_array_mut);
+            for container in containers.into_iter().flatten() {
+                if let Some(mounts) = container
+                    .get_mut("volumeMounts")
+                    .and_then(Value::as_array_mut)
+                {
+                    mounts.retain(|mount| !is_token_volume(&mount["name"]));
+                }
+            }
+        }
+    }
+    spec
+}
+
+fn spec_digest(spec: &Value) -> Result<String> {
+    let encoded = canonical_json::to_vec(&normalized_spec(spec))
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    Ok(format!("sha256:{}", hex::encode(Sha256::digest(&encoded))))
+}
+
+async fn measure_pod(
+    pod: &Value,
+    pcr_index: Option<u64>,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    let operation = format!(
+        "{}/{}",
+        pod["metadata"]["namespace"].as_str().unwrap_or_default(),
+        pod["metadata"]["name"].as_str().unwrap_or_default()
+    );
+    let record = PodRecord {
+        uid: pod["metadata"]["uid"].as_str().unwrap_or_default(),
+        spec_digest: spec_digest(&pod["spec"])?,
+    };
+    let content =
+        canonical_json::to_vec(&record).map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    let content = String::from_utf8_lossy(&content);
+    debug!("Extending pod measurement {} = {}", operation, content);
+    sink.extend(DOMAIN, &operation, &content, pcr_index).await?;
+    info!("Measured pod {}", operation);
+    Ok(())
+}
+
+#[async_trait]
+impl ConfigWatcher for K8sPodWatcher {
+    fn name(&self) -> &str {
+        "K8sPodWatcher"
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.kubernetes.pod_specs
+    }
+
+    /// Polls the pods scheduled onto this node; pods that already run when
+    /// the watcher starts are measured on the first pass.
+    async fn watch(
+        &self,
+        _config_path: PathBuf,
+        shared_config: Arc<RwLock<Config>>,
+        sink: Arc<dyn MeasurementSink>,
+    ) -> Result<()> {
+        let node_name = shared_config
+            .read()
+            .await
+            .kubernetes
+            .resolve_node_name()
+            .ok_or_else(|| {
+                MeasurementError::Kubernetes(
+                    "Set [kubernetes].node_name or NODE_NAME to measure pod specs".to_string(),
+                )
+            })?;
+        let client = KubeClient::in_cluster()?;
+        info!("Measuring pod specs of node {}", node_name);
+
+        let path = format!("/api/v1/pods?fieldSelector=spec.nodeName%3D{}", node_name);
+        let mut measured: HashSet<String> = HashSet::new();
+        loop {
+            let (enabled, pcr_index, poll_interval) = {
+                let config = shared_config.read().await;
+                (
+                    config.kubernetes.pod_specs,
+                    config.kubernetes.pod_pcr_index.map(|v| v as u64),
+                    Duration::from_secs(config.kubernetes.poll_interval_secs),
+                )
+            };
+            if enabled {
+                match client.get(&path).await {
+                    Ok(list) => {
+                        let pods = list["items"].as_array().cloned().unwrap_or_default();
+                        let live: HashSet<&str> = pods
+                            .iter()
+                            .filter_map(|pod| pod["metadata"]["uid"].as_str())
+                            .collect();
+                        measured.retain(|uid| live.contains(uid.as_str()));
+                        for pod in &pods {
+                            let uid = pod["metadata"]["uid"].as_str().unwrap_or_default();
+                            if uid.is_empty() || measured.contains(uid) {
+                                continue;
+                            }
+                            match measure_pod(pod, pcr_index, &sink).await {
+                                Ok(()) => {
+                                    measured.insert(uid.to_string());
+                                }
+                                Err(e) => warn!("Failed to measure pod {}: {}", uid, e),
+                            }
+                        }
+                    }
+                    Err(e) => warn!("Failed to list pods: {}", e),
+                }
+            }
+            sleep(poll_interval).await;
+        }
+    }
+}
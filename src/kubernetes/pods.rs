@@ -0,0 +1,280 @@
+// src/kubernetes/pods.rs
+use crate::config::KubernetesConfig;
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use crate::hook::tree_digest;
+use crate::kubernetes::KubeClient;
+use crate::modules::file_measurer::resolve_in_root;
+use crate::sink::MeasurementSink;
+use log::{debug, info, warn};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Comma-separated container paths a pod asks to have measured.
+pub const PATHS_ANNOTATION: &str = "measurement.io/paths";
+
+const DOMAIN: &str = "pod_path";
+
+/// Measures the paths named in the `measurement.io/paths` annotation of pods
+/// running on this node, once per pod.
+pub struct PodAnnotationWatcher {
+    client: Arc<KubeClient>,
+    node_name: String,
+    kubelet_root: PathBuf,
+    host_root: PathBuf,
+    pcr_index: Option<u64>,
+    poll_interval: Duration,
+}
+
+impl PodAnnotationWatcher {
+    pub fn new(client: Arc<KubeClient>, node_name: String, config: &KubernetesConfig) -> Self {
+        Self {
+            client,
+            node_name,
+            kubelet_root: PathBuf::from(&config.kubelet_root),
+            host_root: PathBuf::from(&config.host_root),
+            pcr_index: config.pod_pcr_index.map(|v| v as u64),
+            poll_interval: Duration::from_secs(config.poll_interval_secs),
+        }
+    }
+
+    pub async fn run(&self, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        info!(
+            "Watching {} annotations on pods of node {}",
+            PATHS_ANNOTATION, self.node_name
+        );
+        let mut measured: HashSet<String> = HashSet::new();
+        loop {
+            match self.running_pods().await {
+                Ok(pods) => {
+                    let live: HashSet<String> = pods.iter().map(pod_uid).collect();
+                    measured.retain(|uid| live.contains(uid));
+                    for pod in &pods {
+                        let uid = pod_uid(pod);
+                        if measured.contains(&uid) {
+                            continue;
+                        }
+                        // A pod is only retried while every path failed, so a
+                        // partially measured pod is not extended twice.
+                        if self.measure_pod(pod, &sink).await {
+                            measured.insert(uid);
+                        }
+                    }
+                }
+                Err(e) => warn!("Failed to list pods: {}", e),
+            }
+            sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn running_pods(&self) -> Result<Vec<Value>> {
+        let list = self
+            .client
+            .get(&format!(
+                "/api/v1/pods?fieldSelector=spec.nodeName%3D{}",
+                self.node_name
+            ))
+            .await?;
+        Ok(list["items"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|pod| {
+                pod["metadata"]["annotations"][PATHS_ANNOTATION].is_string()
+                    && pod["status"]["phase"] == "Running"
+            })
+            .collect())
+    }
+
+    /// Returns true once at least one path of the pod was measured.
+    async fn measure_pod(&self, pod: &Value, sink: &dyn MeasurementSink) -> bool {
+        let namespace = pod["metadata"]["namespace"].as_str().unwrap_or_default();
+        let name = pod["metadata"]["name"].as_str().unwrap_or_default();
+        let paths = pod["metadata"]["annotations"][PATHS_ANNOTATION]
+            .as_str()
+            .unwrap_or_default();
+
+        let mut any_measured = false;
+        for path in paths.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match self.measure_path(pod, path, sink).await {
+                Ok(()) => any_measured = true,
+                Err(e) => warn!(
+                    "Failed to measure {} of pod {}/{}: {}",
+                    path, namespace, name, e
+                ),
+            }
+        }
+        any_measured
+    }
+
+    async fn measure_path(
+        &self,
+        pod: &Value,
+        path: &str,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let (container, host_path) = self.resolve(pod, path).await?;
+        let digest = {
+            let host_path = host_path.clone();
+            tokio::task::spawn_blocking(move || {
+                if host_path.is_dir() {
+                    tree_digest(&host_path)
+                } else {
+                    digest_reader(&mut File::open(&host_path)?, "sha256")
+                }
+            })
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+        };
+        let operation = format!(
+            "{}/{}/{}:{}",
+            pod["metadata"]["namespace"].as_str().unwrap_or_default(),
+            pod["metadata"]["name"].as_str().unwrap_or_default(),
+            container,
+            path
+        );
+        debug!(
+            "{} resolved to {:?}, digest {}",
+            operation, host_path, digest
+        );
+        sink.extend(DOMAIN, &operation, &digest, self.pcr_index)
+            .await?;
+        info!("Measured pod path {}", operation);
+        Ok(())
+    }
+
+    /// Maps a path inside a container to the host path backing it, using the
+    /// container volume mount with the longest matching prefix.
+    ///
+    /// The `subPath` and the rest of the path are resolved inside the volume,
+    /// so `..` and symbolic links cannot lead to other host files.
+    async fn resolve(&self, pod: &Value, path: &str) -> Result<(String, PathBuf)> {
+        let containers = pod["spec"]["containers"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let mut best: Option<(String, Value, usize)> = None;
+        for container in &containers {
+            for mount in container["volumeMounts"].as_array().into_iter().flatten() {
+                let mount_path = mount["mountPath"].as_str().unwrap_or_default();
+                let covers = path == mount_path
+                    || path.starts_with(&format!("{}/", mount_path.trim_end_matches('/')));
+                if covers
+                    && best
+                        .as_ref()
+                        .map_or(true, |(_, _, len)| mount_path.len() > *len)
+                {
+                    let container_name = container["name"].as_str().unwrap_or_default().to_string();
+                    best = Some((container_name, mount.clone(), mount_path.len()));
+                }
+            }
+        }
+        let (container, mount, _) = best.ok_or_else(|| {
+            MeasurementError::Kubernetes(format!("{} is not on a volume mount", path))
+        })?;
+
+        let volume_name = mount["name"].as_str().unwrap_or_default();
+        let volume = pod["spec"]["volumes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|v| v["name"] == volume_name)
+            .ok_or_else(|| {
+                MeasurementError::Kubernetes(format!("Volume {} not found in pod", volume_name))
+            })?;
+
+        let volume_root = self.on_host(&self.volume_host_path(pod, volume).await?);
+        let mut inner = PathBuf::from("/");
+        if let Some(sub_path) = mount["subPath"].as_str() {
+            inner.push(sub_path.trim_start_matches('/'));
+        }
+        let mount_path = mount["mountPath"].as_str().unwrap_or_default();
+        inner.push(path[mount_path.len()..].trim_start_matches('/'));
+        if inner.components().any(|c| c == Component::ParentDir) {
+            return Err(MeasurementError::PathPolicy(format!(
+                "{} of container {} leaves its volume",
+                path, container
+            )));
+        }
+        let resolved = resolve_in_root(&volume_root, &inner)
+            .map_err(|e| MeasurementError::Kubernetes(format!("Cannot resolve {}: {}", path, e)))?;
+        Ok((container, resolved.host))
+    }
+
+    async fn volume_host_path(&self, pod: &Value, volume: &Value) -> Result<PathBuf> {
+        let name = volume["name"].as_str().unwrap_or_default();
+        let pod_volumes = self
+            .kubelet_root
+            .join("pods")
+            .join(pod_uid(pod))
+            .join("volumes");
+
+        if let Some(path) = volume["hostPath"]["path"].as_str() {
+            return Ok(PathBuf::from(path));
+        }
+        if volume["emptyDir"].is_object() {
+            return Ok(pod_volumes.join("kubernetes.io~empty-dir").join(name));
+        }
+        if volume["configMap"].is_object() {
+            return Ok(pod_volumes.join("kubernetes.io~configmap").join(name));
+        }
+        if volume["secret"].is_object() {
+            return Ok(pod_volumes.join("kubernetes.io~secret").join(name));
+        }
+        if let Some(claim) = volume["persistentVolumeClaim"]["claimName"].as_str() {
+            let namespace = pod["metadata"]["namespace"].as_str().unwrap_or_default();
+            let pvc = self
+                .client
+                .get(&format!(
+                    "/api/v1/namespaces/{}/persistentvolumeclaims/{}",
+                    namespace, claim
+                ))
+                .await?;
+            let pv_name = pvc["spec"]["volumeName"].as_str().ok_or_else(|| {
+                MeasurementError::Kubernetes(format!("PVC {} is not bound", claim))
+            })?;
+            let pv = self
+                .client
+                .get(&format!("/api/v1/persistentvolumes/{}", pv_name))
+                .await?;
+            if let Some(path) = pv["spec"]["hostPath"]["path"]
+                .as_str()
+                .or_else(|| pv["spec"]["local"]["path"].as_str())
+            {
+                return Ok(PathBuf::from(path));
+            }
+            if pv["spec"]["csi"].is_object() {
+                return Ok(pod_volumes
+                    .join("kubernetes.io~csi")
+                    .join(pv_name)
+                    .join("mount"));
+            }
+        }
+        Err(MeasurementError::Kubernetes(format!(
+            "Unsupported volume type for {}",
+            name
+        )))
+    }
+
+    /// Prefixes host paths with `host_root` when the host filesystem is
+    /// mounted into the tool's container.
+    fn on_host(&self, path: &Path) -> PathBuf {
+        match path.strip_prefix("/") {
+            Ok(relative) => self.host_root.join(relative),
+            Err(_) => self.host_root.join(path),
+        }
+    }
+}
+
+fn pod_uid(pod: &Value) -> String {
+    pod["metadata"]["uid"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string()
+}
@@ -0,0 +1,400 @@
+// src/kubernetes/policy.rs
+use crate::config::{Config, MeasurementEntry};
+use crate::error::{MeasurementError, Result};
+use crate::kubernetes::{KubeClient, NodeHealthReporter};
+use crate::modules::{
+    run_measurers, ConfigChangeHandler, FileMeasurementChangeHandler,
+    ModelDirMeasurementChangeHandler, RunOutcome,
+};
+use crate::report::now_rfc3339;
+use crate::sink::MeasurementSink;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const POLICY_API: &str = "/apis/measurement.io/v1alpha1";
+
+#[derive(Debug, Deserialize)]
+struct MeasurementPolicy {
+    metadata: ObjectMeta,
+    #[serde(default)]
+    spec: PolicySpec,
+}
+
+/// The node and the policies as reconciled, to watch for changes after.
+struct Observed {
+    node_labels: BTreeMap<String, String>,
+    node_version: String,
+    policies_version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ObjectMeta {
+    name: String,
+    #[serde(default)]
+    generation: i64,
+}
+
+/// The measurement settings a policy may set. Node-local settings such as the
+/// cryptpilot binary or the AA endpoint are deliberately not exposed.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PolicySpec {
+    /// Labels a node must carry for the policy to apply; empty matches all.
+    #[serde(default)]
+    node_selector: BTreeMap<String, String>,
+    #[serde(default)]
+    file_measurement: Option<FilePolicy>,
+    #[serde(default)]
+    model_dir_measurement: Option<ModelDirPolicy>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FilePolicy {
+    enable: Option<bool>,
+    pcr_index: Option<u32>,
+    hash_algorithm: Option<String>,
+    #[serde(default)]
+    files: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelDirPolicy {
+    enable: Option<bool>,
+    pcr_index: Option<u32>,
+    #[serde(default)]
+    directories: Vec<String>,
+}
+
+/// Reconciles the `MeasurementPolicy` objects selecting this node into the
+/// local measurement config and reports the outcome in each policy's status.
+pub struct PolicyController {
+    client: Arc<KubeClient>,
+    node_name: String,
+    namespace: String,
+    base_config: Config,
+    effective_config_path: Option<PathBuf>,
+    /// Delay before listing again after a failed list or watch.
+    retry_interval: Duration,
+    health: Option<NodeHealthReporter>,
+}
+
+impl PolicyController {
+    pub fn new(client: Arc<KubeClient>, base_config: Config) -> Result<Self> {
+        let k8s = &base_config.kubernetes;
+        let node_name = k8s.resolve_node_name().ok_or_else(|| {
+            MeasurementError::Config(
+                "kubernetes.node_name is not set and NODE_NAME is not in the environment"
+                    .to_string(),
+            )
+        })?;
+        let namespace = k8s
+            .policy_namespace
+            .clone()
+            .unwrap_or_else(|| client.namespace().to_string());
+        let health = k8s
+            .node_condition
+            .then(|| NodeHealthReporter::new(client.clone(), node_name.clone()));
+        Ok(Self {
+            client,
+            node_name,
+            namespace,
+            effective_config_path: k8s.effective_config_path.as_ref().map(PathBuf::from),
+            retry_interval: Duration::from_secs(k8s.poll_interval_secs),
+            health,
+            base_config,
+        })
+    }
+
+    /// Reconciles, then watches the policies and the node's labels and
+    /// reconciles again on every change, until the task is cancelled. Fails
+    /// when the first reconcile hits a critical failure, as the daemon's
+    /// initial run does.
+    pub async fn run(&self, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        info!(
+            "Reconciling MeasurementPolicy objects in namespace {} for node {}",
+            self.namespace, self.node_name
+        );
+        let handlers: Vec<Box<dyn ConfigChangeHandler>> = vec![
+            Box::new(FileMeasurementChangeHandler::new()),
+            Box::new(ModelDirMeasurementChangeHandler::new()),
+        ];
+        let mut applied: Option<Config> = None;
+        loop {
+            let observed = match self.reconcile(&mut applied, &handlers, sink.clone()).await {
+                Ok(observed) => observed,
+                Err(e @ MeasurementError::CriticalEntry(_)) => return Err(e),
+                Err(e) => {
+                    warn!("Policy reconciliation failed: {}", e);
+                    sleep(self.retry_interval).await;
+                    continue;
+                }
+            };
+            if let Err(e) = self.wait_for_change(&observed).await {
+                warn!("Watching for policy changes failed: {}", e);
+                sleep(self.retry_interval).await;
+            }
+        }
+    }
+
+    /// Returns once a policy in the namespace or the labels of the node
+    /// changed, or when a watch ended and the state must be listed again.
+    async fn wait_for_change(&self, observed: &Observed) -> Result<()> {
+        let mut policies = self
+            .client
+            .watch(
+                &format!(
+                    "{}/namespaces/{}/measurementpolicies",
+                    POLICY_API, self.namespace
+                ),
+                &observed.policies_version,
+            )
+            .await?;
+        let mut node = self
+            .client
+            .watch(
+                &format!(
+                    "/api/v1/nodes?fieldSelector=metadata.name%3D{}",
+                    self.node_name
+                ),
+                &observed.node_version,
+            )
+            .await?;
+        let node_labels_changed = async {
+            // The node also changes with every status update.
+            while let Some(event) = node.next().await? {
+                let labels: BTreeMap<String, String> =
+                    serde_json::from_value(event["object"]["metadata"]["labels"].clone())
+                        .unwrap_or_default();
+                if event["type"] != "MODIFIED" || labels != observed.node_labels {
+                    break;
+                }
+            }
+            Ok::<_, MeasurementError>(())
+        };
+        tokio::select! {
+            event = policies.next() => event.map(|_| ()),
+            changed = node_labels_changed => changed,
+        }
+    }
+
+    async fn reconcile(
+        &self,
+        applied: &mut Option<Config>,
+        handlers: &[Box<dyn ConfigChangeHandler>],
+        sink: Arc<dyn MeasurementSink>,
+    ) -> Result<Observed> {
+        let node = self
+            .client
+            .get(&format!("/api/v1/nodes/{}", self.node_name))
+            .await?;
+        let node_labels: BTreeMap<String, String> =
+            serde_json::from_value(node["metadata"]["labels"].clone()).unwrap_or_default();
+        let (policies, policies_version) = self.matching_policies(&node_labels).await?;
+        let effective = effective_config(&self.base_config, &policies);
+        let observed = Observed {
+            node_labels,
+            node_version: resource_version(&node),
+            policies_version,
+        };
+
+        if let Some(old) = applied.as_ref() {
+            if old.file_measurement == effective.file_measurement
+                && old.model_dir_measurement == effective.model_dir_measurement
+            {
+                debug!("Measurement policy unchanged.");
+                return Ok(observed);
+            }
+        }
+        info!(
+            "Applying {} matching MeasurementPolicy objects",
+            policies.len()
+        );
+
+        let mut critical = false;
+        let failures = match applied.as_ref() {
+            None => {
+                let run = run_measurers(Arc::new(effective.clone()), sink, None).await;
+                critical = run.outcome == RunOutcome::CriticalFailure;
+                run.failures
+            }
+            Some(old) => {
+                let mut failures = Vec::new();
+                for handler in handlers {
+                    if handler.is_enabled(&effective) {
+                        if let Err(e) = handler.handle_change(old, &effective, sink.clone()).await {
+                            error!("{} failed: {}", handler.name(), e);
+                            failures.push(format!("{}: {}", handler.name(), e));
+                        }
+                    }
+                }
+                failures
+            }
+        };
+        if let Some(ref health) = self.health {
+            health.report(&failures).await;
+        }
+
+        if let Some(ref path) = self.effective_config_path {
+            if let Err(e) = persist_config(path, &effective) {
+                warn!("Failed to write effective config {:?}: {}", path, e);
+            }
+        }
+        *applied = Some(effective);
+
+        for policy in &policies {
+            if let Err(e) = self.report_status(policy, &failures).await {
+                warn!(
+                    "Failed to update status of MeasurementPolicy {}: {}",
+                    policy.metadata.name, e
+                );
+            }
+        }
+        if critical {
+            return Err(MeasurementError::CriticalEntry(failures.join("; ")));
+        }
+        Ok(observed)
+    }
+
+    /// Policies whose node selector matches, sorted by name so that merging is
+    /// deterministic, and the resource version of the list.
+    async fn matching_policies(
+        &self,
+        labels: &BTreeMap<String, String>,
+    ) -> Result<(Vec<MeasurementPolicy>, String)> {
+        let list = self
+            .client
+            .get(&format!(
+                "{}/namespaces/{}/measurementpolicies",
+                POLICY_API, self.namespace
+            ))
+            .await?;
+        let items = list["items"].as_array().cloned().unwrap_or_default();
+        let mut policies = Vec::new();
+        for item in items {
+            match serde_json::from_value::<MeasurementPolicy>(item) {
+                Ok(policy) => {
+                    let matches = policy
+                        .spec
+                        .node_selector
+                        .iter()
+                        .all(|(k, v)| labels.get(k) == Some(v));
+                    if matches {
+                        policies.push(policy);
+                    }
+                }
+                Err(e) => warn!("Skipping malformed MeasurementPolicy: {}", e),
+            }
+        }
+        policies.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+        Ok((policies, resource_version(&list)))
+    }
+
+    /// Records this node's outcome under `status.nodes.<node>`, so that every
+    /// node selected by a policy reports independently.
+    async fn report_status(&self, policy: &MeasurementPolicy, failures: &[String]) -> Result<()> {
+        let patch = json!({
+            "status": {
+                "nodes": {
+                    &self.node_name: {
+                        "lastRun": now_rfc3339(),
+                        "observedGeneration": policy.metadata.generation,
+                        "failures": failures.len(),
+                        "lastError": failures.last(),
+                    }
+                }
+            }
+        });
+        self.client
+            .merge_patch(
+                &format!(
+                    "{}/namespaces/{}/measurementpolicies/{}/status",
+                    POLICY_API, self.namespace, policy.metadata.name
+                ),
+                &patch,
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// Layers the policies over the base config. Booleans and scalars are taken
+/// from the last policy that sets them; file and directory lists are merged.
+fn effective_config(base: &Config, policies: &[MeasurementPolicy]) -> Config {
+    let mut config = base.clone();
+    for policy in policies {
+        if let Some(ref fm) = policy.spec.file_measurement {
+            let target = &mut config.file_measurement;
+            if let Some(enable) = fm.enable {
+                target.enable = enable;
+            }
+            if let Some(pcr_index) = fm.pcr_index {
+                target.pcr_index = pcr_index;
+            }
+            if let Some(ref alg) = fm.hash_algorithm {
+                target.hash_algorithm = alg.clone();
+            }
+            merge_unique(&mut target.files, &fm.files);
+        }
+        if let Some(ref md) = policy.spec.model_dir_measurement {
+            let target = &mut config.model_dir_measurement;
+            if let Some(enable) = md.enable {
+                target.enable = enable;
+            }
+            if md.pcr_index.is_some() {
+                target.pcr_index = md.pcr_index;
+            }
+            merge_unique(&mut target.directories, &md.directories);
+        }
+    }
+    config
+}
+
+fn merge_unique(target: &mut Vec<MeasurementEntry>, extra: &[String]) {
+    for item in extra {
+        if !target.iter().any(|entry| entry.path() == item) {
+            target.push(MeasurementEntry::from(item.clone()));
+        }
+    }
+}
+
+fn resource_version(object: &serde_json::Value) -> String {
+    object["metadata"]["resourceVersion"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Writes the effective measurement sections as a TOML config fragment, as
+/// an auditable record of what the node measures. Whatever format the base
+/// config is in, including it (or dropping it into a `conf.d`) reproduces
+/// the node's measurement settings.
+fn persist_config(target: &Path, config: &Config) -> Result<()> {
+    let mut table = toml::Table::new();
+    table.insert(
+        "file_measurement".to_string(),
+        to_toml(&config.file_measurement)?,
+    );
+    table.insert(
+        "model_dir_measurement".to_string(),
+        to_toml(&config.model_dir_measurement)?,
+    );
+
+    let tmp_path = target.with_extension("tmp");
+    fs::write(&tmp_path, table.to_string())?;
+    fs::rename(&tmp_path, target)?;
+    Ok(())
+}
+
+fn to_toml<T: Serialize>(value: &T) -> Result<toml::Value> {
+    toml::Value::try_from(value)
+        .map_err(|e| MeasurementError::Config(format!("Failed to encode config: {}", e)))
+}
@@ -0,0 +1,62 @@
+// src/lib.rs
+//! Runtime measurement of files, model directories and platform state into
+//! the TEE event log through the Attestation Agent.
+//!
+//! The `measurement_tool` binary is a thin CLI over this crate. Other
+//! daemons, such as an init agent, can embed the measurers directly:
+//!
+//! ```no_run
+//! use measurement_tool::{sink, AAClient, Config, FileMeasurer, Measurable};
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> measurement_tool::Result<()> {
+//! let config = Arc::new(Config::load(None)?);
+//! let aa_client = Arc::new(AAClient::from_config(&config).await?);
+//! let sink = sink::from_config(&config, aa_client)?;
+//! FileMeasurer::new().measure(config, sink).await
+//! # }
+//! ```
+mod alias;
+pub mod api;
+mod archive;
+mod canonical_json;
+mod cel;
+pub mod config;
+mod container;
+pub mod control;
+mod descriptor;
+mod digest;
+pub mod docker;
+pub mod doctor;
+pub mod error;
+mod event_log;
+pub mod evidence;
+mod extension_state;
+mod fs_watch;
+mod hash_cache;
+pub mod hook;
+mod ima;
+mod journal;
+pub mod kata;
+pub mod kubernetes;
+mod listener;
+pub mod logging;
+pub mod modules;
+pub mod nri;
+mod offline_queue;
+mod overlay;
+pub mod report;
+pub mod rpc_client;
+pub mod rvps;
+mod schedule;
+pub mod sink;
+pub mod status;
+mod tpm;
+mod verity;
+mod rpc_generated; // Module for ttrpc generated code
+
+pub use config::Config;
+pub use error::{MeasurementError, Result};
+pub use modules::{FileMeasurer, Measurable, ModelDirMeasurer};
+pub use rpc_client::AAClient;
+pub use sink::MeasurementSink;
@@ -0,0 +1,144 @@
+// src/listener.rs
+use crate::error::{MeasurementError, Result};
+use std::env;
+use std::ffi::CString;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::path::Path;
+use tokio::net::TcpListener;
+
+/// The first descriptor systemd passes to a socket-activated service.
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// Returns the listening socket systemd passed for `name`, the
+/// `FileDescriptorName=` of its socket unit, when the process was started
+/// by socket activation.
+pub fn activated_fd(name: &str) -> Option<RawFd> {
+    let pid: u32 = env::var("LISTEN_PID").ok()?.parse().ok()?;
+    if pid != std::process::id() {
+        return None;
+    }
+    let count: usize = env::var("LISTEN_FDS").ok()?.parse().ok()?;
+    let names = env::var("LISTEN_FDNAMES").ok()?;
+    let index = names.split(':').take(count).position(|n| n == name)?;
+    let fd = SD_LISTEN_FDS_START + index as RawFd;
+    // Keep the socket out of the commands the measurers run.
+    unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+    Some(fd)
+}
+
+/// The TCP listener systemd passed for `name`, or a new one bound to
+/// `listen`.
+pub async fn tcp_listener(name: &str, listen: &str) -> Result<TcpListener> {
+    match activated_fd(name) {
+        Some(fd) => {
+            let listener = unsafe { std::net::TcpListener::from_raw_fd(fd) };
+            listener.set_nonblocking(true)?;
+            Ok(TcpListener::from_std(listener)?)
+        }
+        None => TcpListener::bind(listen)
+            .await
+            .map_err(|e| MeasurementError::Config(format!("Cannot listen on {}: {}", listen, e))),
+    }
+}
+
+/// Resolves a user name or numeric uid.
+fn uid(user: &str) -> Result<u32> {
+    if let Ok(uid) = user.parse() {
+        return Ok(uid);
+    }
+    let name = CString::new(user)
+        .map_err(|_| MeasurementError::Config(format!("Invalid user name '{}'", user)))?;
+    let entry = unsafe { libc::getpwnam(name.as_ptr()) };
+    if entry.is_null() {
+        return Err(MeasurementError::Config(format!("Unknown user '{}'", user)));
+    }
+    Ok(unsafe { (*entry).pw_uid })
+}
+
+/// Resolves a group name or numeric gid.
+fn gid(group: &str) -> Result<u32> {
+    if let Ok(gid) = group.parse() {
+        return Ok(gid);
+    }
+    let name = CString::new(group)
+        .map_err(|_| MeasurementError::Config(format!("Invalid group name '{}'", group)))?;
+    let entry = unsafe { libc::getgrnam(name.as_ptr()) };
+    if entry.is_null() {
+        return Err(MeasurementError::Config(format!(
+            "Unknown group '{}'",
+            group
+        )));
+    }
+    Ok(unsafe { (*entry).gr_gid })
+}
+
+/// Binds a unix socket at `path` with the given owner, group and mode.
+///
+/// `bind` creates the socket inside a private 0700 directory next to
+/// `path`, where no other user can reach it, and the socket is moved into
+/// place only once its ownership and mode are set. The move also replaces a
+/// socket left behind by an earlier run.
+pub fn bind_unix_socket<T>(
+    path: &Path,
+    owner: Option<&str>,
+    group: Option<&str>,
+    mode: u32,
+    bind: impl FnOnce(&Path) -> Result<T>,
+) -> Result<T> {
+    let uid = owner.map(uid).transpose()?;
+    let gid = group.map(gid).transpose()?;
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = path.file_name().ok_or_else(|| {
+        MeasurementError::Config(format!("{} is not a socket path", path.display()))
+    })?;
+    fs::create_dir_all(parent)?;
+    let staging = tempfile::Builder::new()
+        .prefix(".bind-")
+        .tempdir_in(parent)?;
+    fs::set_permissions(staging.path(), fs::Permissions::from_mode(0o700))?;
+    let staged = staging.path().join(file_name);
+
+    let bound = bind(&staged)?;
+    if uid.is_some() || gid.is_some() {
+        std::os::unix::fs::chown(&staged, uid, gid)?;
+    }
+    fs::set_permissions(&staged, fs::Permissions::from_mode(mode))?;
+    fs::rename(&staged, path)?;
+    Ok(bound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+
+    #[test]
+    fn unix_socket_is_moved_into_place_with_its_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run/control.sock");
+        // A stale socket from an earlier run is replaced.
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        drop(UnixListener::bind(&path).unwrap());
+
+        let _listener = bind_unix_socket(&path, None, None, 0o600, |staged| {
+            assert_ne!(staged, path);
+            let staging = fs::metadata(staged.parent().unwrap()).unwrap();
+            assert_eq!(staging.permissions().mode() & 0o777, 0o700);
+            Ok(UnixListener::bind(staged)?)
+        })
+        .unwrap();
+
+        let metadata = fs::metadata(&path).unwrap();
+        assert!(metadata.file_type().is_socket());
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        let entries: Vec<_> = fs::read_dir(path.parent().unwrap()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+        std::os::unix::net::UnixStream::connect(&path).unwrap();
+    }
+}
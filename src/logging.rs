@@ -0,0 +1,121 @@
+// src/logging.rs
+use crate::config::LogFormat;
+use crate::report::{unix_timestamp, MeasurementRecord};
+use log::{info, Log};
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Log target of the audit records, e.g. for `RUST_LOG=audit=off`.
+pub const AUDIT_TARGET: &str = "audit";
+
+/// Whether log lines are written as JSON objects.
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Passes each record to the env_logger of the current format.
+struct Logger {
+    text: env_logger::Logger,
+    json: env_logger::Logger,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.text.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if JSON.load(Ordering::Relaxed) {
+            self.json.log(record);
+        } else {
+            self.text.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.text.flush();
+        self.json.flush();
+    }
+}
+
+fn builder() -> env_logger::Builder {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+}
+
+/// One JSON object per line. Audit records are already JSON and are
+/// nested as such.
+fn format_json(buf: &mut env_logger::fmt::Formatter, record: &log::Record) -> std::io::Result<()> {
+    let message = record.args().to_string();
+    let mut line = serde_json::json!({
+        "timestamp": buf.timestamp().to_string(),
+        "level": record.level().as_str(),
+        "target": record.target(),
+    });
+    match serde_json::from_str::<serde_json::Value>(&message) {
+        Ok(audit) if record.target() == AUDIT_TARGET => line["audit"] = audit,
+        _ => line["message"] = message.into(),
+    }
+    writeln!(buf, "{}", line)
+}
+
+/// Installs env_logger, filtered by `RUST_LOG` (default `info`), in plain
+/// text until a configuration with `log_format = "json"` is loaded.
+pub fn init() {
+    let text = builder().build();
+    let json = builder().format(format_json).build();
+    let max_level = text.filter();
+    if log::set_logger(Box::leak(Box::new(Logger { text, json }))).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// Switches the format of the log lines written from now on.
+pub fn set_format(format: LogFormat) {
+    JSON.store(format == LogFormat::Json, Ordering::Relaxed);
+}
+
+/// What happened to one extension.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// A backend acknowledged it.
+    Extended,
+    /// The extension state shows it was already extended in this boot.
+    Unchanged,
+    /// Held in the offline queue until a backend is reachable.
+    Queued,
+    /// A queued extension the backend refused.
+    Dropped,
+    Failed,
+}
+
+#[derive(Serialize)]
+struct AuditRecord<'a> {
+    timestamp: u64,
+    domain: &'a str,
+    operation: &'a str,
+    digest: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pcr: Option<u64>,
+    outcome: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backend: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Logs one audit record of `record` under the `audit` target.
+pub fn audit(record: &MeasurementRecord, outcome: Outcome, error: Option<String>) {
+    let audit = AuditRecord {
+        timestamp: unix_timestamp(),
+        domain: &record.domain,
+        operation: &record.operation,
+        digest: &record.content,
+        pcr: record.register_index,
+        outcome,
+        backend: record.backend.as_deref(),
+        error,
+    };
+    if let Ok(line) = serde_json::to_string(&audit) {
+        info!(target: AUDIT_TARGET, "{}", line);
+    }
+}
@@ -1,31 +1,724 @@
 // src/main.rs
-mod config;
-mod error;
-mod modules;
-mod rpc_client;
-mod rpc_generated; // Module for ttrpc generated code
-
-use crate::config::Config;
-use crate::modules::{
-    ConfigChangeHandler, ConfigFileWatcher, ConfigWatcher, FileMeasurementChangeHandler,
-    FileMeasurer, Measurable, ModelDirMeasurementChangeHandler, ModelDirMeasurer,
-};
-use crate::rpc_client::AAClient;
+mod cli;
+mod daemon;
+
+use crate::cli::Command;
+use crate::daemon::Pidfile;
 use anyhow::Result;
-use log::{error, info};
+use glob::Pattern;
+use log::{error, info, warn};
+use measurement_tool::config::{self, Config, MeasurementEntry, WatcherSupervisionConfig};
+use measurement_tool::evidence::EvidenceBundle;
+use measurement_tool::kubernetes::{
+    K8sPodWatcher, KubeClient, PodAnnotationWatcher, PolicyController,
+};
+use measurement_tool::modules::{
+    registered_measurers, run_measurers, ConfigChangeHandler, ConfigFileWatcher, ConfigWatcher,
+    ExecMeasurer, FileContentWatcher, FileMeasurementChangeHandler, FileMeasurer,
+    MeasurerToggleHandler, ModelDirContentWatcher, ModelDirMeasurementChangeHandler,
+    ModelDirMeasurer, MountMeasurer, RunOutcome,
+};
+use measurement_tool::report::SummaryReport;
+use measurement_tool::rpc_client::AAClient;
+use measurement_tool::rvps::ManifestFormat;
+use measurement_tool::sink::MeasurementSink;
+use measurement_tool::status::DaemonStatus;
+use measurement_tool::{
+    api, control, docker, doctor, hook, kata, logging, nri, report, rvps, sink, status,
+};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::{Notify, RwLock};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     // Initialize logger based on RUST_LOG env var, or default to info
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    logging::init();
+
+    // Prints help, version and usage errors and exits, with status 2 for
+    // the errors.
+    let invocation = cli::parse(env::args_os()).unwrap_or_else(|e| e.exit());
+    let config_options = config::set_cli_overrides(&invocation.overrides).and_then(|()| {
+        match invocation.config_format {
+            Some(format) => config::set_config_format(format),
+            None => Ok(()),
+        }
+    });
+    if let Err(e) = config_options {
+        eprintln!("{}", e);
+        exit(2);
+    }
+    let command = invocation.command;
+
+    // Detaching has to happen before the runtime spawns its worker threads.
+    if let Command::Daemon {
+        pidfile,
+        foreground,
+        ..
+    } = &command
+    {
+        if let Some(path) = pidfile {
+            if let Err(e) = Pidfile::check(path) {
+                error!("{:#}", e);
+                exit(1);
+            }
+        }
+        if !foreground {
+            if let Err(e) = daemon::daemonize() {
+                error!("Failed to daemonize: {:#}", e);
+                exit(1);
+            }
+        }
+    }
+
+    tokio::runtime::Runtime::new()?.block_on(dispatch(command))
+}
+
+async fn dispatch(command: Command) -> Result<()> {
+    match command {
+        Command::Run {
+            config_path,
+            dry_run,
+            force_remeasure,
+        } => run(config_path, None, dry_run, force_remeasure).await,
+        Command::OneShot {
+            config_path,
+            dry_run,
+            force_remeasure,
+        } => run(config_path, Some(true), dry_run, force_remeasure).await,
+        Command::Daemon {
+            config_path,
+            pidfile,
+            dry_run,
+            force_remeasure,
+            ..
+        } => {
+            let _pidfile = match pidfile {
+                Some(path) => match Pidfile::acquire(&path) {
+                    Ok(guard) => Some(guard),
+                    Err(e) => {
+                        error!("{:#}", e);
+                        exit(1);
+                    }
+                },
+                None => None,
+            };
+            run(config_path, Some(false), dry_run, force_remeasure).await
+        }
+        Command::CheckConfig { config_path } => {
+            check_config(config_path);
+            Ok(())
+        }
+        Command::Measure {
+            config_path,
+            files,
+            dirs,
+            dry_run,
+            force_remeasure,
+        } => measure_paths(config_path, files, dirs, dry_run, force_remeasure).await,
+        Command::Diff { old, new } => match report::run_diff(&old, &new) {
+            Ok(code) => exit(code),
+            Err(e) => {
+                error!("Failed to diff summary reports: {:#}", e);
+                exit(2);
+            }
+        },
+        Command::Replay {
+            config_path,
+            journal_path,
+        } => replay(config_path, journal_path).await,
+        Command::Nri { config_path } => nri_plugin(config_path).await,
+        Command::Hook { config_path } => oci_hook(config_path).await,
+        Command::Docker { config_path } => docker_events(config_path).await,
+        Command::Controller { config_path } => policy_controller(config_path).await,
+        Command::Kata { config_path } => kata_guest(config_path).await,
+        Command::ProvisionRvps { config_path } => provision_rvps(config_path).await,
+        Command::ExportReferenceValues {
+            config_path,
+            format,
+            output,
+        } => export_reference_values(config_path, format, output).await,
+        Command::Verify {
+            config_path,
+            reference,
+            extend_failure,
+        } => verify(config_path, reference, extend_failure).await,
+        Command::ListMeasurers { config_path } => {
+            list_measurers(config_path);
+            Ok(())
+        }
+        Command::Doctor { config_path } => {
+            let path = config_path.unwrap_or_else(|| PathBuf::from("runtime-measurer-config.toml"));
+            exit(doctor::run_doctor(&path))
+        }
+    }
+}
+
+/// Loads the configuration and switches the log lines written from then on
+/// to its `log_format`.
+fn load_config(config_path: Option<&Path>) -> Result<Config> {
+    let config = Config::load(config_path)?;
+    logging::set_format(config.log_format);
+    Ok(config)
+}
+
+/// Loads the configuration as `run` would and prints the effective values,
+/// defaults and included fragments applied. Exits 1 if it is invalid.
+fn check_config(config_path: Option<PathBuf>) {
+    let path = config_path.unwrap_or_else(|| PathBuf::from("runtime-measurer-config.toml"));
+    let loaded = match Config::load_with_sources(&path) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            error!("Invalid configuration: {:#}", e);
+            exit(1);
+        }
+    };
+    println!("# Configuration is valid.");
+    for source in &loaded.sources {
+        println!("# Source: {}", source.display());
+    }
+    for key in &loaded.overrides {
+        println!("# Override: {}", key);
+    }
+    println!("# Digest: sha256:{}", loaded.digest);
+    println!("{:#?}", loaded.config);
+}
+
+/// Measures the given files and model directories once with the settings of
+/// their config sections, whether or not those are enabled. Every path is
+/// critical: one that cannot be measured makes the command exit 1.
+async fn measure_paths(
+    config_path: Option<PathBuf>,
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+    dry_run: bool,
+    force_remeasure: bool,
+) -> Result<()> {
+    let config = match load_config(config_path.as_deref()) {
+        Ok(mut cfg) => {
+            cfg.dry_run |= dry_run;
+            cfg.force_remeasure = force_remeasure;
+            cfg
+        }
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            exit(1);
+        }
+    };
+    let aa_client = match AAClient::from_config(&config).await {
+        Ok(client) => Arc::new(client),
+        Err(e) => {
+            error!("Failed to connect to Attestation Agent: {}", e);
+            exit(1);
+        }
+    };
+    let sink = sink::from_config(&config, aa_client.clone())?;
+    let entries = |paths: &[PathBuf], escape: bool| -> Vec<MeasurementEntry> {
+        paths
+            .iter()
+            .map(|path| {
+                let path = path.to_string_lossy();
+                MeasurementEntry::Detailed {
+                    path: if escape {
+                        Pattern::escape(&path)
+                    } else {
+                        path.to_string()
+                    },
+                    critical: true,
+                    pcr_index: None,
+                    hash_algorithm: None,
+                }
+            })
+            .collect()
+    };
+
+    let mut failed = false;
+    if !files.is_empty() {
+        if let Err(e) = FileMeasurer::new()
+            .measure_patterns(
+                &entries(&files, true),
+                &config.file_measurement,
+                config.max_concurrency,
+                sink.clone(),
+            )
+            .await
+        {
+            error!("File measurement failed: {}", e);
+            failed = true;
+        }
+    }
+    if !dirs.is_empty() {
+        if let Err(e) = ModelDirMeasurer::new()
+            .measure_specific_dirs(
+                &entries(&dirs, false),
+                &config.model_dir_measurement,
+                config.max_concurrency,
+                sink,
+            )
+            .await
+        {
+            error!("Model directory measurement failed: {}", e);
+            failed = true;
+        }
+    }
+
+    let queued = aa_client.flush_queue().await;
+    if queued > 0 {
+        warn!(
+            "{} measurements are still queued for an unreachable Attestation Agent.",
+            queued
+        );
+    }
+    if failed {
+        exit(1);
+    }
+    info!(
+        "Measured {} files and {} directories.",
+        files.len(),
+        dirs.len()
+    );
+    Ok(())
+}
+
+/// Prints the registered measurers. The config is optional here: without one
+/// the enable state is shown as unknown.
+fn list_measurers(config_path: Option<PathBuf>) {
+    let config = match load_config(config_path.as_deref()) {
+        Ok(cfg) => Some(Arc::new(cfg)),
+        Err(e) => {
+            warn!("Showing measurers without configuration: {:#}", e);
+            None
+        }
+    };
+
+    println!(
+        "{:<18} {:<8} {:<22} {:<10} {:<8} DESCRIPTION",
+        "NAME", "ENABLED", "SECTION", "DOMAIN", "PCR"
+    );
+    let tenant = config.as_ref().and_then(|cfg| cfg.tenant.clone());
+    for measurer in registered_measurers() {
+        let domain = match tenant {
+            Some(ref tenant) => format!("{}/{}", tenant, measurer.domain()),
+            None => measurer.domain().to_string(),
+        };
+        let (enabled, pcr) = match &config {
+            Some(cfg) => {
+                let enabled = if measurer.is_enabled(cfg.clone()) {
+                    "yes"
+                } else {
+                    "no"
+                };
+                let pcr = measurer
+                    .pcr_index(cfg)
+                    .map_or_else(|| "default".to_string(), |i| i.to_string());
+                (enabled, pcr)
+            }
+            None => ("unknown", "unknown".to_string()),
+        };
+        println!(
+            "{:<18} {:<8} {:<22} {:<10} {:<8} {}",
+            measurer.name(),
+            enabled,
+            measurer.config_section(),
+            domain,
+            pcr,
+            measurer.description()
+        );
+    }
+}
+
+async fn nri_plugin(config_path: Option<PathBuf>) -> Result<()> {
+    let config = match load_config(config_path.as_deref()) {
+        Ok(cfg) => Arc::new(cfg),
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            exit(1);
+        }
+    };
+    let aa_client = match AAClient::from_config(&config).await {
+        Ok(client) => Arc::new(client),
+        Err(e) => {
+            error!("Failed to connect to Attestation Agent: {}", e);
+            exit(1);
+        }
+    };
+    let sink = sink::from_config(&config, aa_client)?;
+    if let Err(e) = nri::run_plugin(config, sink).await {
+        error!("NRI plugin stopped: {}", e);
+        exit(1);
+    }
+    Ok(())
+}
+
+async fn docker_events(config_path: Option<PathBuf>) -> Result<()> {
+    let config = match load_config(config_path.as_deref()) {
+        Ok(cfg) => Arc::new(cfg),
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            exit(1);
+        }
+    };
+    let aa_client = match AAClient::from_config(&config).await {
+        Ok(client) => Arc::new(client),
+        Err(e) => {
+            error!("Failed to connect to Attestation Agent: {}", e);
+            exit(1);
+        }
+    };
+    let sink = sink::from_config(&config, aa_client)?;
+    tokio::select! {
+        res = docker::watch_events(config, sink) => {
+            if let Err(e) = res {
+                error!("Docker watcher stopped: {}", e);
+                exit(1);
+            }
+        }
+        res = wait_for_termination() => {
+            res?;
+            info!("Termination signal received. Shutting down.");
+        }
+    }
+    Ok(())
+}
+
+/// Runs the enabled measurers against a dry-run client and returns what they
+/// would have extended as reference values, with the loaded config. Exits 1
+/// if any measurer fails, since the set would be incomplete.
+async fn dry_run_reference_values(
+    config_path: Option<PathBuf>,
+) -> Result<(Arc<Config>, BTreeMap<String, Vec<String>>)> {
+    let config = match load_config(config_path.as_deref()) {
+        Ok(cfg) => Arc::new(cfg),
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            exit(1);
+        }
+    };
+    let aa_client = Arc::new(AAClient::dry_run());
+    let sink = sink::from_config(&config, aa_client.clone())?;
+    if run_measurers(config.clone(), sink, None).await.outcome != RunOutcome::Succeeded {
+        error!("Refusing to use an incomplete measurement set.");
+        exit(1);
+    }
+    let values = rvps::reference_values(&aa_client.records(), &config.rvps.name_prefix);
+    Ok((config, values))
+}
 
-    let config_path_str = env::args().nth(1);
-    let config_path = config_path_str.as_ref().map(PathBuf::from);
+/// Writes the dry-run measurement set as a reference value manifest.
+async fn export_reference_values(
+    config_path: Option<PathBuf>,
+    format: ManifestFormat,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let (_, values) = dry_run_reference_values(config_path).await?;
+    let manifest = rvps::manifest(&values, format)?;
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, format!("{}\n", manifest.trim_end())) {
+                error!("Failed to write {:?}: {}", path, e);
+                exit(1);
+            }
+            info!("Wrote {} reference values to {:?}", values.len(), path);
+        }
+        None => println!("{}", manifest.trim_end()),
+    }
+    Ok(())
+}
+
+/// Domain of the event extended when verification fails.
+const VERIFICATION_DOMAIN: &str = "verification";
+
+/// Compares the dry-run measurement set with the reference manifest and
+/// prints the differences as JSON. Exits 1 if an entry does not match or is
+/// missing, after extending a `verification` event if `extend_failure` is
+/// set, and 2 if the manifest cannot be read.
+async fn verify(
+    config_path: Option<PathBuf>,
+    reference: PathBuf,
+    extend_failure: bool,
+) -> Result<()> {
+    let expected = match rvps::load_manifest(&reference) {
+        Ok(values) => values,
+        Err(e) => {
+            error!("{:#}", e);
+            exit(2);
+        }
+    };
+    let (config, current) = dry_run_reference_values(config_path).await?;
+    let verification = rvps::verify(&expected, &current);
+    let diff = serde_json::to_string_pretty(&verification)?;
+    println!("{}", diff);
+    if verification.passed() {
+        info!(
+            "All {} reference values match ({} unexpected entries).",
+            expected.len(),
+            verification.unexpected.len()
+        );
+        return Ok(());
+    }
+
+    error!(
+        "Verification failed: {} mismatched, {} missing.",
+        verification.mismatched.len(),
+        verification.missing.len()
+    );
+    if extend_failure {
+        let content = format!("sha256:{}", hex::encode(Sha256::digest(diff.as_bytes())));
+        let result = match AAClient::from_config(&config)
+            .await
+            .and_then(|client| sink::from_config(&config, Arc::new(client)))
+        {
+            Ok(sink) => {
+                sink.extend(
+                    VERIFICATION_DOMAIN,
+                    "failed",
+                    &content,
+                    config.policy_pcr_index.map(|v| v as u64),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            error!("Failed to record the verification failure: {}", e);
+        }
+    }
+    exit(1);
+}
+
+/// Runs the enabled measurers against a dry-run client and registers what
+/// they would have extended as reference values.
+async fn provision_rvps(config_path: Option<PathBuf>) -> Result<()> {
+    let (config, values) = dry_run_reference_values(config_path).await?;
+    for (name, accepted) in &values {
+        println!("{} = {}", name, accepted.join(", "));
+    }
+    if let Err(e) = rvps::register(&config.rvps, &values).await {
+        error!("Failed to register reference values: {:#}", e);
+        exit(1);
+    }
+    Ok(())
+}
+
+/// Kata / peer-pods guest profile: locate the AA, measure once with the
+/// policy from the agent's config mount, then measure pod volumes as they
+/// appear.
+async fn kata_guest(config_path: Option<PathBuf>) -> Result<()> {
+    let config_path = config_path.unwrap_or_else(|| PathBuf::from(kata::DEFAULT_POLICY_PATH));
+    info!("Loading Kata measurement policy from: {:?}", config_path);
+    let mut config = match load_config(Some(&config_path)) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            exit(1);
+        }
+    };
+    if let Err(e) = kata::discover_attestation_agent(&mut config) {
+        error!("{}", e);
+        exit(1);
+    }
+    let config = Arc::new(config);
+    let aa_client = match AAClient::from_config(&config).await {
+        Ok(client) => Arc::new(client),
+        Err(e) => {
+            error!("Failed to connect to Attestation Agent: {}", e);
+            exit(1);
+        }
+    };
+    let sink = sink::from_config(&config, aa_client)?;
+
+    let run = run_measurers(config.clone(), sink.clone(), None).await;
+    match run.outcome {
+        RunOutcome::Succeeded => {}
+        RunOutcome::Failed | RunOutcome::Incomplete => {
+            error!("One or more measurements failed during sandbox start.")
+        }
+        RunOutcome::CriticalFailure => {
+            error!("A critical measurement failed during sandbox start.");
+            exit(1);
+        }
+    }
+
+    tokio::select! {
+        res = kata::watch_volumes(config, sink) => res?,
+        res = wait_for_termination() => {
+            res?;
+            info!("Termination signal received. Shutting down.");
+        }
+    }
+    Ok(())
+}
+
+async fn policy_controller(config_path: Option<PathBuf>) -> Result<()> {
+    let config_path = config_path.unwrap_or_else(|| PathBuf::from("runtime-measurer-config.toml"));
+    let config = match load_config(Some(&config_path)) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            exit(1);
+        }
+    };
+    let aa_client = match AAClient::from_config(&config).await {
+        Ok(client) => Arc::new(client),
+        Err(e) => {
+            error!("Failed to connect to Attestation Agent: {}", e);
+            exit(1);
+        }
+    };
+    let sink = sink::from_config(&config, aa_client)?;
+    let kube_client = match KubeClient::in_cluster() {
+        Ok(client) => Arc::new(client),
+        Err(e) => {
+            error!("Failed to create Kubernetes client: {}", e);
+            exit(1);
+        }
+    };
+    let pod_watcher = match config.kubernetes.resolve_node_name() {
+        Some(node_name) if config.kubernetes.pod_annotations => Some(PodAnnotationWatcher::new(
+            kube_client.clone(),
+            node_name,
+            &config.kubernetes,
+        )),
+        _ => None,
+    };
+    let controller = match PolicyController::new(kube_client, config) {
+        Ok(controller) => controller,
+        Err(e) => {
+            error!("Failed to start policy controller: {}", e);
+            exit(1);
+        }
+    };
+
+    if let Some(watcher) = pod_watcher {
+        let sink = sink.clone();
+        tokio::spawn(async move {
+            if let Err(e) = watcher.run(sink).await {
+                error!("Pod annotation watcher stopped: {}", e);
+            }
+        });
+    }
+
+    tokio::select! {
+        res = controller.run(sink) => res?,
+        res = wait_for_termination() => {
+            res?;
+            info!("Termination signal received. Shutting down.");
+        }
+    }
+    Ok(())
+}
+
+/// Exits 0 on success. On failure the exit code is 1 only when the config asks
+/// to block the container, since a non-zero hook aborts container creation.
+async fn oci_hook(config_path: Option<PathBuf>) -> Result<()> {
+    let state = match hook::ContainerState::from_reader(std::io::stdin().lock()) {
+        Ok(state) => state,
+        Err(e) => {
+            error!("{}", e);
+            exit(1);
+        }
+    };
+    let config = match load_config(config_path.as_deref()) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            exit(1);
+        }
+    };
+    let result = match AAClient::from_config(&config)
+        .await
+        .and_then(|client| sink::from_config(&config, Arc::new(client)))
+    {
+        Ok(sink) => hook::run_hook(&state, &config, sink.as_ref()).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        error!("Failed to measure container {}: {}", state.id, e);
+        if config.hook_measurement.block_on_failure {
+            exit(1);
+        }
+    }
+    Ok(())
+}
+
+async fn replay(config_path: Option<PathBuf>, journal_path: Option<PathBuf>) -> Result<()> {
+    let mut config = match load_config(config_path.as_deref()) {
+        Ok(cfg) => cfg,
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            exit(1);
+        }
+    };
+    if let Some(path) = journal_path {
+        config.journal_path = Some(path.to_string_lossy().to_string());
+    }
+    if config.journal_path.is_none() {
+        error!("No journal to replay: set journal_path or pass --journal.");
+        exit(1);
+    }
+
+    let aa_client = match AAClient::from_config(&config).await {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Failed to connect to Attestation Agent: {}", e);
+            exit(1);
+        }
+    };
+    match aa_client.replay_journal().await {
+        Ok(replay) => {
+            info!("Replayed {} journaled measurements.", replay.replayed);
+            match replay.compacted {
+                Some(dropped) => info!("Compacted the journal: dropped {} entries.", dropped),
+                None => warn!("The journal is in use by another process; not compacting it."),
+            }
+            if !replay.rejected.is_empty() {
+                error!(
+                    "{} journaled measurements were rejected and remain pending: {}",
+                    replay.rejected.len(),
+                    replay.rejected.join("; ")
+                );
+                exit(1);
+            }
+            Ok(())
+        }
+        Err(e) => {
+            error!("Journal replay stopped: {}", e);
+            exit(1);
+        }
+    }
+}
+
+/// Collects and publishes the `[evidence_bundle]` of the records so far.
+async fn write_evidence_bundle(config: &Config, aa_client: &AAClient) {
+    let bundle_config = &config.evidence_bundle;
+    let summary = SummaryReport::new(aa_client.records());
+    let result =
+        match EvidenceBundle::collect(bundle_config, summary, aa_client, config.structured_content)
+            .await
+        {
+            Ok(bundle) => bundle.publish(bundle_config).await,
+            Err(e) => Err(e),
+        };
+    if let Err(e) = result {
+        error!("Failed to produce evidence bundle: {:#}", e);
+    }
+}
+
+/// Runs the initial measurement pass and, unless configured as one-shot (or
+/// `daemon` forces it), keeps watching the configuration until terminated.
+/// `one_shot` overrides the config's `one_shot` setting when given;
+/// `dry_run` turns on the config's `dry_run`.
+async fn run(
+    config_path: Option<PathBuf>,
+    one_shot: Option<bool>,
+    dry_run: bool,
+    force_remeasure: bool,
+) -> Result<()> {
     if let Some(ref path) = config_path {
         info!("Loading configuration from: {:?}", path);
     } else {
@@ -34,15 +727,26 @@ async fn main() -> Result<()> {
 
     info!("measurement tool starting...");
 
-    let config = match Config::load(config_path.as_deref()) {
-        Ok(cfg) => Arc::new(cfg),
+    let config = match load_config(config_path.as_deref()) {
+        Ok(mut cfg) => {
+            cfg.dry_run |= dry_run;
+            cfg.force_remeasure = force_remeasure;
+            Arc::new(cfg)
+        }
         Err(e) => {
             error!("Failed to load configuration: {}", e);
             exit(1);
         }
     };
 
-    let aa_client = match AAClient::from_config(&config).await {
+    let one_shot = one_shot.unwrap_or(config.one_shot);
+    // Only the daemon waits for an Attestation Agent starting alongside it.
+    let connected = if one_shot {
+        AAClient::from_config(&config).await
+    } else {
+        AAClient::from_config_waiting(&config).await
+    };
+    let aa_client = match connected {
         Ok(client) => Arc::new(client),
         Err(e) => {
             error!("Failed to connect to Attestation Agent: {}", e);
@@ -50,49 +754,99 @@ async fn main() -> Result<()> {
         }
     };
 
+    let sink = sink::from_config(&config, aa_client.clone())?;
+
+    // Served from the start, so that probes see the initial run in progress.
+    let status = Arc::new(DaemonStatus::new());
+    if config.status.enable && !one_shot {
+        let status_config = config.status.clone();
+        let status = status.clone();
+        let aa = aa_client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = status::serve(status_config, status, aa).await {
+                error!("Status server exited with error: {}", e);
+            }
+        });
+    }
+
+    // Installed before the initial run, whose SIGHUP would otherwise
+    // terminate the process; the request waits for the config watcher.
+    let reload_requests = Arc::new(Notify::new());
+    if !one_shot {
+        let mut hangup = signal(SignalKind::hangup())?;
+        let reload_requests = reload_requests.clone();
+        tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                reload_requests.notify_one();
+            }
+        });
+    }
+
     // Shared config for runtime watchers
     let shared_config = Arc::new(RwLock::new((*config).clone()));
 
-    // --- Register Measurers ---
-    // Add new measurers to this vector as they are implemented.
-    let measurers: Vec<Box<dyn Measurable + Send + Sync>> = vec![
-        Box::new(FileMeasurer::new()),
-        Box::new(ModelDirMeasurer::new()),
-        // Box::new(ProcessMeasurer::new()), // Example for future measurer
-    ];
-    // --------------------------
-
     // Initial one-shot run
+    let initial_outcome;
     {
         let config_snapshot = {
             let guard = shared_config.read().await;
             guard.clone()
         };
-        let arc_snapshot = Arc::new(config_snapshot);
-        let mut success = true;
-        for measurer in measurers {
-            if measurer.is_enabled(arc_snapshot.clone()) {
-                info!("Running measurer: {}", measurer.name());
-                if let Err(e) = measurer
-                    .measure(arc_snapshot.clone(), aa_client.clone())
-                    .await
-                {
-                    error!("Error during {} execution: {}", measurer.name(), e);
-                    success = false;
-                }
-            } else {
-                info!("Measurer {} is disabled. Skipping.", measurer.name());
-            }
-        }
-        if !success {
+        let outcome = run_measurers(Arc::new(config_snapshot), sink.clone(), Some(&status))
+            .await
+            .outcome;
+        let succeeded = outcome == RunOutcome::Succeeded;
+        status.record_run(succeeded);
+        if !succeeded {
             error!("One or more measurements failed during initial run.");
         } else {
             info!("Initial measurement run completed successfully.");
         }
+
+        if let Some(ref report_path) = config.summary_report_path {
+            let report = SummaryReport::new(aa_client.records());
+            match report.save(report_path.as_ref()) {
+                Ok(()) => info!("Summary report written to {}", report_path),
+                Err(e) => error!("Failed to write summary report: {:#}", e),
+            }
+        }
+
+        if config.evidence_bundle.enable {
+            if config.dry_run {
+                info!("Skipping evidence bundle in dry-run mode.");
+            } else if succeeded {
+                write_evidence_bundle(&config, &aa_client).await;
+            } else {
+                warn!("Skipping evidence bundle because the initial run failed.");
+            }
+        }
+
+        // Critical entries and fail_fast measurers fail the run regardless
+        // of mode.
+        if outcome == RunOutcome::CriticalFailure {
+            error!("A critical measurement failed; exiting.");
+            exit(outcome.exit_code());
+        }
+        initial_outcome = outcome;
+    }
+
+    if !one_shot {
+        aa_client.stop_recording();
     }
 
-    if config.one_shot {
+    if one_shot {
+        let queued = aa_client.flush_queue().await;
+        if queued > 0 {
+            warn!(
+                "{} measurements are still queued for an unreachable Attestation Agent.",
+                queued
+            );
+        }
         info!("One-shot mode enabled. Exiting after initial measurement.");
+        if initial_outcome == RunOutcome::Incomplete {
+            error!("Measurers under the fail_at_end policy failed; exiting.");
+            exit(initial_outcome.exit_code());
+        }
         return Ok(());
     }
 
@@ -104,28 +858,202 @@ async fn main() -> Result<()> {
     let config_handlers: Vec<Box<dyn ConfigChangeHandler>> = vec![
         Box::new(FileMeasurementChangeHandler::new()),
         Box::new(ModelDirMeasurementChangeHandler::new()),
+        Box::new(MeasurerToggleHandler::new()),
     ];
 
-    let watchers: Vec<Box<dyn ConfigWatcher + Send + Sync>> = vec![Box::new(
-        ConfigFileWatcher::new(config_handlers),
-    )];
+    let watchers: Vec<Box<dyn ConfigWatcher + Send + Sync>> = vec![
+        Box::new(ConfigFileWatcher::new(
+            config_handlers,
+            reload_requests.clone(),
+        )),
+        Box::new(FileContentWatcher::new()),
+        Box::new(ModelDirContentWatcher::new()),
+        Box::new(ExecMeasurer::new()),
+        Box::new(MountMeasurer::new()),
+        Box::new(K8sPodWatcher::new()),
+    ];
     for watcher in watchers {
         if watcher.is_enabled(Arc::new(shared_config.read().await.clone())) {
-            let cfg = shared_config.clone();
-            let aa = aa_client.clone();
-            let path = effective_config_path.clone();
-            tokio::spawn(async move {
-                if let Err(e) = watcher.watch(path, cfg, aa).await {
-                    error!("Config watcher exited with error: {}", e);
-                }
-            });
+            tokio::spawn(supervise_watcher(
+                Arc::from(watcher),
+                effective_config_path.clone(),
+                shared_config.clone(),
+                sink.clone(),
+                config.watcher_supervision.clone(),
+                status.clone(),
+            ));
         } else {
             info!("Watcher {} is disabled. Skipping.", watcher.name());
         }
     }
 
-    // Keep running as a daemon
-    std::future::pending::<()>().await;
-    #[allow(unreachable_code)]
+    if config.aa_retry.queue_size > 0 {
+        let interval = Duration::from_secs(config.aa_retry.flush_interval_secs.max(1));
+        let aa = aa_client.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                aa.flush_queue().await;
+            }
+        });
+    }
+
+    if config.api.enable {
+        let api_config = config.api.clone();
+        let sink = sink.clone();
+        tokio::spawn(async move {
+            if let Err(e) = api::serve(api_config, sink).await {
+                error!("Measurement API exited with error: {}", e);
+            }
+        });
+    }
+
+    if config.control.enable {
+        let control_config = config.control.clone();
+        let cfg = shared_config.clone();
+        let sink = sink.clone();
+        let status = status.clone();
+        let aa = aa_client.clone();
+        tokio::spawn(async move {
+            if let Err(e) =
+                control::serve(control_config, cfg, sink, reload_requests, status, aa).await
+            {
+                error!("Control API exited with error: {}", e);
+            }
+        });
+    }
+
+    if let Err(e) = daemon::sd_notify("READY=1") {
+        warn!("{:#}", e);
+    }
+    if let Some(interval) = daemon::watchdog_interval() {
+        info!("Sending watchdog keep-alives every {:?}", interval);
+        let status = status.clone();
+        let aa = aa_client.clone();
+        tokio::spawn(async move {
+            loop {
+                let health = if status.is_healthy(&aa) {
+                    "healthy"
+                } else {
+                    "unhealthy"
+                };
+                if let Err(e) = daemon::sd_notify(&format!("WATCHDOG=1\nSTATUS={}", health)) {
+                    warn!("{:#}", e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    // Keep running as a daemon until asked to stop
+    wait_for_termination().await?;
+    info!("Termination signal received. Shutting down.");
+    let _ = daemon::sd_notify("STOPPING=1");
+    flush_on_shutdown(&config, &aa_client).await;
+    Ok(())
+}
+
+/// Runs `watcher` for the life of the daemon, restarting it with exponential
+/// backoff whenever it returns an error or panics, so that a failed notify
+/// backend does not silently stop re-measurement. A watcher that returns
+/// without an error has nothing left to watch and is not restarted.
+async fn supervise_watcher(
+    watcher: Arc<dyn ConfigWatcher + Send + Sync>,
+    config_path: PathBuf,
+    shared_config: Arc<RwLock<Config>>,
+    sink: Arc<dyn MeasurementSink>,
+    supervision: WatcherSupervisionConfig,
+    status: Arc<DaemonStatus>,
+) {
+    let name = watcher.name().to_string();
+    let initial_backoff = Duration::from_millis(supervision.initial_backoff_ms);
+    let max_backoff = Duration::from_millis(supervision.max_backoff_ms);
+    let mut backoff = initial_backoff;
+    let mut failures = 0;
+    loop {
+        status.record_watcher_running(&name);
+        let mut task = {
+            let watcher = watcher.clone();
+            let path = config_path.clone();
+            let cfg = shared_config.clone();
+            let sink = sink.clone();
+            tokio::spawn(async move { watcher.watch(path, cfg, sink).await })
+        };
+        let result = tokio::select! {
+            result = &mut task => result,
+            _ = tokio::time::sleep(max_backoff), if failures > 0 => {
+                info!("Watcher {} is running again after {} failures.", name, failures);
+                status.record_watcher_stable(&name);
+                failures = 0;
+                backoff = initial_backoff;
+                task.await
+            }
+        };
+        let error = match result {
+            Ok(Ok(())) => {
+                info!("Watcher {} finished.", name);
+                status.record_watcher_finished(&name);
+                return;
+            }
+            Ok(Err(e)) => e.to_string(),
+            Err(e) => e.to_string(),
+        };
+
+        failures += 1;
+        let failing = !supervision.restart || failures >= supervision.failure_threshold;
+        status.record_watcher_failure(&name, &error, failures, failing);
+        if !supervision.restart {
+            error!("Watcher {} exited with error: {}", name, error);
+            return;
+        }
+        if failing {
+            error!(
+                "Watcher {} failed {} times in a row: {}; restarting in {:?}",
+                name, failures, error, backoff
+            );
+        } else {
+            warn!(
+                "Watcher {} failed: {}; restarting in {:?}",
+                name, error, backoff
+            );
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(max_backoff);
+    }
+}
+
+/// How long shutdown waits for queued extensions to be sent.
+const SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Gives queued extensions a last chance to reach the Attestation Agent
+/// before the daemon exits.
+async fn flush_on_shutdown(config: &Config, aa_client: &AAClient) {
+    let queued = match tokio::time::timeout(SHUTDOWN_FLUSH_TIMEOUT, aa_client.flush_queue()).await {
+        Ok(0) => return,
+        Ok(queued) => queued,
+        Err(_) => {
+            warn!("Timed out flushing queued measurements on shutdown.");
+            aa_client.connectivity().queued
+        }
+    };
+    match config.aa_retry.queue_path {
+        Some(ref path) => info!(
+            "{} measurements stay queued in {} for the next start.",
+            queued, path
+        ),
+        None => warn!(
+            "Dropping {} measurements still queued for an unreachable Attestation Agent.",
+            queued
+        ),
+    }
+}
+
+async fn wait_for_termination() -> Result<()> {
+    let mut sigterm = signal(SignalKind::terminate())?;
+    let mut sigint = signal(SignalKind::interrupt())?;
+    tokio::select! {
+        _ = sigterm.recv() => {}
+        _ = sigint.recv() => {}
+    }
     Ok(())
 }
@@ -0,0 +1,280 @@
+// src/modules/container_image_measurer.rs
+use crate::canonical_json;
+use crate::config::{Config, ContainerImageMeasurementConfig, ContainerRuntime};
+use crate::docker::DockerClient;
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::Pattern;
+use log::{debug, info};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::process::Command;
+
+const DOMAIN: &str = "container_image";
+
+/// Labels the kubelet sets on the containers of a pod.
+const NAMESPACE_LABEL: &str = "io.kubernetes.pod.namespace";
+const POD_LABEL: &str = "io.kubernetes.pod.name";
+
+/// Measures the images of the containers running on the node, as reported by
+/// the local container runtime.
+#[derive(Default)]
+pub struct ContainerImageMeasurer;
+
+/// A running container and the image it was created from.
+struct RunningContainer {
+    namespace: Option<String>,
+    pod: Option<String>,
+    name: String,
+    image: String,
+    /// Manifest digest the image was pulled by; unknown for images built or
+    /// loaded locally.
+    image_digest: Option<String>,
+    /// Digest of the image config (the image id), which pins the layers.
+    config_digest: String,
+}
+
+impl RunningContainer {
+    /// `<namespace>/<pod>/<name>`, leaving out what the runtime does not
+    /// know, so that the operation survives container restarts.
+    fn operation(&self) -> String {
+        [
+            self.namespace.as_deref(),
+            self.pod.as_deref(),
+            Some(self.name.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("/")
+    }
+}
+
+/// Content extended for each container, as canonical JSON.
+#[derive(Serialize)]
+struct ImageRecord<'a> {
+    image: &'a str,
+    image_digest: Option<&'a str>,
+    config_digest: &'a str,
+}
+
+impl ContainerImageMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn label(labels: &Value, key: &str) -> Option<String> {
+    labels[key].as_str().map(str::to_string)
+}
+
+/// The digest of the first `<repository>@<digest>` reference.
+fn repo_digest(repo_digests: &Value) -> Option<String> {
+    repo_digests
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .find_map(|reference| reference.split_once('@'))
+        .map(|(_, digest)| digest.to_string())
+}
+
+async fn docker_containers(socket_path: &str) -> Result<Vec<RunningContainer>> {
+    let client = DockerClient::new(Path::new(socket_path));
+    let mut images: HashMap<String, Value> = HashMap::new();
+    let mut containers = Vec::new();
+    let listing = client.get_json("/containers/json").await?;
+    for container in listing.as_array().into_iter().flatten() {
+        let config_digest = container["ImageID"].as_str().unwrap_or_default();
+        if !images.contains_key(config_digest) {
+            let image = client
+                .get_json(&format!("/images/{}/json", config_digest))
+                .await?;
+            images.insert(config_digest.to_string(), image);
+        }
+        let labels = &container["Labels"];
+        containers.push(RunningContainer {
+            namespace: label(labels, NAMESPACE_LABEL),
+            pod: label(labels, POD_LABEL),
+            name: container["Names"][0]
+                .as_str()
+                .unwrap_or_default()
+                .trim_start_matches('/')
+                .to_string(),
+            image: container["Image"].as_str().unwrap_or_default().to_string(),
+            image_digest: repo_digest(&images[config_digest]["RepoDigests"]),
+            config_digest: config_digest.to_string(),
+        });
+    }
+    Ok(containers)
+}
+
+pub(crate) async fn crictl_json(crictl: &str, endpoint: &str, args: &[&str]) -> Result<Value> {
+    let output = Command::new(crictl)
+        .args(["--runtime-endpoint", endpoint])
+        .args(args)
+        .args(["--output", "json"])
+        .output()
+        .await
+        .map_err(|e| {
+            MeasurementError::CommandExecution(format!("Failed to run {}: {}", crictl, e))
+        })?;
+    if !output.status.success() {
+        return Err(MeasurementError::CommandExecution(format!(
+            "Command '{} {}' failed with status {}: {}",
+            crictl,
+            args.join(" "),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| {
+        MeasurementError::CommandExecution(format!("Invalid output of {}: {}", crictl, e))
+    })
+}
+
+async fn cri_containers(crictl: &str, endpoint: &str) -> Result<Vec<RunningContainer>> {
+    let mut images: HashMap<String, Value> = HashMap::new();
+    let mut containers = Vec::new();
+    let listing = crictl_json(crictl, endpoint, &["ps"]).await?;
+    for container in listing["containers"].as_array().into_iter().flatten() {
+        let image_id = container["imageRef"].as_str().unwrap_or_default();
+        if !images.contains_key(image_id) {
+            let image = crictl_json(crictl, endpoint, &["inspecti", image_id]).await?;
+            images.insert(image_id.to_string(), image);
+        }
+        let image = &images[image_id]["status"];
+        let labels = &container["labels"];
+        containers.push(RunningContainer {
+            namespace: label(labels, NAMESPACE_LABEL),
+            pod: label(labels, POD_LABEL),
+            name: container["metadata"]["name"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            image: image["repoTags"][0]
+                .as_str()
+                .or_else(|| container["image"]["image"].as_str())
+                .unwrap_or_default()
+                .to_string(),
+            image_digest: repo_digest(&image["repoDigests"]),
+            config_digest: image["id"].as_str().unwrap_or(image_id).to_string(),
+        });
+    }
+    Ok(containers)
+}
+
+fn compile(patterns: &[String]) -> Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|p| Pattern::new(p).map_err(MeasurementError::from))
+        .collect()
+}
+
+/// Empty pattern lists match everything; a non-empty namespace list
+/// excludes containers outside Kubernetes.
+fn selected(container: &RunningContainer, namespaces: &[Pattern], names: &[Pattern]) -> bool {
+    let namespace_matches = namespaces.is_empty()
+        || container
+            .namespace
+            .as_deref()
+            .is_some_and(|ns| namespaces.iter().any(|p| p.matches(ns)));
+    let name_matches = names.is_empty() || names.iter().any(|p| p.matches(&container.name));
+    namespace_matches && name_matches
+}
+
+async fn measure_container(
+    container: &RunningContainer,
+    config: &ContainerImageMeasurementConfig,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    let content = canonical_json::to_vec(&ImageRecord {
+        image: &container.image,
+        image_digest: container.image_digest.as_deref(),
+        config_digest: &container.config_digest,
+    })
+    .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    let operation = container.operation();
+    let content = String::from_utf8_lossy(&content);
+    debug!(
+        "Extending container image measurement {} = {}",
+        operation, content
+    );
+    sink.extend(
+        DOMAIN,
+        &operation,
+        &content,
+        config.pcr_index.map(|v| v as u64),
+    )
+    .await
+}
+
+#[async_trait]
+impl Measurable for ContainerImageMeasurer {
+    fn name(&self) -> &str {
+        "ContainerImageMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Records the image and config digests of running containers"
+    }
+
+    fn config_section(&self) -> &str {
+        "container_image_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.container_image_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.container_image_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let ci_config = &config.container_image_measurement;
+        if !ci_config.enable {
+            debug!("Container image measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!(
+            "Starting container image measurement with domain '{}'",
+            DOMAIN
+        );
+        let namespaces = compile(&ci_config.namespaces)?;
+        let names = compile(&ci_config.names)?;
+        let socket_path = ci_config.resolved_socket_path();
+        let containers = match ci_config.runtime {
+            ContainerRuntime::Docker => docker_containers(&socket_path).await?,
+            ContainerRuntime::Cri => cri_containers(&ci_config.crictl_path, &socket_path).await?,
+        };
+
+        let mut failures = EntryFailures::default();
+        let mut measured = 0;
+        for container in containers
+            .iter()
+            .filter(|c| selected(c, &namespaces, &names))
+        {
+            match measure_container(container, ci_config, &sink).await {
+                Ok(()) => measured += 1,
+                Err(e) => failures.record(&container.operation(), false, e),
+            }
+        }
+        info!(
+            "Container image measurement completed for {} of {} containers.",
+            measured,
+            containers.len()
+        );
+        failures.into_result()
+    }
+}
@@ -0,0 +1,333 @@
+// src/modules/dataset_measurer.rs
+use crate::config::{Config, DatasetMeasurementConfig};
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::Pattern;
+use log::{debug, info, warn};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DOMAIN: &str = "dataset";
+
+/// Measures dataset directories through a manifest: index files are hashed
+/// fully, shards are recorded by size plus sampled windows, and a
+/// deterministic subset of shards can be hashed fully. Full verity over
+/// multi-TB datasets is not feasible.
+#[derive(Default)]
+pub struct DatasetMeasurer;
+
+/// How a file contributed to the manifest.
+#[derive(Clone, Copy)]
+enum EntryKind {
+    Index,
+    Full,
+    Sampled,
+}
+
+impl EntryKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            EntryKind::Index => "index",
+            EntryKind::Full => "full",
+            EntryKind::Sampled => "sampled",
+        }
+    }
+}
+
+impl DatasetMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn measure_single_dir(
+        &self,
+        dir: &str,
+        config: &DatasetMeasurementConfig,
+        sink: Arc<dyn MeasurementSink>,
+    ) -> Result<()> {
+        let canonical_dir = PathBuf::from(dir)
+            .canonicalize()
+            .map_err(|e| MeasurementError::InvalidDirectory(format!("{} ({})", dir, e)))?;
+        if !canonical_dir.is_dir() {
+            return Err(MeasurementError::InvalidDirectory(format!(
+                "{:?} is not a directory",
+                canonical_dir
+            )));
+        }
+
+        info!("Building dataset manifest for {:?}", canonical_dir);
+        let manifest = {
+            let root = canonical_dir.clone();
+            let config = config.clone();
+            tokio::task::spawn_blocking(move || build_manifest(&root, &config, &|_| false))
+                .await
+                .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+        };
+        let digest = format!(
+            "sha256:{}",
+            hex::encode(Sha256::digest(manifest.as_bytes()))
+        );
+        let operation = canonical_dir.to_string_lossy().to_string();
+
+        if let Some(ref manifest_dir) = config.manifest_dir {
+            let name = format!("{}.manifest", operation.trim_matches('/').replace('/', "_"));
+            let path = Path::new(manifest_dir).join(name);
+            if let Err(e) =
+                fs::create_dir_all(manifest_dir).and_then(|_| fs::write(&path, &manifest))
+            {
+                warn!("Failed to write dataset manifest {:?}: {}", path, e);
+            }
+        }
+
+        debug!(
+            "Extending dataset measurement: domain={}, operation={}, manifest={}",
+            DOMAIN, operation, digest
+        );
+        sink.extend(
+            DOMAIN,
+            &operation,
+            &digest,
+            config.pcr_index.map(|v| v as u64),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Digest of a manifest in which every file is hashed fully, used where
+/// verity cannot be applied.
+pub fn full_manifest_digest(root: &Path) -> Result<String> {
+    manifest_digest_without(root, &|_| false)
+}
+
+/// Like [`full_manifest_digest`], leaving out the files whose relative path
+/// `skip` accepts.
+pub fn manifest_digest_without(root: &Path, skip: &dyn Fn(&str) -> bool) -> Result<String> {
+    let config = DatasetMeasurementConfig {
+        index_patterns: vec!["*".to_string()],
+        ..Default::default()
+    };
+    let manifest = build_manifest(root, &config, skip)?;
+    Ok(format!(
+        "sha256:{}",
+        hex::encode(Sha256::digest(manifest.as_bytes()))
+    ))
+}
+
+/// Returns the manifest of `root`: one sorted line per regular file of the
+/// form `<relative path>\0<kind>\0<size>\0<sha256>`. Symbolic links to
+/// files are measured by the content they lead to, with `\0<link target>`
+/// appended. Files whose relative path `skip` accepts are left out.
+fn build_manifest(
+    root: &Path,
+    config: &DatasetMeasurementConfig,
+    skip: &dyn Fn(&str) -> bool,
+) -> Result<String> {
+    let index_patterns = config
+        .index_patterns
+        .iter()
+        .map(|p| Pattern::new(p))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut files = Vec::new();
+    collect_files(root, &mut files)?;
+    let mut lines = Vec::with_capacity(files.len());
+    for TreeFile { path, link_target } in files {
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string();
+        if skip(&relative) {
+            continue;
+        }
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let size = fs::metadata(&path)?.len();
+
+        let kind = if index_patterns.iter().any(|p| p.matches(&file_name)) {
+            EntryKind::Index
+        } else if is_sampled(&relative, config.shard_sample_rate) {
+            EntryKind::Full
+        } else {
+            EntryKind::Sampled
+        };
+        let digest = match kind {
+            EntryKind::Index | EntryKind::Full => {
+                let mut hasher = Sha256::new();
+                std::io::copy(&mut File::open(&path)?, &mut hasher)?;
+                hex::encode(hasher.finalize())
+            }
+            EntryKind::Sampled => sampled_digest(&path, size, config.shard_sample_bytes)?,
+        };
+        let mut line = format!("{}\0{}\0{}\0{}", relative, kind.as_str(), size, digest);
+        if let Some(target) = link_target {
+            line.push('\0');
+            line.push_str(&target.to_string_lossy());
+        }
+        lines.push(line);
+    }
+    lines.sort();
+    debug!(
+        "Dataset manifest for {:?} has {} entries",
+        root,
+        lines.len()
+    );
+
+    let mut manifest = lines.join("\n");
+    manifest.push('\n');
+    Ok(manifest)
+}
+
+/// A file found by [`collect_files`].
+pub(crate) struct TreeFile {
+    pub(crate) path: PathBuf,
+    /// Where `path` leads when it is a symbolic link; opening `path` reads
+    /// the file it leads to.
+    pub(crate) link_target: Option<PathBuf>,
+}
+
+/// Collects the regular files under `dir` and the symbolic links to regular
+/// files, such as the snapshots of a Hugging Face cache. Links to anything
+/// else fail, so that no content is silently left out; other special files
+/// are skipped.
+pub(crate) fn collect_files(dir: &Path, files: &mut Vec<TreeFile>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push(TreeFile {
+                path: entry.path(),
+                link_target: None,
+            });
+        } else if file_type.is_symlink() {
+            let path = entry.path();
+            let target = link_to_file(&path)?;
+            files.push(TreeFile {
+                path,
+                link_target: Some(target),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the target of the symbolic link `path`, failing unless it leads
+/// to a regular file.
+pub(crate) fn link_to_file(path: &Path) -> Result<PathBuf> {
+    let target = fs::read_link(path)?;
+    match fs::metadata(path) {
+        Ok(meta) if meta.is_file() => Ok(target),
+        Ok(_) => Err(MeasurementError::InvalidDirectory(format!(
+            "{:?} links to {:?}, which is not a regular file",
+            path, target
+        ))),
+        Err(e) => Err(MeasurementError::InvalidDirectory(format!(
+            "{:?} links to {:?}, which cannot be read ({})",
+            path, target, e
+        ))),
+    }
+}
+
+/// Selects shards for full hashing from the hash of their relative path, so
+/// that every node (and the verifier) picks the same subset.
+fn is_sampled(relative: &str, rate: f64) -> bool {
+    if rate <= 0.0 {
+        return false;
+    }
+    let hash = Sha256::digest(relative.as_bytes());
+    let bucket = u64::from_be_bytes(hash[..8].try_into().unwrap_or_default());
+    (bucket as f64 / u64::MAX as f64) < rate
+}
+
+/// Hashes windows of `window` bytes at the start, middle and end of the file.
+/// Files no larger than three windows are hashed fully.
+fn sampled_digest(path: &Path, size: u64, window: u64) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    if size <= window.saturating_mul(3) {
+        std::io::copy(&mut file, &mut hasher)?;
+    } else {
+        let mut buf = vec![0u8; window as usize];
+        for offset in [0, (size - window) / 2, size - window] {
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut buf)?;
+            hasher.update(&buf);
+        }
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[async_trait]
+impl Measurable for DatasetMeasurer {
+    fn name(&self) -> &str {
+        "DatasetMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Hashes a manifest of dataset directories with sampled shards"
+    }
+
+    fn config_section(&self) -> &str {
+        "dataset_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.dataset_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.dataset_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let ds_config = &config.dataset_measurement;
+        if !ds_config.enable {
+            debug!("Dataset measurement is disabled. Skipping.");
+            return Ok(());
+        }
+        if ds_config.directories.is_empty() {
+            warn!("Dataset measurement is enabled but no directories configured.");
+            return Ok(());
+        }
+        if !(0.0..=1.0).contains(&ds_config.shard_sample_rate) {
+            return Err(MeasurementError::Config(format!(
+                "dataset_measurement.shard_sample_rate must be between 0 and 1, got {}",
+                ds_config.shard_sample_rate
+            )));
+        }
+
+        info!(
+            "Starting dataset measurement with domain '{}', shard sample rate {}",
+            DOMAIN, ds_config.shard_sample_rate
+        );
+        let mut measured_dirs = HashSet::new();
+        let mut failures = EntryFailures::default();
+        for entry in &ds_config.directories {
+            let dir = entry.path();
+            if !measured_dirs.insert(dir.to_string()) {
+                debug!("Skipping duplicate directory entry: {}", dir);
+                continue;
+            }
+            if let Err(e) = self.measure_single_dir(dir, ds_config, sink.clone()).await {
+                failures.record(dir, entry.is_critical(), e);
+            }
+        }
+        info!(
+            "Dataset measurement completed for {} unique directories.",
+            measured_dirs.len()
+        );
+        failures.into_result()
+    }
+}
@@ -0,0 +1,283 @@
+// src/modules/device_measurer.rs
+use crate::canonical_json;
+use crate::config::{Config, DeviceMeasurementConfig, MeasurementEntry};
+use crate::digest::digest_bytes;
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::process::Command;
+
+const DOMAIN: &str = "device";
+const PCI_DEVICES: &str = "/sys/bus/pci/devices";
+const NVIDIA_VENDOR: &str = "0x10de";
+
+/// Measures the identity and firmware of PCI devices, such as the GPUs and
+/// accelerators of confidential AI deployments.
+#[derive(Default)]
+pub struct DeviceMeasurer;
+
+impl DeviceMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// The stable identity of a PCI device, extended as the digest of its
+/// canonical JSON. Bus resources, IRQs and link state are left out, as they
+/// change between boots.
+#[derive(Debug, Serialize)]
+struct DeviceIdentity {
+    class: String,
+    vendor: String,
+    device: String,
+    subsystem_vendor: String,
+    subsystem_device: String,
+    revision: String,
+    /// The bound kernel driver, `null` when none is.
+    driver: Option<String>,
+    /// VBIOS version from sysfs (amdgpu) or nvidia-smi, `null` when neither
+    /// reports one.
+    vbios_version: Option<String>,
+}
+
+/// The PCI class code prefixes of a configured device class.
+fn class_prefixes(class: &str) -> Result<Vec<String>> {
+    let prefixes = match class {
+        "gpu" => vec!["03".to_string()],
+        "accelerator" => vec!["12".to_string()],
+        "network" => vec!["02".to_string()],
+        "storage" => vec!["01".to_string()],
+        other => {
+            let hex = other.strip_prefix("0x").unwrap_or_default().to_lowercase();
+            if hex.is_empty() || hex.len() > 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(MeasurementError::Config(format!(
+                    "Unknown device class '{}'",
+                    other
+                )));
+            }
+            vec![hex]
+        }
+    };
+    Ok(prefixes)
+}
+
+fn read_attribute(dir: &Path, name: &str) -> Result<Option<String>> {
+    match fs::read_to_string(dir.join(name)) {
+        Ok(value) => Ok(Some(value.trim().to_lowercase())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Lists the PCI devices as `(address, identity)` in address order, without
+/// the VBIOS version of NVIDIA GPUs, which sysfs does not expose.
+fn pci_devices() -> Result<Vec<(String, DeviceIdentity)>> {
+    let mut devices = Vec::new();
+    for entry in fs::read_dir(PCI_DEVICES)? {
+        let dir = entry?.path();
+        let address = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let attribute = |name: &str| -> Result<String> {
+            read_attribute(&dir, name)?.ok_or_else(|| {
+                MeasurementError::InvalidDirectory(format!("{} has no {}", address, name))
+            })
+        };
+        let identity = DeviceIdentity {
+            class: attribute("class")?,
+            vendor: attribute("vendor")?,
+            device: attribute("device")?,
+            subsystem_vendor: attribute("subsystem_vendor")?,
+            subsystem_device: attribute("subsystem_device")?,
+            revision: attribute("revision")?,
+            driver: fs::read_link(dir.join("driver"))
+                .ok()
+                .and_then(|target| target.file_name().map(|n| n.to_string_lossy().to_string())),
+            vbios_version: read_attribute(&dir, "vbios_version")?,
+        };
+        devices.push((address, identity));
+    }
+    devices.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(devices)
+}
+
+/// Maps `00000000:3B:00.0`, as nvidia-smi prints bus ids, to the sysfs
+/// address `0000:3b:00.0`.
+fn sysfs_address(bus_id: &str) -> Option<String> {
+    let (domain, rest) = bus_id.trim().split_once(':')?;
+    let domain = u32::from_str_radix(domain, 16).ok()?;
+    Some(format!("{:04x}:{}", domain, rest.to_lowercase()))
+}
+
+/// VBIOS versions of the NVIDIA GPUs by sysfs address. A missing nvidia-smi
+/// gives none.
+async fn nvidia_vbios_versions(nvidia_smi: &str) -> Result<HashMap<String, String>> {
+    let output = match Command::new(nvidia_smi)
+        .args([
+            "--query-gpu=pci.bus_id,vbios_version",
+            "--format=csv,noheader",
+        ])
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            debug!("{} not found; skipping NVIDIA VBIOS versions", nvidia_smi);
+            return Ok(HashMap::new());
+        }
+        Err(e) => {
+            return Err(MeasurementError::CommandExecution(format!(
+                "Failed to run {}: {}",
+                nvidia_smi, e
+            )))
+        }
+    };
+    if !output.status.success() {
+        return Err(MeasurementError::CommandExecution(format!(
+            "{} failed with status {}: {}",
+            nvidia_smi,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (bus_id, version) = line.split_once(',')?;
+            Some((sysfs_address(bus_id)?, version.trim().to_string()))
+        })
+        .collect())
+}
+
+async fn measure_device(
+    address: &str,
+    identity: &DeviceIdentity,
+    entry: &MeasurementEntry,
+    config: &DeviceMeasurementConfig,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    let encoded = canonical_json::to_vec(identity)
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    let hash_algorithm = entry.hash_algorithm().unwrap_or(&config.hash_algorithm);
+    let digest = digest_bytes(&encoded, hash_algorithm)?;
+    let operation = format!("pci:{}", address);
+    debug!(
+        "Extending device measurement {} ({}) = {}",
+        operation,
+        String::from_utf8_lossy(&encoded),
+        digest
+    );
+    sink.extend(
+        DOMAIN,
+        &operation,
+        &digest,
+        entry.pcr_index().or(config.pcr_index).map(|v| v as u64),
+    )
+    .await
+}
+
+#[async_trait]
+impl Measurable for DeviceMeasurer {
+    fn name(&self) -> &str {
+        "DeviceMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Records the identity and firmware versions of GPUs and other PCI devices"
+    }
+
+    fn config_section(&self) -> &str {
+        "device_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.device_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.device_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let dm_config = &config.device_measurement;
+        if !dm_config.enable {
+            debug!("Device measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting device measurement with domain '{}'", DOMAIN);
+        let mut devices = tokio::task::spawn_blocking(pci_devices)
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??;
+        let has_nvidia = devices.iter().any(|(_, d)| d.vendor == NVIDIA_VENDOR);
+        if has_nvidia && !dm_config.nvidia_smi_path.is_empty() {
+            match nvidia_vbios_versions(&dm_config.nvidia_smi_path).await {
+                Ok(versions) => {
+                    for (address, identity) in &mut devices {
+                        if let Some(version) = versions.get(address.as_str()) {
+                            identity.vbios_version = Some(version.clone());
+                        }
+                    }
+                }
+                Err(e) => warn!("Cannot read NVIDIA VBIOS versions: {}", e),
+            }
+        }
+
+        let mut measured = HashSet::new();
+        let mut failures = EntryFailures::default();
+        for entry in &dm_config.classes {
+            let prefixes = match class_prefixes(entry.path()) {
+                Ok(prefixes) => prefixes,
+                Err(e) => {
+                    failures.record(entry.path(), entry.is_critical(), e);
+                    continue;
+                }
+            };
+            let matched: Vec<&(String, DeviceIdentity)> = devices
+                .iter()
+                .filter(|(_, d)| {
+                    let class = d.class.trim_start_matches("0x");
+                    prefixes.iter().any(|p| class.starts_with(p.as_str()))
+                })
+                .collect();
+            if matched.is_empty() {
+                if entry.is_critical() {
+                    let e = std::io::Error::new(ErrorKind::NotFound, "no device of this class");
+                    failures.record(entry.path(), true, e.into());
+                } else {
+                    debug!("No device of class '{}'", entry.path());
+                }
+                continue;
+            }
+            for (address, identity) in matched {
+                // A device in several configured classes is measured once.
+                if !measured.insert(address.clone()) {
+                    continue;
+                }
+                if let Err(e) =
+                    measure_device(address, identity, entry, dm_config, sink.as_ref()).await
+                {
+                    failures.record(address, entry.is_critical(), e);
+                }
+            }
+        }
+        info!(
+            "Device measurement completed for {} devices.",
+            measured.len()
+        );
+        failures.into_result()
+    }
+}
@@ -0,0 +1,197 @@
+// src/modules/dir_tree_measurer.rs
+use crate::config::{Config, DirTreeMeasurementConfig};
+use crate::digest;
+use crate::error::{MeasurementError, Result};
+use crate::modules::dataset_measurer::link_to_file;
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::Pattern;
+use log::{debug, info, warn};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DOMAIN: &str = "dir";
+
+/// Measures directory trees as one rolled-up digest over every regular file
+/// and its relative path, for directories that do not need dm-verity.
+#[derive(Default)]
+pub struct DirTreeMeasurer;
+
+impl DirTreeMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn measure_single_dir(
+        &self,
+        dir: &str,
+        config: &DirTreeMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let canonical_dir = PathBuf::from(dir)
+            .canonicalize()
+            .map_err(|e| MeasurementError::InvalidDirectory(format!("{} ({})", dir, e)))?;
+        if !canonical_dir.is_dir() {
+            return Err(MeasurementError::InvalidDirectory(format!(
+                "{:?} is not a directory",
+                canonical_dir
+            )));
+        }
+
+        info!("Hashing directory tree {:?}", canonical_dir);
+        let digest = {
+            let root = canonical_dir.clone();
+            let config = config.clone();
+            tokio::task::spawn_blocking(move || tree_digest(&root, &config))
+                .await
+                .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+        };
+        let operation = canonical_dir.to_string_lossy().to_string();
+        debug!(
+            "Extending directory tree measurement: domain={}, operation={}, digest={}",
+            DOMAIN, operation, digest
+        );
+        sink.extend(
+            DOMAIN,
+            &operation,
+            &digest,
+            config.pcr_index.map(|v| v as u64),
+        )
+        .await
+    }
+}
+
+/// Returns `<algorithm>:<hex>` of the tree listing of `root`.
+fn tree_digest(root: &Path, config: &DirTreeMeasurementConfig) -> Result<String> {
+    let exclude = config
+        .exclude
+        .iter()
+        .map(|p| Pattern::new(p))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    tree_hash(root, &exclude, &config.hash_algorithm)
+}
+
+/// Hashes the listing of every regular file under `root` that no `exclude`
+/// pattern matches: one `<relative path>\0<hex file digest>\n` line per file,
+/// sorted by relative path. The file digests use the same algorithm. A
+/// symbolic link to a file is hashed by the content it leads to, with
+/// `\0<link target>` before the newline.
+fn tree_hash(root: &Path, exclude: &[Pattern], hash_algorithm: &str) -> Result<String> {
+    let mut listing = digest::Hasher::new(hash_algorithm)?;
+    let mut files = Vec::new();
+    collect_tree(root, root, exclude, &mut files)?;
+    files.sort();
+    debug!("Directory tree {:?} has {} files", root, files.len());
+
+    for (relative, link_target) in &files {
+        let mut hasher = digest::Hasher::new(hash_algorithm)?;
+        hasher.update_reader(&mut File::open(root.join(relative))?)?;
+        listing.update(relative.as_bytes());
+        listing.update(b"\0");
+        listing.update(hasher.finalize_hex().as_bytes());
+        if let Some(target) = link_target {
+            listing.update(b"\0");
+            listing.update(target.to_string_lossy().as_bytes());
+        }
+        listing.update(b"\n");
+    }
+    Ok(listing.finalize_prefixed())
+}
+
+/// Collects the relative paths of the regular files under `dir`, and of the
+/// symbolic links to regular files with their targets. Links to anything
+/// else fail rather than being left out; other special files are skipped.
+fn collect_tree(
+    root: &Path,
+    dir: &Path,
+    exclude: &[Pattern],
+    files: &mut Vec<(String, Option<PathBuf>)>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string();
+        if exclude.iter().any(|p| p.matches(&relative)) {
+            debug!("Excluding {:?} from the directory tree", path);
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_tree(root, &path, exclude, files)?;
+        } else if file_type.is_file() {
+            files.push((relative, None));
+        } else if file_type.is_symlink() {
+            let target = link_to_file(&path)?;
+            files.push((relative, Some(target)));
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Measurable for DirTreeMeasurer {
+    fn name(&self) -> &str {
+        "DirTreeMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Hashes whole directory trees into one digest without dm-verity"
+    }
+
+    fn config_section(&self) -> &str {
+        "dir_tree_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.dir_tree_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.dir_tree_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let dt_config = &config.dir_tree_measurement;
+        if !dt_config.enable {
+            debug!("Directory tree measurement is disabled. Skipping.");
+            return Ok(());
+        }
+        if dt_config.directories.is_empty() {
+            warn!("Directory tree measurement is enabled but no directories configured.");
+            return Ok(());
+        }
+
+        info!(
+            "Starting directory tree measurement with domain '{}'",
+            DOMAIN
+        );
+        let mut measured_dirs = HashSet::new();
+        let mut failures = EntryFailures::default();
+        for entry in &dt_config.directories {
+            let dir = entry.path();
+            if !measured_dirs.insert(dir.to_string()) {
+                debug!("Skipping duplicate directory entry: {}", dir);
+                continue;
+            }
+            if let Err(e) = self.measure_single_dir(dir, dt_config, &sink).await {
+                failures.record(dir, entry.is_critical(), e);
+            }
+        }
+        info!(
+            "Directory tree measurement completed for {} unique directories.",
+            measured_dirs.len()
+        );
+        failures.into_result()
+    }
+}
@@ -0,0 +1,326 @@
+// src/modules/efi_measurer.rs
+use crate::canonical_json;
+use crate::config::{Config, EfiMeasurementConfig};
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::glob;
+use log::{debug, info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DOMAIN: &str = "efi";
+
+const PE32_MAGIC: u16 = 0x10b;
+const PE32_PLUS_MAGIC: u16 = 0x20b;
+/// Index of the certificate table in the optional header data directories.
+const CERTIFICATE_TABLE: usize = 4;
+const SECTION_HEADER_SIZE: usize = 40;
+
+/// Sections whose text content is recorded alongside their digest.
+const TEXT_SECTIONS: [&str; 3] = [".cmdline", ".sbat", ".uname"];
+
+/// Measures Unified Kernel Images and other EFI binaries: the Authenticode
+/// hash that firmware and shim verify, and a digest of every PE section.
+#[derive(Default)]
+pub struct EfiMeasurer;
+
+/// A PE section header.
+struct Section {
+    name: String,
+    virtual_size: u32,
+    raw_size: u32,
+    raw_offset: u32,
+}
+
+impl Section {
+    /// The section data as loaded; the raw data may carry file alignment
+    /// padding beyond the virtual size.
+    fn data<'a>(&self, image: &'a [u8]) -> Result<&'a [u8]> {
+        let size = match self.virtual_size {
+            0 => self.raw_size,
+            size => size.min(self.raw_size),
+        };
+        slice(image, self.raw_offset as usize, size as usize)
+    }
+}
+
+/// The parts of the PE headers the measurement depends on.
+struct PeImage {
+    checksum_offset: usize,
+    /// Offset of the certificate table data directory entry, if present.
+    cert_dir_offset: Option<usize>,
+    cert_table_size: usize,
+    size_of_headers: usize,
+    sections: Vec<Section>,
+}
+
+/// Structured content extended for a binary's sections.
+#[derive(Debug, Serialize)]
+struct SectionRecord {
+    sections: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    text: BTreeMap<String, String>,
+}
+
+impl EfiMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn measure_binary(
+        &self,
+        path: &Path,
+        config: &EfiMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let (authenticode, sections) = {
+            let path = path.to_path_buf();
+            tokio::task::spawn_blocking(move || -> Result<_> {
+                let image = fs::read(&path)?;
+                let pe = parse_pe(&image)
+                    .map_err(|e| MeasurementError::Config(format!("{:?}: {}", path, e)))?;
+                Ok((
+                    authenticode_digest(&image, &pe)?,
+                    section_record(&image, &pe)?,
+                ))
+            })
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+        };
+        let path_str = path.to_string_lossy();
+        let pcr_index = config.pcr_index.map(|v| v as u64);
+
+        let operation = format!("authenticode:{}", path_str);
+        debug!("Extending EFI measurement {} = {}", operation, authenticode);
+        sink.extend(DOMAIN, &operation, &authenticode, pcr_index)
+            .await?;
+
+        let content = canonical_json::to_vec(&sections)
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        sink.extend(
+            DOMAIN,
+            &format!("sections:{}", path_str),
+            &String::from_utf8_lossy(&content),
+            pcr_index,
+        )
+        .await?;
+        info!(
+            "Measured EFI binary {} ({} sections)",
+            path_str,
+            sections.sections.len()
+        );
+        Ok(())
+    }
+}
+
+fn slice(image: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| image.get(offset..end))
+        .ok_or_else(|| MeasurementError::Config("truncated PE image".to_string()))
+}
+
+fn le_u16(image: &[u8], offset: usize) -> Result<u16> {
+    let bytes = slice(image, offset, 2)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn le_u32(image: &[u8], offset: usize) -> Result<u32> {
+    let bytes = slice(image, offset, 4)?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Parses the DOS, COFF and optional headers and the section table (see
+/// the Microsoft PE/COFF specification).
+fn parse_pe(image: &[u8]) -> Result<PeImage> {
+    let not_pe = || MeasurementError::Config("not a PE/COFF image".to_string());
+    if slice(image, 0, 2)? != b"MZ" {
+        return Err(not_pe());
+    }
+    let pe_offset = le_u32(image, 0x3c)? as usize;
+    if slice(image, pe_offset, 4)? != b"PE\0\0" {
+        return Err(not_pe());
+    }
+    let coff = pe_offset + 4;
+    let section_count = le_u16(image, coff + 2)? as usize;
+    let optional_header_size = le_u16(image, coff + 16)? as usize;
+    let optional = coff + 20;
+
+    let (rva_count_offset, data_dirs) = match le_u16(image, optional)? {
+        PE32_MAGIC => (optional + 92, optional + 96),
+        PE32_PLUS_MAGIC => (optional + 108, optional + 112),
+        _ => return Err(not_pe()),
+    };
+    let rva_count = le_u32(image, rva_count_offset)? as usize;
+    let (cert_dir_offset, cert_table_size) = if rva_count > CERTIFICATE_TABLE {
+        let entry = data_dirs + CERTIFICATE_TABLE * 8;
+        (Some(entry), le_u32(image, entry + 4)? as usize)
+    } else {
+        (None, 0)
+    };
+
+    let table = optional + optional_header_size;
+    let sections = (0..section_count)
+        .map(|i| {
+            let header = table + i * SECTION_HEADER_SIZE;
+            let name = slice(image, header, 8)?;
+            Ok(Section {
+                name: String::from_utf8_lossy(name)
+                    .trim_end_matches('\0')
+                    .to_string(),
+                virtual_size: le_u32(image, header + 8)?,
+                raw_size: le_u32(image, header + 16)?,
+                raw_offset: le_u32(image, header + 20)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(PeImage {
+        checksum_offset: optional + 64,
+        cert_dir_offset,
+        cert_table_size,
+        size_of_headers: le_u32(image, optional + 60)? as usize,
+        sections,
+    })
+}
+
+/// The Authenticode image hash: headers without the checksum and the
+/// certificate table entry, sections in file order, then any trailing data
+/// except the certificate table itself.
+fn authenticode_digest(image: &[u8], pe: &PeImage) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(slice(image, 0, pe.checksum_offset)?);
+    let after_checksum = pe.checksum_offset + 4;
+    match pe.cert_dir_offset {
+        Some(cert_dir) => {
+            hasher.update(slice(image, after_checksum, cert_dir - after_checksum)?);
+            let after_cert_dir = cert_dir + 8;
+            hasher.update(slice(
+                image,
+                after_cert_dir,
+                pe.size_of_headers.saturating_sub(after_cert_dir),
+            )?);
+        }
+        None => hasher.update(slice(
+            image,
+            after_checksum,
+            pe.size_of_headers.saturating_sub(after_checksum),
+        )?),
+    }
+
+    let mut sections: Vec<&Section> = pe.sections.iter().filter(|s| s.raw_size > 0).collect();
+    sections.sort_by_key(|s| s.raw_offset);
+    let mut hashed = pe.size_of_headers;
+    for section in sections {
+        hasher.update(slice(
+            image,
+            section.raw_offset as usize,
+            section.raw_size as usize,
+        )?);
+        hashed += section.raw_size as usize;
+    }
+
+    let end = image.len().saturating_sub(pe.cert_table_size);
+    if end > hashed {
+        hasher.update(&image[hashed..end]);
+    }
+    Ok(format!("sha256:{}", hex::encode(hasher.finalize())))
+}
+
+fn section_record(image: &[u8], pe: &PeImage) -> Result<SectionRecord> {
+    let mut record = SectionRecord {
+        sections: BTreeMap::new(),
+        text: BTreeMap::new(),
+    };
+    for section in &pe.sections {
+        let data = section.data(image)?;
+        record.sections.insert(
+            section.name.clone(),
+            format!("sha256:{}", hex::encode(Sha256::digest(data))),
+        );
+        if TEXT_SECTIONS.contains(&section.name.as_str()) {
+            record.text.insert(
+                section.name.clone(),
+                String::from_utf8_lossy(data)
+                    .trim_end_matches('\0')
+                    .to_string(),
+            );
+        }
+    }
+    Ok(record)
+}
+
+#[async_trait]
+impl Measurable for EfiMeasurer {
+    fn name(&self) -> &str {
+        "EfiMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Hashes UKIs and EFI binaries (Authenticode and per-section digests)"
+    }
+
+    fn config_section(&self) -> &str {
+        "efi_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.efi_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.efi_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let efi_config = &config.efi_measurement;
+        if !efi_config.enable {
+            debug!("EFI measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting EFI measurement with domain '{}'", DOMAIN);
+        let mut measured: HashSet<PathBuf> = HashSet::new();
+        let mut failures = EntryFailures::default();
+        for entry in &efi_config.binaries {
+            let pattern = entry.path();
+            let paths: Vec<PathBuf> = match glob(pattern) {
+                Ok(paths) => paths.flatten().filter(|p| p.is_file()).collect(),
+                Err(e) => {
+                    failures.record(pattern, entry.is_critical(), e.into());
+                    continue;
+                }
+            };
+            if paths.is_empty() {
+                if entry.is_critical() {
+                    let e = std::io::Error::new(std::io::ErrorKind::NotFound, "no file matches");
+                    failures.record(pattern, true, e.into());
+                } else {
+                    warn!("EFI pattern '{}' matches no files", pattern);
+                }
+                continue;
+            }
+            for path in paths {
+                if !measured.insert(path.clone()) {
+                    debug!("Skipping already measured EFI binary {:?}", path);
+                    continue;
+                }
+                if let Err(e) = self.measure_binary(&path, efi_config, &sink).await {
+                    failures.record(&path.to_string_lossy(), entry.is_critical(), e);
+                }
+            }
+        }
+        info!("EFI measurement completed for {} binaries.", measured.len());
+        failures.into_result()
+    }
+}
@@ -0,0 +1,407 @@
+// src/modules/exec_measurer.rs
+use crate::config::{Config, ExecMeasurementConfig, ExecMonitor};
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use crate::modules::file_config_watcher::is_relevant_event;
+use crate::modules::watcher::ConfigWatcher;
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use notify::{recommended_watcher, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::ffi::CString;
+use std::fs::{self, File};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
+
+const DOMAIN: &str = "exec";
+/// Bytes of fanotify events read at once.
+const EVENT_BUFFER_SIZE: usize = 8192;
+/// Changes to an executable within this interval are measured once.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Measures binaries as they are executed, IMA-style, and extends them under
+/// the `exec` domain with the binary's path as the operation. Runs in
+/// daemon mode while `exec_measurement.enable` is set; the settings are read
+/// once at startup.
+#[derive(Default)]
+pub struct ExecMeasurer;
+
+impl ExecMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// A binary as far as its metadata tells: one rewritten in place changes
+/// its modification or change time.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct BinaryKey {
+    path: PathBuf,
+    dev: u64,
+    ino: u64,
+    size: u64,
+    mtime: (i64, i64),
+    ctime: (i64, i64),
+}
+
+impl BinaryKey {
+    fn new(path: &Path, metadata: &fs::Metadata) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+            size: metadata.size(),
+            mtime: (metadata.mtime(), metadata.mtime_nsec()),
+            ctime: (metadata.ctime(), metadata.ctime_nsec()),
+        }
+    }
+}
+
+/// The binaries extended so far, forgotten oldest first once `capacity` is
+/// reached.
+struct MeasuredCache {
+    capacity: usize,
+    keys: HashSet<BinaryKey>,
+    order: VecDeque<BinaryKey>,
+}
+
+impl MeasuredCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            keys: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn contains(&self, key: &BinaryKey) -> bool {
+        self.keys.contains(key)
+    }
+
+    fn insert(&mut self, key: BinaryKey) {
+        if self.capacity == 0 || !self.keys.insert(key.clone()) {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// What the monitors share: the settings, the cache and the way back into
+/// the runtime to extend.
+struct ExecContext {
+    config: ExecMeasurementConfig,
+    cache: MeasuredCache,
+    sink: Arc<dyn MeasurementSink>,
+    runtime: Handle,
+}
+
+impl ExecContext {
+    /// Whether `path` is under an `include` prefix and no `exclude` prefix.
+    fn is_selected(&self, path: &Path) -> bool {
+        self.config.include.iter().any(|p| path.starts_with(p))
+            && !self.config.exclude.iter().any(|p| path.starts_with(p))
+    }
+
+    /// Hashes and extends `file`, opened from `path`, unless the same
+    /// binary was already extended.
+    fn measure_binary(&mut self, path: &Path, file: &File) -> Result<()> {
+        let key = BinaryKey::new(path, &file.metadata()?);
+        if self.cache.contains(&key) {
+            return Ok(());
+        }
+        let digest = digest_reader(&mut &*file, &self.config.hash_algorithm)?;
+        let operation = path.to_string_lossy();
+        debug!(
+            "Extending exec measurement: domain={}, operation={}, digest={}",
+            DOMAIN, operation, digest
+        );
+        self.runtime.block_on(self.sink.extend(
+            DOMAIN,
+            &operation,
+            &digest,
+            self.config.pcr_index.map(|v| v as u64),
+        ))?;
+        self.cache.insert(key);
+        Ok(())
+    }
+}
+
+/// A fanotify group reporting executions on whole mounts.
+struct Fanotify {
+    fd: OwnedFd,
+    permission: bool,
+}
+
+/// An execution reported by fanotify, with the executed file opened
+/// read-only.
+struct ExecEvent {
+    file: File,
+    pid: i32,
+}
+
+impl Fanotify {
+    fn new(permission: bool) -> Result<Self> {
+        let class = if permission {
+            libc::FAN_CLASS_CONTENT
+        } else {
+            libc::FAN_CLASS_NOTIF
+        };
+        let event_flags = libc::O_RDONLY | libc::O_LARGEFILE | libc::O_CLOEXEC;
+        let fd = unsafe { libc::fanotify_init(libc::FAN_CLOEXEC | class, event_flags as u32) };
+        if fd < 0 {
+            return Err(MeasurementError::Config(format!(
+                "fanotify_init failed (exec measurement needs CAP_SYS_ADMIN): {}",
+                io::Error::last_os_error()
+            )));
+        }
+        Ok(Self {
+            fd: unsafe { OwnedFd::from_raw_fd(fd) },
+            permission,
+        })
+    }
+
+    /// Reports executions on the whole mount holding `path`.
+    fn mark_mount(&self, path: &Path) -> Result<()> {
+        let c_path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        let mask = if self.permission {
+            libc::FAN_OPEN_EXEC_PERM
+        } else {
+            libc::FAN_OPEN_EXEC
+        };
+        let ret = unsafe {
+            libc::fanotify_mark(
+                self.fd.as_raw_fd(),
+                libc::FAN_MARK_ADD | libc::FAN_MARK_MOUNT,
+                mask,
+                libc::AT_FDCWD,
+                c_path.as_ptr(),
+            )
+        };
+        if ret != 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    /// Blocks until executions are reported.
+    fn read_events(&self) -> Result<Vec<ExecEvent>> {
+        let mut buf = [0u8; EVENT_BUFFER_SIZE];
+        let len = unsafe {
+            libc::read(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+            )
+        };
+        if len < 0 {
+            let e = io::Error::last_os_error();
+            if e.kind() == io::ErrorKind::Interrupted {
+                return Ok(Vec::new());
+            }
+            return Err(e.into());
+        }
+
+        let len = len as usize;
+        let header = std::mem::size_of::<libc::fanotify_event_metadata>();
+        let mut events = Vec::new();
+        let mut offset = 0;
+        while offset + header <= len {
+            let metadata: libc::fanotify_event_metadata = unsafe {
+                std::ptr::read_unaligned(
+                    buf.as_ptr().add(offset) as *const libc::fanotify_event_metadata
+                )
+            };
+            if metadata.vers != libc::FANOTIFY_METADATA_VERSION {
+                return Err(MeasurementError::Other(anyhow::anyhow!(
+                    "Unsupported fanotify metadata version {}",
+                    metadata.vers
+                )));
+            }
+            if (metadata.event_len as usize) < header {
+                break;
+            }
+            offset += metadata.event_len as usize;
+            if metadata.mask & libc::FAN_Q_OVERFLOW != 0 {
+                warn!("fanotify queue overflowed; some executions were not measured");
+            }
+            if metadata.fd == libc::FAN_NOFD {
+                continue;
+            }
+            events.push(ExecEvent {
+                file: unsafe { File::from_raw_fd(metadata.fd) },
+                pid: metadata.pid,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Lets an execution held by a permission event proceed.
+    fn allow(&self, event: &ExecEvent) {
+        let response = libc::fanotify_response {
+            fd: event.file.as_raw_fd(),
+            response: libc::FAN_ALLOW,
+        };
+        let ret = unsafe {
+            libc::write(
+                self.fd.as_raw_fd(),
+                &response as *const libc::fanotify_response as *const libc::c_void,
+                std::mem::size_of::<libc::fanotify_response>(),
+            )
+        };
+        if ret < 0 {
+            warn!(
+                "Failed to allow execution by pid {}: {}",
+                event.pid,
+                io::Error::last_os_error()
+            );
+        }
+    }
+}
+
+/// Measures every execution on the mounts of `include` through fanotify.
+/// Permission events are always allowed, once the binary is extended or
+/// failed to be.
+fn monitor_fanotify(mut ctx: ExecContext, permission: bool) -> Result<()> {
+    let fanotify = Fanotify::new(permission)?;
+    for prefix in &ctx.config.include {
+        match fanotify.mark_mount(Path::new(prefix)) {
+            Ok(()) => debug!("Watching executions on the mount of {}", prefix),
+            Err(e) => warn!("Cannot watch executions under {}: {}", prefix, e),
+        }
+    }
+    info!(
+        "Measuring executed binaries under {:?} with fanotify",
+        ctx.config.include
+    );
+
+    loop {
+        for event in fanotify.read_events()? {
+            let path = fs::read_link(format!("/proc/self/fd/{}", event.file.as_raw_fd()));
+            match path {
+                Ok(path) if ctx.is_selected(&path) => {
+                    if let Err(e) = ctx.measure_binary(&path, &event.file) {
+                        warn!(
+                            "Failed to measure {:?} executed by pid {}: {}",
+                            path, event.pid, e
+                        );
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => warn!(
+                    "Cannot resolve the binary executed by pid {}: {}",
+                    event.pid, e
+                ),
+            }
+            if permission {
+                fanotify.allow(&event);
+            }
+        }
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Measures executables under `include` whenever they are created or
+/// modified. inotify cannot tell executions from other opens, so binaries
+/// are measured as they are installed rather than as they run.
+fn monitor_inotify(mut ctx: ExecContext) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    for prefix in &ctx.config.include {
+        match watcher.watch(Path::new(prefix), RecursiveMode::Recursive) {
+            Ok(()) => debug!("Watching {} for new executables", prefix),
+            Err(e) => warn!("Cannot watch {} for new executables: {}", prefix, e),
+        }
+    }
+    info!(
+        "Measuring executables created under {:?} with inotify",
+        ctx.config.include
+    );
+
+    while let Ok(event) = rx.recv() {
+        if !is_relevant_event(&event.kind) {
+            continue;
+        }
+        let mut changed: BTreeSet<PathBuf> = event.paths.into_iter().collect();
+        std::thread::sleep(DEBOUNCE);
+        while let Ok(event) = rx.try_recv() {
+            if is_relevant_event(&event.kind) {
+                changed.extend(event.paths);
+            }
+        }
+        for path in changed {
+            if !ctx.is_selected(&path) || !is_executable(&path) {
+                continue;
+            }
+            let result = File::open(&path)
+                .map_err(MeasurementError::from)
+                .and_then(|file| ctx.measure_binary(&path, &file));
+            if let Err(e) = result {
+                warn!("Failed to measure executable {:?}: {}", path, e);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl ConfigWatcher for ExecMeasurer {
+    fn name(&self) -> &str {
+        "ExecMeasurer"
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.exec_measurement.enable
+    }
+
+    async fn watch(
+        &self,
+        _config_path: PathBuf,
+        shared_config: Arc<RwLock<Config>>,
+        sink: Arc<dyn MeasurementSink>,
+    ) -> Result<()> {
+        let config = shared_config.read().await.exec_measurement.clone();
+        if config.include.is_empty() {
+            warn!("Exec measurement is enabled but no include prefixes configured.");
+            return Ok(());
+        }
+        let monitor = config.monitor;
+        let ctx = ExecContext {
+            cache: MeasuredCache::new(config.cache_size),
+            config,
+            sink,
+            runtime: Handle::current(),
+        };
+        tokio::task::spawn_blocking(move || match monitor {
+            ExecMonitor::Fanotify => monitor_fanotify(ctx, false),
+            ExecMonitor::FanotifyPermission => monitor_fanotify(ctx, true),
+            ExecMonitor::Inotify => monitor_inotify(ctx),
+        })
+        .await
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?
+    }
+}
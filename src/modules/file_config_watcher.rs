@@ -1,34 +1,118 @@
 // src/modules/file_config_watcher.rs
-use crate::config::Config;
+use crate::config::{
+    BlackoutAction, Config, FileMeasurementConfig, LoadedConfig, MeasurementEntry,
+    ModelDirMeasurementConfig,
+};
 use crate::error::{MeasurementError, Result};
+use crate::fs_watch::PathWatcher;
+use crate::modules::job_queue::{JobQueue, Priority};
 use crate::modules::model_dir_measurer::ModelDirMeasurer;
-use crate::modules::{watcher::ConfigWatcher, FileMeasurer};
-use crate::rpc_client::AAClient;
+use crate::modules::{registered_measurers, watcher::ConfigWatcher, FileMeasurer, Measurable};
+use crate::schedule::BlackoutSchedule;
+use crate::sink::MeasurementSink;
 use async_trait::async_trait;
-use hex;
-use log::{debug, info, warn};
-use notify::{recommended_watcher, EventKind, RecursiveMode, Watcher};
-use sha2::{Digest, Sha256};
-use std::collections::HashSet;
-use std::fs;
+use log::{debug, error, info, warn};
+use notify::{EventKind, RecursiveMode};
+use std::collections::{BTreeSet, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{Notify, RwLock};
 use tokio::time::sleep;
 
 #[async_trait]
 pub trait ConfigChangeHandler: Send + Sync {
     fn name(&self) -> &str;
     fn is_enabled(&self, cfg: &Config) -> bool;
+    /// Urgency of the work this handler does on a change; queued work runs
+    /// most urgent first.
+    fn priority(&self) -> Priority {
+        Priority::Normal
+    }
     async fn handle_change(
         &self,
         old_config: &Config,
         new_config: &Config,
-        aa_client: Arc<AAClient>,
+        sink: Arc<dyn MeasurementSink>,
     ) -> Result<()>;
 }
 
+/// How the entries of a section changed on reload. Entries are matched by
+/// path, so one whose settings (such as `critical`) changed counts as added
+/// but not as removed.
+struct EntryDiff {
+    added: Vec<MeasurementEntry>,
+    removed: BTreeSet<String>,
+}
+
+impl EntryDiff {
+    fn new(old: &[MeasurementEntry], new: &[MeasurementEntry]) -> Self {
+        let old_entries: HashSet<&MeasurementEntry> = old.iter().collect();
+        let new_paths: HashSet<&str> = new.iter().map(MeasurementEntry::path).collect();
+        Self {
+            added: new
+                .iter()
+                .filter(|entry| !old_entries.contains(entry))
+                .cloned()
+                .collect(),
+            removed: old
+                .iter()
+                .map(MeasurementEntry::path)
+                .filter(|path| !new_paths.contains(path))
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Settings that change what is extended for the same files.
+fn file_settings_changed(old: &FileMeasurementConfig, new: &FileMeasurementConfig) -> bool {
+    old.pcr_index != new.pcr_index
+        || !old.hash_algorithm.eq_ignore_ascii_case(&new.hash_algorithm)
+        || old.ima_signatures != new.ima_signatures
+        || old.archive_members != new.archive_members
+}
+
+/// Settings that change what is extended for the same directories.
+fn model_dir_settings_changed(
+    old: &ModelDirMeasurementConfig,
+    new: &ModelDirMeasurementConfig,
+) -> bool {
+    old.pcr_index != new.pcr_index
+        || old.backend != new.backend
+        || old.mode != new.mode
+        || old.versioned_operation != new.versioned_operation
+        || old.version_labels != new.version_labels
+        || old.network_fs_fallback != new.network_fs_fallback
+}
+
+/// Extends a `measurement_policy` event recording `change` to `section`,
+/// if `reload.tombstones` is set.
+async fn record_tombstone(
+    config: &Config,
+    sink: &dyn MeasurementSink,
+    change: &str,
+    section: &str,
+    content: &str,
+) {
+    if !config.reload.tombstones {
+        return;
+    }
+    let operation = format!("{}:{}", change, section);
+    if let Err(e) = sink
+        .extend(
+            POLICY_DOMAIN,
+            &operation,
+            content,
+            config.policy_pcr_index.map(|v| v as u64),
+        )
+        .await
+    {
+        warn!("Failed to record {} '{}': {}", operation, content, e);
+    }
+}
+
+#[derive(Default)]
 pub struct FileMeasurementChangeHandler {
     measurer: FileMeasurer,
 }
@@ -51,31 +135,57 @@ impl ConfigChangeHandler for FileMeasurementChangeHandler {
         cfg.file_measurement.enable
     }
 
+    fn priority(&self) -> Priority {
+        Priority::Critical
+    }
+
     async fn handle_change(
         &self,
         old_config: &Config,
         new_config: &Config,
-        aa_client: Arc<AAClient>,
+        sink: Arc<dyn MeasurementSink>,
     ) -> Result<()> {
-        let old_files: HashSet<String> = old_config.file_measurement.files.iter().cloned().collect();
-        let new_files: HashSet<String> = new_config.file_measurement.files.iter().cloned().collect();
-        let added: Vec<String> = new_files.difference(&old_files).cloned().collect();
-
-        if added.is_empty() {
-            debug!("No new file measurement patterns detected.");
+        if !old_config.file_measurement.enable {
+            // Newly enabled; MeasurerToggleHandler runs the full measurement.
             return Ok(());
         }
+        let (old_fm, new_fm) = (&old_config.file_measurement, &new_config.file_measurement);
+        let diff = EntryDiff::new(&old_fm.files, &new_fm.files);
+        for path in &diff.removed {
+            info!("File measurement pattern '{}' removed from config.", path);
+            record_tombstone(new_config, &sink, "removed", "file_measurement", path).await;
+        }
 
-        info!(
-            "Detected {} new file measurement patterns; triggering measurement.",
-            added.len()
-        );
+        let patterns = if new_config.reload.remeasure_on_settings_change
+            && file_settings_changed(old_fm, new_fm)
+        {
+            info!(
+                "File measurement settings changed; re-measuring all {} patterns.",
+                new_fm.files.len()
+            );
+            new_fm.files.clone()
+        } else if diff.added.is_empty() {
+            debug!("No new file measurement patterns detected.");
+            return Ok(());
+        } else {
+            info!(
+                "Detected {} new file measurement patterns; triggering measurement.",
+                diff.added.len()
+            );
+            diff.added
+        };
         self.measurer
-            .measure_patterns(&added, &new_config.file_measurement, aa_client)
+            .measure_patterns(
+                &patterns,
+                &new_config.file_measurement,
+                new_config.max_concurrency,
+                sink,
+            )
             .await
     }
 }
 
+#[derive(Default)]
 pub struct ModelDirMeasurementChangeHandler {
     measurer: ModelDirMeasurer,
 }
@@ -102,59 +212,350 @@ impl ConfigChangeHandler for ModelDirMeasurementChangeHandler {
         &self,
         old_config: &Config,
         new_config: &Config,
-        aa_client: Arc<AAClient>,
+        sink: Arc<dyn MeasurementSink>,
     ) -> Result<()> {
-        let old_dirs: HashSet<String> =
-            old_config.model_dir_measurement.directories.iter().cloned().collect();
-        let new_dirs: HashSet<String> =
-            new_config.model_dir_measurement.directories.iter().cloned().collect();
-        let added: Vec<String> = new_dirs.difference(&old_dirs).cloned().collect();
-
-        if added.is_empty() {
-            debug!("No new model directory entries detected.");
+        if !old_config.model_dir_measurement.enable {
+            // Newly enabled; MeasurerToggleHandler runs the full measurement.
             return Ok(());
         }
-
-        info!(
-            "Detected {} new model directories; triggering measurement.",
-            added.len()
+        let (old_md, new_md) = (
+            &old_config.model_dir_measurement,
+            &new_config.model_dir_measurement,
         );
+        let diff = EntryDiff::new(&old_md.directories, &new_md.directories);
+        for path in &diff.removed {
+            info!("Model directory '{}' removed from config.", path);
+            record_tombstone(new_config, &sink, "removed", "model_dir_measurement", path).await;
+        }
+
+        let directories = if new_config.reload.remeasure_on_settings_change
+            && model_dir_settings_changed(old_md, new_md)
+        {
+            info!(
+                "Model directory measurement settings changed; re-measuring all {} directories.",
+                new_md.directories.len()
+            );
+            new_md.directories.clone()
+        } else if diff.added.is_empty() {
+            debug!("No new model directory entries detected.");
+            return Ok(());
+        } else {
+            info!(
+                "Detected {} new model directories; triggering measurement.",
+                diff.added.len()
+            );
+            diff.added
+        };
 
         // Reuse measurer logic; it will deduplicate internally.
         self.measurer
-            .measure_specific_dirs(&added, &new_config.model_dir_measurement, aa_client)
+            .measure_specific_dirs(
+                &directories,
+                &new_config.model_dir_measurement,
+                new_config.max_concurrency,
+                sink,
+            )
             .await
     }
 }
 
+/// Starts or stops registered measurers whose section flips `enable` on
+/// reload. A newly enabled measurer runs a full measurement right away; a
+/// disabled one stops, as its change handlers are skipped from then on.
+pub struct MeasurerToggleHandler {
+    measurers: Vec<Box<dyn Measurable + Send + Sync>>,
+}
+
+impl MeasurerToggleHandler {
+    pub fn new() -> Self {
+        Self {
+            measurers: registered_measurers(),
+        }
+    }
+}
+
+impl Default for MeasurerToggleHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ConfigChangeHandler for MeasurerToggleHandler {
+    fn name(&self) -> &str {
+        "MeasurerToggleHandler"
+    }
+
+    fn is_enabled(&self, _cfg: &Config) -> bool {
+        true
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Bulk
+    }
+
+    async fn handle_change(
+        &self,
+        old_config: &Config,
+        new_config: &Config,
+        sink: Arc<dyn MeasurementSink>,
+    ) -> Result<()> {
+        let old_config = Arc::new(old_config.clone());
+        let new_config = Arc::new(new_config.clone());
+        let mut first_error = None;
+        for measurer in &self.measurers {
+            let was_enabled = measurer.is_enabled(old_config.clone());
+            let is_enabled = measurer.is_enabled(new_config.clone());
+            match (was_enabled, is_enabled) {
+                (false, true) => {
+                    info!(
+                        "[{}] enabled in config; starting measurer.",
+                        measurer.config_section()
+                    );
+                    if let Err(e) = measurer.measure(new_config.clone(), sink.clone()).await {
+                        warn!(
+                            "Measurer {} failed after being enabled: {}",
+                            measurer.name(),
+                            e
+                        );
+                        first_error.get_or_insert(e);
+                    }
+                }
+                (true, false) => {
+                    info!(
+                        "[{}] disabled in config; stopping measurer.",
+                        measurer.config_section()
+                    );
+                    record_tombstone(
+                        &new_config,
+                        &sink,
+                        "disabled",
+                        measurer.config_section(),
+                        "enable = false",
+                    )
+                    .await;
+                }
+                _ => {}
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
 pub struct ConfigFileWatcher {
     handlers: Vec<Box<dyn ConfigChangeHandler>>,
+    /// Notified on SIGHUP to force a reload and a full re-measurement.
+    reload_requests: Arc<Notify>,
+    /// Kept here rather than in `watch`, so that a watch restarted by the
+    /// supervisor neither renumbers the reloads nor forgets the config.
+    applied: std::sync::Mutex<AppliedConfig>,
+}
+
+/// The config the handlers last acted on.
+#[derive(Default)]
+struct AppliedConfig {
+    /// Digest of its sources; `None` until the first watch loads them.
+    digest: Option<String>,
+    /// Reloads extended as `reload:<n>` so far.
+    reloads: u64,
 }
 
 impl ConfigFileWatcher {
-    pub fn new(handlers: Vec<Box<dyn ConfigChangeHandler>>) -> Self {
-        Self { handlers }
+    pub fn new(handlers: Vec<Box<dyn ConfigChangeHandler>>, reload_requests: Arc<Notify>) -> Self {
+        Self {
+            handlers,
+            reload_requests,
+            applied: Default::default(),
+        }
+    }
+
+    /// Queues the work every enabled handler does for a change.
+    fn enqueue_changes(
+        &self,
+        queue: &mut JobQueue<ChangeJob>,
+        old_config: Config,
+        new_config: Config,
+    ) {
+        let old_config = Arc::new(old_config);
+        let new_config = Arc::new(new_config);
+        for (handler, h) in self.handlers.iter().enumerate() {
+            if h.is_enabled(&new_config) {
+                queue.push(
+                    h.priority(),
+                    ChangeJob::Handler {
+                        handler,
+                        old_config: old_config.clone(),
+                        new_config: new_config.clone(),
+                    },
+                );
+            }
+        }
+        debug!("{} config change jobs queued.", queue.len());
     }
+
+    /// Runs the next job as one measurement run of `sink`.
+    async fn run_next_job(&self, queue: &mut JobQueue<ChangeJob>, sink: Arc<dyn MeasurementSink>) {
+        sink.begin_run();
+        match queue.pop() {
+            Some(ChangeJob::Handler {
+                handler,
+                old_config,
+                new_config,
+            }) => {
+                let handler = &self.handlers[handler];
+                if let Err(e) = handler
+                    .handle_change(&old_config, &new_config, sink.clone())
+                    .await
+                {
+                    warn!(
+                        "Handler {} failed during config change: {}",
+                        handler.name(),
+                        e
+                    );
+                }
+            }
+            Some(ChangeJob::Remeasure(config)) => {
+                for measurer in registered_measurers() {
+                    if !measurer.is_enabled(config.clone()) {
+                        continue;
+                    }
+                    if let Err(e) = sink.check_run() {
+                        error!("{}; skipping the rest of the re-measurement.", e);
+                        break;
+                    }
+                    if let Err(e) = measurer.measure(config.clone(), sink.clone()).await {
+                        warn!(
+                            "Measurer {} failed on re-measurement: {}",
+                            measurer.name(),
+                            e
+                        );
+                    }
+                }
+                info!("Full re-measurement completed.");
+            }
+            None => {}
+        }
+        sink.end_run();
+    }
+}
+
+/// Work queued by the config watcher.
+enum ChangeJob {
+    /// One handler's share of a config change.
+    Handler {
+        handler: usize,
+        old_config: Arc<Config>,
+        new_config: Arc<Config>,
+    },
+    /// Every enabled measurer, run again after a reload forced by SIGHUP.
+    Remeasure(Arc<Config>),
+}
+
+/// What wakes the config watcher up.
+enum Trigger {
+    Changed(notify::Event),
+    /// SIGHUP: reload even if no change was seen, and re-measure everything.
+    Hangup,
+    /// The config changed while the watch was down; reload it.
+    Restarted,
 }
 
+/// Returns how long the blackout window in effect now lasts, if any.
+fn blackout_remaining(config: &Config) -> Option<Duration> {
+    match BlackoutSchedule::from_config(&config.maintenance) {
+        Ok(schedule) => schedule.remaining(SystemTime::now()),
+        Err(e) => {
+            warn!("Ignoring maintenance windows: {}", e);
+            None
+        }
+    }
+}
+
+/// Domain under which every accepted configuration is extended.
+const POLICY_DOMAIN: &str = "measurement_policy";
+
 const MAX_RELOAD_RETRIES: usize = 3;
 const RELOAD_RETRY_DELAY_MS: u64 = 200;
 
-fn load_config_with_hash(path: &Path) -> Result<(Config, String)> {
-    let content = fs::read_to_string(path).map_err(|e| {
-        MeasurementError::InvalidDirectory(format!(
-            "Failed to read config {:?}: {}",
-            path, e
-        ))
-    })?;
-    let cfg: Config = toml::from_str(&content).map_err(|e| {
-        MeasurementError::Config(format!(
-            "Failed to parse config {:?}: {}",
-            path, e
-        ))
-    })?;
-    let hash = hex::encode(Sha256::digest(content.as_bytes()));
-    Ok((cfg, hash))
+fn load_config_sources(path: &Path) -> Result<LoadedConfig> {
+    Config::load_with_sources(path)
+        .map_err(|e| MeasurementError::Config(format!("Failed to load config {:?}: {:#}", path, e)))
+}
+
+/// Returns the directory to watch for files matching `pattern`: its longest
+/// leading part free of glob metacharacters, and whether matches may lie
+/// deeper than that directory's immediate children.
+pub(crate) fn glob_watch_dir(pattern: &str) -> (PathBuf, RecursiveMode) {
+    let path = Path::new(pattern);
+    let mut dir = PathBuf::new();
+    let mut rest = 0;
+    for component in path.components() {
+        if rest > 0
+            || component
+                .as_os_str()
+                .to_string_lossy()
+                .contains(['*', '?', '['])
+        {
+            rest += 1;
+        } else {
+            dir.push(component);
+        }
+    }
+    if rest == 0 {
+        // A plain file name; watch the directory holding it.
+        dir.pop();
+        return (dir, RecursiveMode::NonRecursive);
+    }
+    let mode = if rest > 1 {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    (dir, mode)
+}
+
+/// The files a reload depends on besides the main config file.
+#[derive(Default)]
+struct ConfigSources {
+    fragments: HashSet<PathBuf>,
+    patterns: Vec<glob::Pattern>,
+}
+
+impl ConfigSources {
+    /// Records the sources of `loaded` and asks the watcher thread to watch
+    /// the directories of any include pattern not seen before.
+    fn update(
+        &mut self,
+        loaded: &LoadedConfig,
+        watched: &mut HashSet<PathBuf>,
+        dir_tx: &std::sync::mpsc::Sender<(PathBuf, RecursiveMode)>,
+    ) {
+        self.fragments = loaded.sources.iter().cloned().collect();
+        self.patterns = loaded
+            .include_patterns
+            .iter()
+            .filter_map(|p| glob::Pattern::new(p).ok())
+            .collect();
+        for pattern in &loaded.include_patterns {
+            let (dir, mode) = glob_watch_dir(pattern);
+            if watched.insert(dir.clone()) {
+                let _ = dir_tx.send((dir, mode));
+            }
+        }
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.fragments.contains(path) || self.patterns.iter().any(|p| p.matches_path(path))
+    }
+}
+
+/// Aborts a background task when dropped.
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
 }
 
 #[async_trait]
@@ -172,9 +573,10 @@ impl ConfigWatcher for ConfigFileWatcher {
         &self,
         config_path: PathBuf,
         shared_config: Arc<RwLock<Config>>,
-        aa_client: Arc<AAClient>,
+        sink: Arc<dyn MeasurementSink>,
     ) -> Result<()> {
         let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let (dir_tx, dir_rx) = std::sync::mpsc::channel::<(PathBuf, RecursiveMode)>();
 
         let parent_dir = config_path
             .parent()
@@ -187,11 +589,47 @@ impl ConfigWatcher for ConfigFileWatcher {
             )));
         };
 
+        let mut watched_dirs = HashSet::from([parent_dir.clone()]);
+        let mut sources = ConfigSources::default();
+        match load_config_sources(&config_path) {
+            Ok(loaded) => {
+                sources.update(&loaded, &mut watched_dirs, &dir_tx);
+                // The first watch starts from the config the daemon loaded,
+                // so touching the file without changing it is no reload.
+                let mut applied = self.applied.lock().unwrap();
+                match &applied.digest {
+                    None => applied.digest = Some(loaded.digest),
+                    Some(digest) if *digest != loaded.digest => {
+                        let _ = tx.send(Trigger::Restarted);
+                    }
+                    Some(_) => {}
+                }
+            }
+            Err(e) => warn!("Cannot resolve config fragments to watch: {}", e),
+        }
+
+        let reload_requests = self.reload_requests.clone();
+        let hangup_tx = tx.clone();
+        // Stopped with the watch, so that a restarted watcher gets every
+        // SIGHUP.
+        let _hangup_forwarder = AbortOnDrop(tokio::spawn(async move {
+            loop {
+                reload_requests.notified().await;
+                if hangup_tx.send(Trigger::Hangup).is_err() {
+                    break;
+                }
+            }
+        }));
+
+        let watch_config = shared_config.read().await.clone();
+        let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
         tokio::task::spawn_blocking(move || {
             let tx_clone = tx.clone();
-            let watcher_result = recommended_watcher(move |res: notify::Result<notify::Event>| {
+            // A polled config is compared by content: it is small, and an
+            // edit may keep its size and, on coarse clocks, its mtime.
+            let watcher_result = PathWatcher::new(&watch_config, true, move |res| {
                 if let Ok(event) = res {
-                    let _ = tx_clone.send(event);
+                    let _ = tx_clone.send(Trigger::Changed(event));
                 }
             })
             .and_then(|mut watcher| {
@@ -199,43 +637,109 @@ impl ConfigWatcher for ConfigFileWatcher {
                 Ok(watcher)
             });
 
-            if watcher_result.is_err() {
-                return;
-            }
-
-            loop {
-                std::thread::sleep(Duration::from_secs(3600));
+            let mut watcher = match watcher_result {
+                Ok(watcher) => {
+                    let _ = ready_tx.send(Ok(()));
+                    watcher
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+
+            // Directories of include patterns arrive as the config is
+            // (re)loaded, until the watch ends and drops the sender; the
+            // notify watcher lives as long.
+            for (dir, mode) in dir_rx {
+                if let Err(e) = watcher.watch(&dir, mode) {
+                    warn!("Failed to watch config fragment directory {:?}: {}", dir, e);
+                }
             }
         });
+        ready_rx
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?
+            .map_err(|e| {
+                MeasurementError::Other(anyhow::anyhow!("Cannot watch {:?}: {}", config_path, e))
+            })?;
+
+        // Config as it was before the first change deferred by a blackout.
+        let mut deferred: Option<Config> = None;
 
-        let mut last_config_hash: Option<String> = None;
+        let mut queue = JobQueue::default();
 
         loop {
-            if let Some(event) = rx.recv().await {
-                if !is_relevant_event(&event.kind) {
-                    continue;
+            // Deferred changes join the queue once their blackout window closes.
+            if let Some(old_config) = deferred.take() {
+                let current = shared_config.read().await.clone();
+                if blackout_remaining(&current).is_none() {
+                    info!("Blackout window ended; queueing deferred config changes.");
+                    self.enqueue_changes(&mut queue, old_config, current);
+                } else {
+                    deferred = Some(old_config);
                 }
-                if !event
-                    .paths
-                    .iter()
-                    .any(|p| p.file_name() == Some(&config_file_name))
-                {
-                    continue;
+            }
+
+            // Pending events are taken in before each job, so urgent work
+            // triggered while a long job ran goes ahead of queued bulk work.
+            let event = if !queue.is_empty() {
+                match rx.try_recv() {
+                    Ok(event) => Some(event),
+                    Err(_) => {
+                        self.run_next_job(&mut queue, sink.clone()).await;
+                        continue;
+                    }
                 }
+            } else if deferred.is_some() {
+                let wait = {
+                    let guard = shared_config.read().await;
+                    blackout_remaining(&guard).unwrap_or_default()
+                };
+                tokio::select! {
+                    event = rx.recv() => event,
+                    _ = sleep(wait) => continue,
+                }
+            } else {
+                rx.recv().await
+            };
+            if let Some(trigger) = event {
+                let forced = match trigger {
+                    Trigger::Hangup => {
+                        info!("SIGHUP received; reloading config and re-measuring.");
+                        true
+                    }
+                    Trigger::Restarted => {
+                        info!("Config changed while the watcher was stopped; reloading.");
+                        false
+                    }
+                    Trigger::Changed(event) => {
+                        // A removed fragment changes the merged config too.
+                        if !is_relevant_event(&event.kind)
+                            && !matches!(event.kind, EventKind::Remove(_))
+                        {
+                            continue;
+                        }
+                        if !event.paths.iter().any(|p| {
+                            p.file_name() == Some(&config_file_name) || sources.contains(p)
+                        }) {
+                            continue;
+                        }
 
-                // Debounce rapid writes
-                sleep(Duration::from_millis(150)).await;
+                        // Debounce rapid writes
+                        sleep(Duration::from_millis(150)).await;
+                        false
+                    }
+                };
 
                 let old_config = { shared_config.read().await.clone() };
 
-                let mut new_config: Option<Config> = None;
-                let mut new_hash: Option<String> = None;
+                let mut loaded: Option<LoadedConfig> = None;
 
                 for attempt in 1..=MAX_RELOAD_RETRIES {
-                    match load_config_with_hash(&config_path) {
-                        Ok((cfg, hash)) => {
-                            new_config = Some(cfg);
-                            new_hash = Some(hash);
+                    match load_config_sources(&config_path) {
+                        Ok(cfg) => {
+                            loaded = Some(cfg);
                             break;
                         }
                         Err(e) => {
@@ -250,7 +754,7 @@ impl ConfigWatcher for ConfigFileWatcher {
                     }
                 }
 
-                let new_config = match new_config {
+                let loaded = match loaded {
                     Some(cfg) => cfg,
                     None => {
                         warn!("Giving up config reload after {} attempts.", MAX_RELOAD_RETRIES);
@@ -258,10 +762,19 @@ impl ConfigWatcher for ConfigFileWatcher {
                     }
                 };
 
-                let new_hash = new_hash.unwrap_or_default();
+                // Includes may have changed; follow the fragments of this load.
+                sources.update(&loaded, &mut watched_dirs, &dir_tx);
+                let LoadedConfig {
+                    config: new_config,
+                    digest: new_hash,
+                    ..
+                } = loaded;
 
-                if last_config_hash.as_ref() == Some(&new_hash) {
+                if self.applied.lock().unwrap().digest.as_ref() == Some(&new_hash) {
                     debug!("Config content unchanged; skipping handlers.");
+                    if forced {
+                        queue.push(Priority::Bulk, ChangeJob::Remeasure(Arc::new(new_config)));
+                    }
                     continue;
                 }
 
@@ -269,28 +782,62 @@ impl ConfigWatcher for ConfigFileWatcher {
                     let mut guard = shared_config.write().await;
                     *guard = new_config.clone();
                 }
-                last_config_hash = Some(new_hash);
-
-                for handler in &self.handlers {
-                    if handler.is_enabled(&new_config) {
-                        if let Err(e) = handler
-                            .handle_change(&old_config, &new_config, aa_client.clone())
-                            .await
-                        {
-                            warn!(
-                                "Handler {} failed during config change: {}",
-                                handler.name(),
-                                e
-                            );
-                        }
+                crate::logging::set_format(new_config.log_format);
+                let reload_count = {
+                    let mut applied = self.applied.lock().unwrap();
+                    applied.reloads += 1;
+                    applied.reloads
+                };
+                // Recorded before any handler runs, so the policy change
+                // precedes the measurements it causes.
+                let operation = format!("reload:{}", reload_count);
+                if let Err(e) = sink
+                    .extend(
+                        POLICY_DOMAIN,
+                        &operation,
+                        &format!("sha256:{}", new_hash),
+                        new_config.policy_pcr_index.map(|v| v as u64),
+                    )
+                    .await
+                {
+                    warn!("Failed to measure config reload {}: {}", reload_count, e);
+                }
+                self.applied.lock().unwrap().digest = Some(new_hash);
+
+                // An explicit SIGHUP is not held back by blackout windows.
+                if let Some(wait) = blackout_remaining(&new_config).filter(|_| !forced) {
+                    if new_config.maintenance.blackout_action == BlackoutAction::Skip {
+                        info!("In a blackout window; skipping re-measurement for this change.");
+                        deferred = None;
+                    } else {
+                        info!(
+                            "In a blackout window for another {}s; deferring re-measurement.",
+                            wait.as_secs()
+                        );
+                        deferred.get_or_insert(old_config);
                     }
+                    continue;
+                }
+
+                // Changes deferred earlier are handled together with this one.
+                let old_config = deferred.take().unwrap_or(old_config);
+                if forced {
+                    let config = Arc::new(new_config.clone());
+                    queue.push(Priority::Bulk, ChangeJob::Remeasure(config));
                 }
+                self.enqueue_changes(&mut queue, old_config, new_config);
             }
         }
     }
 }
 
-fn is_relevant_event(kind: &EventKind) -> bool {
+/// The error of a watcher whose notify backend stopped delivering events,
+/// so that it is restarted.
+pub(crate) fn notify_stopped() -> MeasurementError {
+    MeasurementError::Other(anyhow::anyhow!("File change notifications stopped"))
+}
+
+pub(crate) fn is_relevant_event(kind: &EventKind) -> bool {
     matches!(
         kind,
         EventKind::Modify(_) | EventKind::Create(_) | EventKind::Any
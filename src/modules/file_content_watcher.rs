@@ -0,0 +1,151 @@
+// src/modules/file_content_watcher.rs
+use crate::config::{Config, FileMeasurementConfig, MeasurementEntry};
+use crate::container::ContainerPath;
+use crate::error::{MeasurementError, Result};
+use crate::fs_watch::PathWatcher;
+use crate::modules::file_config_watcher::{glob_watch_dir, is_relevant_event, notify_stopped};
+use crate::modules::{watcher::ConfigWatcher, FileMeasurer};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::Pattern;
+use log::{debug, info, warn};
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+
+/// Writes to a file within this interval are measured once.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+/// How often patterns added by a config reload are picked up.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Re-extends files matched by `file_measurement.files` whenever they are
+/// modified or replaced, so that tampering after the initial run shows up in
+/// the event log. Runs while `file_measurement.watch_content` is set.
+#[derive(Default)]
+pub struct FileContentWatcher {
+    measurer: FileMeasurer,
+}
+
+impl FileContentWatcher {
+    pub fn new() -> Self {
+        Self {
+            measurer: FileMeasurer::new(),
+        }
+    }
+}
+
+/// The host entries to watch. Container entries are measured at startup
+/// and on config changes only.
+fn host_entries(fm_config: &FileMeasurementConfig) -> Vec<MeasurementEntry> {
+    if !fm_config.enable || !fm_config.watch_content {
+        return Vec::new();
+    }
+    fm_config
+        .files
+        .iter()
+        .filter(|entry| !ContainerPath::is_container_entry(entry.path()))
+        .cloned()
+        .collect()
+}
+
+#[async_trait]
+impl ConfigWatcher for FileContentWatcher {
+    fn name(&self) -> &str {
+        "FileContentWatcher"
+    }
+
+    fn is_enabled(&self, _config: Arc<Config>) -> bool {
+        // `watch_content` is checked on every pass, so that it can be
+        // switched on by a config reload.
+        true
+    }
+
+    async fn watch(
+        &self,
+        _config_path: PathBuf,
+        shared_config: Arc<RwLock<Config>>,
+        sink: Arc<dyn MeasurementSink>,
+    ) -> Result<()> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = {
+            let config = shared_config.read().await;
+            PathWatcher::new(&config, config.watch_poll_compare_contents, move |res| {
+                if let Ok(event) = res {
+                    let _ = tx.send(event);
+                }
+            })
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?
+        };
+        let mut watched_dirs = HashSet::new();
+
+        loop {
+            let (fm_config, max_concurrency) = {
+                let config = shared_config.read().await;
+                (config.file_measurement.clone(), config.max_concurrency)
+            };
+            let host_entries = host_entries(&fm_config);
+            for entry in &host_entries {
+                let (dir, mode) = glob_watch_dir(entry.path());
+                if dir.is_dir() && !watched_dirs.contains(&dir) {
+                    match watcher.watch(&dir, mode) {
+                        Ok(()) => {
+                            debug!("Watching {:?} for changes to measured files", dir);
+                            watched_dirs.insert(dir);
+                        }
+                        Err(e) => warn!("Failed to watch {:?}: {}", dir, e),
+                    }
+                }
+            }
+
+            let event = tokio::select! {
+                event = rx.recv() => event,
+                _ = sleep(REFRESH_INTERVAL) => continue,
+            };
+            let Some(event) = event else {
+                return Err(notify_stopped());
+            };
+            if !is_relevant_event(&event.kind) {
+                continue;
+            }
+            let mut changed: BTreeSet<PathBuf> = event.paths.into_iter().collect();
+            sleep(DEBOUNCE).await;
+            while let Ok(event) = rx.try_recv() {
+                if is_relevant_event(&event.kind) {
+                    changed.extend(event.paths);
+                }
+            }
+
+            // Each changed file is re-measured with the settings of the first
+            // entry matching it.
+            let patterns: Vec<(Pattern, &MeasurementEntry)> = host_entries
+                .iter()
+                .filter_map(|entry| Pattern::new(entry.path()).ok().map(|p| (p, entry)))
+                .collect();
+            let entries: Vec<MeasurementEntry> = changed
+                .iter()
+                .filter(|path| path.is_file())
+                .filter_map(|path| {
+                    let (_, entry) = patterns.iter().find(|(p, _)| p.matches_path(path))?;
+                    Some(entry.with_path(Pattern::escape(&path.to_string_lossy())))
+                })
+                .collect();
+            if entries.is_empty() {
+                continue;
+            }
+            info!(
+                "{} measured files changed; re-extending their measurements.",
+                entries.len()
+            );
+            if let Err(e) = self
+                .measurer
+                .measure_patterns(&entries, &fm_config, max_concurrency, sink.clone())
+                .await
+            {
+                warn!("Failed to re-measure changed files: {}", e);
+            }
+        }
+    }
+}
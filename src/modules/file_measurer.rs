@@ -1,109 +1,490 @@
 // src/modules/file_measurer.rs
-use crate::config::{Config, FileMeasurementConfig};
+use crate::archive::{self, ArchiveKind};
+use crate::canonical_json;
+use crate::config::{Config, FileMeasurementConfig, MeasurementEntry, SymlinkPolicy};
+use crate::container::{self, ContainerPath};
+use crate::digest;
 use crate::error::{MeasurementError, Result};
-use crate::modules::measurable::Measurable;
-use crate::rpc_client::AAClient;
+use crate::hash_cache::{self, FileStamp};
+use crate::ima::IntegrityXattrs;
+use crate::modules::measurable::{run_ordered, EntryFailures, Measurable};
+use crate::sink::{Extension, MeasurementSink};
 use async_trait::async_trait;
-use glob::glob;
+use glob::{glob, Pattern};
 use log::{debug, info, warn};
-use sha2::{Digest, Sha256, Sha384};
+use serde::Serialize;
 use std::collections::HashSet;
+use std::ffi::OsString;
 use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
+use tokio::io::AsyncReadExt;
 
+#[derive(Default)]
 pub struct FileMeasurer;
 
+/// A file matched by an entry, with the settings it is measured with.
+struct MatchedFile {
+    path: String,
+    /// Where the content is read from once symbolic links are resolved.
+    read_path: String,
+    operation: String,
+    critical: bool,
+    pcr_index: u32,
+    hash_algorithm: String,
+}
+
 const DOMAIN: &str = "file";
+const ARCHIVE_MEMBER_DOMAIN: &str = "archive_member";
+
+/// Links followed while resolving a path before giving up, as in Linux.
+const MAX_SYMLINKS: usize = 40;
+/// Pseudo-filesystems skipped with `skip_special_files`.
+const SPECIAL_ROOTS: [&str; 3] = ["/proc", "/sys", "/dev"];
 
 impl FileMeasurer {
     pub fn new() -> Self {
         Self
     }
 
+    /// Measures every file matched by `entries`. A critical pattern fails if
+    /// it matches no file or any matched file cannot be measured.
+    ///
+    /// `container://<name-or-id>/<path>` entries are matched inside the
+    /// container's mount namespace and recorded under that form.
+    ///
+    /// Up to `max_concurrency` files are hashed at once; they are extended
+    /// in the order the patterns matched them.
+    ///
+    /// An entry's `pcr_index` and `hash_algorithm` override those of
+    /// `fm_config` for the files it matches.
+    ///
+    /// Matches are checked against the `symlinks`, `allowed_roots` and
+    /// `skip_special_files` policy first. A rejected match is skipped, and
+    /// fails the entry if it is critical. Matches of an `exclude` pattern
+    /// and files larger than `max_file_size` are skipped with a log line and
+    /// do not count as matches.
     pub async fn measure_patterns(
         &self,
-        patterns: &[String],
+        entries: &[MeasurementEntry],
         fm_config: &FileMeasurementConfig,
-        aa_client: Arc<AAClient>,
+        max_concurrency: usize,
+        sink: Arc<dyn MeasurementSink>,
     ) -> Result<()> {
         let mut measured_files = HashSet::new();
-        for pattern in patterns {
-            match glob(pattern) {
-                Ok(entries) => {
-                    for entry in entries {
-                        if let Ok(path) = entry {
-                            if path.is_file() {
-                                let path_str = path.to_string_lossy().to_string();
-                                if measured_files.insert(path_str.clone()) {
-                                    self.measure_single_file(
-                                        &path_str,
-                                        fm_config,
-                                        aa_client.clone(),
-                                    )
-                                    .await?;
+        let mut files = Vec::new();
+        let mut failures = EntryFailures::default();
+        let excludes = exclude_patterns(&fm_config.exclude)?;
+        for entry in entries {
+            let pattern = entry.path();
+            let critical = entry.is_critical();
+            let pcr_index = entry.pcr_index().unwrap_or(fm_config.pcr_index);
+            let hash_algorithm = entry.hash_algorithm().unwrap_or(&fm_config.hash_algorithm);
+            debug!("Processing pattern: {}", pattern);
+
+            let (host_pattern, container) = if ContainerPath::is_container_entry(pattern) {
+                match resolve_container_entry(pattern, fm_config).await {
+                    Ok((host_pattern, target)) => (host_pattern, Some(target)),
+                    Err(e) if critical => {
+                        failures.record(pattern, true, e);
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("Cannot resolve container entry '{}': {}", pattern, e);
+                        continue;
+                    }
+                }
+            } else {
+                (pattern.to_string(), None)
+            };
+
+            let paths = match glob(&host_pattern) {
+                Ok(paths) => paths,
+                Err(e) if critical => {
+                    failures.record(pattern, true, e.into());
+                    continue;
+                }
+                Err(e) => {
+                    warn!("Invalid glob pattern '{}': {}", pattern, e);
+                    continue;
+                }
+            };
+            let mut matched = false;
+            for path in paths {
+                match path {
+                    Ok(path) => {
+                        let path_str = path.to_string_lossy().to_string();
+                        let operation = match &container {
+                            Some((target, root)) => target.operation(root, &path),
+                            None => path_str.clone(),
+                        };
+                        if let Some(pattern) = excludes
+                            .iter()
+                            .find(|p| p.matches(&path_str) || p.matches(&operation))
+                        {
+                            info!("Skipping {}: excluded by '{}'", operation, pattern);
+                            continue;
+                        }
+                        let root = container.as_ref().map(|(_, root)| root.as_path());
+                        let read_path = match admit(&path, root, fm_config) {
+                            Ok(Some(read_path)) => read_path,
+                            Ok(None) => continue,
+                            Err(e) if critical => {
+                                matched = true;
+                                failures.record(&operation, true, e);
+                                continue;
+                            }
+                            Err(e) => {
+                                warn!("Skipping {}: {}", operation, e);
+                                continue;
+                            }
+                        };
+                        if let Some(limit) = fm_config.max_file_size {
+                            match fs::metadata(&read_path) {
+                                Ok(metadata) if metadata.len() > limit => {
+                                    info!(
+                                        "Skipping {}: {} bytes exceeds max_file_size of {} bytes",
+                                        operation,
+                                        metadata.len(),
+                                        limit
+                                    );
+                                    continue;
                                 }
+                                _ => {}
                             }
                         }
+                        matched = true;
+                        if !measured_files.insert(operation.clone()) {
+                            debug!("Skipping already measured file: {}", operation);
+                            continue;
+                        }
+                        files.push(MatchedFile {
+                            path: path_str,
+                            read_path,
+                            operation,
+                            critical,
+                            pcr_index,
+                            hash_algorithm: hash_algorithm.to_string(),
+                        });
                     }
+                    Err(e) if critical => failures.record(pattern, true, e.into_error().into()),
+                    Err(e) => warn!(
+                        "Error while accessing path matched by pattern '{}': {}",
+                        pattern, e
+                    ),
                 }
+            }
+            if critical && !matched {
+                let e = std::io::Error::new(std::io::ErrorKind::NotFound, "no file matches");
+                failures.record(pattern, true, e.into());
+            }
+        }
+
+        let jobs = files
+            .iter()
+            .map(|file| {
+                measured_content(
+                    file.read_path.clone(),
+                    file.hash_algorithm.clone(),
+                    fm_config.clone(),
+                )
+            })
+            .collect();
+        let contents = run_ordered(max_concurrency, jobs).await;
+        // Everything is extended as one batch, with the members of an
+        // archive right after it; `owners` maps each extension to its file.
+        let mut extensions = Vec::new();
+        let mut owners = Vec::new();
+        for (index, (file, content)) in files.iter().zip(contents).enumerate() {
+            let content = match content {
+                Ok(content) => content,
                 Err(e) => {
-                    warn!("Invalid glob pattern '{}': {}", pattern, e);
+                    failures.record(&file.operation, file.critical, e);
+                    continue;
+                }
+            };
+            debug!(
+                "Extending measurement for file: {}, PCR: {}, Domain: {}, Operation: {}, Content: {}",
+                file.path, file.pcr_index, DOMAIN, file.operation, content
+            );
+            extensions.push(Extension {
+                domain: DOMAIN.to_string(),
+                operation: file.operation.clone(),
+                content,
+                pcr_index: Some(file.pcr_index as u64),
+            });
+            owners.push(index);
+            if fm_config.archive_members {
+                if let Some(kind) = ArchiveKind::from_path(Path::new(&file.path)) {
+                    match archive_member_extensions(file, kind) {
+                        Ok(members) => {
+                            owners.extend(std::iter::repeat(index).take(members.len()));
+                            extensions.extend(members);
+                        }
+                        Err(e) => failures.record(&file.operation, file.critical, e),
+                    }
                 }
             }
         }
-        Ok(())
+        let results = sink.extend_batch(&extensions).await;
+        let mut failed = HashSet::new();
+        for (index, result) in owners.into_iter().zip(results) {
+            if let Err(e) = result {
+                // One failure per file, however many of its members failed.
+                if failed.insert(index) {
+                    failures.record(&files[index].operation, files[index].critical, e);
+                }
+            }
+        }
+        if fm_config.hash_cache {
+            hash_cache::save();
+        }
+        debug!("Measured {} unique files.", measured_files.len());
+        failures.into_result()
     }
+}
 
-    async fn measure_single_file(
-        &self,
-        file_path: &str,
-        fm_config: &FileMeasurementConfig,
-        aa_client: Arc<AAClient>,
-    ) -> Result<()> {
-        debug!("Measuring file: {}", file_path);
-        match fs::read(file_path) {
-            Ok(content) => {
-                let file_hash_hex = match fm_config.hash_algorithm.to_lowercase().as_str() {
-                    "sha256" => {
-                        let mut hasher = Sha256::new();
-                        hasher.update(&content);
-                        hex::encode(hasher.finalize())
-                    }
-                    "sha384" => {
-                        let mut hasher = Sha384::new();
-                        hasher.update(&content);
-                        hex::encode(hasher.finalize())
-                    }
-                    other => {
-                        return Err(MeasurementError::UnsupportedHashAlgorithm(
-                            other.to_string(),
-                        ));
-                    }
-                };
+/// One `archive_member` extension per regular file in the archive, with
+/// `<archive operation>:<member path>` as the operation.
+fn archive_member_extensions(file: &MatchedFile, kind: ArchiveKind) -> Result<Vec<Extension>> {
+    let members = archive::member_digests(Path::new(&file.read_path), kind, &file.hash_algorithm)?;
+    debug!("Archive {} has {} members", file.path, members.len());
+    Ok(members
+        .into_iter()
+        .map(|(member, digest)| Extension {
+            domain: ARCHIVE_MEMBER_DOMAIN.to_string(),
+            operation: format!("{}:{}", file.operation, member),
+            content: digest,
+            pcr_index: Some(file.pcr_index as u64),
+        })
+        .collect())
+}
 
-                debug!(
-                    "Extending measurement for file: {}, PCR: {}, Domain: {}, Operation: {}, Content: {}",
-                    file_path, fm_config.pcr_index, DOMAIN, file_path, file_hash_hex
-                );
-
-                aa_client
-                    .extend_runtime_measurement(
-                        Some(fm_config.pcr_index as u64),
-                        DOMAIN,
-                        file_path,
-                        &file_hash_hex,
-                    )
-                    .await?;
-                Ok(())
-            }
-            Err(e) => {
-                warn!("Failed to read file for measurement '{}': {}", file_path, e);
-                // Decide if this should be a hard error or just a warning
-                // For now, just warn and continue with other files.
-                Ok(())
-            }
+/// Resolves a `container://` entry to a host glob pattern under the
+/// container's `/proc/<pid>/root`, together with what is needed to turn
+/// matches back into `container://` operations.
+async fn resolve_container_entry<'a>(
+    pattern: &'a str,
+    fm_config: &FileMeasurementConfig,
+) -> Result<(String, (ContainerPath<'a>, PathBuf))> {
+    let target = ContainerPath::parse(pattern)?;
+    let root =
+        container::root_dir(target.container, Path::new(&fm_config.docker_socket_path)).await?;
+    let host_pattern = format!("{}{}", root.to_string_lossy(), target.path);
+    Ok((host_pattern, (target, root)))
+}
+
+/// Where a matched path leads once symbolic links are resolved.
+pub(crate) struct ResolvedPath {
+    /// The path to open, as seen from the host.
+    pub(crate) host: PathBuf,
+    /// The same path as seen from inside the root it was resolved in.
+    pub(crate) visible: PathBuf,
+    /// Whether a symbolic link was followed on the way.
+    pub(crate) through_symlink: bool,
+}
+
+/// Resolves `path` the way a process whose root directory is `root` would:
+/// absolute link targets and `..` never lead out of `root`. Through
+/// `/proc/<pid>/root`, the kernel would resolve absolute links against the
+/// host root instead.
+pub(crate) fn resolve_in_root(root: &Path, path: &Path) -> io::Result<ResolvedPath> {
+    let mut pending: Vec<OsString> = Vec::new();
+    push_components(&mut pending, path);
+    let mut visible = PathBuf::from("/");
+    let mut links = 0;
+    while let Some(component) = pending.pop() {
+        if component == ".." {
+            visible.pop();
+            continue;
+        }
+        let candidate = visible.join(&component);
+        let host = root.join(candidate.strip_prefix("/").unwrap_or(&candidate));
+        if !fs::symlink_metadata(&host)?.file_type().is_symlink() {
+            visible = candidate;
+            continue;
+        }
+        links += 1;
+        if links > MAX_SYMLINKS {
+            return Err(io::Error::other("too many levels of symbolic links"));
+        }
+        let target = fs::read_link(&host)?;
+        if target.is_absolute() {
+            visible = PathBuf::from("/");
+        }
+        push_components(&mut pending, &target);
+    }
+    Ok(ResolvedPath {
+        host: root.join(visible.strip_prefix("/").unwrap_or(&visible)),
+        visible,
+        through_symlink: links > 0,
+    })
+}
+
+/// Pushes the components of `path` onto `pending` so that they are popped
+/// in order.
+fn push_components(pending: &mut Vec<OsString>, path: &Path) {
+    let components: Vec<OsString> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_os_string()),
+            Component::ParentDir => Some(OsString::from("..")),
+            _ => None,
+        })
+        .collect();
+    pending.extend(components.into_iter().rev());
+}
+
+fn exclude_patterns(globs: &[String]) -> Result<Vec<Pattern>> {
+    globs
+        .iter()
+        .map(|glob| {
+            Pattern::new(glob).map_err(|e| {
+                MeasurementError::Config(format!("Invalid exclude pattern '{}': {}", glob, e))
+            })
+        })
+        .collect()
+}
+
+/// Applies the `symlinks`, `allowed_roots` and `skip_special_files` policy
+/// to a matched path and returns the path to read. Matches that are not
+/// regular files, dangling links included, give `None` and are ignored.
+///
+/// Container matches are resolved inside the container root, and the
+/// policy applies to their paths inside the container.
+fn admit(
+    path: &Path,
+    root: Option<&Path>,
+    fm_config: &FileMeasurementConfig,
+) -> Result<Option<String>> {
+    let (root, inner) = match root {
+        Some(root) => (root, path.strip_prefix(root).unwrap_or(path).to_path_buf()),
+        None => (Path::new("/"), std::env::current_dir()?.join(path)),
+    };
+    let resolved = match resolve_in_root(root, &inner) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            debug!("Cannot resolve {:?}: {}", path, e);
+            return Ok(None);
+        }
+    };
+    if !fs::metadata(&resolved.host)
+        .map(|m| m.is_file())
+        .unwrap_or(false)
+    {
+        return Ok(None);
+    }
+
+    if resolved.through_symlink && fm_config.symlinks == SymlinkPolicy::Deny {
+        return Err(MeasurementError::PathPolicy(format!(
+            "{:?} is reached through a symbolic link",
+            path
+        )));
+    }
+    if fm_config.skip_special_files
+        && SPECIAL_ROOTS
+            .iter()
+            .any(|special| resolved.visible.starts_with(special))
+    {
+        return Err(MeasurementError::PathPolicy(format!(
+            "{:?} resolves to {:?} on a pseudo-filesystem",
+            path, resolved.visible
+        )));
+    }
+    if !fm_config.allowed_roots.is_empty()
+        && !fm_config
+            .allowed_roots
+            .iter()
+            .any(|allowed| resolved.visible.starts_with(allowed))
+    {
+        return Err(MeasurementError::PathPolicy(format!(
+            "{:?} resolves to {:?}, outside allowed_roots",
+            path, resolved.visible
+        )));
+    }
+    Ok(Some(resolved.host.to_string_lossy().to_string()))
+}
+
+/// Returns the content to extend for `file_path`. A file that cannot be
+/// read is a failure of its entry, critical or not.
+async fn measured_content(
+    file_path: String,
+    hash_algorithm: String,
+    fm_config: FileMeasurementConfig,
+) -> Result<String> {
+    debug!("Measuring file: {}", file_path);
+    let file_digest = if fm_config.hash_cache {
+        cached_digest(&file_path, &hash_algorithm, &fm_config).await?
+    } else {
+        stream_digest(&file_path, &hash_algorithm, fm_config.read_buffer_bytes).await?
+    };
+
+    if fm_config.ima_signatures {
+        signed_file_content(&file_path, file_digest)
+    } else {
+        Ok(file_digest)
+    }
+}
+
+/// Returns the cached digest of `file_path` if its size, inode and
+/// timestamps are unchanged, or hashes it. A file that changes while it is
+/// hashed is not cached.
+async fn cached_digest(
+    file_path: &str,
+    hash_algorithm: &str,
+    fm_config: &FileMeasurementConfig,
+) -> Result<String> {
+    let persist_path = fm_config.hash_cache_path.as_deref();
+    let stamp = FileStamp::of(Path::new(file_path))?;
+    if let Some(digest) = hash_cache::lookup(file_path, hash_algorithm, &stamp, persist_path) {
+        return Ok(digest);
+    }
+    let digest = stream_digest(file_path, hash_algorithm, fm_config.read_buffer_bytes).await?;
+    if FileStamp::of(Path::new(file_path))? == stamp {
+        hash_cache::store(file_path, hash_algorithm, stamp, &digest, persist_path);
+    }
+    Ok(digest)
+}
+
+/// Hashes `file_path` in chunks of `buffer_size` bytes, so that multi-GB
+/// files are never held in memory, and returns `<algorithm>:<hex>`.
+async fn stream_digest(
+    file_path: &str,
+    hash_algorithm: &str,
+    buffer_size: usize,
+) -> Result<String> {
+    let mut hasher = digest::Hasher::new(hash_algorithm)?;
+    let mut file = tokio::fs::File::open(file_path).await?;
+    let mut buf = vec![0u8; buffer_size.max(1)];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            break;
         }
+        hasher.update(&buf[..n]);
     }
+    Ok(hasher.finalize_prefixed())
+}
+
+/// Content extended for a file when `ima_signatures` is enabled.
+#[derive(Serialize)]
+struct SignedFileContent {
+    digest: String,
+    #[serde(flatten)]
+    xattrs: IntegrityXattrs,
+}
+
+/// Returns the canonical JSON of the file digest together with what its
+/// `security.ima` and `security.evm` attributes hold.
+fn signed_file_content(file_path: &str, digest: String) -> Result<String> {
+    let content = SignedFileContent {
+        digest,
+        xattrs: IntegrityXattrs::read(Path::new(file_path))?,
+    };
+    let encoded = canonical_json::to_vec(&content)
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    Ok(String::from_utf8_lossy(&encoded).into_owned())
 }
 
 #[async_trait]
@@ -112,11 +493,27 @@ impl Measurable for FileMeasurer {
         "FileMeasurer"
     }
 
+    fn description(&self) -> &str {
+        "Hashes files matched by glob patterns"
+    }
+
+    fn config_section(&self) -> &str {
+        "file_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        Some(config.file_measurement.pcr_index)
+    }
+
     fn is_enabled(&self, config: Arc<Config>) -> bool {
         config.file_measurement.enable
     }
 
-    async fn measure(&self, config: Arc<Config>, aa_client: Arc<AAClient>) -> Result<()> {
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
         let fm_config = &config.file_measurement;
         if !fm_config.enable {
             debug!("File measurement is disabled. Skipping.");
@@ -128,49 +525,198 @@ impl Measurable for FileMeasurer {
             fm_config.pcr_index, DOMAIN, fm_config.hash_algorithm
         );
 
-        let mut measured_files = HashSet::new();
+        let result = self
+            .measure_patterns(&fm_config.files, fm_config, config.max_concurrency, sink)
+            .await;
+        info!("File measurement completed.");
+        result
+    }
+}
 
-        for pattern in &fm_config.files {
-            debug!("Processing pattern: {}", pattern);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
 
-            match glob(pattern) {
-                Ok(entries) => {
-                    for entry in entries {
-                        match entry {
-                            Ok(path) => {
-                                if path.is_file() {
-                                    let path_str = path.to_string_lossy().to_string();
-                                    if measured_files.insert(path_str.clone()) {
-                                        self.measure_single_file(
-                                            &path_str,
-                                            fm_config,
-                                            aa_client.clone(),
-                                        )
-                                        .await?;
-                                    } else {
-                                        debug!("Skipping already measured file: {}", path_str);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                warn!(
-                                    "Error while accessing path matched by pattern '{}': {}",
-                                    pattern, e
-                                );
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!("Invalid glob pattern '{}': {}", pattern, e);
-                }
-            }
+    fn config(toml: &str) -> FileMeasurementConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    /// A container root holding `/etc/app.conf`.
+    fn container_root() -> TempDir {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir(root.path().join("etc")).unwrap();
+        fs::write(root.path().join("etc/app.conf"), "x").unwrap();
+        root
+    }
+
+    fn host(root: &Path, path: &str) -> Option<String> {
+        Some(root.join(path).to_string_lossy().to_string())
+    }
+
+    fn is_policy_error(result: Result<Option<String>>) -> bool {
+        matches!(result, Err(MeasurementError::PathPolicy(_)))
+    }
+
+    #[test]
+    fn follow_reads_the_link_target() {
+        let root = container_root();
+        let root = root.path();
+        symlink("app.conf", root.join("etc/link")).unwrap();
+
+        let admitted = admit(&root.join("etc/link"), Some(root), &config("")).unwrap();
+        assert_eq!(admitted, host(root, "etc/app.conf"));
+    }
+
+    #[test]
+    fn deny_rejects_links_but_not_regular_files() {
+        let root = container_root();
+        let root = root.path();
+        symlink("app.conf", root.join("etc/link")).unwrap();
+        let deny = config(r#"symlinks = "deny""#);
+
+        assert!(is_policy_error(admit(
+            &root.join("etc/link"),
+            Some(root),
+            &deny
+        )));
+        let admitted = admit(&root.join("etc/app.conf"), Some(root), &deny).unwrap();
+        assert_eq!(admitted, host(root, "etc/app.conf"));
+    }
+
+    #[test]
+    fn absolute_links_resolve_inside_the_container_root() {
+        let root = container_root();
+        let root = root.path();
+        let outside = tempfile::tempdir().unwrap();
+        let secret = outside.path().join("secret");
+        fs::write(&secret, "host").unwrap();
+        symlink("/etc/app.conf", root.join("etc/abs")).unwrap();
+        symlink(&secret, root.join("etc/escape")).unwrap();
+
+        let admitted = admit(&root.join("etc/abs"), Some(root), &config("")).unwrap();
+        assert_eq!(admitted, host(root, "etc/app.conf"));
+        // The host file does not exist under the container root.
+        let escaped = admit(&root.join("etc/escape"), Some(root), &config("")).unwrap();
+        assert_eq!(escaped, None);
+    }
+
+    #[test]
+    fn parent_links_stop_at_the_container_root() {
+        let root = container_root();
+        let root = root.path();
+        symlink("../../../../../etc/app.conf", root.join("etc/up")).unwrap();
+
+        let resolved = resolve_in_root(root, Path::new("/etc/up")).unwrap();
+        assert_eq!(resolved.visible, Path::new("/etc/app.conf"));
+        assert_eq!(resolved.host, root.join("etc/app.conf"));
+        assert!(resolved.through_symlink);
+    }
+
+    #[test]
+    fn allowed_roots_match_whole_components() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = fs::canonicalize(dir.path()).unwrap();
+        fs::create_dir(base.join("app")).unwrap();
+        fs::create_dir(base.join("application")).unwrap();
+        fs::write(base.join("app/file"), "x").unwrap();
+        fs::write(base.join("application/file"), "x").unwrap();
+        symlink(base.join("application/file"), base.join("app/link")).unwrap();
+        let fm_config = config(&format!(
+            "allowed_roots = [{:?}]",
+            base.join("app").to_string_lossy()
+        ));
+
+        let admitted = admit(&base.join("app/file"), None, &fm_config).unwrap();
+        assert_eq!(
+            admitted,
+            Some(base.join("app/file").to_string_lossy().to_string())
+        );
+        assert!(is_policy_error(admit(
+            &base.join("application/file"),
+            None,
+            &fm_config
+        )));
+        // A link inside an allowed root is checked by its target.
+        assert!(is_policy_error(admit(
+            &base.join("app/link"),
+            None,
+            &fm_config
+        )));
+    }
+
+    #[test]
+    fn special_files_are_rejected() {
+        let root = container_root();
+        let root = root.path();
+        fs::create_dir(root.join("proc")).unwrap();
+        fs::create_dir(root.join("dev")).unwrap();
+        fs::write(root.join("proc/cmdline"), "x").unwrap();
+        fs::write(root.join("dev/disk"), "x").unwrap();
+        symlink("/proc/cmdline", root.join("etc/cmdline")).unwrap();
+        let defaults = config("");
+
+        for path in ["proc/cmdline", "dev/disk", "etc/cmdline"] {
+            assert!(
+                is_policy_error(admit(&root.join(path), Some(root), &defaults)),
+                "{} was admitted",
+                path
+            );
         }
+        let admitted = admit(
+            &root.join("dev/disk"),
+            Some(root),
+            &config("skip_special_files = false"),
+        )
+        .unwrap();
+        assert_eq!(admitted, host(root, "dev/disk"));
+    }
 
-        info!(
-            "File measurement completed. Measured {} unique files.",
-            measured_files.len()
+    #[test]
+    fn host_proc_files_are_rejected() {
+        assert!(is_policy_error(admit(
+            Path::new("/proc/self/status"),
+            None,
+            &config("")
+        )));
+    }
+
+    #[test]
+    fn symlink_loops_end_after_max_symlinks() {
+        let root = container_root();
+        let root = root.path();
+        symlink("b", root.join("etc/a")).unwrap();
+        symlink("a", root.join("etc/b")).unwrap();
+
+        assert!(resolve_in_root(root, Path::new("/etc/a")).is_err());
+        assert_eq!(
+            admit(&root.join("etc/a"), Some(root), &config("")).unwrap(),
+            None
         );
-        Ok(())
+    }
+
+    #[test]
+    fn symlink_chains_resolve_up_to_max_symlinks() {
+        let root = container_root();
+        let root = root.path();
+        // link0 -> link1 -> ... -> link{n-1} -> app.conf
+        let chain = |n: usize, prefix: &str| {
+            for i in 0..n {
+                let target = if i + 1 == n {
+                    "app.conf".to_string()
+                } else {
+                    format!("{}{}", prefix, i + 1)
+                };
+                symlink(target, root.join(format!("etc/{}{}", prefix, i))).unwrap();
+            }
+            Path::new("/etc").join(format!("{}0", prefix))
+        };
+
+        let longest = chain(MAX_SYMLINKS, "ok");
+        let resolved = resolve_in_root(root, &longest).unwrap();
+        assert_eq!(resolved.visible, Path::new("/etc/app.conf"));
+        let too_long = chain(MAX_SYMLINKS + 1, "long");
+        assert!(resolve_in_root(root, &too_long).is_err());
     }
 }
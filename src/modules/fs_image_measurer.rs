@@ -0,0 +1,310 @@
+// src/modules/fs_image_measurer.rs
+use crate::canonical_json;
+use crate::config::{Config, FsImageMeasurementConfig};
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use crate::modules::dataset_measurer::full_manifest_digest;
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::process::Command;
+
+const DOMAIN: &str = "fs_image";
+
+const SQUASHFS_MAGIC: u32 = 0x7371_7368; // "hsqs"
+const EROFS_MAGIC: u32 = 0xE0F5_E1E2;
+const EROFS_SUPERBLOCK_OFFSET: u64 = 1024;
+
+/// Measures read-only filesystem images (squashfs, EROFS): the digest of the
+/// image file plus what its superblock says, optionally together with a
+/// manifest of the mounted content.
+#[derive(Default)]
+pub struct FsImageMeasurer;
+
+/// Superblock fields recorded for an image.
+#[derive(Debug, Serialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+enum Superblock {
+    Squashfs {
+        version: String,
+        compression: &'static str,
+        block_size: u32,
+        inodes: u32,
+        mkfs_time: u32,
+        bytes_used: u64,
+    },
+    Erofs {
+        uuid: String,
+        label: String,
+        block_size: u32,
+        blocks: u32,
+        inodes: u64,
+        build_time: u64,
+    },
+}
+
+impl Superblock {
+    fn fs_type(&self) -> &'static str {
+        match self {
+            Superblock::Squashfs { .. } => "squashfs",
+            Superblock::Erofs { .. } => "erofs",
+        }
+    }
+}
+
+/// The structured content extended for an image.
+#[derive(Debug, Serialize)]
+struct ImageRecord {
+    digest: String,
+    #[serde(flatten)]
+    superblock: Superblock,
+    /// Digest of the full-content manifest of the mounted image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    manifest: Option<String>,
+}
+
+impl FsImageMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn measure_image(
+        &self,
+        image: &str,
+        config: &FsImageMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let path = PathBuf::from(image)
+            .canonicalize()
+            .map_err(|e| MeasurementError::Config(format!("Image {}: {}", image, e)))?;
+
+        let (digest, superblock) = {
+            let path = path.clone();
+            let hash_algorithm = config.hash_algorithm.clone();
+            tokio::task::spawn_blocking(move || -> Result<_> {
+                Ok((
+                    digest_reader(&mut File::open(&path)?, &hash_algorithm)?,
+                    read_superblock(&path)?,
+                ))
+            })
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+        };
+        debug!("Image {:?} is {:?}", path, superblock);
+
+        let manifest = if config.mount_manifest {
+            Some(
+                self.mounted_manifest(&path, superblock.fs_type(), config)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let record = ImageRecord {
+            digest,
+            superblock,
+            manifest,
+        };
+        let content = canonical_json::to_vec(&record)
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        let operation = path.to_string_lossy().to_string();
+        sink.extend(
+            DOMAIN,
+            &operation,
+            &String::from_utf8_lossy(&content),
+            config.pcr_index.map(|v| v as u64),
+        )
+        .await?;
+        info!("Measured filesystem image {}", operation);
+        Ok(())
+    }
+
+    /// Loop-mounts the image read-only on a temporary directory and hashes
+    /// a manifest of its content.
+    async fn mounted_manifest(
+        &self,
+        image: &Path,
+        fs_type: &str,
+        config: &FsImageMeasurementConfig,
+    ) -> Result<String> {
+        std::fs::create_dir_all(&config.mount_dir)?;
+        let mount_point = tempfile::Builder::new()
+            .prefix("image-")
+            .tempdir_in(&config.mount_dir)?;
+        let mount_path = mount_point.path().to_path_buf();
+        let image_str = image.to_string_lossy().to_string();
+        let mount_str = mount_path.to_string_lossy().to_string();
+
+        run_command(
+            "mount",
+            &["-t", fs_type, "-o", "ro,loop", &image_str, &mount_str],
+        )
+        .await?;
+        let manifest = {
+            let root = mount_path.clone();
+            tokio::task::spawn_blocking(move || full_manifest_digest(&root))
+                .await
+                .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))
+        };
+        if let Err(e) = run_command("umount", &[&mount_str]).await {
+            warn!("Failed to unmount {}: {}", mount_str, e);
+        }
+        manifest?
+    }
+}
+
+/// Identifies the image by its magic number and decodes the superblock
+/// (see the kernel's fs/squashfs/squashfs_fs.h and fs/erofs/erofs_fs.h).
+fn read_superblock(path: &Path) -> Result<Superblock> {
+    let mut file = File::open(path)?;
+    let mut buf = [0u8; 128];
+    file.read_exact(&mut buf[..96])?;
+    if le_u32(&buf, 0) == SQUASHFS_MAGIC {
+        return Ok(Superblock::Squashfs {
+            version: format!("{}.{}", le_u16(&buf, 28), le_u16(&buf, 30)),
+            compression: squashfs_compression(le_u16(&buf, 20)),
+            block_size: le_u32(&buf, 12),
+            inodes: le_u32(&buf, 4),
+            mkfs_time: le_u32(&buf, 8),
+            bytes_used: le_u64(&buf, 40),
+        });
+    }
+
+    file.seek(SeekFrom::Start(EROFS_SUPERBLOCK_OFFSET))?;
+    file.read_exact(&mut buf)?;
+    if le_u32(&buf, 0) == EROFS_MAGIC {
+        let uuid = hex::encode(&buf[48..64]);
+        let label = String::from_utf8_lossy(&buf[64..80])
+            .trim_end_matches('\0')
+            .to_string();
+        return Ok(Superblock::Erofs {
+            uuid: format!(
+                "{}-{}-{}-{}-{}",
+                &uuid[..8],
+                &uuid[8..12],
+                &uuid[12..16],
+                &uuid[16..20],
+                &uuid[20..]
+            ),
+            label,
+            block_size: 1 << buf[12].min(31),
+            blocks: le_u32(&buf, 36),
+            inodes: le_u64(&buf, 16),
+            build_time: le_u64(&buf, 24),
+        });
+    }
+
+    Err(MeasurementError::Config(format!(
+        "{:?} is neither a squashfs nor an EROFS image",
+        path
+    )))
+}
+
+fn squashfs_compression(id: u16) -> &'static str {
+    match id {
+        1 => "gzip",
+        2 => "lzma",
+        3 => "lzo",
+        4 => "xz",
+        5 => "lz4",
+        6 => "zstd",
+        _ => "unknown",
+    }
+}
+
+fn le_u16(buf: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn le_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap_or_default())
+}
+
+fn le_u64(buf: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap_or_default())
+}
+
+async fn run_command(binary: &str, args: &[&str]) -> Result<()> {
+    let output = Command::new(binary)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| {
+            MeasurementError::CommandExecution(format!("Failed to run {}: {}", binary, e))
+        })?;
+    if !output.status.success() {
+        return Err(MeasurementError::CommandExecution(format!(
+            "Command '{} {}' failed with status {}: {}",
+            binary,
+            args.join(" "),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Measurable for FsImageMeasurer {
+    fn name(&self) -> &str {
+        "FsImageMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Hashes squashfs/EROFS images and records their superblock"
+    }
+
+    fn config_section(&self) -> &str {
+        "fs_image_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.fs_image_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.fs_image_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let fi_config = &config.fs_image_measurement;
+        if !fi_config.enable {
+            debug!("Filesystem image measurement is disabled. Skipping.");
+            return Ok(());
+        }
+        if fi_config.images.is_empty() {
+            warn!("Filesystem image measurement is enabled but no images configured.");
+            return Ok(());
+        }
+
+        info!(
+            "Starting filesystem image measurement with domain '{}'",
+            DOMAIN
+        );
+        let mut measured = HashSet::new();
+        let mut failures = EntryFailures::default();
+        for entry in &fi_config.images {
+            let image = entry.path();
+            if !measured.insert(image.to_string()) {
+                debug!("Skipping duplicate image entry: {}", image);
+                continue;
+            }
+            if let Err(e) = self.measure_image(image, fi_config, &sink).await {
+                failures.record(image, entry.is_critical(), e);
+            }
+        }
+        failures.into_result()
+    }
+}
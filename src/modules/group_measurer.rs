@@ -0,0 +1,190 @@
+// src/modules/group_measurer.rs
+use crate::config::{Config, GroupMeasurementConfig, MeasurementGroup};
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use crate::hook::tree_digest_with;
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::glob;
+use log::{debug, info, warn};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::sync::Arc;
+
+const DOMAIN: &str = "group";
+const ITEM_DOMAIN: &str = "group_item";
+
+/// Combines the digests of a named set of files and directories into one
+/// composite measurement, so that policies can refer to a bundle as a whole.
+#[derive(Default)]
+pub struct GroupMeasurer;
+
+impl GroupMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn measure_group(
+        &self,
+        group: &MeasurementGroup,
+        gm_config: &GroupMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let items = {
+            let group = group.clone();
+            let hash_algorithm = gm_config.hash_algorithm.clone();
+            tokio::task::spawn_blocking(move || collect_item_digests(&group, &hash_algorithm))
+                .await
+                .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+        };
+        if items.is_empty() {
+            warn!("Measurement group '{}' matched no paths", group.name);
+        }
+        let pcr_index = gm_config.pcr_index.map(|v| v as u64);
+
+        if gm_config.emit_items {
+            for (path, digest) in &items {
+                let operation = format!("{}:{}", group.name, path);
+                sink.extend(ITEM_DOMAIN, &operation, digest, pcr_index)
+                    .await?;
+            }
+        }
+
+        let composite = composite_digest(&items);
+        debug!(
+            "Group '{}' has {} items, composite digest {}",
+            group.name,
+            items.len(),
+            composite
+        );
+        sink.extend(DOMAIN, &group.name, &composite, pcr_index)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Digests of every file and directory matched by the group's patterns,
+/// keyed (and therefore sorted) by path. A match that cannot be read fails
+/// the group, since its composite would otherwise silently lack a member.
+fn collect_item_digests(
+    group: &MeasurementGroup,
+    hash_algorithm: &str,
+) -> Result<BTreeMap<String, String>> {
+    let mut items = BTreeMap::new();
+    for pattern in &group.paths {
+        for matched in glob(pattern)? {
+            let path = matched.map_err(|e| {
+                MeasurementError::Io(std::io::Error::new(
+                    e.error().kind(),
+                    format!(
+                        "{} matched by '{}' cannot be read: {}",
+                        e.path().display(),
+                        pattern,
+                        e.error()
+                    ),
+                ))
+            })?;
+            let path_str = path.to_string_lossy().to_string();
+            if items.contains_key(&path_str) {
+                continue;
+            }
+            let digest = if path.is_dir() {
+                tree_digest_with(&path, hash_algorithm)?
+            } else if path.is_file() {
+                digest_reader(&mut File::open(&path)?, hash_algorithm)?
+            } else {
+                continue;
+            };
+            items.insert(path_str, digest);
+        }
+    }
+    Ok(items)
+}
+
+/// SHA-256 over the sorted `<path>\0<digest>\n` lines of the group.
+fn composite_digest(items: &BTreeMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    for (path, digest) in items {
+        hasher.update(path.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(digest.as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("sha256:{}", hex::encode(hasher.finalize()))
+}
+
+#[async_trait]
+impl Measurable for GroupMeasurer {
+    fn name(&self) -> &str {
+        "GroupMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Extends one composite digest per named group of files and directories"
+    }
+
+    fn config_section(&self) -> &str {
+        "group_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.group_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.group_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let gm_config = &config.group_measurement;
+        if !gm_config.enable {
+            debug!("Group measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!(
+            "Starting group measurement of {} groups",
+            gm_config.groups.len()
+        );
+        let mut failures = EntryFailures::default();
+        for group in &gm_config.groups {
+            if let Err(e) = self.measure_group(group, gm_config, &sink).await {
+                failures.record(&group.name, false, e);
+            }
+        }
+        info!("Group measurement completed.");
+        failures.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn files_and_directories_use_the_hash_algorithm() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("app.yaml"), b"replicas: 2").unwrap();
+        fs::create_dir(dir.path().join("certs")).unwrap();
+        fs::write(dir.path().join("certs/ca.pem"), b"ca").unwrap();
+        let group = MeasurementGroup {
+            name: "app".to_string(),
+            paths: vec![format!("{}/*", dir.path().display())],
+        };
+
+        let items = collect_item_digests(&group, "sha512").unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items.values().all(|digest| digest.starts_with("sha512:")));
+        assert_eq!(
+            items[&format!("{}/certs", dir.path().display())],
+            tree_digest_with(&dir.path().join("certs"), "sha512").unwrap()
+        );
+    }
+}
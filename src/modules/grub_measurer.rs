@@ -0,0 +1,231 @@
+// src/modules/grub_measurer.rs
+use crate::canonical_json;
+use crate::config::{Config, GrubMeasurementConfig, MeasurementEntry};
+use crate::digest::digest_bytes;
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::glob;
+use log::{debug, info};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DOMAIN: &str = "boot";
+
+/// Measures the GRUB boot chain configuration of legacy-boot hosts:
+/// `grub.cfg`, the grubenv environment block and the modules `grub.cfg`
+/// loads.
+#[derive(Default)]
+pub struct GrubMeasurer;
+
+impl GrubMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the files matched by `entries`. Unlike other measurers, a
+    /// non-critical pattern matching nothing is expected: the defaults name
+    /// both the `grub` and `grub2` layouts.
+    fn matched_files(
+        entries: &[MeasurementEntry],
+        failures: &mut EntryFailures,
+    ) -> Vec<(PathBuf, bool)> {
+        let mut files = Vec::new();
+        for entry in entries {
+            let pattern = entry.path();
+            let paths: Vec<PathBuf> = match glob(pattern) {
+                Ok(paths) => paths.flatten().filter(|p| p.is_file()).collect(),
+                Err(e) => {
+                    failures.record(pattern, entry.is_critical(), e.into());
+                    continue;
+                }
+            };
+            if paths.is_empty() {
+                if entry.is_critical() {
+                    let e = std::io::Error::new(std::io::ErrorKind::NotFound, "no file matches");
+                    failures.record(pattern, true, e.into());
+                } else {
+                    debug!("GRUB pattern '{}' matches no files", pattern);
+                }
+            }
+            files.extend(paths.into_iter().map(|p| (p, entry.is_critical())));
+        }
+        files
+    }
+
+    async fn extend(
+        &self,
+        config: &GrubMeasurementConfig,
+        sink: &dyn MeasurementSink,
+        operation: &str,
+        content: &str,
+    ) -> Result<()> {
+        debug!("Extending boot measurement {} = {}", operation, content);
+        sink.extend(
+            DOMAIN,
+            operation,
+            content,
+            config.pcr_index.map(|v| v as u64),
+        )
+        .await
+    }
+
+    /// Measures a `grub.cfg` and the modules it loads.
+    async fn measure_config(
+        &self,
+        path: &Path,
+        config: &GrubMeasurementConfig,
+        sink: &dyn MeasurementSink,
+        measured_modules: &mut HashSet<PathBuf>,
+    ) -> Result<()> {
+        let content = fs::read(path)?;
+        let digest = digest_bytes(&content, &config.hash_algorithm)?;
+        self.extend(
+            config,
+            sink,
+            &format!("grub_cfg:{}", path.to_string_lossy()),
+            &digest,
+        )
+        .await?;
+
+        for module in loaded_modules(path, &String::from_utf8_lossy(&content)) {
+            if !measured_modules.insert(module.clone()) {
+                continue;
+            }
+            let digest = digest_bytes(&fs::read(&module)?, &config.hash_algorithm)?;
+            self.extend(
+                config,
+                sink,
+                &format!("module:{}", module.to_string_lossy()),
+                &digest,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Measures the variables of a grubenv block as canonical JSON.
+    async fn measure_grubenv(
+        &self,
+        path: &Path,
+        config: &GrubMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let content = fs::read_to_string(path)?;
+        let variables: BTreeMap<&str, &str> = content
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .filter(|(key, _)| !config.grubenv_ignore.iter().any(|k| k == *key))
+            .collect();
+        let encoded = canonical_json::to_vec(&variables)
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        self.extend(
+            config,
+            sink,
+            &format!("grubenv:{}", path.to_string_lossy()),
+            &String::from_utf8_lossy(&encoded),
+        )
+        .await
+    }
+}
+
+/// Resolves the `insmod` commands of a `grub.cfg` to module files in the
+/// platform directories (`i386-pc`, `x86_64-efi`, ...) next to it. Module
+/// names built from variables cannot be resolved and are skipped.
+fn loaded_modules(cfg_path: &Path, cfg: &str) -> Vec<PathBuf> {
+    let names: BTreeSet<&str> = cfg
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("insmod "))
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !name.contains('$'))
+        .collect();
+    let Some(grub_dir) = cfg_path.parent() else {
+        return Vec::new();
+    };
+    let platform_dirs: Vec<PathBuf> = fs::read_dir(grub_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|dir| dir.is_dir())
+        .collect();
+    let mut modules: Vec<PathBuf> = names
+        .iter()
+        .flat_map(|name| {
+            platform_dirs
+                .iter()
+                .map(move |dir| dir.join(format!("{}.mod", name)))
+        })
+        .filter(|module| module.is_file())
+        .collect();
+    modules.sort();
+    modules
+}
+
+#[async_trait]
+impl Measurable for GrubMeasurer {
+    fn name(&self) -> &str {
+        "GrubMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Hashes grub.cfg, grubenv variables and loaded GRUB modules"
+    }
+
+    fn config_section(&self) -> &str {
+        "grub_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.grub_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.grub_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let grub_config = &config.grub_measurement;
+        if !grub_config.enable {
+            debug!("GRUB measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting GRUB measurement with domain '{}'", DOMAIN);
+        let mut failures = EntryFailures::default();
+        let mut measured_modules = HashSet::new();
+
+        let config_files = Self::matched_files(&grub_config.config_files, &mut failures);
+        for (path, critical) in &config_files {
+            if let Err(e) = self
+                .measure_config(path, grub_config, &sink, &mut measured_modules)
+                .await
+            {
+                failures.record(&path.to_string_lossy(), *critical, e);
+            }
+        }
+
+        let grubenv_files = Self::matched_files(&grub_config.grubenv_files, &mut failures);
+        for (path, critical) in &grubenv_files {
+            if let Err(e) = self.measure_grubenv(path, grub_config, &sink).await {
+                failures.record(&path.to_string_lossy(), *critical, e);
+            }
+        }
+
+        info!(
+            "GRUB measurement completed for {} configs, {} environment blocks and {} modules.",
+            config_files.len(),
+            grubenv_files.len(),
+            measured_modules.len()
+        );
+        failures.into_result()
+    }
+}
@@ -0,0 +1,71 @@
+// src/modules/job_queue.rs
+use std::time::{Duration, Instant};
+
+/// Urgency of queued re-measurement work, least urgent first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Inventories and full measurer runs.
+    Bulk = 0,
+    /// Model directories.
+    Normal = 1,
+    /// Security-relevant configuration files.
+    Critical = 2,
+}
+
+/// How long a job waits before it counts as one level more urgent, so that
+/// a steady stream of urgent work cannot starve bulk jobs.
+const AGING_STEP: Duration = Duration::from_secs(60);
+
+struct QueuedJob<T> {
+    priority: Priority,
+    enqueued: Instant,
+    job: T,
+}
+
+impl<T> QueuedJob<T> {
+    fn rank(&self, now: Instant) -> u64 {
+        let aged = now.duration_since(self.enqueued).as_secs() / AGING_STEP.as_secs();
+        self.priority as u64 + aged
+    }
+}
+
+/// Pending work, handed out most urgent first.
+pub struct JobQueue<T> {
+    jobs: Vec<QueuedJob<T>>,
+}
+
+impl<T> Default for JobQueue<T> {
+    fn default() -> Self {
+        Self { jobs: Vec::new() }
+    }
+}
+
+impl<T> JobQueue<T> {
+    pub fn push(&mut self, priority: Priority, job: T) {
+        self.jobs.push(QueuedJob {
+            priority,
+            enqueued: Instant::now(),
+            job,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Removes the job with the highest priority after aging; among equals,
+    /// the one queued first.
+    pub fn pop(&mut self) -> Option<T> {
+        let now = Instant::now();
+        let (index, _) = self.jobs.iter().enumerate().max_by(|(_, a), (_, b)| {
+            a.rank(now)
+                .cmp(&b.rank(now))
+                .then(b.enqueued.cmp(&a.enqueued))
+        })?;
+        Some(self.jobs.remove(index).job)
+    }
+}
@@ -0,0 +1,177 @@
+// src/modules/kernel_state_measurer.rs
+use crate::canonical_json;
+use crate::config::{Config, KernelStateMeasurementConfig};
+use crate::digest::digest_bytes;
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use log::{debug, info};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const DOMAIN: &str = "kernel";
+const CMDLINE_PATH: &str = "/proc/cmdline";
+const MODULES_PATH: &str = "/proc/modules";
+const SYSCTL_ROOT: &str = "/proc/sys";
+
+/// Measures the runtime state of the guest kernel: its command line, the
+/// loaded modules and selected sysctls.
+#[derive(Default)]
+pub struct KernelStateMeasurer;
+
+impl KernelStateMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn extend(
+        &self,
+        config: &KernelStateMeasurementConfig,
+        sink: &dyn MeasurementSink,
+        operation: &str,
+        content: &str,
+    ) -> Result<()> {
+        debug!("Extending kernel measurement {} = {}", operation, content);
+        sink.extend(
+            DOMAIN,
+            operation,
+            content,
+            config.pcr_index.map(|v| v as u64),
+        )
+        .await
+    }
+
+    async fn measure_cmdline(
+        &self,
+        config: &KernelStateMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let digest = digest_bytes(&fs::read(CMDLINE_PATH)?, &config.hash_algorithm)?;
+        self.extend(config, sink, "cmdline", &digest).await
+    }
+
+    async fn measure_modules(
+        &self,
+        config: &KernelStateMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let names = module_names(&fs::read_to_string(MODULES_PATH)?);
+        let digest = digest_bytes(names.as_bytes(), &config.hash_algorithm)?;
+        self.extend(config, sink, "modules", &digest).await
+    }
+
+    /// Extends the sysctl values as canonical JSON.
+    async fn measure_sysctls(
+        &self,
+        config: &KernelStateMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let values = read_sysctls(&config.sysctls)?;
+        let encoded = canonical_json::to_vec(&values)
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        self.extend(config, sink, "sysctl", &String::from_utf8_lossy(&encoded))
+            .await
+    }
+}
+
+/// Names of the loaded modules, one per line in sorted order. The other
+/// columns of `/proc/modules` (sizes, use counts, addresses) change at
+/// runtime and are left out.
+fn module_names(modules: &str) -> String {
+    let names: BTreeSet<&str> = modules
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .collect();
+    names
+        .into_iter()
+        .map(|name| format!("{}\n", name))
+        .collect()
+}
+
+/// Maps `kernel.yama.ptrace_scope` to `/proc/sys/kernel/yama/ptrace_scope`.
+fn sysctl_path(name: &str) -> Result<PathBuf> {
+    if name.is_empty() || name.contains('/') || name.split('.').any(|part| part.is_empty()) {
+        return Err(MeasurementError::Config(format!(
+            "Invalid sysctl name '{}'",
+            name
+        )));
+    }
+    Ok(PathBuf::from(SYSCTL_ROOT).join(name.replace('.', "/")))
+}
+
+/// Reads the configured sysctls with their whitespace normalized. Sysctls
+/// this kernel does not have are recorded as `null`, so that their absence
+/// is measured too.
+fn read_sysctls(names: &[String]) -> Result<BTreeMap<&str, Option<String>>> {
+    let mut values = BTreeMap::new();
+    for name in names {
+        let value = match fs::read_to_string(sysctl_path(name)?) {
+            Ok(value) => Some(value.split_whitespace().collect::<Vec<_>>().join(" ")),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        values.insert(name.as_str(), value);
+    }
+    Ok(values)
+}
+
+#[async_trait]
+impl Measurable for KernelStateMeasurer {
+    fn name(&self) -> &str {
+        "KernelStateMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Records the kernel command line, loaded modules and selected sysctls"
+    }
+
+    fn config_section(&self) -> &str {
+        "kernel_state_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.kernel_state_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.kernel_state_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let ks_config = &config.kernel_state_measurement;
+        if !ks_config.enable {
+            debug!("Kernel state measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting kernel state measurement with domain '{}'", DOMAIN);
+        let mut failures = EntryFailures::default();
+
+        if ks_config.cmdline {
+            if let Err(e) = self.measure_cmdline(ks_config, &sink).await {
+                failures.record(CMDLINE_PATH, false, e);
+            }
+        }
+        if ks_config.modules {
+            if let Err(e) = self.measure_modules(ks_config, &sink).await {
+                failures.record(MODULES_PATH, false, e);
+            }
+        }
+        if !ks_config.sysctls.is_empty() {
+            if let Err(e) = self.measure_sysctls(ks_config, &sink).await {
+                failures.record(SYSCTL_ROOT, false, e);
+            }
+        }
+
+        info!("Kernel state measurement completed.");
+        failures.into_result()
+    }
+}
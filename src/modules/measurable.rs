@@ -1,17 +1,97 @@
 use crate::config::Config;
-use crate::error::Result;
-use crate::rpc_client::AAClient;
+use crate::error::{MeasurementError, Result};
+use crate::sink::MeasurementSink;
 use async_trait::async_trait;
+use log::error;
+use std::future::Future;
 use std::sync::Arc;
+use tokio::sync::Semaphore;
 
 #[async_trait]
 pub trait Measurable {
     /// Returns the name of the measurer (e.g., "FileMeasurer").
     fn name(&self) -> &str;
 
+    /// One-line summary shown by `list-measurers`.
+    fn description(&self) -> &str;
+
+    /// The config section that controls this measurer (e.g., "file_measurement").
+    fn config_section(&self) -> &str;
+
+    /// The measurement domain used when extending.
+    fn domain(&self) -> &str;
+
+    /// The PCR index this measurer extends, or `None` to use the backend default.
+    fn pcr_index(&self, config: &Config) -> Option<u32>;
+
     /// Checks if this measurer is enabled in the configuration.
     fn is_enabled(&self, config: Arc<Config>) -> bool;
 
-    /// Performs the measurement and sends results via the AAClient.
-    async fn measure(&self, config: Arc<Config>, aa_client: Arc<AAClient>) -> Result<()>;
+    /// Performs the measurement and records the results in `sink`.
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()>;
+}
+
+/// Collects per-entry failures so that one failed entry does not keep the
+/// others from being measured.
+#[derive(Default)]
+pub struct EntryFailures {
+    critical: Vec<String>,
+    first: Option<MeasurementError>,
+}
+
+impl EntryFailures {
+    pub fn record(&mut self, entry: &str, critical: bool, error: MeasurementError) {
+        if critical {
+            error!("Failed to measure critical entry {}: {}", entry, error);
+            self.critical.push(entry.to_string());
+        } else {
+            error!("Failed to measure {}: {}", entry, error);
+            self.first.get_or_insert(error);
+        }
+    }
+
+    /// Critical failures take precedence and are reported as
+    /// `MeasurementError::CriticalEntry`; otherwise the first failure is
+    /// returned.
+    pub fn into_result(self) -> Result<()> {
+        if !self.critical.is_empty() {
+            return Err(MeasurementError::CriticalEntry(self.critical.join(", ")));
+        }
+        match self.first {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Runs `jobs` with at most `limit` in flight and returns their results in
+/// the order of `jobs`. Measurers compute digests this way and extend them
+/// afterwards in that order, so the event log does not depend on which job
+/// finished first.
+pub async fn run_ordered<T, F>(limit: usize, jobs: Vec<F>) -> Vec<Result<T>>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(limit.max(1)));
+    let handles: Vec<_> = jobs
+        .into_iter()
+        .map(|job| {
+            let semaphore = semaphore.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                job.await
+            })
+        })
+        .collect();
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(
+            handle
+                .await
+                .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))
+                .and_then(|result| result),
+        );
+    }
+    results
 }
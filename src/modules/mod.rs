@@ -1,17 +1,81 @@
 // src/modules/mod.rs
 
+pub mod container_image_measurer;
+pub mod dataset_measurer;
+pub mod device_measurer;
+pub mod dir_tree_measurer;
+pub mod efi_measurer;
+pub mod exec_measurer;
 pub mod file_config_watcher;
+pub mod file_content_watcher;
 pub mod file_measurer;
+pub mod fs_image_measurer;
+pub mod group_measurer;
+pub mod grub_measurer;
+pub mod job_queue;
+pub mod kernel_state_measurer;
+pub mod model_dir_content_watcher;
 pub mod model_dir_measurer;
 pub mod measurable;
+pub mod mount_measurer;
+pub mod network_measurer;
+pub mod object_measurer;
+pub mod process_measurer;
+pub mod runner;
+pub mod sbom_measurer;
+pub mod vmm_measurer;
 pub mod watcher;
 
 // Re-export for easier access
+pub use container_image_measurer::ContainerImageMeasurer;
+pub use dataset_measurer::DatasetMeasurer;
+pub use device_measurer::DeviceMeasurer;
+pub use dir_tree_measurer::DirTreeMeasurer;
+pub use efi_measurer::EfiMeasurer;
+pub use exec_measurer::ExecMeasurer;
 pub use file_measurer::FileMeasurer;
+pub use fs_image_measurer::FsImageMeasurer;
+pub use group_measurer::GroupMeasurer;
+pub use grub_measurer::GrubMeasurer;
+pub use kernel_state_measurer::KernelStateMeasurer;
 pub use model_dir_measurer::ModelDirMeasurer;
 pub use measurable::Measurable;
+pub use mount_measurer::MountMeasurer;
+pub use network_measurer::NetworkStateMeasurer;
+pub use object_measurer::ObjectMeasurer;
+pub use process_measurer::ProcessMeasurer;
+pub use runner::{run_measurers, MeasurementRun, RunOutcome};
+pub use sbom_measurer::SbomMeasurer;
+pub use vmm_measurer::VmmMeasurer;
 pub use watcher::ConfigWatcher;
 pub use file_config_watcher::{
     ConfigChangeHandler, ConfigFileWatcher, FileMeasurementChangeHandler,
-    ModelDirMeasurementChangeHandler,
+    MeasurerToggleHandler, ModelDirMeasurementChangeHandler,
 };
+pub use file_content_watcher::FileContentWatcher;
+pub use model_dir_content_watcher::ModelDirContentWatcher;
+
+/// Every measurer known to the binary, in the order they run.
+///
+/// Add new measurers to this vector as they are implemented.
+pub fn registered_measurers() -> Vec<Box<dyn Measurable + Send + Sync>> {
+    vec![
+        Box::new(FileMeasurer::new()),
+        Box::new(ModelDirMeasurer::new()),
+        Box::new(DatasetMeasurer::new()),
+        Box::new(DirTreeMeasurer::new()),
+        Box::new(ObjectMeasurer::new()),
+        Box::new(GroupMeasurer::new()),
+        Box::new(FsImageMeasurer::new()),
+        Box::new(VmmMeasurer::new()),
+        Box::new(EfiMeasurer::new()),
+        Box::new(GrubMeasurer::new()),
+        Box::new(KernelStateMeasurer::new()),
+        Box::new(DeviceMeasurer::new()),
+        Box::new(MountMeasurer::new()),
+        Box::new(NetworkStateMeasurer::new()),
+        Box::new(ProcessMeasurer::new()),
+        Box::new(SbomMeasurer::new()),
+        Box::new(ContainerImageMeasurer::new()),
+    ]
+}
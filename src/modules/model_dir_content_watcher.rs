@@ -0,0 +1,166 @@
+// src/modules/model_dir_content_watcher.rs
+use crate::config::{Config, MeasurementEntry, ModelDirMeasurementConfig};
+use crate::error::{MeasurementError, Result};
+use crate::fs_watch::PathWatcher;
+use crate::modules::file_config_watcher::notify_stopped;
+use crate::modules::{watcher::ConfigWatcher, ModelDirMeasurer};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use notify::{EventKind, RecursiveMode};
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+
+/// How often directories added by a config reload, or created after
+/// startup, are picked up.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Re-measures the directories of `model_dir_measurement.directories` once
+/// their content has changed and no write has followed for `settle_secs`,
+/// so that weights swapped after the initial run show up in the event log.
+/// Runs while `model_dir_measurement.watch_content` is set.
+#[derive(Default)]
+pub struct ModelDirContentWatcher {
+    measurer: ModelDirMeasurer,
+}
+
+impl ModelDirContentWatcher {
+    pub fn new() -> Self {
+        Self {
+            measurer: ModelDirMeasurer::new(),
+        }
+    }
+}
+
+/// Writes, creations and removals; reads by the measurement itself are
+/// not changes.
+fn is_content_event(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_) | EventKind::Any
+    )
+}
+
+/// The configured directories to watch, by their resolved path.
+fn watched_entries(md_config: &ModelDirMeasurementConfig) -> Vec<(PathBuf, MeasurementEntry)> {
+    if !md_config.enable || !md_config.watch_content {
+        return Vec::new();
+    }
+    md_config
+        .directories
+        .iter()
+        .filter_map(|entry| {
+            let dir = PathBuf::from(entry.path()).canonicalize().ok()?;
+            dir.is_dir().then(|| (dir, entry.clone()))
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ConfigWatcher for ModelDirContentWatcher {
+    fn name(&self) -> &str {
+        "ModelDirContentWatcher"
+    }
+
+    fn is_enabled(&self, _config: Arc<Config>) -> bool {
+        // `watch_content` is checked on every pass, so that it can be
+        // switched on by a config reload.
+        true
+    }
+
+    async fn watch(
+        &self,
+        _config_path: PathBuf,
+        shared_config: Arc<RwLock<Config>>,
+        sink: Arc<dyn MeasurementSink>,
+    ) -> Result<()> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = {
+            let config = shared_config.read().await;
+            PathWatcher::new(&config, config.watch_poll_compare_contents, move |res| {
+                if let Ok(event) = res {
+                    let _ = tx.send(event);
+                }
+            })
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?
+        };
+        let mut watched_dirs = HashSet::new();
+
+        loop {
+            let md_config = shared_config.read().await.model_dir_measurement.clone();
+            // A directory that was removed or replaced is watched again once
+            // it reappears.
+            watched_dirs.retain(|dir: &PathBuf| dir.is_dir());
+            for (dir, _) in watched_entries(&md_config) {
+                if watched_dirs.contains(&dir) {
+                    continue;
+                }
+                match watcher.watch(&dir, RecursiveMode::Recursive) {
+                    Ok(()) => {
+                        debug!("Watching model directory {:?} for changes", dir);
+                        watched_dirs.insert(dir);
+                    }
+                    Err(e) => warn!("Failed to watch {:?}: {}", dir, e),
+                }
+            }
+
+            let event = tokio::select! {
+                event = rx.recv() => event,
+                _ = sleep(REFRESH_INTERVAL) => continue,
+            };
+            let Some(event) = event else {
+                return Err(notify_stopped());
+            };
+            if !is_content_event(&event.kind) {
+                continue;
+            }
+
+            // A model download writes for minutes; wait until the writes
+            // stop for `settle_secs` before measuring.
+            let settle = Duration::from_secs(md_config.settle_secs);
+            let mut changed: BTreeSet<PathBuf> = event.paths.into_iter().collect();
+            info!(
+                "Model directory content changed; re-measuring after {}s without writes",
+                md_config.settle_secs
+            );
+            loop {
+                tokio::select! {
+                    event = rx.recv() => match event {
+                        Some(event) if is_content_event(&event.kind) => changed.extend(event.paths),
+                        Some(_) => {}
+                        None => return Err(notify_stopped()),
+                    },
+                    _ = sleep(settle) => break,
+                }
+            }
+
+            let (md_config, max_concurrency) = {
+                let config = shared_config.read().await;
+                (config.model_dir_measurement.clone(), config.max_concurrency)
+            };
+            let entries: Vec<MeasurementEntry> = watched_entries(&md_config)
+                .into_iter()
+                .filter(|(dir, _)| changed.iter().any(|path| path.starts_with(dir)))
+                .map(|(_, entry)| entry)
+                .collect();
+            if entries.is_empty() {
+                continue;
+            }
+            info!(
+                "{} model directories settled after changes; re-measuring them.",
+                entries.len()
+            );
+            if let Err(e) = self
+                .measurer
+                .measure_specific_dirs(&entries, &md_config, max_concurrency, sink.clone())
+                .await
+            {
+                warn!("Failed to re-measure changed model directories: {}", e);
+            }
+        }
+    }
+}
@@ -1,18 +1,60 @@
-use crate::config::{Config, ModelDirMeasurementConfig};
+use crate::config::{
+    Config, MeasurementEntry, ModelDirMeasurementConfig, ModelDirMode, VerityBackend,
+};
 use crate::error::{MeasurementError, Result};
-use crate::modules::measurable::Measurable;
-use crate::rpc_client::AAClient;
+use crate::modules::dataset_measurer::{full_manifest_digest, manifest_digest_without};
+use crate::modules::measurable::{run_ordered, EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use crate::verity;
 use async_trait::async_trait;
 use log::{debug, info, warn};
-use std::collections::HashSet;
-use std::path::PathBuf;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use tempfile::NamedTempFile;
 use tokio::process::Command;
 
 const DOMAIN: &str = "model_dir";
+const SHARD_DOMAIN: &str = "model_shard";
 
+/// Weight index files of sharded checkpoints, as written by
+/// `save_pretrained`.
+const SHARD_INDEX_SUFFIXES: [&str; 2] = [".safetensors.index.json", ".bin.index.json"];
+
+/// Where `huggingface-cli download --local-dir` records the revision of each
+/// downloaded file.
+const HF_LOCAL_DIR_METADATA: &str = ".cache/huggingface/download";
+
+/// Prefix of the content extended for directories measured by manifest
+/// instead of a verity root hash.
+const MANIFEST_FALLBACK_PREFIX: &str = "manifest:";
+
+/// Prefix of the content extended for a verity root hash; cryptpilot builds
+/// the hash tree with SHA-256.
+const VERITY_PREFIX: &str = "verity:sha256:";
+
+/// Prefix of the content extended for a root hash computed by the native
+/// backend. It hashes another image of the directory than cryptpilot, so
+/// the two kinds of root hash never compare equal.
+const NATIVE_VERITY_PREFIX: &str = "verity-native:sha256:";
+
+/// `statfs` magic numbers of filesystems on which verity formatting fails or
+/// does not pin the content (see statfs(2)). virtiofs reports the FUSE magic.
+#[cfg(target_os = "linux")]
+const NETWORK_FILESYSTEMS: &[(u32, &str)] = &[
+    (0x6969, "nfs"),
+    (0x6573_5546, "fuse/virtiofs"),
+    (0x794c_7630, "overlay"),
+    (0xff53_4d42, "cifs"),
+    (0xfe53_4d42, "smb2"),
+    (0x0102_1997, "9p"),
+];
+
+#[derive(Default)]
 pub struct ModelDirMeasurer;
 
 impl ModelDirMeasurer {
@@ -20,42 +62,168 @@ impl ModelDirMeasurer {
         Self
     }
 
+    /// Computes up to `max_concurrency` root hashes at once and extends them
+    /// in the order of `directories`. With `mode = shards`, the shards of a
+    /// directory are extended in its place, in order.
     pub async fn measure_specific_dirs(
         &self,
-        directories: &[String],
+        directories: &[MeasurementEntry],
         config: &ModelDirMeasurementConfig,
-        aa_client: Arc<AAClient>,
+        max_concurrency: usize,
+        sink: Arc<dyn MeasurementSink>,
     ) -> Result<()> {
         let mut measured_dirs = HashSet::new();
-        for dir in directories {
-            if measured_dirs.insert(dir.clone()) {
-                self.measure_single_dir(dir, config, aa_client.clone()).await?;
-            } else {
-                debug!("Skipping duplicate directory entry: {}", dir);
+        let mut failures = EntryFailures::default();
+        let entries: Vec<&MeasurementEntry> = directories
+            .iter()
+            .filter(|entry| {
+                let first = measured_dirs.insert(entry.path().to_string());
+                if !first {
+                    debug!("Skipping duplicate directory entry: {}", entry.path());
+                }
+                first
+            })
+            .collect();
+        let dry_run = sink.is_dry_run();
+        let jobs = entries
+            .iter()
+            .map(|entry| {
+                let (dir, config) = (entry.path().to_string(), config.clone());
+                async move {
+                    let measurer = ModelDirMeasurer::new();
+                    if config.mode == ModelDirMode::Shards {
+                        if let Some(shards) = measurer.shard_measurements(&dir, &config).await? {
+                            return Ok(shards);
+                        }
+                    }
+                    let (operation, root_hash) =
+                        measurer.dir_measurement(&dir, &config, dry_run).await?;
+                    Ok::<_, MeasurementError>(vec![(DOMAIN, operation, root_hash)])
+                }
+            })
+            .collect();
+        let measurements = run_ordered(max_concurrency, jobs).await;
+        for (entry, measurement) in entries.iter().zip(measurements) {
+            let result = match measurement {
+                Ok(measurements) => {
+                    let mut result = Ok(());
+                    for (domain, operation, content) in measurements {
+                        debug!(
+                            "Extending model directory measurement: domain={}, operation={}, content={}",
+                            domain, operation, content
+                        );
+                        result = sink
+                            .extend(
+                                domain,
+                                &operation,
+                                &content,
+                                config.pcr_index.map(|v| v as u64),
+                            )
+                            .await;
+                        if result.is_err() {
+                            break;
+                        }
+                    }
+                    result
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                failures.record(entry.path(), entry.is_critical(), e);
             }
         }
-        Ok(())
+        debug!("Measured {} unique model directories.", measured_dirs.len());
+        failures.into_result()
     }
 
-    async fn measure_single_dir(
+    /// Measures the weight index files of a sharded checkpoint in `dir`
+    /// and every shard they list, as `(domain, operation, content)` with the
+    /// operation `<directory operation>:<file name>`, then the manifest of
+    /// every other file as `<directory operation>:*`. `None` if `dir` has
+    /// no weight index.
+    async fn shard_measurements(
         &self,
         dir: &str,
         config: &ModelDirMeasurementConfig,
-        aa_client: Arc<AAClient>,
-    ) -> Result<()> {
-        let dir_path = PathBuf::from(dir);
-        let canonical_dir = dir_path
-            .canonicalize()
-            .map_err(|e| MeasurementError::InvalidDirectory(format!("{} ({})", dir, e)))?;
-        let canonical_dir_str = canonical_dir.to_string_lossy().to_string();
-
-        if !canonical_dir.is_dir() {
-            return Err(MeasurementError::InvalidDirectory(format!(
-                "{} is not a directory",
-                canonical_dir_str
-            )));
+    ) -> Result<Option<Vec<(&'static str, String, String)>>> {
+        let canonical_dir = canonical_dir(dir)?;
+        let root = canonical_dir.clone();
+        let digests = tokio::task::spawn_blocking(move || shard_digests(&root))
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??;
+        if digests.is_empty() {
+            info!(
+                "Model directory {:?} has no weight index; measuring it as a whole",
+                canonical_dir
+            );
+            return Ok(None);
         }
+        let operation = dir_operation(dir, &canonical_dir, config);
+        Ok(Some(
+            digests
+                .into_iter()
+                .map(|(file, digest)| (SHARD_DOMAIN, format!("{}:{}", operation, file), digest))
+                .collect(),
+        ))
+    }
+
+    /// Returns the operation and content to extend for `dir`.
+    async fn dir_measurement(
+        &self,
+        dir: &str,
+        config: &ModelDirMeasurementConfig,
+        dry_run: bool,
+    ) -> Result<(String, String)> {
+        let canonical_dir = canonical_dir(dir)?;
+
+        let root_hash = match network_filesystem(&canonical_dir) {
+            Some(fs_type) if config.network_fs_fallback => {
+                warn!(
+                    "Model directory {:?} is on {}; measuring it by manifest instead of verity",
+                    canonical_dir, fs_type
+                );
+                let root = canonical_dir.clone();
+                let digest = tokio::task::spawn_blocking(move || full_manifest_digest(&root))
+                    .await
+                    .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??;
+                format!("{}{}", MANIFEST_FALLBACK_PREFIX, digest)
+            }
+            _ if config.backend == VerityBackend::Native => {
+                if config.activate_verity {
+                    warn!(
+                        "Activating verity needs the cryptpilot backend; measuring {:?} without activating it",
+                        canonical_dir
+                    );
+                }
+                let root = canonical_dir.clone();
+                let root_hash =
+                    tokio::task::spawn_blocking(move || verity::directory_root_hash(&root))
+                        .await
+                        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??;
+                format!("{}{}", NATIVE_VERITY_PREFIX, root_hash)
+            }
+            // Computing reference values must not remount anything.
+            _ if config.activate_verity && !dry_run => format!(
+                "{}{}",
+                VERITY_PREFIX,
+                self.activate_verity(&canonical_dir, config).await?
+            ),
+            _ => format!(
+                "{}{}",
+                VERITY_PREFIX,
+                self.verity_root_hash(&canonical_dir, config).await?
+            ),
+        };
+
+        Ok((dir_operation(dir, &canonical_dir, config), root_hash))
+    }
 
+    /// Formats the directory with cryptpilot and returns its verity root hash.
+    async fn verity_root_hash(
+        &self,
+        canonical_dir: &Path,
+        config: &ModelDirMeasurementConfig,
+    ) -> Result<String> {
         let hash_file = NamedTempFile::new().map_err(|e| {
             MeasurementError::CommandExecution(format!(
                 "Failed to create temp hash file for {}: {}",
@@ -63,8 +231,68 @@ impl ModelDirMeasurer {
                 e
             ))
         })?;
-        let hash_file_path = hash_file.path().to_path_buf();
+        self.format_verity(canonical_dir, hash_file.path(), config)
+            .await
+    }
+
+    /// Formats the directory with its hash tree kept under `verity_hash_dir`,
+    /// then mounts it back over itself through dm-verity so that every later
+    /// read is checked against the measured root hash.
+    async fn activate_verity(
+        &self,
+        canonical_dir: &Path,
+        config: &ModelDirMeasurementConfig,
+    ) -> Result<String> {
+        let canonical_dir_str = canonical_dir.to_string_lossy().to_string();
+        if is_mount_point(canonical_dir) {
+            // Typically activated by an earlier run; mounting again would stack.
+            warn!(
+                "{} is already a mount point; measuring without activating verity",
+                canonical_dir_str
+            );
+            return self.verity_root_hash(canonical_dir, config).await;
+        }
+
+        let hash_dir = Path::new(&config.verity_hash_dir);
+        fs::create_dir_all(hash_dir)?;
+        let hash_file_path = hash_dir.join(format!(
+            "{}.hash",
+            canonical_dir_str.trim_matches('/').replace('/', "_")
+        ));
+        let root_hash = self
+            .format_verity(canonical_dir, &hash_file_path, config)
+            .await?;
 
+        info!(
+            "Activating dm-verity for model directory {:?} with root hash {}",
+            canonical_dir, root_hash
+        );
+        let hash_file_str = hash_file_path.to_string_lossy().to_string();
+        self.run_command(
+            &config.cryptpilot_binary,
+            &[
+                "verity",
+                "mount",
+                "--data-dir",
+                canonical_dir_str.as_str(),
+                "--hash-file",
+                hash_file_str.as_str(),
+                "--root-hash",
+                root_hash.as_str(),
+                canonical_dir_str.as_str(),
+            ],
+        )
+        .await?;
+        Ok(root_hash)
+    }
+
+    async fn format_verity(
+        &self,
+        canonical_dir: &Path,
+        hash_file_path: &Path,
+        config: &ModelDirMeasurementConfig,
+    ) -> Result<String> {
+        let canonical_dir_str = canonical_dir.to_string_lossy().to_string();
         info!(
             "Formatting model directory with cryptpilot: {:?}",
             canonical_dir
@@ -110,23 +338,7 @@ impl ModelDirMeasurer {
             )));
         }
 
-        debug!(
-            "Extending model directory measurement: domain={}, operation={}, root_hash={}",
-            DOMAIN,
-            canonical_dir_str.as_str(),
-            root_hash
-        );
-
-        aa_client
-            .extend_runtime_measurement(
-                config.pcr_index.map(|v| v as u64),
-                DOMAIN,
-                canonical_dir_str.as_str(),
-                &root_hash,
-            )
-            .await?;
-
-        Ok(())
+        Ok(root_hash)
     }
 
     async fn run_command(&self, binary: &str, args: &[&str]) -> Result<std::process::Output> {
@@ -160,17 +372,233 @@ impl ModelDirMeasurer {
     }
 }
 
+/// True if `path` is the root of a mount, i.e. lives on another device than
+/// its parent.
+fn is_mount_point(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(path), path.parent().map(fs::metadata)) {
+        (Ok(meta), Some(Ok(parent))) => meta.dev() != parent.dev(),
+        _ => false,
+    }
+}
+
+/// Returns the name of the filesystem holding `path` if verity cannot be
+/// applied there. These are also the filesystems whose changes inotify
+/// misses.
+#[cfg(target_os = "linux")]
+pub(crate) fn network_filesystem(path: &Path) -> Option<&'static str> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let magic = stat.f_type as u32;
+    NETWORK_FILESYSTEMS
+        .iter()
+        .find(|(m, _)| *m == magic)
+        .map(|(_, name)| *name)
+}
+
+/// dm-verity only exists on Linux, so elsewhere (e.g. when developing on
+/// macOS) every directory takes the manifest path.
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn network_filesystem(_path: &Path) -> Option<&'static str> {
+    Some("a non-Linux host")
+}
+
+/// Resolves a configured model directory.
+fn canonical_dir(dir: &str) -> Result<PathBuf> {
+    let canonical_dir = PathBuf::from(dir)
+        .canonicalize()
+        .map_err(|e| MeasurementError::InvalidDirectory(format!("{} ({})", dir, e)))?;
+    if !canonical_dir.is_dir() {
+        return Err(MeasurementError::InvalidDirectory(format!(
+            "{} is not a directory",
+            canonical_dir.display()
+        )));
+    }
+    Ok(canonical_dir)
+}
+
+/// The operation of a directory: `name@version:path` with
+/// `versioned_operation`, otherwise its canonical path.
+fn dir_operation(dir: &str, canonical_dir: &Path, config: &ModelDirMeasurementConfig) -> String {
+    if config.versioned_operation {
+        versioned_operation(dir, canonical_dir, config)
+    } else {
+        canonical_dir.to_string_lossy().to_string()
+    }
+}
+
+/// The part of a weight index that lists the shards: tensor name to shard
+/// file.
+#[derive(Deserialize)]
+struct ShardIndex {
+    weight_map: BTreeMap<String, String>,
+}
+
+/// Returns the SHA-256 digests of the weight index files directly in
+/// `root`, in name order, followed by those of the shards they list, in
+/// name order, and by `*` with the manifest digest of every other file.
+/// Empty if there is no index.
+fn shard_digests(root: &Path) -> Result<Vec<(String, String)>> {
+    let mut indexes: Vec<String> = fs::read_dir(root)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| SHARD_INDEX_SUFFIXES.iter().any(|s| name.ends_with(s)))
+        .collect();
+    indexes.sort();
+
+    let mut digests = Vec::new();
+    let mut shards = BTreeSet::new();
+    for index in indexes {
+        let raw = fs::read(root.join(&index))?;
+        let parsed: ShardIndex = serde_json::from_slice(&raw).map_err(|e| {
+            MeasurementError::InvalidDirectory(format!(
+                "Invalid weight index {:?}: {}",
+                root.join(&index),
+                e
+            ))
+        })?;
+        for shard in parsed.weight_map.into_values() {
+            // An index must not point outside its directory.
+            if !Path::new(&shard)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+            {
+                return Err(MeasurementError::InvalidDirectory(format!(
+                    "Weight index {} lists shard {:?} outside {:?}",
+                    index, shard, root
+                )));
+            }
+            shards.insert(shard);
+        }
+        digests.push((
+            index,
+            format!("sha256:{}", hex::encode(Sha256::digest(&raw))),
+        ));
+    }
+    if digests.is_empty() {
+        return Ok(digests);
+    }
+    for shard in &shards {
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut fs::File::open(root.join(shard))?, &mut hasher)?;
+        digests.push((
+            shard.clone(),
+            format!("sha256:{}", hex::encode(hasher.finalize())),
+        ));
+    }
+
+    // Configs, tokenizers and remote code load along with the weights. The
+    // download metadata records when files were fetched, so it is left out.
+    let measured: BTreeSet<String> = digests.iter().map(|(file, _)| file.clone()).collect();
+    let rest = manifest_digest_without(root, &|relative| {
+        measured.contains(relative) || Path::new(relative).starts_with(HF_LOCAL_DIR_METADATA)
+    })?;
+    digests.push((
+        "*".to_string(),
+        format!("{}{}", MANIFEST_FALLBACK_PREFIX, rest),
+    ));
+    Ok(digests)
+}
+
+/// Composes `name@version:path` from the configured label or the version
+/// metadata found in the directory, falling back to the canonical path.
+fn versioned_operation(
+    dir: &str,
+    canonical_dir: &Path,
+    config: &ModelDirMeasurementConfig,
+) -> String {
+    let default_name = canonical_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let labelled = config
+        .version_labels
+        .get(dir)
+        .map(|label| match label.split_once('@') {
+            Some((name, version)) => (name.to_string(), version.to_string()),
+            None => (default_name.clone(), label.clone()),
+        });
+    match labelled.or_else(|| detect_version(canonical_dir, &default_name)) {
+        Some((name, version)) => format!("{}@{}:{}", name, version, dir),
+        None => {
+            warn!(
+                "No version metadata found for {:?}; using the canonical path as operation",
+                canonical_dir
+            );
+            canonical_dir.to_string_lossy().to_string()
+        }
+    }
+}
+
+/// Looks for, in order: a `VERSION` file, a Hugging Face cache snapshot
+/// (`models--<org>--<name>/snapshots/<revision>`), and the revision recorded
+/// by a Hugging Face `--local-dir` download.
+fn detect_version(dir: &Path, default_name: &str) -> Option<(String, String)> {
+    if let Ok(content) = fs::read_to_string(dir.join("VERSION")) {
+        if let Some(version) = content
+            .lines()
+            .next()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            return Some((default_name.to_string(), version.to_string()));
+        }
+    }
+
+    let parent = dir.parent()?;
+    if parent.file_name().map_or(false, |n| n == "snapshots") {
+        let repo = parent.parent()?.file_name()?.to_string_lossy();
+        if let Some(repo_id) = repo.strip_prefix("models--") {
+            return Some((repo_id.replace("--", "/"), default_name.to_string()));
+        }
+    }
+
+    let metadata_dir = dir.join(HF_LOCAL_DIR_METADATA);
+    for entry in fs::read_dir(metadata_dir).ok()?.flatten() {
+        if entry.path().extension().map_or(false, |e| e == "metadata") {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            let revision = content.lines().next()?.trim();
+            if !revision.is_empty() {
+                return Some((default_name.to_string(), revision.to_string()));
+            }
+        }
+    }
+    None
+}
+
 #[async_trait]
 impl Measurable for ModelDirMeasurer {
     fn name(&self) -> &str {
         "ModelDirMeasurer"
     }
 
+    fn description(&self) -> &str {
+        "Computes dm-verity root hashes of model directories"
+    }
+
+    fn config_section(&self) -> &str {
+        "model_dir_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.model_dir_measurement.pcr_index
+    }
+
     fn is_enabled(&self, config: Arc<Config>) -> bool {
         config.model_dir_measurement.enable
     }
 
-    async fn measure(&self, config: Arc<Config>, aa_client: Arc<AAClient>) -> Result<()> {
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
         let md_config = &config.model_dir_measurement;
         if !md_config.enable {
             debug!("Model directory measurement is disabled. Skipping.");
@@ -182,27 +610,26 @@ impl Measurable for ModelDirMeasurer {
             return Ok(());
         }
 
-        info!(
-            "Starting model directory measurement with domain '{}' using cryptpilot binary '{}'",
-            DOMAIN, md_config.cryptpilot_binary
-        );
-
-        let mut measured_dirs = HashSet::new();
-
-        for dir in &md_config.directories {
-            if measured_dirs.insert(dir.clone()) {
-                self.measure_single_dir(dir, md_config, aa_client.clone())
-                    .await?;
-            } else {
-                debug!("Skipping duplicate directory entry: {}", dir);
-            }
+        match md_config.backend {
+            VerityBackend::Cryptpilot => info!(
+                "Starting model directory measurement with domain '{}' using cryptpilot binary '{}'",
+                DOMAIN, md_config.cryptpilot_binary
+            ),
+            VerityBackend::Native => info!(
+                "Starting model directory measurement with domain '{}' using the native verity backend",
+                DOMAIN
+            ),
         }
 
-        info!(
-            "Model directory measurement completed for {} unique directories.",
-            measured_dirs.len()
-        );
-        Ok(())
+        let result = self
+            .measure_specific_dirs(
+                &md_config.directories,
+                md_config,
+                config.max_concurrency,
+                sink,
+            )
+            .await;
+        info!("Model directory measurement completed.");
+        result
     }
 }
-
@@ -0,0 +1,303 @@
+// src/modules/mount_measurer.rs
+use crate::canonical_json;
+use crate::config::{Config, MountMeasurementConfig};
+use crate::digest::digest_bytes;
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::Measurable;
+use crate::modules::watcher::ConfigWatcher;
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::Pattern;
+use log::{debug, error, info, warn};
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::os::unix::io::AsRawFd;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
+
+const DOMAIN: &str = "mounts";
+const OPERATION: &str = "mount_table";
+const MOUNTINFO_PATH: &str = "/proc/self/mountinfo";
+
+/// Measures the mount table, so that a filesystem mounted over a measured
+/// path after the initial run shows up in the event log.
+#[derive(Default)]
+pub struct MountMeasurer;
+
+impl MountMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// One mount as measured. Mount and device IDs change between boots and
+/// are left out.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+struct MountEntry {
+    target: String,
+    /// The directory of the source filesystem that is mounted; anything
+    /// but `/` is a bind mount.
+    root: String,
+    fstype: String,
+    source: String,
+    /// Mount and superblock options, sorted.
+    options: Vec<String>,
+}
+
+/// The selected mounts and the digest of their canonical JSON.
+struct Snapshot {
+    mounts: Vec<MountEntry>,
+    digest: String,
+}
+
+/// Decodes the octal escapes (`\040` for a space) of mountinfo fields.
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = bytes
+            .get(i + 1..i + 4)
+            .filter(|digits| bytes[i] == b'\\' && digits.iter().all(|d| (b'0'..=b'7').contains(d)));
+        if let Some(digits) = escape {
+            decoded.push(
+                digits
+                    .iter()
+                    .fold(0u8, |n, d| n.wrapping_mul(8) + (d - b'0')),
+            );
+            i += 4;
+            continue;
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parses `/proc/self/mountinfo` lines:
+/// `id parent major:minor root target options [optional...] - fstype source super_options`.
+fn parse_mountinfo(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (mount, superblock) = line.split_once(" - ")?;
+            let mount: Vec<&str> = mount.split(' ').collect();
+            let superblock: Vec<&str> = superblock.split(' ').collect();
+            if mount.len() < 6 || superblock.len() < 3 {
+                warn!("Ignoring malformed mountinfo line: {}", line);
+                return None;
+            }
+            let mut options: Vec<String> = mount[5]
+                .split(',')
+                .chain(superblock[2].split(','))
+                .map(unescape)
+                .collect();
+            options.sort();
+            options.dedup();
+            Some(MountEntry {
+                target: unescape(mount[4]),
+                root: unescape(mount[3]),
+                fstype: superblock[0].to_string(),
+                source: unescape(superblock[1]),
+                options,
+            })
+        })
+        .collect()
+}
+
+fn patterns(globs: &[String]) -> Result<Vec<Pattern>> {
+    globs
+        .iter()
+        .map(|glob| {
+            Pattern::new(glob).map_err(|e| {
+                MeasurementError::Config(format!("Invalid mount point pattern '{}': {}", glob, e))
+            })
+        })
+        .collect()
+}
+
+/// Selects the configured mounts of `mountinfo` in mount point order.
+/// Mounts stacked on one mount point keep their mount order, so that the
+/// one on top is last.
+fn snapshot(mountinfo: &str, config: &MountMeasurementConfig) -> Result<Snapshot> {
+    let include = patterns(&config.mount_points)?;
+    let exclude = patterns(&config.exclude)?;
+    let mut mounts: Vec<MountEntry> = parse_mountinfo(mountinfo)
+        .into_iter()
+        .filter(|mount| {
+            !config.exclude_fstypes.contains(&mount.fstype)
+                && (include.is_empty() || include.iter().any(|p| p.matches(&mount.target)))
+                && !exclude.iter().any(|p| p.matches(&mount.target))
+        })
+        .collect();
+    mounts.sort_by(|a, b| a.target.cmp(&b.target));
+    let encoded =
+        canonical_json::to_vec(&mounts).map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    let digest = digest_bytes(&encoded, &config.hash_algorithm)?;
+    Ok(Snapshot { mounts, digest })
+}
+
+async fn extend(
+    snapshot: &Snapshot,
+    config: &MountMeasurementConfig,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    debug!(
+        "Extending mount table of {} mounts = {}",
+        snapshot.mounts.len(),
+        snapshot.digest
+    );
+    sink.extend(
+        DOMAIN,
+        OPERATION,
+        &snapshot.digest,
+        config.pcr_index.map(|v| v as u64),
+    )
+    .await
+}
+
+/// Logs the mounts that appeared or went away between two snapshots.
+fn log_changes(old: &Snapshot, new: &Snapshot) {
+    for mount in new.mounts.iter().filter(|m| !old.mounts.contains(m)) {
+        warn!(
+            "Mounted {} ({}, root {}) on {}",
+            mount.source, mount.fstype, mount.root, mount.target
+        );
+    }
+    for mount in old.mounts.iter().filter(|m| !new.mounts.contains(m)) {
+        warn!("Unmounted {} from {}", mount.source, mount.target);
+    }
+}
+
+/// Reads the mount table again through `file`, which also acknowledges the
+/// change that woke the watcher.
+fn reread(file: &mut File) -> Result<String> {
+    let mut content = String::new();
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Re-reads the mount table whenever the kernel flags `/proc/self/mountinfo`
+/// as changed, or every `poll_interval_secs`, and extends it when its digest
+/// differs from the last one.
+fn watch_mountinfo(
+    config: MountMeasurementConfig,
+    sink: Arc<dyn MeasurementSink>,
+    runtime: Handle,
+) -> Result<()> {
+    let mut file = File::open(MOUNTINFO_PATH)?;
+    let mut last = snapshot(&reread(&mut file)?, &config)?;
+    let timeout_ms = match config.poll_interval_secs {
+        0 => -1,
+        secs => secs.saturating_mul(1000).min(i32::MAX as u64) as i32,
+    };
+    info!(
+        "Watching the mount table for changes ({} mounts measured)",
+        last.mounts.len()
+    );
+
+    loop {
+        let mut pollfd = libc::pollfd {
+            fd: file.as_raw_fd(),
+            events: libc::POLLPRI,
+            revents: 0,
+        };
+        // The kernel raises POLLPRI and POLLERR once the mount table differs
+        // from what was last read through this file.
+        if unsafe { libc::poll(&mut pollfd, 1, timeout_ms) } < 0 {
+            let e = io::Error::last_os_error();
+            if e.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(e.into());
+        }
+        let current = match reread(&mut file).and_then(|content| snapshot(&content, &config)) {
+            Ok(current) => current,
+            Err(e) => {
+                error!("Failed to read the mount table: {}", e);
+                continue;
+            }
+        };
+        if current.digest == last.digest {
+            continue;
+        }
+        warn!("Mount table changed; re-measuring");
+        log_changes(&last, &current);
+        if let Err(e) = runtime.block_on(extend(&current, &config, sink.as_ref())) {
+            error!("Failed to extend the changed mount table: {}", e);
+        }
+        last = current;
+    }
+}
+
+#[async_trait]
+impl Measurable for MountMeasurer {
+    fn name(&self) -> &str {
+        "MountMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Records the mount table and re-measures it when it changes"
+    }
+
+    fn config_section(&self) -> &str {
+        "mount_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.mount_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.mount_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let mm_config = &config.mount_measurement;
+        if !mm_config.enable {
+            debug!("Mount measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting mount measurement with domain '{}'", DOMAIN);
+        let snapshot = snapshot(&fs::read_to_string(MOUNTINFO_PATH)?, mm_config)?;
+        extend(&snapshot, mm_config, sink.as_ref()).await?;
+        info!(
+            "Mount measurement completed for {} mounts.",
+            snapshot.mounts.len()
+        );
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ConfigWatcher for MountMeasurer {
+    fn name(&self) -> &str {
+        "MountMeasurer"
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.mount_measurement.enable && config.mount_measurement.watch
+    }
+
+    async fn watch(
+        &self,
+        _config_path: PathBuf,
+        shared_config: Arc<RwLock<Config>>,
+        sink: Arc<dyn MeasurementSink>,
+    ) -> Result<()> {
+        let config = shared_config.read().await.mount_measurement.clone();
+        let runtime = Handle::current();
+        tokio::task::spawn_blocking(move || watch_mountinfo(config, sink, runtime))
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?
+    }
+}
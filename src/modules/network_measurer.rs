@@ -0,0 +1,228 @@
+// src/modules/network_measurer.rs
+use crate::canonical_json;
+use crate::config::{Config, FirewallBackend, NetworkMeasurementConfig};
+use crate::digest::digest_bytes;
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use log::{debug, info};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use tokio::process::Command;
+
+const DOMAIN: &str = "network";
+const LISTENERS_OPERATION: &str = "listeners";
+
+/// `st` of a listening TCP socket in `/proc/net/tcp`.
+const TCP_LISTEN: &str = "0A";
+/// `st` of an unconnected UDP socket, which is how bound ones show up.
+const UDP_UNCONNECTED: &str = "07";
+
+/// Measures the listening sockets and, optionally, the firewall rules, so
+/// that attestation shows which services a CVM exposes.
+#[derive(Default)]
+pub struct NetworkStateMeasurer;
+
+impl NetworkStateMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// One listening socket as measured. Inodes, queues and owners change
+/// between boots and are left out.
+#[derive(Debug, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+struct Listener {
+    protocol: String,
+    address: String,
+    port: u16,
+}
+
+/// Decodes a `/proc/net` address: the address words are printed as host
+/// order integers, the port as a big-endian one.
+fn parse_address(field: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let word = |i: usize| -> Option<[u8; 4]> {
+        let hex = address.get(i * 8..i * 8 + 8)?;
+        Some(u32::from_str_radix(hex, 16).ok()?.to_ne_bytes())
+    };
+    let address = match address.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(word(0)?)),
+        32 => {
+            let mut octets = [0u8; 16];
+            for i in 0..4 {
+                octets[i * 4..i * 4 + 4].copy_from_slice(&word(i)?);
+            }
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+    Some((address, port))
+}
+
+/// The listening sockets of one `/proc/net` table.
+fn read_listeners(protocol: &str, config: &NetworkMeasurementConfig) -> Result<Vec<Listener>> {
+    let state = match protocol {
+        "tcp" | "tcp6" => TCP_LISTEN,
+        "udp" | "udp6" => UDP_UNCONNECTED,
+        other => {
+            return Err(MeasurementError::Config(format!(
+                "Unknown protocol '{}'; expected tcp, tcp6, udp or udp6",
+                other
+            )))
+        }
+    };
+    let table = fs::read_to_string(format!("/proc/net/{}", protocol))?;
+    let mut listeners = Vec::new();
+    for line in table.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 || fields[3] != state {
+            continue;
+        }
+        let Some((address, port)) = parse_address(fields[1]) else {
+            debug!("Ignoring malformed /proc/net/{} line: {}", protocol, line);
+            continue;
+        };
+        if port == 0
+            || (!config.ports.is_empty() && !config.ports.contains(&port))
+            || (config.exclude_loopback && address.is_loopback())
+        {
+            continue;
+        }
+        listeners.push(Listener {
+            protocol: protocol.to_string(),
+            address: address.to_string(),
+            port,
+        });
+    }
+    Ok(listeners)
+}
+
+async fn command_output(binary: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(binary)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| {
+            MeasurementError::CommandExecution(format!("Failed to run {}: {}", binary, e))
+        })?;
+    if !output.status.success() {
+        return Err(MeasurementError::CommandExecution(format!(
+            "{} failed with status {}: {}",
+            binary,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The ruleset of `backend`, without what changes while the rules do not:
+/// comments with timestamps and packet counters.
+async fn firewall_ruleset(
+    backend: FirewallBackend,
+    config: &NetworkMeasurementConfig,
+) -> Result<String> {
+    let ruleset = match backend {
+        FirewallBackend::Nftables => {
+            command_output(&config.nft_binary, &["--stateless", "list", "ruleset"]).await?
+        }
+        FirewallBackend::Iptables => {
+            let v4 = command_output(&config.iptables_save_binary, &[]).await?;
+            let v6 = command_output(&config.ip6tables_save_binary, &[]).await?;
+            let counters = Regex::new(r"\[\d+:\d+\]").expect("valid counter pattern");
+            format!("{}{}", v4, v6)
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .map(|line| format!("{}\n", counters.replace_all(line, "[0:0]")))
+                .collect()
+        }
+    };
+    Ok(ruleset)
+}
+
+fn firewall_operation(backend: FirewallBackend) -> &'static str {
+    match backend {
+        FirewallBackend::Nftables => "firewall:nftables",
+        FirewallBackend::Iptables => "firewall:iptables",
+    }
+}
+
+#[async_trait]
+impl Measurable for NetworkStateMeasurer {
+    fn name(&self) -> &str {
+        "NetworkStateMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Records the listening sockets and firewall rules"
+    }
+
+    fn config_section(&self) -> &str {
+        "network_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.network_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.network_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let nm_config = &config.network_measurement;
+        if !nm_config.enable {
+            debug!("Network measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting network measurement with domain '{}'", DOMAIN);
+        let pcr_index = nm_config.pcr_index.map(|v| v as u64);
+        let mut listeners = BTreeSet::new();
+        for protocol in &nm_config.protocols {
+            listeners.extend(read_listeners(protocol, nm_config)?);
+        }
+        let encoded = canonical_json::to_vec(&listeners)
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+        let digest = digest_bytes(&encoded, &nm_config.hash_algorithm)?;
+        debug!(
+            "Extending {} listening sockets ({}) = {}",
+            listeners.len(),
+            String::from_utf8_lossy(&encoded),
+            digest
+        );
+        sink.extend(DOMAIN, LISTENERS_OPERATION, &digest, pcr_index)
+            .await?;
+
+        let mut failures = EntryFailures::default();
+        for backend in &nm_config.firewall {
+            let operation = firewall_operation(*backend);
+            let result = async {
+                let ruleset = firewall_ruleset(*backend, nm_config).await?;
+                let digest = digest_bytes(ruleset.as_bytes(), &nm_config.hash_algorithm)?;
+                debug!("Extending {} = {}", operation, digest);
+                sink.extend(DOMAIN, operation, &digest, pcr_index).await
+            }
+            .await;
+            if let Err(e) = result {
+                failures.record(operation, false, e);
+            }
+        }
+        info!(
+            "Network measurement completed for {} listening sockets.",
+            listeners.len()
+        );
+        failures.into_result()
+    }
+}
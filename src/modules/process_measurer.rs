@@ -0,0 +1,177 @@
+// src/modules/process_measurer.rs
+use crate::config::{Config, ProcessMeasurementConfig};
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::Pattern;
+use log::{debug, info};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DOMAIN: &str = "process";
+const PROC: &str = "/proc";
+
+/// Measures the executables of running processes selected by name.
+#[derive(Default)]
+pub struct ProcessMeasurer;
+
+/// A running process as seen in `/proc`.
+struct ProcessInfo {
+    pid: u32,
+    comm: String,
+    /// Target of `/proc/<pid>/exe`; ends in ` (deleted)` when the binary was
+    /// replaced or removed after the process started.
+    exe: PathBuf,
+}
+
+impl ProcessMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Lists the processes whose executable can be resolved. Kernel threads
+/// have none, and processes of other users are unreadable without
+/// privileges; both are skipped.
+fn running_processes() -> Result<Vec<ProcessInfo>> {
+    let mut processes = Vec::new();
+    for entry in fs::read_dir(PROC)?.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        let dir = entry.path();
+        let Ok(exe) = fs::read_link(dir.join("exe")) else {
+            continue;
+        };
+        let comm = fs::read_to_string(dir.join("comm"))
+            .map(|c| c.trim_end().to_string())
+            .unwrap_or_default();
+        processes.push(ProcessInfo { pid, comm, exe });
+    }
+    processes.sort_by_key(|p| p.pid);
+    Ok(processes)
+}
+
+fn matches(pattern: &Pattern, process: &ProcessInfo) -> bool {
+    let exe_name = process
+        .exe
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    pattern.matches(&process.comm) || pattern.matches(exe_name.trim_end_matches(" (deleted)"))
+}
+
+async fn measure_process(
+    process: &ProcessInfo,
+    config: &ProcessMeasurementConfig,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    // `/proc/<pid>/exe` reaches the file the process runs even if the path
+    // now names a different one.
+    let exe = Path::new(PROC).join(process.pid.to_string()).join("exe");
+    let digest = {
+        let hash_algorithm = config.hash_algorithm.clone();
+        tokio::task::spawn_blocking(move || digest_reader(&mut File::open(exe)?, &hash_algorithm))
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+    };
+    let operation = process.exe.to_string_lossy();
+    debug!(
+        "Extending process measurement {} (pid {}, {}) = {}",
+        operation, process.pid, process.comm, digest
+    );
+    sink.extend(
+        DOMAIN,
+        &operation,
+        &digest,
+        config.pcr_index.map(|v| v as u64),
+    )
+    .await
+}
+
+#[async_trait]
+impl Measurable for ProcessMeasurer {
+    fn name(&self) -> &str {
+        "ProcessMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Hashes the executables of running processes selected by name"
+    }
+
+    fn config_section(&self) -> &str {
+        "process_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.process_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.process_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let pm_config = &config.process_measurement;
+        if !pm_config.enable {
+            debug!("Process measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting process measurement with domain '{}'", DOMAIN);
+        let processes = tokio::task::spawn_blocking(running_processes)
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??;
+        let mut measured: HashSet<PathBuf> = HashSet::new();
+        let mut failures = EntryFailures::default();
+        for entry in &pm_config.processes {
+            let pattern = match Pattern::new(entry.path()) {
+                Ok(pattern) => pattern,
+                Err(e) => {
+                    failures.record(entry.path(), entry.is_critical(), e.into());
+                    continue;
+                }
+            };
+            let matched: Vec<&ProcessInfo> =
+                processes.iter().filter(|p| matches(&pattern, p)).collect();
+            if matched.is_empty() {
+                if entry.is_critical() {
+                    let e = std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        "no running process matches",
+                    );
+                    failures.record(entry.path(), true, e.into());
+                } else {
+                    debug!("No running process matches '{}'", entry.path());
+                }
+                continue;
+            }
+            for process in matched {
+                // Processes sharing a binary are measured once.
+                if !measured.insert(process.exe.clone()) {
+                    continue;
+                }
+                if let Err(e) = measure_process(process, pm_config, &sink).await {
+                    failures.record(
+                        &format!("{} (pid {})", process.exe.to_string_lossy(), process.pid),
+                        entry.is_critical(),
+                        e,
+                    );
+                }
+            }
+        }
+        info!(
+            "Process measurement completed for {} executables.",
+            measured.len()
+        );
+        failures.into_result()
+    }
+}
@@ -0,0 +1,102 @@
+// src/modules/runner.rs
+use crate::config::{Config, FailurePolicy};
+use crate::error::MeasurementError;
+use crate::modules::registered_measurers;
+use crate::sink::MeasurementSink;
+use crate::status::DaemonStatus;
+use log::{error, info};
+use std::sync::Arc;
+
+/// Outcome of running every enabled measurer once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    Succeeded,
+    /// Best-effort entries or measurers failed under the `continue` policy.
+    Failed,
+    /// A measurer under the `fail_at_end` policy failed; the others ran.
+    Incomplete,
+    /// An entry marked `critical = true` could not be measured, or a
+    /// measurer under the `fail_fast` policy failed and stopped the run.
+    CriticalFailure,
+}
+
+impl RunOutcome {
+    /// Exit status of a one-shot run: 1 when measurement stopped or a
+    /// critical entry failed, 2 when `fail_at_end` measurers failed.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RunOutcome::Succeeded | RunOutcome::Failed => 0,
+            RunOutcome::Incomplete => 2,
+            RunOutcome::CriticalFailure => 1,
+        }
+    }
+}
+
+/// What [`run_measurers`] did.
+#[derive(Debug)]
+pub struct MeasurementRun {
+    pub outcome: RunOutcome,
+    /// `<measurer>: <error>` for every measurer that failed, and the reason
+    /// the run stopped early, if it did.
+    pub failures: Vec<String>,
+}
+
+/// Runs every enabled measurer once under the `[failure_policy]` of each and
+/// the per-run limits of `sink`, recording each result in `status` when
+/// given.
+pub async fn run_measurers(
+    config: Arc<Config>,
+    sink: Arc<dyn MeasurementSink>,
+    status: Option<&DaemonStatus>,
+) -> MeasurementRun {
+    let mut outcome = RunOutcome::Succeeded;
+    let mut failures = Vec::new();
+    sink.begin_run();
+    for measurer in registered_measurers() {
+        if let Err(e) = sink.check_run() {
+            error!("{}; skipping the remaining measurers.", e);
+            failures.push(e.to_string());
+            outcome = RunOutcome::CriticalFailure;
+            break;
+        }
+        if measurer.is_enabled(config.clone()) {
+            info!("Running measurer: {}", measurer.name());
+            let result = measurer.measure(config.clone(), sink.clone()).await;
+            if let Some(status) = status {
+                status.record_measurer(measurer.name(), &result);
+            }
+            let Err(e) = result else {
+                continue;
+            };
+            error!("Error during {} execution: {}", measurer.name(), e);
+            failures.push(format!("{}: {}", measurer.name(), e));
+            if matches!(e, MeasurementError::CriticalEntry(_)) {
+                outcome = RunOutcome::CriticalFailure;
+            }
+            match config.failure_policy(measurer.config_section()) {
+                FailurePolicy::Continue => {
+                    if outcome == RunOutcome::Succeeded {
+                        outcome = RunOutcome::Failed;
+                    }
+                }
+                FailurePolicy::FailAtEnd => {
+                    if outcome != RunOutcome::CriticalFailure {
+                        outcome = RunOutcome::Incomplete;
+                    }
+                }
+                FailurePolicy::FailFast => {
+                    error!(
+                        "{} failed under the fail_fast policy; skipping the remaining measurers.",
+                        measurer.name()
+                    );
+                    outcome = RunOutcome::CriticalFailure;
+                    break;
+                }
+            }
+        } else {
+            info!("Measurer {} is disabled. Skipping.", measurer.name());
+        }
+    }
+    sink.end_run();
+    MeasurementRun { outcome, failures }
+}
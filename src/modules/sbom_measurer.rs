@@ -0,0 +1,318 @@
+// src/modules/sbom_measurer.rs
+use crate::config::{Config, SbomMeasurementConfig};
+use crate::digest::{self, digest_bytes, Hasher};
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::glob;
+use log::{debug, info, warn};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+const DOMAIN: &str = "sbom";
+
+/// Measures SPDX and CycloneDX documents and, optionally, checks the files
+/// they list against the disk, tying supply-chain metadata into the
+/// runtime event log.
+#[derive(Default)]
+pub struct SbomMeasurer;
+
+impl SbomMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[derive(Deserialize)]
+struct SpdxDocument {
+    #[serde(rename = "spdxVersion")]
+    spdx_version: String,
+    #[serde(default)]
+    files: Vec<SpdxFile>,
+}
+
+#[derive(Deserialize)]
+struct SpdxFile {
+    #[serde(rename = "fileName")]
+    file_name: String,
+    #[serde(default)]
+    checksums: Vec<SpdxChecksum>,
+}
+
+#[derive(Deserialize)]
+struct SpdxChecksum {
+    algorithm: String,
+    #[serde(rename = "checksumValue")]
+    checksum_value: String,
+}
+
+#[derive(Deserialize)]
+struct CycloneDxDocument {
+    #[serde(rename = "specVersion")]
+    spec_version: String,
+    #[serde(default)]
+    components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Deserialize)]
+struct CycloneDxComponent {
+    #[serde(rename = "type")]
+    kind: String,
+    name: String,
+    #[serde(default)]
+    hashes: Vec<CycloneDxHash>,
+    #[serde(default)]
+    components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Deserialize)]
+struct CycloneDxHash {
+    alg: String,
+    content: String,
+}
+
+/// A file an SBOM lists with a hash this tool can check.
+struct ListedFile {
+    name: String,
+    algorithm: &'static str,
+    expected: String,
+}
+
+/// An SBOM as far as it is measured: its format and the files it lists.
+struct Sbom {
+    format: &'static str,
+    version: String,
+    files: Vec<ListedFile>,
+}
+
+/// The first hash of `hashes` that can be checked. SPDX (`SHA256`) and
+/// CycloneDX (`SHA-256`) names map to the registered ones.
+fn listed_file<'a>(
+    name: &str,
+    hashes: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Option<ListedFile> {
+    hashes.into_iter().find_map(|(algorithm, value)| {
+        Some(ListedFile {
+            name: name.to_string(),
+            algorithm: digest::canonical_name(algorithm)?,
+            expected: value.to_lowercase(),
+        })
+    })
+}
+
+fn collect_cyclonedx_files(components: &[CycloneDxComponent], files: &mut Vec<ListedFile>) {
+    for component in components {
+        if component.kind == "file" {
+            let hashes = component
+                .hashes
+                .iter()
+                .map(|h| (h.alg.as_str(), h.content.as_str()));
+            files.extend(listed_file(&component.name, hashes));
+        }
+        collect_cyclonedx_files(&component.components, files);
+    }
+}
+
+/// Parses an SPDX or CycloneDX JSON document, telling them apart by
+/// `spdxVersion` and `bomFormat`.
+fn parse_sbom(content: &[u8]) -> Result<Sbom> {
+    let invalid =
+        |e: &dyn std::fmt::Display| MeasurementError::Other(anyhow::anyhow!("Invalid SBOM: {}", e));
+    let document: serde_json::Value = serde_json::from_slice(content).map_err(|e| invalid(&e))?;
+    if document.get("spdxVersion").is_some() {
+        let spdx: SpdxDocument = serde_json::from_value(document).map_err(|e| invalid(&e))?;
+        let files = spdx
+            .files
+            .iter()
+            .filter_map(|file| {
+                let hashes = file
+                    .checksums
+                    .iter()
+                    .map(|c| (c.algorithm.as_str(), c.checksum_value.as_str()));
+                listed_file(&file.file_name, hashes)
+            })
+            .collect();
+        return Ok(Sbom {
+            format: "spdx",
+            version: spdx.spdx_version,
+            files,
+        });
+    }
+    if document.get("bomFormat").and_then(|f| f.as_str()) == Some("CycloneDX") {
+        let cyclonedx: CycloneDxDocument =
+            serde_json::from_value(document).map_err(|e| invalid(&e))?;
+        let mut files = Vec::new();
+        collect_cyclonedx_files(&cyclonedx.components, &mut files);
+        return Ok(Sbom {
+            format: "cyclonedx",
+            version: cyclonedx.spec_version,
+            files,
+        });
+    }
+    Err(invalid(&"neither an SPDX nor a CycloneDX JSON document"))
+}
+
+/// Hex digest of the file at `path`, with an algorithm returned by
+/// [`digest::canonical_name`].
+fn file_hex_digest(path: &Path, algorithm: &str) -> Result<String> {
+    let mut hasher = Hasher::new(algorithm)?;
+    hasher.update_reader(&mut File::open(path)?)?;
+    Ok(hasher.finalize_hex())
+}
+
+/// Resolves a listed file name under `root`. Names are taken as relative,
+/// and names leaving `root` are rejected.
+fn resolve(root: &Path, name: &str) -> Option<PathBuf> {
+    let mut path = root.to_path_buf();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(path)
+}
+
+/// Checks the listed files against the disk and returns a description of
+/// each that is missing or differs.
+fn verify_files(files: &[ListedFile], root: &Path) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    for file in files {
+        let Some(path) = resolve(root, &file.name) else {
+            mismatches.push(format!("{}: path leaves {:?}", file.name, root));
+            continue;
+        };
+        match file_hex_digest(&path, file.algorithm) {
+            Ok(actual) if actual == file.expected => {}
+            Ok(actual) => mismatches.push(format!(
+                "{}: {} is {}, the SBOM lists {}",
+                file.name, file.algorithm, actual, file.expected
+            )),
+            Err(e) => mismatches.push(format!("{}: {}", file.name, e)),
+        }
+    }
+    mismatches
+}
+
+/// Extends the digest of the SBOM at `path` and, when enabled, checks the
+/// files it lists.
+async fn measure_sbom(
+    path: &Path,
+    sb_config: &SbomMeasurementConfig,
+    sink: &dyn MeasurementSink,
+) -> Result<()> {
+    let content = fs::read(path)?;
+    let sbom = parse_sbom(&content)?;
+    let digest = digest_bytes(&content, &sb_config.hash_algorithm)?;
+    let operation = format!("{}:{}", sbom.format, path.to_string_lossy());
+    debug!(
+        "Extending {} {} SBOM {} = {}",
+        sbom.format, sbom.version, operation, digest
+    );
+    sink.extend(
+        DOMAIN,
+        &operation,
+        &digest,
+        sb_config.pcr_index.map(|v| v as u64),
+    )
+    .await?;
+
+    if !sb_config.verify_components {
+        return Ok(());
+    }
+    let checked = sbom.files.len();
+    let root = PathBuf::from(&sb_config.root);
+    let mismatches = tokio::task::spawn_blocking(move || verify_files(&sbom.files, &root))
+        .await
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    for mismatch in &mismatches {
+        warn!("SBOM {:?} does not match the disk: {}", path, mismatch);
+    }
+    info!(
+        "Checked {} files listed in {:?}: {} mismatches",
+        checked,
+        path,
+        mismatches.len()
+    );
+    if sb_config.fail_on_mismatch && !mismatches.is_empty() {
+        return Err(MeasurementError::Other(anyhow::anyhow!(
+            "{} of {} listed files do not match the disk",
+            mismatches.len(),
+            checked
+        )));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl Measurable for SbomMeasurer {
+    fn name(&self) -> &str {
+        "SbomMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Measures SPDX and CycloneDX SBOMs and checks the files they list"
+    }
+
+    fn config_section(&self) -> &str {
+        "sbom_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.sbom_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.sbom_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let sb_config = &config.sbom_measurement;
+        if !sb_config.enable {
+            debug!("SBOM measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting SBOM measurement with domain '{}'", DOMAIN);
+        let mut measured = HashSet::new();
+        let mut failures = EntryFailures::default();
+        for entry in &sb_config.sboms {
+            let pattern = entry.path();
+            let paths: Vec<PathBuf> = match glob(pattern) {
+                Ok(paths) => paths.flatten().filter(|p| p.is_file()).collect(),
+                Err(e) => {
+                    failures.record(pattern, entry.is_critical(), e.into());
+                    continue;
+                }
+            };
+            if paths.is_empty() {
+                if entry.is_critical() {
+                    let e = std::io::Error::new(std::io::ErrorKind::NotFound, "no file matches");
+                    failures.record(pattern, true, e.into());
+                } else {
+                    warn!("SBOM pattern '{}' matches no files", pattern);
+                }
+                continue;
+            }
+            for path in paths {
+                if !measured.insert(path.clone()) {
+                    continue;
+                }
+                if let Err(e) = measure_sbom(&path, sb_config, sink.as_ref()).await {
+                    failures.record(&path.to_string_lossy(), entry.is_critical(), e);
+                }
+            }
+        }
+        info!("SBOM measurement completed for {} SBOMs.", measured.len());
+        failures.into_result()
+    }
+}
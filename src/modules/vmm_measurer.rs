@@ -0,0 +1,239 @@
+// src/modules/vmm_measurer.rs
+use crate::config::{Config, MeasurementEntry, VmmMeasurementConfig};
+use crate::digest::digest_reader;
+use crate::error::{MeasurementError, Result};
+use crate::modules::measurable::{EntryFailures, Measurable};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use glob::glob;
+use log::{debug, info, warn};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const DOMAIN: &str = "vmm";
+
+const QCOW2_MAGIC: &[u8; 4] = b"QFI\xfb";
+/// Longest backing chain followed, guarding against loops.
+const MAX_BACKING_DEPTH: usize = 16;
+
+/// Measures the virtualization stack of hosts running nested VMs: libvirt
+/// domain XML, firmware binaries and disk images with their backing chains.
+#[derive(Default)]
+pub struct VmmMeasurer;
+
+/// What a configured path is, used as the operation prefix.
+#[derive(Clone, Copy)]
+enum ArtifactKind {
+    DomainXml,
+    Firmware,
+    Disk,
+}
+
+impl ArtifactKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ArtifactKind::DomainXml => "xml",
+            ArtifactKind::Firmware => "firmware",
+            ArtifactKind::Disk => "disk",
+        }
+    }
+}
+
+impl VmmMeasurer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Measures every file matched by `entries`, plus the backing chain of
+    /// disk images when enabled.
+    async fn measure_entries(
+        &self,
+        kind: ArtifactKind,
+        entries: &[MeasurementEntry],
+        config: &VmmMeasurementConfig,
+        sink: &dyn MeasurementSink,
+        measured: &mut HashSet<PathBuf>,
+        failures: &mut EntryFailures,
+    ) {
+        for entry in entries {
+            let pattern = entry.path();
+            let paths: Vec<PathBuf> = match glob(pattern) {
+                Ok(paths) => paths.flatten().filter(|p| p.is_file()).collect(),
+                Err(e) => {
+                    failures.record(pattern, entry.is_critical(), e.into());
+                    continue;
+                }
+            };
+            if paths.is_empty() {
+                if entry.is_critical() {
+                    let e = std::io::Error::new(std::io::ErrorKind::NotFound, "no file matches");
+                    failures.record(pattern, true, e.into());
+                } else {
+                    warn!(
+                        "VMM {} pattern '{}' matches no files",
+                        kind.as_str(),
+                        pattern
+                    );
+                }
+                continue;
+            }
+
+            for path in paths {
+                let chain = match kind {
+                    ArtifactKind::Disk if config.follow_backing_chain => {
+                        match backing_chain(&path) {
+                            Ok(chain) => chain,
+                            Err(e) => {
+                                failures.record(pattern, entry.is_critical(), e);
+                                continue;
+                            }
+                        }
+                    }
+                    _ => vec![path],
+                };
+                for path in chain {
+                    if !measured.insert(path.clone()) {
+                        debug!("Skipping already measured VMM artifact {:?}", path);
+                        continue;
+                    }
+                    if let Err(e) = self.measure_file(kind, &path, config, sink).await {
+                        failures.record(&path.to_string_lossy(), entry.is_critical(), e);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn measure_file(
+        &self,
+        kind: ArtifactKind,
+        path: &Path,
+        config: &VmmMeasurementConfig,
+        sink: &dyn MeasurementSink,
+    ) -> Result<()> {
+        let digest = {
+            let path = path.to_path_buf();
+            let hash_algorithm = config.hash_algorithm.clone();
+            tokio::task::spawn_blocking(move || {
+                digest_reader(&mut File::open(&path)?, &hash_algorithm)
+            })
+            .await
+            .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))??
+        };
+        let operation = format!("{}:{}", kind.as_str(), path.to_string_lossy());
+        debug!("Extending VMM measurement {} = {}", operation, digest);
+        sink.extend(
+            DOMAIN,
+            &operation,
+            &digest,
+            config.pcr_index.map(|v| v as u64),
+        )
+        .await
+    }
+}
+
+/// Returns `image` followed by the images it is layered on, read from the
+/// qcow2 header's backing file name. Relative backing names are resolved
+/// against the directory of the image that refers to them.
+fn backing_chain(image: &Path) -> Result<Vec<PathBuf>> {
+    let mut chain = vec![image.to_path_buf()];
+    let mut current = image.to_path_buf();
+    while let Some(backing) = qcow2_backing_file(&current)? {
+        let backing = match current.parent() {
+            Some(dir) if backing.is_relative() => dir.join(backing),
+            _ => backing,
+        };
+        if chain.contains(&backing) || chain.len() > MAX_BACKING_DEPTH {
+            return Err(MeasurementError::Config(format!(
+                "Backing chain of {:?} loops or is deeper than {}",
+                image, MAX_BACKING_DEPTH
+            )));
+        }
+        chain.push(backing.clone());
+        current = backing;
+    }
+    Ok(chain)
+}
+
+/// Reads the backing file name of a qcow2 image, or `None` for raw images
+/// and qcow2 images without a backing file.
+fn qcow2_backing_file(path: &Path) -> Result<Option<PathBuf>> {
+    let mut file = File::open(path)?;
+    let mut header = [0u8; 20];
+    if file.read_exact(&mut header).is_err() || &header[..4] != QCOW2_MAGIC {
+        return Ok(None);
+    }
+    let offset = u64::from_be_bytes(header[8..16].try_into().unwrap_or_default());
+    let size = u32::from_be_bytes(header[16..20].try_into().unwrap_or_default());
+    if offset == 0 || size == 0 {
+        return Ok(None);
+    }
+    let mut name = vec![0u8; size as usize];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut name)?;
+    Ok(Some(PathBuf::from(
+        String::from_utf8_lossy(&name).into_owned(),
+    )))
+}
+
+#[async_trait]
+impl Measurable for VmmMeasurer {
+    fn name(&self) -> &str {
+        "VmmMeasurer"
+    }
+
+    fn description(&self) -> &str {
+        "Hashes libvirt domain XML, VM firmware and disk image backing chains"
+    }
+
+    fn config_section(&self) -> &str {
+        "vmm_measurement"
+    }
+
+    fn domain(&self) -> &str {
+        DOMAIN
+    }
+
+    fn pcr_index(&self, config: &Config) -> Option<u32> {
+        config.vmm_measurement.pcr_index
+    }
+
+    fn is_enabled(&self, config: Arc<Config>) -> bool {
+        config.vmm_measurement.enable
+    }
+
+    async fn measure(&self, config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+        let vmm_config = &config.vmm_measurement;
+        if !vmm_config.enable {
+            debug!("VMM measurement is disabled. Skipping.");
+            return Ok(());
+        }
+
+        info!("Starting VMM measurement with domain '{}'", DOMAIN);
+        let mut measured = HashSet::new();
+        let mut failures = EntryFailures::default();
+        for (kind, entries) in [
+            (ArtifactKind::DomainXml, &vmm_config.domain_xml),
+            (ArtifactKind::Firmware, &vmm_config.firmware),
+            (ArtifactKind::Disk, &vmm_config.disk_images),
+        ] {
+            self.measure_entries(
+                kind,
+                entries,
+                vmm_config,
+                &sink,
+                &mut measured,
+                &mut failures,
+            )
+            .await;
+        }
+        info!(
+            "VMM measurement completed for {} artifacts.",
+            measured.len()
+        );
+        failures.into_result()
+    }
+}
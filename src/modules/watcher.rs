@@ -1,7 +1,7 @@
 // src/modules/watcher.rs
 use crate::config::Config;
 use crate::error::Result;
-use crate::rpc_client::AAClient;
+use crate::sink::MeasurementSink;
 use async_trait::async_trait;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -20,6 +20,6 @@ pub trait ConfigWatcher {
         &self,
         config_path: PathBuf,
         shared_config: Arc<RwLock<Config>>,
-        aa_client: Arc<AAClient>,
+        sink: Arc<dyn MeasurementSink>,
     ) -> Result<()>;
 }
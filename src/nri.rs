@@ -0,0 +1,416 @@
+// src/nri.rs
+use crate::canonical_json;
+use crate::config::{Config, NriMeasurementConfig};
+use crate::error::{MeasurementError, Result};
+use crate::hook;
+use crate::modules::container_image_measurer::crictl_json;
+use crate::rpc_generated::nri::{
+    ConfigureRequest, ConfigureResponse, Container, CreateContainerRequest,
+    CreateContainerResponse, Empty, Event, PodSandbox, RegisterPluginRequest, StateChangeEvent,
+    SynchronizeRequest, SynchronizeResponse,
+};
+use crate::rpc_generated::nri_ttrpc::{create_plugin, Plugin, RuntimeClient};
+use crate::sink::MeasurementSink;
+use async_trait::async_trait;
+use log::{debug, error, info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, Notify};
+use ttrpc::asynchronous::{Client, Server, TtrpcContext};
+
+const SPEC_DOMAIN: &str = "container_spec";
+const IMAGE_DOMAIN: &str = "container_image";
+const ROOTFS_DOMAIN: &str = "container_rootfs";
+
+/// Annotations under which CRI runtimes record the image a container runs.
+pub(crate) const IMAGE_ANNOTATIONS: &[&str] = &[
+    "io.kubernetes.cri.image-name",
+    "io.kubernetes.cri-o.ImageName",
+];
+
+// NRI multiplexes two ttrpc connections over the plugin socket. Each frame is
+// a big-endian (connection id, payload length) header followed by payload.
+const PLUGIN_SERVICE_CONN: u32 = 1;
+const RUNTIME_SERVICE_CONN: u32 = 2;
+const MUX_HEADER_LEN: usize = 8;
+const MUX_READ_CHUNK: usize = 64 * 1024;
+
+/// The parts of a container that are measured, serialized with sorted keys so
+/// that the digest does not depend on map iteration order.
+#[derive(Serialize)]
+struct ContainerSpecDigestInput<'a> {
+    args: &'a [String],
+    env: &'a [String],
+    mounts: Vec<MountDigestInput<'a>>,
+    annotations: BTreeMap<&'a str, &'a str>,
+    labels: BTreeMap<&'a str, &'a str>,
+}
+
+#[derive(Serialize)]
+struct MountDigestInput<'a> {
+    destination: &'a str,
+    #[serde(rename = "type")]
+    mount_type: &'a str,
+    source: &'a str,
+    options: &'a [String],
+}
+
+struct MeasurementPlugin {
+    nri_config: NriMeasurementConfig,
+    structured_content: bool,
+    sink: Arc<dyn MeasurementSink>,
+    shutdown: Arc<Notify>,
+}
+
+impl MeasurementPlugin {
+    async fn measure_container(&self, pod: &PodSandbox, container: &Container) -> Result<()> {
+        let operation = format!("{}/{}/{}", pod.namespace, pod.name, container.name);
+        let pcr_index = self.nri_config.pcr_index.map(|v| v as u64);
+
+        let spec_digest = container_spec_digest(container, self.structured_content)?;
+        debug!("Container {} spec digest: {}", operation, spec_digest);
+        let image = IMAGE_ANNOTATIONS
+            .iter()
+            .find_map(|key| container.annotations.get(*key))
+            .ok_or_else(|| {
+                MeasurementError::Nri(format!("Container {} has no image annotation", operation))
+            })?;
+        let image_id = self.image_id(image).await?;
+        debug!("Container {} image {} is {}", operation, image, image_id);
+
+        self.sink
+            .extend(SPEC_DOMAIN, &operation, &spec_digest, pcr_index)
+            .await?;
+        self.sink
+            .extend(IMAGE_DOMAIN, &operation, &image_id, pcr_index)
+            .await?;
+        info!("Measured container {} before start", operation);
+        Ok(())
+    }
+
+    /// Extends the tree digest of the container's rootfs, which containerd
+    /// mounts only after the container was created, right before it starts.
+    async fn measure_rootfs(&self, pod: &PodSandbox, container: &Container) -> Result<()> {
+        let operation = format!("{}/{}/{}", pod.namespace, pod.name, container.name);
+        let pcr_index = self.nri_config.pcr_index.map(|v| v as u64);
+        let rootfs = Path::new(&self.nri_config.task_dir)
+            .join(&container.id)
+            .join("rootfs");
+        let digest = {
+            let rootfs = rootfs.clone();
+            tokio::task::spawn_blocking(move || hook::tree_digest(&rootfs))
+                .await
+                .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?
+                .map_err(|e| {
+                    MeasurementError::Nri(format!(
+                        "Cannot hash the rootfs {} of container {}: {}",
+                        rootfs.display(),
+                        operation,
+                        e
+                    ))
+                })?
+        };
+        debug!("Container {} rootfs digest: {}", operation, digest);
+
+        self.sink
+            .extend(ROOTFS_DOMAIN, &operation, &digest, pcr_index)
+            .await?;
+        info!(
+            "Measured the rootfs of container {} before start",
+            operation
+        );
+        Ok(())
+    }
+
+    /// The config digest (image id) of `image` as `<algorithm>:<hex>`, asked
+    /// from the CRI runtime. Unlike the name, it pins the layers the
+    /// container is created from.
+    async fn image_id(&self, image: &str) -> Result<String> {
+        let inspected = crictl_json(
+            &self.nri_config.crictl_path,
+            &self.nri_config.runtime_endpoint,
+            &["inspecti", image],
+        )
+        .await?;
+        inspected["status"]["id"]
+            .as_str()
+            .filter(|id| is_prefixed_digest(id))
+            .map(str::to_string)
+            .ok_or_else(|| {
+                MeasurementError::Nri(format!("The runtime reports no digest for image {}", image))
+            })
+    }
+}
+
+/// Whether `digest` has the form `<algorithm>:<hex>`.
+fn is_prefixed_digest(digest: &str) -> bool {
+    digest.split_once(':').is_some_and(|(algorithm, hex)| {
+        !algorithm.is_empty() && !hex.is_empty() && hex.bytes().all(|b| b.is_ascii_hexdigit())
+    })
+}
+
+#[async_trait]
+impl Plugin for MeasurementPlugin {
+    async fn configure(
+        &self,
+        _ctx: &TtrpcContext,
+        req: ConfigureRequest,
+    ) -> ttrpc::Result<ConfigureResponse> {
+        info!(
+            "Configured by NRI runtime {} {}",
+            req.runtime_name, req.runtime_version
+        );
+        let mut resp = ConfigureResponse::new();
+        resp.events = 1 << (Event::CREATE_CONTAINER as i32 - 1);
+        if self.nri_config.measure_rootfs {
+            resp.events |= 1 << (Event::START_CONTAINER as i32 - 1);
+        }
+        Ok(resp)
+    }
+
+    async fn synchronize(
+        &self,
+        _ctx: &TtrpcContext,
+        req: SynchronizeRequest,
+    ) -> ttrpc::Result<SynchronizeResponse> {
+        // Containers that already exist were created before the plugin was
+        // registered; they are not measured retroactively.
+        debug!(
+            "Synchronized with {} pods and {} containers",
+            req.pods.len(),
+            req.containers.len()
+        );
+        Ok(SynchronizeResponse::new())
+    }
+
+    async fn shutdown(&self, _ctx: &TtrpcContext, _: Empty) -> ttrpc::Result<Empty> {
+        info!("NRI runtime requested plugin shutdown");
+        self.shutdown.notify_one();
+        Ok(Empty::new())
+    }
+
+    async fn create_container(
+        &self,
+        _ctx: &TtrpcContext,
+        req: CreateContainerRequest,
+    ) -> ttrpc::Result<CreateContainerResponse> {
+        if let Err(e) = self.measure_container(&req.pod, &req.container).await {
+            error!("Failed to measure container {}: {}", req.container.id, e);
+            if self.nri_config.block_on_failure {
+                return Err(ttrpc::Error::RpcStatus(ttrpc::get_status(
+                    ttrpc::Code::INTERNAL,
+                    format!("measurement failed: {}", e),
+                )));
+            }
+        }
+        Ok(CreateContainerResponse::new())
+    }
+
+    /// The runtime waits for the plugin before starting the container, so a
+    /// failure here stops the start under `block_on_failure`.
+    async fn state_change(
+        &self,
+        _ctx: &TtrpcContext,
+        req: StateChangeEvent,
+    ) -> ttrpc::Result<Empty> {
+        if req.event.enum_value() != Ok(Event::START_CONTAINER) {
+            return Ok(Empty::new());
+        }
+        if let Err(e) = self.measure_rootfs(&req.pod, &req.container).await {
+            error!(
+                "Failed to measure the rootfs of container {}: {}",
+                req.container.id, e
+            );
+            if self.nri_config.block_on_failure {
+                return Err(ttrpc::Error::RpcStatus(ttrpc::get_status(
+                    ttrpc::Code::INTERNAL,
+                    format!("rootfs measurement failed: {}", e),
+                )));
+            }
+        }
+        Ok(Empty::new())
+    }
+}
+
+/// Returns `sha256:<hex>` over the container's args, env, mounts, annotations
+/// and labels.
+fn container_spec_digest(container: &Container, canonical: bool) -> Result<String> {
+    let input = ContainerSpecDigestInput {
+        args: &container.args,
+        env: &container.env,
+        mounts: container
+            .mounts
+            .iter()
+            .map(|m| MountDigestInput {
+                destination: &m.destination,
+                mount_type: &m.type_,
+                source: &m.source,
+                options: &m.options,
+            })
+            .collect(),
+        annotations: sorted(&container.annotations),
+        labels: sorted(&container.labels),
+    };
+    let encoded = canonical_json::encode(&input, canonical)
+        .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&encoded);
+    Ok(format!("sha256:{}", hex::encode(hasher.finalize())))
+}
+
+fn sorted(map: &std::collections::HashMap<String, String>) -> BTreeMap<&str, &str> {
+    map.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect()
+}
+
+/// Registers with the NRI runtime and serves plugin requests until the runtime
+/// shuts the plugin down or closes the connection.
+pub async fn run_plugin(config: Arc<Config>, sink: Arc<dyn MeasurementSink>) -> Result<()> {
+    let nri_config = config.nri_measurement.clone();
+    info!("Connecting to NRI socket {}", nri_config.socket_path);
+    let nri_stream = UnixStream::connect(&nri_config.socket_path)
+        .await
+        .map_err(|e| {
+            MeasurementError::Nri(format!(
+                "Failed to connect to {}: {}",
+                nri_config.socket_path, e
+            ))
+        })?;
+
+    // ttrpc only speaks over whole sockets, so each multiplexed connection is
+    // bridged to a private socket pair served by the regular ttrpc types.
+    let bridge_dir = tempfile::tempdir()?;
+    let plugin_sock = bridge_dir.path().join("plugin.sock");
+    let runtime_sock = bridge_dir.path().join("runtime.sock");
+
+    let shutdown = Arc::new(Notify::new());
+    let plugin = MeasurementPlugin {
+        nri_config: nri_config.clone(),
+        structured_content: config.structured_content,
+        sink,
+        shutdown: shutdown.clone(),
+    };
+    let mut server = Server::new()
+        .bind(&unix_address(&plugin_sock))
+        .map_err(nri_error)?
+        .register_service(create_plugin(Arc::new(plugin)));
+    server.start().await.map_err(nri_error)?;
+    let plugin_local = UnixStream::connect(&plugin_sock).await?;
+
+    let runtime_listener = UnixListener::bind(&runtime_sock)?;
+    let runtime_client =
+        RuntimeClient::new(Client::connect(&unix_address(&runtime_sock)).map_err(nri_error)?);
+    let (runtime_local, _) = runtime_listener.accept().await?;
+
+    let mux = tokio::spawn(run_mux(nri_stream, plugin_local, runtime_local));
+
+    let mut req = RegisterPluginRequest::new();
+    req.plugin_name = nri_config.plugin_name.clone();
+    req.plugin_idx = nri_config.plugin_index.clone();
+    runtime_client
+        .register_plugin(ttrpc::context::with_timeout(5_000_000_000), &req)
+        .await
+        .map_err(|e| MeasurementError::Nri(format!("Plugin registration failed: {}", e)))?;
+    info!(
+        "Registered NRI plugin {}-{}",
+        nri_config.plugin_index, nri_config.plugin_name
+    );
+
+    tokio::select! {
+        _ = shutdown.notified() => {}
+        res = mux => match res {
+            Ok(Ok(())) => info!("NRI runtime closed the connection"),
+            Ok(Err(e)) => warn!("NRI connection failed: {}", e),
+            Err(e) => warn!("NRI multiplexer task failed: {}", e),
+        },
+    }
+    server.shutdown().await.map_err(nri_error)?;
+    Ok(())
+}
+
+fn nri_error(e: ttrpc::Error) -> MeasurementError {
+    MeasurementError::Nri(e.to_string())
+}
+
+fn unix_address(path: &Path) -> String {
+    format!("unix://{}", path.display())
+}
+
+/// Shuttles bytes between the NRI socket and the two local ttrpc connections
+/// until either side closes.
+async fn run_mux(
+    nri_stream: UnixStream,
+    plugin_local: UnixStream,
+    runtime_local: UnixStream,
+) -> std::io::Result<()> {
+    let (mut nri_rd, mut nri_wr) = nri_stream.into_split();
+    let (plugin_rd, mut plugin_wr) = plugin_local.into_split();
+    let (runtime_rd, mut runtime_wr) = runtime_local.into_split();
+
+    let (frame_tx, mut frame_rx) = mpsc::channel::<(u32, Vec<u8>)>(64);
+    for (conn, mut local_rd) in [
+        (PLUGIN_SERVICE_CONN, plugin_rd),
+        (RUNTIME_SERVICE_CONN, runtime_rd),
+    ] {
+        let frame_tx = frame_tx.clone();
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; MUX_READ_CHUNK];
+            loop {
+                match local_rd.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        if frame_tx.send((conn, buf[..n].to_vec())).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    }
+    drop(frame_tx);
+
+    let writer = async move {
+        while let Some((conn, payload)) = frame_rx.recv().await {
+            let mut header = [0u8; MUX_HEADER_LEN];
+            header[..4].copy_from_slice(&conn.to_be_bytes());
+            header[4..].copy_from_slice(&(payload.len() as u32).to_be_bytes());
+            nri_wr.write_all(&header).await?;
+            nri_wr.write_all(&payload).await?;
+        }
+        Ok::<(), std::io::Error>(())
+    };
+
+    let reader = async move {
+        let mut header = [0u8; MUX_HEADER_LEN];
+        loop {
+            match nri_rd.read_exact(&mut header).await {
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+                Err(e) => return Err(e),
+            }
+            let conn = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+            let len = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+            let mut payload = vec![0u8; len];
+            nri_rd.read_exact(&mut payload).await?;
+            let target: &mut OwnedWriteHalf = match conn {
+                PLUGIN_SERVICE_CONN => &mut plugin_wr,
+                RUNTIME_SERVICE_CONN => &mut runtime_wr,
+                other => {
+                    warn!("Dropping NRI frame for unknown connection {}", other);
+                    continue;
+                }
+            };
+            target.write_all(&payload).await?;
+        }
+    };
+
+    tokio::select! {
+        res = writer => res,
+        res = reader => res,
+    }
+}
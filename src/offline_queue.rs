@@ -0,0 +1,128 @@
+// src/offline_queue.rs
+use crate::error::{MeasurementError, Result};
+use crate::report::MeasurementRecord;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// An extension that could not reach any backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedExtension {
+    /// Journal sequence number, marked extended once the queue is flushed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+    pub record: MeasurementRecord,
+}
+
+/// Extensions waiting, in order, for a backend to become reachable,
+/// optionally mirrored to a JSON-lines file.
+pub struct OfflineQueue {
+    entries: VecDeque<QueuedExtension>,
+    capacity: usize,
+    path: Option<PathBuf>,
+}
+
+impl Default for OfflineQueue {
+    /// A disabled queue.
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: 0,
+            path: None,
+        }
+    }
+}
+
+impl OfflineQueue {
+    /// Opens the queue, taking over entries a previous run left in `path`.
+    pub fn open(capacity: usize, path: Option<&Path>) -> Result<Self> {
+        let mut entries = VecDeque::new();
+        if let Some(path) = path.filter(|p| p.exists()) {
+            for line in fs::read_to_string(path)?.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str(line) {
+                    Ok(entry) => entries.push_back(entry),
+                    Err(e) => warn!("Skipping malformed queue entry in {:?}: {}", path, e),
+                }
+            }
+            if !entries.is_empty() {
+                info!(
+                    "Loaded {} queued measurements from {:?}",
+                    entries.len(),
+                    path
+                );
+            }
+        }
+        Ok(Self {
+            entries,
+            capacity,
+            path: path.map(Path::to_path_buf),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn front(&self) -> Option<&QueuedExtension> {
+        self.entries.front()
+    }
+
+    pub fn push(&mut self, entry: QueuedExtension) -> Result<()> {
+        if self.entries.len() >= self.capacity {
+            return Err(MeasurementError::RpcClient(format!(
+                "Offline queue is full ({} measurements); dropping {}",
+                self.capacity, entry.record.operation
+            )));
+        }
+        self.entries.push_back(entry);
+        self.persist();
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<QueuedExtension> {
+        let entry = self.entries.pop_front();
+        self.persist();
+        entry
+    }
+
+    /// Rewrites the queue file. A queue that cannot be written is still
+    /// kept in memory.
+    fn persist(&self) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        if let Err(e) = self.write(path) {
+            warn!("Failed to write offline queue {:?}: {}", path, e);
+        }
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp)?;
+        for entry in &self.entries {
+            let line = serde_json::to_string(entry)
+                .map_err(|e| MeasurementError::Other(anyhow::anyhow!(e)))?;
+            writeln!(file, "{}", line)?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
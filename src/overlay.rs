@@ -0,0 +1,100 @@
+// src/overlay.rs
+use crate::error::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MOUNTINFO: &str = "/proc/self/mountinfo";
+
+/// The directories an overlayfs mount is assembled from.
+#[derive(Debug)]
+pub struct OverlayLayers {
+    /// Read-only layers, bottom (base image layer) first.
+    pub lower: Vec<PathBuf>,
+    /// Writable layer, absent for read-only overlays.
+    pub upper: Option<PathBuf>,
+}
+
+/// Returns the layers of the overlayfs mounted at `mount_point`, or `None`
+/// if no overlay is mounted there.
+pub fn overlay_layers(mount_point: &Path) -> Result<Option<OverlayLayers>> {
+    let mount_point = mount_point.canonicalize()?;
+    let mountinfo = fs::read_to_string(MOUNTINFO)?;
+    // The last matching line is the mount visible at that path.
+    let Some(options) = mountinfo
+        .lines()
+        .filter_map(parse_mountinfo_line)
+        .filter(|(point, fs_type, _)| point == &mount_point && fs_type == "overlay")
+        .map(|(_, _, options)| options)
+        .last()
+    else {
+        return Ok(None);
+    };
+
+    let mut lower = Vec::new();
+    let mut upper = None;
+    for option in options.split(',') {
+        if let Some(dirs) = option.strip_prefix("lowerdir=") {
+            // Listed top-most first.
+            lower = dirs.split(':').rev().map(PathBuf::from).collect();
+        } else if let Some(dir) = option.strip_prefix("upperdir=") {
+            upper = Some(PathBuf::from(dir));
+        }
+    }
+    Ok(Some(OverlayLayers { lower, upper }))
+}
+
+/// Splits a mountinfo line (see proc(5)) into mount point, filesystem type
+/// and super block options.
+fn parse_mountinfo_line(line: &str) -> Option<(PathBuf, String, String)> {
+    let (mount, fs) = line.split_once(" - ")?;
+    let mount_point = mount.split(' ').nth(4)?;
+    let mut fs = fs.split(' ');
+    let fs_type = fs.next()?;
+    let options = fs.nth(1)?;
+    Some((
+        PathBuf::from(unescape(mount_point)),
+        fs_type.to_string(),
+        unescape(options),
+    ))
+}
+
+/// Undoes the octal escaping (`\040` for a space) of mountinfo fields.
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes
+            .get(i + 1..i + 4)
+            .and_then(|d| std::str::from_utf8(d).ok())
+            .and_then(|d| u8::from_str_radix(d, 8).ok());
+        match (bytes[i], octal) {
+            (b'\\', Some(byte)) => {
+                out.push(byte);
+                i += 4;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Looks up the diff-id of a Docker overlay2 layer: `layer_dir` is
+/// `<overlay2>/<cache id>/diff`, and the layer database records the cache id
+/// and diff-id of every layer under its chain id.
+pub fn docker_diff_id(layer_dir: &Path, layerdb_dir: &Path) -> Option<String> {
+    let cache_id = layer_dir.parent()?.file_name()?.to_str()?;
+    fs::read_dir(layerdb_dir)
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            fs::read_to_string(entry.path().join("cache-id"))
+                .map(|id| id.trim() == cache_id)
+                .unwrap_or(false)
+        })
+        .and_then(|entry| fs::read_to_string(entry.path().join("diff")).ok())
+        .map(|diff_id| diff_id.trim().to_string())
+}
@@ -0,0 +1,191 @@
+// src/report.rs
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single runtime measurement as it was extended into the event log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MeasurementRecord {
+    pub domain: String,
+    pub operation: String,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub register_index: Option<u64>,
+    /// The operation before `path_aliases` rewrote it: the real path on
+    /// this node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unaliased_operation: Option<String>,
+    /// Name of the Attestation Agent endpoint that took the measurement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+}
+
+impl MeasurementRecord {
+    /// A measurement of `operation` not yet rewritten by `path_aliases` nor
+    /// extended through any backend.
+    pub fn new(domain: &str, operation: &str, content: &str, register_index: Option<u64>) -> Self {
+        Self {
+            domain: domain.to_string(),
+            operation: operation.to_string(),
+            content: content.to_string(),
+            register_index,
+            unaliased_operation: None,
+            backend: None,
+        }
+    }
+}
+
+/// Inventory of the measurements performed during a run, exported as JSON so
+/// that two systems (e.g. a golden image and a running guest) can be compared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryReport {
+    /// Seconds since the UNIX epoch at which the report was generated.
+    pub generated_at: u64,
+    pub measurements: Vec<MeasurementRecord>,
+}
+
+impl SummaryReport {
+    pub fn new(measurements: Vec<MeasurementRecord>) -> Self {
+        Self {
+            generated_at: unix_timestamp(),
+            measurements,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read summary report: {:?}", path))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse summary report: {:?}", path))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write summary report: {:?}", path))
+    }
+
+    /// Indexes measurements by (domain, operation). When an entry was extended
+    /// more than once, the last extension wins.
+    fn index(&self) -> BTreeMap<(&str, &str), &MeasurementRecord> {
+        self.measurements
+            .iter()
+            .map(|m| ((m.domain.as_str(), m.operation.as_str()), m))
+            .collect()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReportDiff<'a> {
+    pub added: Vec<&'a MeasurementRecord>,
+    pub removed: Vec<&'a MeasurementRecord>,
+    pub changed: Vec<(&'a MeasurementRecord, &'a MeasurementRecord)>,
+}
+
+impl ReportDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compares two reports. Entries are matched on (domain, operation); a change
+/// in content or register index counts as a changed measurement.
+pub fn diff_reports<'a>(old: &'a SummaryReport, new: &'a SummaryReport) -> ReportDiff<'a> {
+    let old_index = old.index();
+    let new_index = new.index();
+    let mut diff = ReportDiff::default();
+
+    for (key, old_record) in &old_index {
+        match new_index.get(key) {
+            Some(new_record)
+                if new_record.content != old_record.content
+                    || new_record.register_index != old_record.register_index =>
+            {
+                diff.changed.push((old_record, new_record))
+            }
+            Some(_) => {}
+            None => diff.removed.push(old_record),
+        }
+    }
+    for (key, new_record) in &new_index {
+        if !old_index.contains_key(key) {
+            diff.added.push(new_record);
+        }
+    }
+    diff
+}
+
+/// Runs the `diff` subcommand and returns the process exit code: 0 when both
+/// reports are equivalent, 1 when they differ.
+pub fn run_diff(old_path: &Path, new_path: &Path) -> Result<i32> {
+    let old = SummaryReport::load(old_path)?;
+    let new = SummaryReport::load(new_path)?;
+    let diff = diff_reports(&old, &new);
+
+    for record in &diff.added {
+        println!("+ [{}] {}: {}", record.domain, record.operation, record.content);
+    }
+    for record in &diff.removed {
+        println!("- [{}] {}: {}", record.domain, record.operation, record.content);
+    }
+    for (old_record, new_record) in &diff.changed {
+        println!(
+            "~ [{}] {}: {} -> {}",
+            old_record.domain,
+            old_record.operation,
+            describe(old_record),
+            describe(new_record)
+        );
+    }
+    println!(
+        "{} added, {} removed, {} changed",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    );
+
+    Ok(if diff.is_empty() { 0 } else { 1 })
+}
+
+fn describe(record: &MeasurementRecord) -> String {
+    match record.register_index {
+        Some(index) => format!("{} (register {})", record.content, index),
+        None => record.content.clone(),
+    }
+}
+
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Current time as an RFC 3339 UTC timestamp, as used in Kubernetes status
+/// fields and request signatures.
+pub fn now_rfc3339() -> String {
+    let secs = unix_timestamp();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // Civil-from-days (Howard Hinnant), valid for dates after 1970.
+    let z = days as i64 + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
@@ -1,22 +1,162 @@
 // src/rpc_client.rs
-use crate::config::{Config, MeasurementChannel};
+use crate::config::{
+    AaBatchConfig, AaEndpoint, AaHttpConfig, AaRetryConfig, Config, MeasurementChannel, TpmConfig,
+};
 use crate::error::{MeasurementError, Result};
-use crate::rpc_generated::attestation_agent::ExtendRuntimeMeasurementRequest;
+use crate::event_log::{aael_event, EventLog};
+use crate::extension_state::ExtensionState;
+use crate::journal::Journal;
+use crate::logging::{self, Outcome};
+use crate::offline_queue::{OfflineQueue, QueuedExtension};
+use crate::report::{unix_timestamp, MeasurementRecord};
+use crate::rpc_generated::attestation_agent::{
+    ExtendRuntimeMeasurementRequest, GetEvidenceRequest, GetTokenRequest,
+};
 use crate::rpc_generated::attestation_agent_ttrpc::AttestationAgentServiceClient;
-use log::{debug, info};
+use crate::sink::Extension;
+use crate::tpm;
+use log::{debug, info, warn};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 use ttrpc::asynchronous::Client;
 
+/// Request timeout of a ttrpc endpoint that does not set one.
+const DEFAULT_TTRPC_TIMEOUT_MS: u64 = 5000;
+
+#[derive(Clone)]
 enum ClientImpl {
-    Ttrpc(AttestationAgentServiceClient),
+    Ttrpc {
+        client: AttestationAgentServiceClient,
+        timeout_ms: u64,
+    },
     Http {
         http_client: reqwest::Client,
         base_url: String,
     },
+    /// Records measurements without sending them anywhere.
+    DryRun,
+    /// Extends a PCR of the TPM at `device` directly.
+    Tpm {
+        device: PathBuf,
+        config: TpmConfig,
+    },
+}
+
+/// Why a backend did not take a measurement.
+enum SendFailure {
+    /// The backend could not be reached; the next one is tried.
+    Unreachable(MeasurementError),
+    /// The backend refused the measurement; another one would too.
+    Rejected(MeasurementError),
+}
+
+impl SendFailure {
+    fn into_error(self) -> MeasurementError {
+        match self {
+            SendFailure::Unreachable(e) | SendFailure::Rejected(e) => e,
+        }
+    }
+}
+
+/// One measurement backend, reconnected on next use after it became
+/// unreachable.
+struct Backend {
+    name: String,
+    /// `None` for backends that cannot be reconnected (dry run).
+    endpoint: Option<AaEndpoint>,
+    client: tokio::sync::Mutex<Option<ClientImpl>>,
+}
+
+impl Backend {
+    fn fixed(name: &str, client: ClientImpl) -> Self {
+        Self {
+            name: name.to_string(),
+            endpoint: None,
+            client: tokio::sync::Mutex::new(Some(client)),
+        }
+    }
+
+    async fn client(&self) -> Result<ClientImpl> {
+        let mut guard = self.client.lock().await;
+        if let Some(client) = guard.as_ref() {
+            return Ok(client.clone());
+        }
+        let endpoint = self.endpoint.as_ref().ok_or_else(|| {
+            MeasurementError::RpcClient(format!("Backend {} is unavailable", self.name))
+        })?;
+        let client = AAClient::connect(endpoint).await?;
+        *guard = Some(client.clone());
+        Ok(client)
+    }
+
+    /// Drops the connection so that the next use reconnects.
+    async fn disconnect(&self) {
+        if self.endpoint.is_some() {
+            *self.client.lock().await = None;
+        }
+    }
+}
+
+/// What the client last saw of the Attestation Agent, for the status server.
+#[derive(Debug, Clone, Serialize)]
+pub struct AaConnectivity {
+    /// Whether the latest attempt reached a backend; true until one fails.
+    pub reachable: bool,
+    /// Unix time and backend of the latest successful extension.
+    pub last_success: Option<u64>,
+    pub backend: Option<String>,
+    /// Unix time and error of the latest attempt that reached no backend.
+    pub last_failure: Option<u64>,
+    pub last_error: Option<String>,
+    /// Extensions waiting in the offline queue.
+    pub queued: usize,
+}
+
+/// What [`AAClient::replay_journal`] did.
+#[derive(Debug, Default)]
+pub struct JournalReplay {
+    pub replayed: usize,
+    /// `<seq> <operation>: <error>` of every entry the backend rejected;
+    /// they stay pending in the journal.
+    pub rejected: Vec<String>,
+    /// Journal lines dropped by compaction; `None` when another process had
+    /// the journal open and it was not compacted.
+    pub compacted: Option<usize>,
 }
 
 pub struct AAClient {
-    inner: ClientImpl,
+    backends: Vec<Backend>,
+    dry_run: bool,
+    /// What was extended or skipped, for the summary report and exports;
+    /// `None` once [`Self::stop_recording`] was called.
+    records: Mutex<Option<Vec<MeasurementRecord>>>,
+    journal: Option<Journal>,
+    extension_state: Option<ExtensionState>,
+    /// Extend measurements the extension state shows unchanged.
+    force_remeasure: bool,
+    retry: AaRetryConfig,
+    batch: AaBatchConfig,
+    /// Cleared once an `http_api` backend turns out to lack the batch API.
+    batch_api_supported: AtomicBool,
+    /// Written before each measurement is sent, so that it also holds
+    /// measurements that never reached the Attestation Agent; with a TPM
+    /// backend, only after each successful PCR extension instead.
+    event_log: Option<EventLog>,
+    /// Whether a TPM backend writes the event log.
+    log_after_extend: bool,
+    /// False when the local event log is the only sink.
+    forward_to_aa: bool,
+    /// Also serializes extensions, so that queued ones are never overtaken.
+    queue: tokio::sync::Mutex<OfflineQueue>,
+    connectivity: Mutex<AaConnectivity>,
 }
 
 #[derive(Serialize)]
@@ -29,59 +169,916 @@ struct HttpAaelRequest<'a> {
 }
 
 impl AAClient {
+    /// Connects to the configured backends, failing at once when none is
+    /// reachable.
     pub async fn from_config(config: &Config) -> Result<Self> {
-        match config.aa_channel {
+        Self::build(config, false).await
+    }
+
+    /// Like [`Self::from_config`], but waits up to
+    /// `[aa_retry].startup_timeout_secs` for a backend to come up. Meant for
+    /// the daemon, which may start together with the Attestation Agent.
+    pub async fn from_config_waiting(config: &Config) -> Result<Self> {
+        Self::build(config, true).await
+    }
+
+    async fn build(config: &Config, wait: bool) -> Result<Self> {
+        if config.dry_run {
+            info!("Dry run: not connecting to the Attestation Agent.");
+            return Ok(Self::dry_run());
+        }
+        let event_log_only = config.local_event_log.enable && !config.local_event_log.forward_to_aa;
+        let endpoints = if event_log_only {
+            Vec::new()
+        } else {
+            config.resolved_aa_endpoints()
+        };
+        // A PCR alone cannot be verified without the events extended into it.
+        let uses_tpm = endpoints
+            .iter()
+            .any(|endpoint| endpoint.channel == MeasurementChannel::Tpm);
+        let backends = endpoints
+            .into_iter()
+            .map(|endpoint| Backend {
+                name: endpoint.name().to_string(),
+                endpoint: Some(endpoint),
+                client: tokio::sync::Mutex::new(None),
+            })
+            .collect();
+
+        let mut client = Self::new(backends);
+        if !event_log_only {
+            if wait {
+                client.wait_for_backend(&config.aa_retry).await?;
+            } else {
+                client.check_connection().await?;
+            }
+        }
+        if let Some(ref journal_path) = config.journal_path {
+            let journal = Journal::open(Path::new(journal_path))?;
+            info!("Journaling measurements to {}", journal_path);
+            client.journal = Some(journal);
+        }
+        if let Some(ref state_path) = config.state_path {
+            client.extension_state = Some(ExtensionState::open(Path::new(state_path))?);
+            if config.force_remeasure {
+                info!("Extending every measurement regardless of {}", state_path);
+            }
+            client.force_remeasure = config.force_remeasure;
+        }
+        if config.local_event_log.enable || uses_tpm {
+            let path = &config.local_event_log.path;
+            client.event_log = Some(EventLog::open(Path::new(path))?);
+            if event_log_only {
+                warn!("Writing measurements to local event log {} only", path);
+            } else {
+                info!("Writing measurements to local event log {}", path);
+            }
+            client.forward_to_aa = !event_log_only;
+            client.log_after_extend = uses_tpm;
+        }
+        client.retry = config.aa_retry.clone();
+        client.batch = config.aa_batch.clone();
+        client.queue = tokio::sync::Mutex::new(OfflineQueue::open(
+            config.aa_retry.queue_size,
+            config.aa_retry.queue_path.as_deref().map(Path::new),
+        )?);
+        Ok(client)
+    }
+
+    /// A client that only logs and records what would be extended, for
+    /// computing the measurement set without a TEE.
+    pub fn dry_run() -> Self {
+        let mut client = Self::new(vec![Backend::fixed("dry_run", ClientImpl::DryRun)]);
+        client.dry_run = true;
+        client
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    fn connect_ttrpc(endpoint: &AaEndpoint) -> Result<ClientImpl> {
+        let client = Client::connect(&endpoint.address)
+            .map_err(|e| MeasurementError::RpcClient(format!("Failed to connect to AA: {}", e)))?;
+        Ok(ClientImpl::Ttrpc {
+            client: AttestationAgentServiceClient::new(client),
+            timeout_ms: endpoint
+                .ttrpc_timeout_ms
+                .unwrap_or(DEFAULT_TTRPC_TIMEOUT_MS),
+        })
+    }
+
+    async fn connect(endpoint: &AaEndpoint) -> Result<ClientImpl> {
+        match endpoint.channel {
             MeasurementChannel::UnixSocket => {
                 info!(
                     "Connecting to Attestation Agent via ttrpc socket: {}",
-                    config.attestation_agent_socket
+                    endpoint.address
                 );
-                let client = Client::connect(&config.attestation_agent_socket).map_err(|e| {
-                    MeasurementError::RpcClient(format!(
-                        "Failed to connect to AA: {}",
-                        e.to_string()
-                    ))
-                })?;
-                Ok(Self {
-                    inner: ClientImpl::Ttrpc(AttestationAgentServiceClient::new(client)),
-                })
+                Self::connect_ttrpc(endpoint)
+            }
+            MeasurementChannel::Vsock => {
+                if !endpoint.address.starts_with("vsock://") {
+                    return Err(MeasurementError::Config(format!(
+                        "Invalid vsock address '{}': set aa_vsock_cid, or use vsock://<cid>:<port>",
+                        endpoint.address
+                    )));
+                }
+                info!(
+                    "Connecting to Attestation Agent via ttrpc over vsock: {}",
+                    endpoint.address
+                );
+                Self::connect_ttrpc(endpoint)
             }
             MeasurementChannel::HttpApi => {
-                let base_url = config.trustiflux_api_endpoint.clone().ok_or_else(|| {
-                    MeasurementError::Config(
+                if endpoint.address.is_empty() {
+                    return Err(MeasurementError::Config(
                         "trustiflux_api_endpoint must be set when measurement_channel=http_api"
                             .to_string(),
-                    )
-                })?;
+                    ));
+                }
+                let base_url = endpoint.address.clone();
+                info!("Using trustiflux API server for measurement: {}", base_url);
+                let http_config = endpoint.http.clone().unwrap_or_default();
+                let http_client = build_http_client(&base_url, &http_config)?;
+                Ok(ClientImpl::Http {
+                    http_client,
+                    base_url,
+                })
+            }
+            MeasurementChannel::Tpm => {
+                let device = PathBuf::from(&endpoint.address);
+                if !device.exists() {
+                    return Err(MeasurementError::Tpm(format!(
+                        "TPM device {:?} not found",
+                        device
+                    )));
+                }
+                let config = endpoint.tpm.clone().unwrap_or_default();
+                if tpm::bank_algorithm(&config.hash_algorithm).is_none() {
+                    return Err(MeasurementError::UnsupportedHashAlgorithm(format!(
+                        "{} (no TPM PCR bank)",
+                        config.hash_algorithm
+                    )));
+                }
                 info!(
-                    "Using trustiflux API server for measurement: {}",
-                    base_url
+                    "Extending PCR {} ({} bank) of the TPM at {:?} directly",
+                    config.pcr_index, config.hash_algorithm, device
+                );
+                Ok(ClientImpl::Tpm { device, config })
+            }
+        }
+    }
+
+    fn new(backends: Vec<Backend>) -> Self {
+        Self {
+            backends,
+            dry_run: false,
+            records: Mutex::new(Some(Vec::new())),
+            journal: None,
+            extension_state: None,
+            force_remeasure: false,
+            retry: AaRetryConfig::default(),
+            batch: AaBatchConfig::default(),
+            batch_api_supported: AtomicBool::new(true),
+            event_log: None,
+            log_after_extend: false,
+            forward_to_aa: true,
+            queue: tokio::sync::Mutex::new(OfflineQueue::default()),
+            connectivity: Mutex::new(AaConnectivity {
+                reachable: true,
+                last_success: None,
+                backend: None,
+                last_failure: None,
+                last_error: None,
+                queued: 0,
+            }),
+        }
+    }
+
+    /// Connects every backend that is not connected yet and returns how
+    /// many are; fails when none is reachable.
+    pub async fn check_connection(&self) -> Result<usize> {
+        let mut connected = 0;
+        let mut last_error = None;
+        for backend in &self.backends {
+            match backend.client().await {
+                Ok(_) => connected += 1,
+                Err(e) => {
+                    warn!(
+                        "Attestation Agent endpoint {} is unavailable: {}",
+                        backend.name, e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        if connected == 0 {
+            return Err(last_error.unwrap_or_else(|| {
+                MeasurementError::Config("No Attestation Agent endpoint configured".to_string())
+            }));
+        }
+        Ok(connected)
+    }
+
+    /// Runs [`Self::check_connection`] with exponential backoff until a
+    /// backend is reachable or `[aa_retry].startup_timeout_secs` have
+    /// passed, so that an Attestation Agent still starting at boot is
+    /// waited for.
+    async fn wait_for_backend(&self, retry: &AaRetryConfig) -> Result<()> {
+        let deadline = Instant::now() + Duration::from_secs(retry.startup_timeout_secs);
+        let mut backoff = Duration::from_millis(retry.initial_backoff_ms);
+        let max_backoff = Duration::from_millis(retry.max_backoff_ms);
+        loop {
+            let e = match self.check_connection().await {
+                Ok(_) => return Ok(()),
+                Err(e) => e,
+            };
+            // A misconfigured endpoint does not fix itself by waiting.
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || matches!(e, MeasurementError::Config(_)) {
+                return Err(e);
+            }
+            let delay = backoff.min(remaining);
+            info!(
+                "Waiting {:?} for the Attestation Agent to come up ({:?} left)",
+                delay, remaining
+            );
+            tokio::time::sleep(delay).await;
+            backoff = (backoff * 2).min(max_backoff);
+        }
+    }
+
+    /// Returns the outcome of the latest attempts to reach a backend. Kept
+    /// apart from the queue lock, so it can be read while retries run.
+    pub fn connectivity(&self) -> AaConnectivity {
+        self.connectivity.lock().unwrap().clone()
+    }
+
+    /// Returns every measurement successfully extended through this client,
+    /// until [`Self::stop_recording`] is called.
+    pub fn records(&self) -> Vec<MeasurementRecord> {
+        self.records.lock().unwrap().clone().unwrap_or_default()
+    }
+
+    /// Drops the records collected so far and stops collecting them. The
+    /// daemon calls this once the initial run is reported, so that they do
+    /// not pile up while it keeps measuring.
+    pub fn stop_recording(&self) {
+        *self.records.lock().unwrap() = None;
+    }
+
+    fn keep_record(&self, record: MeasurementRecord) {
+        if let Some(records) = self.records.lock().unwrap().as_mut() {
+            records.push(record);
+        }
+    }
+
+    pub async fn extend_runtime_measurement(
+        &self,
+        pcr_index_opt: Option<u64>,
+        domain: &str,
+        operation: &str,
+        content: &str,
+    ) -> Result<()> {
+        self.extend_staged(MeasurementRecord::new(
+            domain,
+            operation,
+            content,
+            pcr_index_opt,
+        ))
+        .await
+    }
+
+    /// Extends a measurement staged by `sink::StagingSink`.
+    pub async fn extend_staged(&self, record: MeasurementRecord) -> Result<()> {
+        let Some(extension) = self.stage(record)? else {
+            return Ok(());
+        };
+        let mut queue = self.queue.lock().await;
+        if !queue.is_empty() {
+            self.flush_locked(&mut queue).await;
+        }
+        // While older extensions wait in the queue, new ones join them.
+        let error = if queue.is_empty() {
+            match self.send_with_retry(&extension.record).await {
+                Ok(backend) => {
+                    let mut record = extension.record;
+                    record.backend = Some(backend);
+                    self.record_extended(extension.seq, record);
+                    return Ok(());
+                }
+                Err(SendFailure::Unreachable(e)) if queue.is_enabled() => e,
+                Err(failure) => return Err(self.failed(&extension.record, failure)),
+            }
+        } else {
+            MeasurementError::RpcClient("Earlier measurements are still queued".to_string())
+        };
+        self.enqueue(&mut queue, extension, error)
+    }
+
+    /// Extends `extensions` in batches of `[aa_batch].batch_size` and returns
+    /// one result each, in order. Within a batch, up to `max_in_flight`
+    /// domains are sent concurrently, each in order.
+    pub async fn extend_runtime_measurements(&self, extensions: &[Extension]) -> Vec<Result<()>> {
+        let records = extensions
+            .iter()
+            .map(|e| MeasurementRecord::new(&e.domain, &e.operation, &e.content, e.pcr_index))
+            .collect();
+        self.extend_staged_batch(records).await
+    }
+
+    /// Extends measurements staged by `sink::StagingSink` like
+    /// [`Self::extend_runtime_measurements`].
+    pub async fn extend_staged_batch(&self, records: Vec<MeasurementRecord>) -> Vec<Result<()>> {
+        let mut results: Vec<Result<()>> = records.iter().map(|_| Ok(())).collect();
+        let mut staged = Vec::new();
+        for (index, record) in records.into_iter().enumerate() {
+            match self.stage(record) {
+                Ok(Some(queued)) => staged.push((index, queued)),
+                Ok(None) => {}
+                Err(e) => results[index] = Err(e),
+            }
+        }
+        if staged.is_empty() {
+            return results;
+        }
+
+        let mut queue = self.queue.lock().await;
+        if !queue.is_empty() {
+            self.flush_locked(&mut queue).await;
+        }
+        for batch in staged.chunks(self.batch.batch_size.max(1)) {
+            let outcomes = if queue.is_empty() {
+                self.send_batch(batch).await
+            } else {
+                batch
+                    .iter()
+                    .map(|_| {
+                        Err(SendFailure::Unreachable(MeasurementError::RpcClient(
+                            "Earlier measurements are still queued".to_string(),
+                        )))
+                    })
+                    .collect()
+            };
+            for ((index, queued), outcome) in batch.iter().zip(outcomes) {
+                results[*index] = match outcome {
+                    Ok(backend) => {
+                        let mut record = queued.record.clone();
+                        record.backend = Some(backend);
+                        self.record_extended(queued.seq, record);
+                        Ok(())
+                    }
+                    Err(SendFailure::Unreachable(e)) if queue.is_enabled() => {
+                        self.enqueue(&mut queue, queued.clone(), e)
+                    }
+                    Err(failure) => Err(self.failed(&queued.record, failure)),
+                };
+            }
+        }
+        results
+    }
+
+    /// Skips `record` if the extension state shows it unchanged, and writes
+    /// the journal and the local event log. Returns what is left to send,
+    /// or `None` when nothing is.
+    fn stage(&self, record: MeasurementRecord) -> Result<Option<QueuedExtension>> {
+        if let Some(state) = &self.extension_state {
+            if !self.force_remeasure && state.is_unchanged(&record) {
+                debug!(
+                    "{} {} is unchanged since it was extended in this boot; skipping",
+                    record.domain, record.operation
+                );
+                logging::audit(&record, Outcome::Unchanged, None);
+                self.keep_record(record);
+                return Ok(None);
+            }
+        }
+        let seq = match &self.journal {
+            Some(journal) => match journal.append_pending(&record) {
+                Ok(seq) => Some(seq),
+                Err(e) => {
+                    warn!("Failed to journal measurement {}: {}", record.operation, e);
+                    None
+                }
+            },
+            None => None,
+        };
+        if let Some(event_log) = self.event_log.as_ref().filter(|_| !self.log_after_extend) {
+            if let Err(e) = event_log.append(&record.domain, &record.operation, &record.content) {
+                if !self.forward_to_aa {
+                    logging::audit(&record, Outcome::Failed, Some(e.to_string()));
+                    return Err(e);
+                }
+                warn!(
+                    "Failed to write measurement {} to the local event log: {}",
+                    record.operation, e
                 );
-                let http_client = reqwest::Client::builder()
-                    .user_agent("measurement-tool/0.1.0")
-                    .build()
+            }
+        }
+        if !self.forward_to_aa {
+            let mut record = record;
+            record.backend = Some("local_event_log".to_string());
+            self.record_extended(seq, record);
+            return Ok(None);
+        }
+        Ok(Some(QueuedExtension { seq, record }))
+    }
+
+    /// Queues `extension`, which could not be sent because of `error`.
+    fn enqueue(
+        &self,
+        queue: &mut OfflineQueue,
+        extension: QueuedExtension,
+        error: MeasurementError,
+    ) -> Result<()> {
+        warn!(
+            "Queueing measurement {} until a backend is reachable: {}",
+            extension.record.operation, error
+        );
+        logging::audit(&extension.record, Outcome::Queued, Some(error.to_string()));
+        let pushed = queue.push(extension);
+        self.connectivity.lock().unwrap().queued = queue.len();
+        pushed
+    }
+
+    /// Audits `record` as failed and returns the error.
+    fn failed(&self, record: &MeasurementRecord, failure: SendFailure) -> MeasurementError {
+        let error = failure.into_error();
+        logging::audit(record, Outcome::Failed, Some(error.to_string()));
+        error
+    }
+
+    /// Sends queued extensions, oldest first, until one fails. Returns how
+    /// many are still queued.
+    pub async fn flush_queue(&self) -> usize {
+        let mut queue = self.queue.lock().await;
+        self.flush_locked(&mut queue).await;
+        queue.len()
+    }
+
+    async fn flush_locked(&self, queue: &mut OfflineQueue) {
+        let mut flushed = 0;
+        while let Some(entry) = queue.front().cloned() {
+            let record = &entry.record;
+            match self
+                .send(
+                    record.register_index,
+                    &record.domain,
+                    &record.operation,
+                    &record.content,
+                )
+                .await
+            {
+                Ok(backend) => {
+                    queue.pop();
+                    let mut record = entry.record;
+                    record.backend = Some(backend);
+                    self.record_extended(entry.seq, record);
+                    flushed += 1;
+                }
+                Err(SendFailure::Rejected(e)) => {
+                    // Retrying cannot help; the journal keeps it as pending.
+                    warn!("Dropping queued measurement {}: {}", record.operation, e);
+                    logging::audit(record, Outcome::Dropped, Some(e.to_string()));
+                    queue.pop();
+                }
+                Err(SendFailure::Unreachable(e)) => {
+                    debug!("Backends still unreachable: {}", e);
+                    break;
+                }
+            }
+        }
+        if flushed > 0 {
+            info!(
+                "Flushed {} queued measurements, {} left",
+                flushed,
+                queue.len()
+            );
+        }
+        self.connectivity.lock().unwrap().queued = queue.len();
+    }
+
+    /// Sends `record`, retrying with exponential backoff while no backend
+    /// can be reached.
+    async fn send_with_retry(
+        &self,
+        record: &MeasurementRecord,
+    ) -> std::result::Result<String, SendFailure> {
+        self.with_retry(&record.operation, || {
+            self.send(
+                record.register_index,
+                &record.domain,
+                &record.operation,
+                &record.content,
+            )
+        })
+        .await
+    }
+
+    /// Runs `send` until it reaches a backend or `[aa_retry].max_retries`
+    /// retries with exponential backoff are exhausted.
+    async fn with_retry<T, F, Fut>(
+        &self,
+        what: &str,
+        send: F,
+    ) -> std::result::Result<T, SendFailure>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<T, SendFailure>>,
+    {
+        let mut backoff = Duration::from_millis(self.retry.initial_backoff_ms);
+        let max_backoff = Duration::from_millis(self.retry.max_backoff_ms);
+        let mut attempt = 0;
+        loop {
+            match send().await {
+                Err(SendFailure::Unreachable(e)) if attempt < self.retry.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "No backend reachable for {} ({}); retry {}/{} in {:?}",
+                        what, e, attempt, self.retry.max_retries, backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(max_backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends one batch and returns the backend or failure of each extension,
+    /// in order: as a single request where the backend has a batch API,
+    /// otherwise pipelined.
+    async fn send_batch(
+        &self,
+        batch: &[(usize, QueuedExtension)],
+    ) -> Vec<std::result::Result<String, SendFailure>> {
+        if self.batch.batch_api && self.batch_api_supported.load(Ordering::Relaxed) {
+            let records: Vec<&MeasurementRecord> =
+                batch.iter().map(|(_, queued)| &queued.record).collect();
+            let what = format!("a batch of {} measurements", records.len());
+            match self
+                .with_retry(&what, || self.send_batch_request(&records))
+                .await
+            {
+                Ok(Some(backend)) => return batch.iter().map(|_| Ok(backend.clone())).collect(),
+                Ok(None) => {
+                    info!("The measurement backend has no batch API; sending measurements singly");
+                    self.batch_api_supported.store(false, Ordering::Relaxed);
+                }
+                Err(SendFailure::Unreachable(e)) => {
+                    return batch
+                        .iter()
+                        .map(|_| {
+                            Err(SendFailure::Unreachable(MeasurementError::RpcClient(
+                                e.to_string(),
+                            )))
+                        })
+                        .collect()
+                }
+                // Sent again singly, so that each measurement gets its own result.
+                Err(SendFailure::Rejected(e)) => {
+                    warn!("Backend rejected {}; sending them singly: {}", what, e)
+                }
+            }
+        }
+        self.send_pipelined(batch).await
+    }
+
+    /// Sends each domain of `batch` in order, with up to
+    /// `[aa_batch].max_in_flight` domains in flight at once. Once an
+    /// extension cannot reach a backend, the later ones of its domain are
+    /// not sent, so that they cannot overtake it.
+    async fn send_pipelined(
+        &self,
+        batch: &[(usize, QueuedExtension)],
+    ) -> Vec<std::result::Result<String, SendFailure>> {
+        // With one in flight, the whole batch is a single lane.
+        let mut lanes: Vec<Vec<usize>> = Vec::new();
+        let mut lane_of: HashMap<&str, usize> = HashMap::new();
+        for (position, (_, queued)) in batch.iter().enumerate() {
+            let key = if self.batch.max_in_flight > 1 {
+                queued.record.domain.as_str()
+            } else {
+                ""
+            };
+            let lane = *lane_of.entry(key).or_insert_with(|| {
+                lanes.push(Vec::new());
+                lanes.len() - 1
+            });
+            lanes[lane].push(position);
+        }
+
+        let semaphore = &Semaphore::new(self.batch.max_in_flight.max(1));
+        let lane_outcomes = futures::future::join_all(lanes.iter().map(|positions| async move {
+            let _permit = semaphore.acquire().await;
+            let mut outcomes = Vec::with_capacity(positions.len());
+            let mut blocked = false;
+            for &position in positions {
+                let record = &batch[position].1.record;
+                let outcome = if blocked {
+                    Err(SendFailure::Unreachable(MeasurementError::RpcClient(
+                        format!("An earlier {} measurement was not sent", record.domain),
+                    )))
+                } else {
+                    self.send_with_retry(record).await
+                };
+                blocked |= matches!(outcome, Err(SendFailure::Unreachable(_)));
+                outcomes.push((position, outcome));
+            }
+            outcomes
+        }))
+        .await;
+        let mut outcomes: Vec<_> = lane_outcomes.into_iter().flatten().collect();
+        outcomes.sort_by_key(|(position, _)| *position);
+        outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+    }
+
+    /// Sends `records` as one request through the first reachable backend
+    /// and returns its name, or `None` when that backend has no batch API.
+    async fn send_batch_request(
+        &self,
+        records: &[&MeasurementRecord],
+    ) -> std::result::Result<Option<String>, SendFailure> {
+        let result = self
+            .failover(|client| async move {
+                let ClientImpl::Http {
+                    http_client,
+                    base_url,
+                } = client
+                else {
+                    return Ok(false);
+                };
+                let url = format!("{}/aa/aael/batch", base_url.trim_end_matches('/'));
+                let payload: Vec<HttpAaelRequest> = records
+                    .iter()
+                    .map(|record| HttpAaelRequest {
+                        domain: &record.domain,
+                        operation: &record.operation,
+                        content: &record.content,
+                        register_index: record.register_index,
+                    })
+                    .collect();
+                debug!(
+                    "Extending {} runtime measurements via HTTP {}",
+                    payload.len(),
+                    url
+                );
+                let resp = http_client
+                    .post(&url)
+                    .json(&payload)
+                    .send()
+                    .await
                     .map_err(|e| {
-                        MeasurementError::Http(format!("Failed to build HTTP client: {}", e))
+                        SendFailure::Unreachable(MeasurementError::Http(format!(
+                            "HTTP request to {} failed: {}",
+                            url, e
+                        )))
                     })?;
-                Ok(Self {
-                    inner: ClientImpl::Http {
+                let status = resp.status();
+                if status.is_success() {
+                    return Ok(true);
+                }
+                if matches!(
+                    status,
+                    reqwest::StatusCode::NOT_FOUND
+                        | reqwest::StatusCode::METHOD_NOT_ALLOWED
+                        | reqwest::StatusCode::NOT_IMPLEMENTED
+                ) {
+                    return Ok(false);
+                }
+                let body = resp.text().await.unwrap_or_default();
+                Err(http_status_failure(
+                    status,
+                    format!("HTTP {} returned status {}: {}", url, status, body),
+                ))
+            })
+            .await;
+        self.note_outcome(&result);
+        let (backend, supported) = result?;
+        Ok(supported.then_some(backend))
+    }
+
+    /// The journal measurements are recorded in, if one is configured.
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// Asks the Attestation Agent for TEE evidence binding `runtime_data`.
+    pub async fn get_evidence(&self, runtime_data: &[u8]) -> Result<Vec<u8>> {
+        let (_, evidence) = self
+            .failover(|client| async move {
+                match client {
+                    ClientImpl::Ttrpc { client, timeout_ms } => {
+                        let mut req = GetEvidenceRequest::new();
+                        req.RuntimeData = runtime_data.to_vec();
+                        let resp = client
+                            .get_evidence(ttrpc_context(timeout_ms), &req)
+                            .await
+                            .map_err(ttrpc_failure)?;
+                        Ok(resp.Evidence)
+                    }
+                    ClientImpl::Http {
                         http_client,
                         base_url,
-                    },
-                })
+                    } => {
+                        let url = format!(
+                            "{}/aa/evidence?runtime_data={}",
+                            base_url.trim_end_matches('/'),
+                            hex::encode(runtime_data)
+                        );
+                        http_get_bytes(&http_client, &url).await
+                    }
+                    ClientImpl::DryRun | ClientImpl::Tpm { .. } => {
+                        Err(SendFailure::Unreachable(no_agent_unsupported()))
+                    }
+                }
+            })
+            .await
+            .map_err(SendFailure::into_error)?;
+        Ok(evidence)
+    }
+
+    /// Asks the Attestation Agent for an attestation token of `token_type`.
+    pub async fn get_token(&self, token_type: &str) -> Result<Vec<u8>> {
+        let (_, token) = self
+            .failover(|client| async move {
+                match client {
+                    ClientImpl::Ttrpc { client, timeout_ms } => {
+                        let mut req = GetTokenRequest::new();
+                        req.TokenType = token_type.to_string();
+                        let resp = client
+                            .get_token(ttrpc_context(timeout_ms), &req)
+                            .await
+                            .map_err(ttrpc_failure)?;
+                        Ok(resp.Token)
+                    }
+                    ClientImpl::Http {
+                        http_client,
+                        base_url,
+                    } => {
+                        let url = format!(
+                            "{}/aa/token?token_type={}",
+                            base_url.trim_end_matches('/'),
+                            token_type
+                        );
+                        http_get_bytes(&http_client, &url).await
+                    }
+                    ClientImpl::DryRun | ClientImpl::Tpm { .. } => {
+                        Err(SendFailure::Unreachable(no_agent_unsupported()))
+                    }
+                }
+            })
+            .await
+            .map_err(SendFailure::into_error)?;
+        Ok(token)
+    }
+
+    /// Pushes journaled measurements that were never acknowledged by the
+    /// backend, then compacts the journal. An entry the backend rejects stays
+    /// pending and the replay goes on; it stops only when no backend can be
+    /// reached.
+    pub async fn replay_journal(&self) -> Result<JournalReplay> {
+        let journal = self.journal.as_ref().ok_or_else(|| {
+            MeasurementError::Config("No measurement journal configured".to_string())
+        })?;
+        let pending = journal.unextended()?;
+        info!(
+            "Replaying {} unextended measurements from {:?}",
+            pending.len(),
+            journal.path()
+        );
+        let mut replay = JournalReplay::default();
+        for entry in &pending {
+            let mut record = entry.record.clone();
+            match self.send_with_retry(&record).await {
+                Ok(backend) => record.backend = Some(backend),
+                Err(SendFailure::Rejected(e)) => {
+                    warn!(
+                        "Journal entry {} ({}) was rejected: {}",
+                        entry.seq, record.operation, e
+                    );
+                    replay
+                        .rejected
+                        .push(format!("{} {}: {}", entry.seq, record.operation, e));
+                    continue;
+                }
+                Err(SendFailure::Unreachable(e)) => return Err(e),
             }
+            debug!("Replayed journal entry {}: {}", entry.seq, record.operation);
+            self.record_extended(Some(entry.seq), record);
+            replay.replayed += 1;
         }
+        replay.compacted = journal.compact()?;
+        Ok(replay)
     }
 
-    pub async fn extend_runtime_measurement(
+    fn record_extended(&self, seq: Option<u64>, record: MeasurementRecord) {
+        if let (Some(journal), Some(seq)) = (&self.journal, seq) {
+            if let Err(e) = journal.mark_extended(seq, &record) {
+                warn!("Failed to journal extension of {}: {}", record.operation, e);
+            }
+        }
+        if let Some(state) = &self.extension_state {
+            state.record(&record);
+        }
+        logging::audit(&record, Outcome::Extended, None);
+        self.keep_record(record);
+    }
+
+    /// Runs `request` against each backend in order until one is reachable.
+    /// Returns the name of the backend that answered.
+    async fn failover<T, F, Fut>(&self, request: F) -> std::result::Result<(String, T), SendFailure>
+    where
+        F: Fn(ClientImpl) -> Fut,
+        Fut: Future<Output = std::result::Result<T, SendFailure>>,
+    {
+        let mut last_error = None;
+        for backend in &self.backends {
+            let client = match backend.client().await {
+                Ok(client) => client,
+                Err(e) => {
+                    debug!("Backend {} is unavailable: {}", backend.name, e);
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+            match request(client).await {
+                Ok(value) => return Ok((backend.name.clone(), value)),
+                Err(SendFailure::Rejected(e)) => return Err(SendFailure::Rejected(e)),
+                Err(SendFailure::Unreachable(e)) => {
+                    warn!(
+                        "Backend {} failed, trying the next one: {}",
+                        backend.name, e
+                    );
+                    backend.disconnect().await;
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(SendFailure::Unreachable(last_error.unwrap_or_else(|| {
+            MeasurementError::RpcClient("No measurement backend configured".to_string())
+        })))
+    }
+
+    /// Extends a measurement through the first reachable backend and returns
+    /// its name.
+    async fn send(
         &self,
         pcr_index_opt: Option<u64>,
         domain: &str,
         operation: &str,
         content: &str,
-    ) -> Result<()> {
-        match &self.inner {
-            ClientImpl::Ttrpc(client) => {
+    ) -> std::result::Result<String, SendFailure> {
+        let result = self
+            .failover(|client| async move {
+                Self::send_to(
+                    client,
+                    self.event_log.as_ref(),
+                    pcr_index_opt,
+                    domain,
+                    operation,
+                    content,
+                )
+                .await
+            })
+            .await;
+        self.note_outcome(&result);
+        let (backend, _) = result?;
+        Ok(backend)
+    }
+
+    /// Updates the connectivity shown by the status server after an attempt
+    /// to send.
+    fn note_outcome<T>(&self, result: &std::result::Result<(String, T), SendFailure>) {
+        let mut connectivity = self.connectivity.lock().unwrap();
+        match result {
+            Ok((backend, _)) => {
+                connectivity.reachable = true;
+                connectivity.last_success = Some(unix_timestamp());
+                connectivity.backend = Some(backend.clone());
+            }
+            // A rejection still came from a reachable backend.
+            Err(SendFailure::Rejected(_)) => connectivity.reachable = true,
+            Err(SendFailure::Unreachable(e)) => {
+                connectivity.reachable = false;
+                connectivity.last_failure = Some(unix_timestamp());
+                connectivity.last_error = Some(e.to_string());
+            }
+        }
+    }
+
+    async fn send_to(
+        client: ClientImpl,
+        event_log: Option<&EventLog>,
+        pcr_index_opt: Option<u64>,
+        domain: &str,
+        operation: &str,
+        content: &str,
+    ) -> std::result::Result<(), SendFailure> {
+        match client {
+            ClientImpl::Ttrpc { client, timeout_ms } => {
                 debug!(
                     "Extending runtime measurement via ttrpc: pcr_opt={:?}, domain={}, op={}, content={}",
                     pcr_index_opt, domain, operation, content
@@ -95,7 +1092,7 @@ impl AAClient {
                 }
 
                 match client
-                    .extend_runtime_measurement(default_ttrpc_context(), &req)
+                    .extend_runtime_measurement(ttrpc_context(timeout_ms), &req)
                     .await
                 {
                     Ok(_) => {
@@ -105,7 +1102,7 @@ impl AAClient {
                     Err(e) => {
                         let err_msg = format!("Failed to extend runtime measurement: {}", e);
                         log::error!("{}", err_msg);
-                        Err(MeasurementError::AttestationAgentClient(e))
+                        Err(ttrpc_failure(e))
                     }
                 }
             }
@@ -130,11 +1127,10 @@ impl AAClient {
                     .send()
                     .await
                     .map_err(|e| {
-                        MeasurementError::Http(format!(
+                        SendFailure::Unreachable(MeasurementError::Http(format!(
                             "HTTP request to {} failed: {}",
-                            url,
-                            e.to_string()
-                        ))
+                            url, e
+                        )))
                     })?;
                 if resp.status().is_success() {
                     debug!("Successfully extended runtime measurement via HTTP.");
@@ -142,20 +1138,181 @@ impl AAClient {
                 }
                 let status = resp.status();
                 let body = resp.text().await.unwrap_or_default();
-                Err(MeasurementError::Http(format!(
-                    "HTTP {} returned status {}: {}",
-                    url,
+                Err(http_status_failure(
                     status,
-                    body
-                )))
+                    format!("HTTP {} returned status {}: {}", url, status, body),
+                ))
             }
+            ClientImpl::DryRun => {
+                info!(
+                    "Dry run: domain={}, op={}, content={}, pcr={:?}",
+                    domain, operation, content, pcr_index_opt
+                );
+                Ok(())
+            }
+            ClientImpl::Tpm { device, config } => {
+                let pcr_index = pcr_index_opt
+                    .map(|index| u32::try_from(index).unwrap_or(u32::MAX))
+                    .unwrap_or(config.pcr_index);
+                // The PCR is replayed from the event log, so an event is
+                // only extended while the log can be written, and only
+                // logged once it was extended.
+                let event_log = event_log.ok_or_else(|| {
+                    SendFailure::Rejected(MeasurementError::Tpm(
+                        "No event log to record PCR extensions in".to_string(),
+                    ))
+                })?;
+                event_log.check_writable().map_err(|e| {
+                    SendFailure::Rejected(MeasurementError::Tpm(format!(
+                        "Not extending PCR {}: the event log cannot be written: {}",
+                        pcr_index, e
+                    )))
+                })?;
+                let event = aael_event(domain, operation, content);
+                let (algorithm, digest) =
+                    tpm::event_digest(event.as_bytes(), &config.hash_algorithm)
+                        .map_err(SendFailure::Rejected)?;
+                debug!(
+                    "Extending PCR {} of {:?} with domain={}, op={}",
+                    pcr_index, device, domain, operation
+                );
+                let result = tokio::task::spawn_blocking(move || {
+                    tpm::pcr_extend(&device, pcr_index, algorithm, &digest)
+                })
+                .await
+                .map_err(|e| {
+                    SendFailure::Unreachable(MeasurementError::Other(anyhow::anyhow!(e)))
+                })?;
+                match result {
+                    Ok(()) => {}
+                    Err(e @ MeasurementError::Io(_)) => return Err(SendFailure::Unreachable(e)),
+                    Err(e) => return Err(SendFailure::Rejected(e)),
+                }
+                event_log.append(domain, operation, content).map_err(|e| {
+                    SendFailure::Rejected(MeasurementError::Tpm(format!(
+                        "PCR {} was extended with {} {}, but the event log write failed: {}",
+                        pcr_index, domain, operation, e
+                    )))
+                })
+            }
+        }
+    }
+}
+
+/// An error status means the agent was reached and refused the request;
+/// anything else is a transport failure.
+fn ttrpc_failure(e: ttrpc::Error) -> SendFailure {
+    match e {
+        ttrpc::Error::RpcStatus(_) => {
+            SendFailure::Rejected(MeasurementError::AttestationAgentClient(e))
         }
+        e => SendFailure::Unreachable(MeasurementError::AttestationAgentClient(e)),
+    }
+}
+
+/// Server errors are worth retrying elsewhere; client errors are not.
+fn http_status_failure(status: reqwest::StatusCode, message: String) -> SendFailure {
+    if status.is_server_error() {
+        SendFailure::Unreachable(MeasurementError::Http(message))
+    } else {
+        SendFailure::Rejected(MeasurementError::Http(message))
+    }
+}
+
+fn no_agent_unsupported() -> MeasurementError {
+    MeasurementError::RpcClient("Not available without an Attestation Agent".to_string())
+}
+
+/// Builds the client of an `http_api` backend with its extra CA, client
+/// identity, bearer token and timeouts.
+fn build_http_client(base_url: &str, config: &AaHttpConfig) -> Result<reqwest::Client> {
+    let read = |path: &str| {
+        fs::read(path).map_err(|e| MeasurementError::Config(format!("Cannot read {}: {}", path, e)))
+    };
+    let mut builder = reqwest::Client::builder()
+        .user_agent("measurement-tool/0.1.0")
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs));
+    if let Some(ref path) = config.ca_cert_path {
+        let ca = reqwest::Certificate::from_pem(&read(path)?).map_err(|e| {
+            MeasurementError::Http(format!("Invalid CA certificate {}: {}", path, e))
+        })?;
+        builder = builder.add_root_certificate(ca);
+    }
+    match (&config.client_cert_path, &config.client_key_path) {
+        (Some(cert_path), Some(key_path)) => {
+            // Identity::from_pem takes the chain and the key in one buffer.
+            let mut pem = read(cert_path)?;
+            pem.push(b'\n');
+            pem.extend(read(key_path)?);
+            let identity = reqwest::Identity::from_pem(&pem).map_err(|e| {
+                MeasurementError::Http(format!(
+                    "Invalid client certificate {} or key {}: {}",
+                    cert_path, key_path, e
+                ))
+            })?;
+            builder = builder.identity(identity);
+        }
+        (None, None) => {}
+        _ => {
+            return Err(MeasurementError::Config(
+                "Set both client_cert_path and client_key_path for mutual TLS".to_string(),
+            ))
+        }
+    }
+    if let Some(ref path) = config.token_path {
+        let token = String::from_utf8_lossy(&read(path)?).trim().to_string();
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| MeasurementError::Config(format!("Invalid bearer token in {}", path)))?;
+        value.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, value);
+        builder = builder.default_headers(headers);
+    }
+    let authenticated = config.token_path.is_some() || config.client_cert_path.is_some();
+    if authenticated && !base_url.starts_with("https://") {
+        warn!(
+            "Credentials for {} are sent without TLS; use an https:// address",
+            base_url
+        );
+    }
+    builder
+        .build()
+        .map_err(|e| MeasurementError::Http(format!("Failed to build HTTP client: {}", e)))
+}
+
+async fn http_get_bytes(
+    http_client: &reqwest::Client,
+    url: &str,
+) -> std::result::Result<Vec<u8>, SendFailure> {
+    let unreachable = |message: String| SendFailure::Unreachable(MeasurementError::Http(message));
+    let resp = http_client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| unreachable(format!("HTTP request to {} failed: {}", url, e)))?;
+    let status = resp.status();
+    let body = resp
+        .bytes()
+        .await
+        .map_err(|e| unreachable(format!("Reading {} failed: {}", url, e)))?;
+    if !status.is_success() {
+        return Err(http_status_failure(
+            status,
+            format!(
+                "HTTP {} returned status {}: {}",
+                url,
+                status,
+                String::from_utf8_lossy(&body)
+            ),
+        ));
     }
+    Ok(body.to_vec())
 }
 
-fn default_ttrpc_context() -> ttrpc::context::Context {
+fn ttrpc_context(timeout_ms: u64) -> ttrpc::context::Context {
     ttrpc::context::Context {
-        timeout_nano: 5_000_000_000,
+        timeout_nano: i64::try_from(timeout_ms.saturating_mul(1_000_000)).unwrap_or(i64::MAX),
         ..Default::default()
     }
 }
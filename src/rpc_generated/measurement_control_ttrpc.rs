@@ -0,0 +1,134 @@
+// This file is generated by ttrpc-compiler 0.7.0. Do not edit
+// @generated
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unknown_lints)]
+#![allow(clipto_camel_casepy)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unsafe_code)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+#![allow(clippy::all)]
+use protobuf::{CodedInputStream, CodedOutputStream, Message};
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct MeasurementControlServiceClient {
+    client: ::ttrpc::r#async::Client,
+}
+
+impl MeasurementControlServiceClient {
+    pub fn new(client: ::ttrpc::r#async::Client) -> Self {
+        MeasurementControlServiceClient {
+            client,
+        }
+    }
+
+    pub async fn measure_file(&self, ctx: ttrpc::context::Context, req: &super::measurement_control::MeasureFileRequest) -> ::ttrpc::Result<super::measurement_control::MeasureResponse> {
+        let mut cres = super::measurement_control::MeasureResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "measurement_control.MeasurementControlService", "MeasureFile", cres);
+    }
+
+    pub async fn measure_dir(&self, ctx: ttrpc::context::Context, req: &super::measurement_control::MeasureDirRequest) -> ::ttrpc::Result<super::measurement_control::MeasureResponse> {
+        let mut cres = super::measurement_control::MeasureResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "measurement_control.MeasurementControlService", "MeasureDir", cres);
+    }
+
+    pub async fn remeasure(&self, ctx: ttrpc::context::Context, req: &super::measurement_control::RemeasureRequest) -> ::ttrpc::Result<super::measurement_control::RemeasureResponse> {
+        let mut cres = super::measurement_control::RemeasureResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "measurement_control.MeasurementControlService", "Remeasure", cres);
+    }
+
+    pub async fn status(&self, ctx: ttrpc::context::Context, req: &super::measurement_control::StatusRequest) -> ::ttrpc::Result<super::measurement_control::StatusResponse> {
+        let mut cres = super::measurement_control::StatusResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "measurement_control.MeasurementControlService", "Status", cres);
+    }
+}
+
+struct MeasureFileMethod {
+    service: Arc<dyn MeasurementControlService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for MeasureFileMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, measurement_control, MeasureFileRequest, measure_file);
+    }
+}
+
+struct MeasureDirMethod {
+    service: Arc<dyn MeasurementControlService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for MeasureDirMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, measurement_control, MeasureDirRequest, measure_dir);
+    }
+}
+
+struct RemeasureMethod {
+    service: Arc<dyn MeasurementControlService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for RemeasureMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, measurement_control, RemeasureRequest, remeasure);
+    }
+}
+
+struct StatusMethod {
+    service: Arc<dyn MeasurementControlService + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for StatusMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, measurement_control, StatusRequest, status);
+    }
+}
+
+#[async_trait]
+pub trait MeasurementControlService: Sync {
+    async fn measure_file(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::measurement_control::MeasureFileRequest) -> ::ttrpc::Result<super::measurement_control::MeasureResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/measurement_control.MeasurementControlService/MeasureFile is not supported".to_string())))
+    }
+    async fn measure_dir(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::measurement_control::MeasureDirRequest) -> ::ttrpc::Result<super::measurement_control::MeasureResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/measurement_control.MeasurementControlService/MeasureDir is not supported".to_string())))
+    }
+    async fn remeasure(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::measurement_control::RemeasureRequest) -> ::ttrpc::Result<super::measurement_control::RemeasureResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/measurement_control.MeasurementControlService/Remeasure is not supported".to_string())))
+    }
+    async fn status(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::measurement_control::StatusRequest) -> ::ttrpc::Result<super::measurement_control::StatusResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/measurement_control.MeasurementControlService/Status is not supported".to_string())))
+    }
+}
+
+pub fn create_measurement_control_service(service: Arc<dyn MeasurementControlService + Send + Sync>) -> HashMap<String, ::ttrpc::r#async::Service> {
+    let mut ret = HashMap::new();
+    let mut methods = HashMap::new();
+    let streams = HashMap::new();
+
+    methods.insert("MeasureFile".to_string(),
+                    Box::new(MeasureFileMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("MeasureDir".to_string(),
+                    Box::new(MeasureDirMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("Remeasure".to_string(),
+                    Box::new(RemeasureMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("Status".to_string(),
+                    Box::new(StatusMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    ret.insert("measurement_control.MeasurementControlService".to_string(), ::ttrpc::r#async::Service{ methods, streams });
+    ret
+}
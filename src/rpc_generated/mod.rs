@@ -3,3 +3,7 @@
 // These modules are generated by build.rs using ttrpc_codegen
 pub mod attestation_agent; // Contains the protobuf message structures
 pub mod attestation_agent_ttrpc; // Contains the ttrpc client and server definitions
+pub mod measurement_control; // Control API messages
+pub mod measurement_control_ttrpc; // Control API client and service definitions
+pub mod nri; // NRI plugin API messages
+pub mod nri_ttrpc; // NRI runtime client and plugin service definitions
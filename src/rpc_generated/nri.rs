@@ -0,0 +1,2604 @@
+// This file is generated by rust-protobuf 3.7.2. Do not edit
+// .proto file is parsed by pure
+// @generated
+
+// https://github.com/rust-lang/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy::all)]
+
+#![allow(unused_attributes)]
+#![cfg_attr(rustfmt, rustfmt::skip)]
+
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unused_results)]
+#![allow(unused_mut)]
+
+//! Generated file from `nri.proto`
+
+/// Generated files are compatible only with the same version
+/// of protobuf runtime.
+const _PROTOBUF_VERSION_CHECK: () = ::protobuf::VERSION_3_7_2;
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.Empty)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct Empty {
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.Empty.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Empty {
+    fn default() -> &'a Empty {
+        <Empty as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Empty {
+    pub fn new() -> Empty {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Empty>(
+            "Empty",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Empty {
+    const NAME: &'static str = "Empty";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Empty {
+        Empty::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Empty {
+        static instance: Empty = Empty {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Empty {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Empty").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Empty {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Empty {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.RegisterPluginRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct RegisterPluginRequest {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.RegisterPluginRequest.plugin_name)
+    pub plugin_name: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.RegisterPluginRequest.plugin_idx)
+    pub plugin_idx: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.RegisterPluginRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a RegisterPluginRequest {
+    fn default() -> &'a RegisterPluginRequest {
+        <RegisterPluginRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl RegisterPluginRequest {
+    pub fn new() -> RegisterPluginRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "plugin_name",
+            |m: &RegisterPluginRequest| { &m.plugin_name },
+            |m: &mut RegisterPluginRequest| { &mut m.plugin_name },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "plugin_idx",
+            |m: &RegisterPluginRequest| { &m.plugin_idx },
+            |m: &mut RegisterPluginRequest| { &mut m.plugin_idx },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<RegisterPluginRequest>(
+            "RegisterPluginRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for RegisterPluginRequest {
+    const NAME: &'static str = "RegisterPluginRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.plugin_name = is.read_string()?;
+                },
+                18 => {
+                    self.plugin_idx = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.plugin_name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.plugin_name);
+        }
+        if !self.plugin_idx.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.plugin_idx);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.plugin_name.is_empty() {
+            os.write_string(1, &self.plugin_name)?;
+        }
+        if !self.plugin_idx.is_empty() {
+            os.write_string(2, &self.plugin_idx)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> RegisterPluginRequest {
+        RegisterPluginRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.plugin_name.clear();
+        self.plugin_idx.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static RegisterPluginRequest {
+        static instance: RegisterPluginRequest = RegisterPluginRequest {
+            plugin_name: ::std::string::String::new(),
+            plugin_idx: ::std::string::String::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for RegisterPluginRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("RegisterPluginRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for RegisterPluginRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RegisterPluginRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.ConfigureRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct ConfigureRequest {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.ConfigureRequest.config)
+    pub config: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.ConfigureRequest.runtime_name)
+    pub runtime_name: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.ConfigureRequest.runtime_version)
+    pub runtime_version: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.ConfigureRequest.registration_timeout)
+    pub registration_timeout: i64,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.ConfigureRequest.request_timeout)
+    pub request_timeout: i64,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.ConfigureRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ConfigureRequest {
+    fn default() -> &'a ConfigureRequest {
+        <ConfigureRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ConfigureRequest {
+    pub fn new() -> ConfigureRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(5);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "config",
+            |m: &ConfigureRequest| { &m.config },
+            |m: &mut ConfigureRequest| { &mut m.config },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "runtime_name",
+            |m: &ConfigureRequest| { &m.runtime_name },
+            |m: &mut ConfigureRequest| { &mut m.runtime_name },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "runtime_version",
+            |m: &ConfigureRequest| { &m.runtime_version },
+            |m: &mut ConfigureRequest| { &mut m.runtime_version },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "registration_timeout",
+            |m: &ConfigureRequest| { &m.registration_timeout },
+            |m: &mut ConfigureRequest| { &mut m.registration_timeout },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "request_timeout",
+            |m: &ConfigureRequest| { &m.request_timeout },
+            |m: &mut ConfigureRequest| { &mut m.request_timeout },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ConfigureRequest>(
+            "ConfigureRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ConfigureRequest {
+    const NAME: &'static str = "ConfigureRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.config = is.read_string()?;
+                },
+                18 => {
+                    self.runtime_name = is.read_string()?;
+                },
+                26 => {
+                    self.runtime_version = is.read_string()?;
+                },
+                32 => {
+                    self.registration_timeout = is.read_int64()?;
+                },
+                40 => {
+                    self.request_timeout = is.read_int64()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.config.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.config);
+        }
+        if !self.runtime_name.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.runtime_name);
+        }
+        if !self.runtime_version.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.runtime_version);
+        }
+        if self.registration_timeout != 0 {
+            my_size += ::protobuf::rt::int64_size(4, self.registration_timeout);
+        }
+        if self.request_timeout != 0 {
+            my_size += ::protobuf::rt::int64_size(5, self.request_timeout);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.config.is_empty() {
+            os.write_string(1, &self.config)?;
+        }
+        if !self.runtime_name.is_empty() {
+            os.write_string(2, &self.runtime_name)?;
+        }
+        if !self.runtime_version.is_empty() {
+            os.write_string(3, &self.runtime_version)?;
+        }
+        if self.registration_timeout != 0 {
+            os.write_int64(4, self.registration_timeout)?;
+        }
+        if self.request_timeout != 0 {
+            os.write_int64(5, self.request_timeout)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ConfigureRequest {
+        ConfigureRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.config.clear();
+        self.runtime_name.clear();
+        self.runtime_version.clear();
+        self.registration_timeout = 0;
+        self.request_timeout = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ConfigureRequest {
+        static instance: ConfigureRequest = ConfigureRequest {
+            config: ::std::string::String::new(),
+            runtime_name: ::std::string::String::new(),
+            runtime_version: ::std::string::String::new(),
+            registration_timeout: 0,
+            request_timeout: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ConfigureRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ConfigureRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ConfigureRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ConfigureRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.ConfigureResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct ConfigureResponse {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.ConfigureResponse.events)
+    pub events: i32,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.ConfigureResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ConfigureResponse {
+    fn default() -> &'a ConfigureResponse {
+        <ConfigureResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ConfigureResponse {
+    pub fn new() -> ConfigureResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(1);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "events",
+            |m: &ConfigureResponse| { &m.events },
+            |m: &mut ConfigureResponse| { &mut m.events },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ConfigureResponse>(
+            "ConfigureResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ConfigureResponse {
+    const NAME: &'static str = "ConfigureResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                16 => {
+                    self.events = is.read_int32()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.events != 0 {
+            my_size += ::protobuf::rt::int32_size(2, self.events);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.events != 0 {
+            os.write_int32(2, self.events)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ConfigureResponse {
+        ConfigureResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.events = 0;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ConfigureResponse {
+        static instance: ConfigureResponse = ConfigureResponse {
+            events: 0,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ConfigureResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ConfigureResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ConfigureResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ConfigureResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.SynchronizeRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct SynchronizeRequest {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.SynchronizeRequest.pods)
+    pub pods: ::std::vec::Vec<PodSandbox>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.SynchronizeRequest.containers)
+    pub containers: ::std::vec::Vec<Container>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.SynchronizeRequest.more)
+    pub more: bool,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.SynchronizeRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a SynchronizeRequest {
+    fn default() -> &'a SynchronizeRequest {
+        <SynchronizeRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SynchronizeRequest {
+    pub fn new() -> SynchronizeRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "pods",
+            |m: &SynchronizeRequest| { &m.pods },
+            |m: &mut SynchronizeRequest| { &mut m.pods },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "containers",
+            |m: &SynchronizeRequest| { &m.containers },
+            |m: &mut SynchronizeRequest| { &mut m.containers },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "more",
+            |m: &SynchronizeRequest| { &m.more },
+            |m: &mut SynchronizeRequest| { &mut m.more },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<SynchronizeRequest>(
+            "SynchronizeRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for SynchronizeRequest {
+    const NAME: &'static str = "SynchronizeRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.pods.push(is.read_message()?);
+                },
+                18 => {
+                    self.containers.push(is.read_message()?);
+                },
+                24 => {
+                    self.more = is.read_bool()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.pods {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        for value in &self.containers {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        if self.more != false {
+            my_size += 1 + 1;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.pods {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        };
+        for v in &self.containers {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        };
+        if self.more != false {
+            os.write_bool(3, self.more)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> SynchronizeRequest {
+        SynchronizeRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.pods.clear();
+        self.containers.clear();
+        self.more = false;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static SynchronizeRequest {
+        static instance: SynchronizeRequest = SynchronizeRequest {
+            pods: ::std::vec::Vec::new(),
+            containers: ::std::vec::Vec::new(),
+            more: false,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for SynchronizeRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("SynchronizeRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for SynchronizeRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SynchronizeRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.SynchronizeResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct SynchronizeResponse {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.SynchronizeResponse.update)
+    pub update: ::std::vec::Vec<ContainerUpdate>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.SynchronizeResponse.more)
+    pub more: bool,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.SynchronizeResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a SynchronizeResponse {
+    fn default() -> &'a SynchronizeResponse {
+        <SynchronizeResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl SynchronizeResponse {
+    pub fn new() -> SynchronizeResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "update",
+            |m: &SynchronizeResponse| { &m.update },
+            |m: &mut SynchronizeResponse| { &mut m.update },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "more",
+            |m: &SynchronizeResponse| { &m.more },
+            |m: &mut SynchronizeResponse| { &mut m.more },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<SynchronizeResponse>(
+            "SynchronizeResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for SynchronizeResponse {
+    const NAME: &'static str = "SynchronizeResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.update.push(is.read_message()?);
+                },
+                16 => {
+                    self.more = is.read_bool()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        for value in &self.update {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        if self.more != false {
+            my_size += 1 + 1;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        for v in &self.update {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        };
+        if self.more != false {
+            os.write_bool(2, self.more)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> SynchronizeResponse {
+        SynchronizeResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.update.clear();
+        self.more = false;
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static SynchronizeResponse {
+        static instance: SynchronizeResponse = SynchronizeResponse {
+            update: ::std::vec::Vec::new(),
+            more: false,
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for SynchronizeResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("SynchronizeResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for SynchronizeResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SynchronizeResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.CreateContainerRequest)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct CreateContainerRequest {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.CreateContainerRequest.pod)
+    pub pod: ::protobuf::MessageField<PodSandbox>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.CreateContainerRequest.container)
+    pub container: ::protobuf::MessageField<Container>,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.CreateContainerRequest.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a CreateContainerRequest {
+    fn default() -> &'a CreateContainerRequest {
+        <CreateContainerRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl CreateContainerRequest {
+    pub fn new() -> CreateContainerRequest {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, PodSandbox>(
+            "pod",
+            |m: &CreateContainerRequest| { &m.pod },
+            |m: &mut CreateContainerRequest| { &mut m.pod },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, Container>(
+            "container",
+            |m: &CreateContainerRequest| { &m.container },
+            |m: &mut CreateContainerRequest| { &mut m.container },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<CreateContainerRequest>(
+            "CreateContainerRequest",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for CreateContainerRequest {
+    const NAME: &'static str = "CreateContainerRequest";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.pod)?;
+                },
+                18 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.container)?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.pod.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if let Some(v) = self.container.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.pod.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        }
+        if let Some(v) = self.container.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> CreateContainerRequest {
+        CreateContainerRequest::new()
+    }
+
+    fn clear(&mut self) {
+        self.pod.clear();
+        self.container.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static CreateContainerRequest {
+        static instance: CreateContainerRequest = CreateContainerRequest {
+            pod: ::protobuf::MessageField::none(),
+            container: ::protobuf::MessageField::none(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for CreateContainerRequest {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("CreateContainerRequest").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for CreateContainerRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for CreateContainerRequest {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.CreateContainerResponse)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct CreateContainerResponse {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.CreateContainerResponse.adjust)
+    pub adjust: ::protobuf::MessageField<ContainerAdjustment>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.CreateContainerResponse.update)
+    pub update: ::std::vec::Vec<ContainerUpdate>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.CreateContainerResponse.evict)
+    pub evict: ::std::vec::Vec<ContainerEviction>,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.CreateContainerResponse.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a CreateContainerResponse {
+    fn default() -> &'a CreateContainerResponse {
+        <CreateContainerResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl CreateContainerResponse {
+    pub fn new() -> CreateContainerResponse {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, ContainerAdjustment>(
+            "adjust",
+            |m: &CreateContainerResponse| { &m.adjust },
+            |m: &mut CreateContainerResponse| { &mut m.adjust },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "update",
+            |m: &CreateContainerResponse| { &m.update },
+            |m: &mut CreateContainerResponse| { &mut m.update },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "evict",
+            |m: &CreateContainerResponse| { &m.evict },
+            |m: &mut CreateContainerResponse| { &mut m.evict },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<CreateContainerResponse>(
+            "CreateContainerResponse",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for CreateContainerResponse {
+    const NAME: &'static str = "CreateContainerResponse";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.adjust)?;
+                },
+                18 => {
+                    self.update.push(is.read_message()?);
+                },
+                26 => {
+                    self.evict.push(is.read_message()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if let Some(v) = self.adjust.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        for value in &self.update {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        for value in &self.evict {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if let Some(v) = self.adjust.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(1, v, os)?;
+        }
+        for v in &self.update {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        };
+        for v in &self.evict {
+            ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> CreateContainerResponse {
+        CreateContainerResponse::new()
+    }
+
+    fn clear(&mut self) {
+        self.adjust.clear();
+        self.update.clear();
+        self.evict.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static CreateContainerResponse {
+        static instance: CreateContainerResponse = CreateContainerResponse {
+            adjust: ::protobuf::MessageField::none(),
+            update: ::std::vec::Vec::new(),
+            evict: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for CreateContainerResponse {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("CreateContainerResponse").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for CreateContainerResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for CreateContainerResponse {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.StateChangeEvent)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct StateChangeEvent {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.StateChangeEvent.event)
+    pub event: ::protobuf::EnumOrUnknown<Event>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.StateChangeEvent.pod)
+    pub pod: ::protobuf::MessageField<PodSandbox>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.StateChangeEvent.container)
+    pub container: ::protobuf::MessageField<Container>,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.StateChangeEvent.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a StateChangeEvent {
+    fn default() -> &'a StateChangeEvent {
+        <StateChangeEvent as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl StateChangeEvent {
+    pub fn new() -> StateChangeEvent {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(3);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "event",
+            |m: &StateChangeEvent| { &m.event },
+            |m: &mut StateChangeEvent| { &mut m.event },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, PodSandbox>(
+            "pod",
+            |m: &StateChangeEvent| { &m.pod },
+            |m: &mut StateChangeEvent| { &mut m.pod },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, Container>(
+            "container",
+            |m: &StateChangeEvent| { &m.container },
+            |m: &mut StateChangeEvent| { &mut m.container },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<StateChangeEvent>(
+            "StateChangeEvent",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for StateChangeEvent {
+    const NAME: &'static str = "StateChangeEvent";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.event = is.read_enum_or_unknown()?;
+                },
+                18 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.pod)?;
+                },
+                26 => {
+                    ::protobuf::rt::read_singular_message_into_field(is, &mut self.container)?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.event != ::protobuf::EnumOrUnknown::new(Event::UNKNOWN) {
+            my_size += ::protobuf::rt::int32_size(1, self.event.value());
+        }
+        if let Some(v) = self.pod.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        if let Some(v) = self.container.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.event != ::protobuf::EnumOrUnknown::new(Event::UNKNOWN) {
+            os.write_enum(1, ::protobuf::EnumOrUnknown::value(&self.event))?;
+        }
+        if let Some(v) = self.pod.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(2, v, os)?;
+        }
+        if let Some(v) = self.container.as_ref() {
+            ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> StateChangeEvent {
+        StateChangeEvent::new()
+    }
+
+    fn clear(&mut self) {
+        self.event = ::protobuf::EnumOrUnknown::new(Event::UNKNOWN);
+        self.pod.clear();
+        self.container.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static StateChangeEvent {
+        static instance: StateChangeEvent = StateChangeEvent {
+            event: ::protobuf::EnumOrUnknown::from_i32(0),
+            pod: ::protobuf::MessageField::none(),
+            container: ::protobuf::MessageField::none(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for StateChangeEvent {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("StateChangeEvent").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for StateChangeEvent {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for StateChangeEvent {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.PodSandbox)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct PodSandbox {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.PodSandbox.id)
+    pub id: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.PodSandbox.name)
+    pub name: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.PodSandbox.uid)
+    pub uid: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.PodSandbox.namespace)
+    pub namespace: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.PodSandbox.labels)
+    pub labels: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.PodSandbox.annotations)
+    pub annotations: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.PodSandbox.runtime_handler)
+    pub runtime_handler: ::std::string::String,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.PodSandbox.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a PodSandbox {
+    fn default() -> &'a PodSandbox {
+        <PodSandbox as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PodSandbox {
+    pub fn new() -> PodSandbox {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(7);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "id",
+            |m: &PodSandbox| { &m.id },
+            |m: &mut PodSandbox| { &mut m.id },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "name",
+            |m: &PodSandbox| { &m.name },
+            |m: &mut PodSandbox| { &mut m.name },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "uid",
+            |m: &PodSandbox| { &m.uid },
+            |m: &mut PodSandbox| { &mut m.uid },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "namespace",
+            |m: &PodSandbox| { &m.namespace },
+            |m: &mut PodSandbox| { &mut m.namespace },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
+            "labels",
+            |m: &PodSandbox| { &m.labels },
+            |m: &mut PodSandbox| { &mut m.labels },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
+            "annotations",
+            |m: &PodSandbox| { &m.annotations },
+            |m: &mut PodSandbox| { &mut m.annotations },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "runtime_handler",
+            |m: &PodSandbox| { &m.runtime_handler },
+            |m: &mut PodSandbox| { &mut m.runtime_handler },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<PodSandbox>(
+            "PodSandbox",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for PodSandbox {
+    const NAME: &'static str = "PodSandbox";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.id = is.read_string()?;
+                },
+                18 => {
+                    self.name = is.read_string()?;
+                },
+                26 => {
+                    self.uid = is.read_string()?;
+                },
+                34 => {
+                    self.namespace = is.read_string()?;
+                },
+                42 => {
+                    let len = is.read_raw_varint32()?;
+                    let old_limit = is.push_limit(len as u64)?;
+                    let mut key = ::std::default::Default::default();
+                    let mut value = ::std::default::Default::default();
+                    while let Some(tag) = is.read_raw_tag_or_eof()? {
+                        match tag {
+                            10 => key = is.read_string()?,
+                            18 => value = is.read_string()?,
+                            _ => ::protobuf::rt::skip_field_for_tag(tag, is)?,
+                        };
+                    }
+                    is.pop_limit(old_limit);
+                    self.labels.insert(key, value);
+                },
+                50 => {
+                    let len = is.read_raw_varint32()?;
+                    let old_limit = is.push_limit(len as u64)?;
+                    let mut key = ::std::default::Default::default();
+                    let mut value = ::std::default::Default::default();
+                    while let Some(tag) = is.read_raw_tag_or_eof()? {
+                        match tag {
+                            10 => key = is.read_string()?,
+                            18 => value = is.read_string()?,
+                            _ => ::protobuf::rt::skip_field_for_tag(tag, is)?,
+                        };
+                    }
+                    is.pop_limit(old_limit);
+                    self.annotations.insert(key, value);
+                },
+                58 => {
+                    self.runtime_handler = is.read_string()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.id.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.id);
+        }
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.name);
+        }
+        if !self.uid.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.uid);
+        }
+        if !self.namespace.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.namespace);
+        }
+        for (k, v) in &self.labels {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::string_size(1, &k);
+            entry_size += ::protobuf::rt::string_size(2, &v);
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(entry_size) + entry_size
+        };
+        for (k, v) in &self.annotations {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::string_size(1, &k);
+            entry_size += ::protobuf::rt::string_size(2, &v);
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(entry_size) + entry_size
+        };
+        if !self.runtime_handler.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.runtime_handler);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.id.is_empty() {
+            os.write_string(1, &self.id)?;
+        }
+        if !self.name.is_empty() {
+            os.write_string(2, &self.name)?;
+        }
+        if !self.uid.is_empty() {
+            os.write_string(3, &self.uid)?;
+        }
+        if !self.namespace.is_empty() {
+            os.write_string(4, &self.namespace)?;
+        }
+        for (k, v) in &self.labels {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::string_size(1, &k);
+            entry_size += ::protobuf::rt::string_size(2, &v);
+            os.write_raw_varint32(42)?; // Tag.
+            os.write_raw_varint32(entry_size as u32)?;
+            os.write_string(1, &k)?;
+            os.write_string(2, &v)?;
+        };
+        for (k, v) in &self.annotations {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::string_size(1, &k);
+            entry_size += ::protobuf::rt::string_size(2, &v);
+            os.write_raw_varint32(50)?; // Tag.
+            os.write_raw_varint32(entry_size as u32)?;
+            os.write_string(1, &k)?;
+            os.write_string(2, &v)?;
+        };
+        if !self.runtime_handler.is_empty() {
+            os.write_string(7, &self.runtime_handler)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> PodSandbox {
+        PodSandbox::new()
+    }
+
+    fn clear(&mut self) {
+        self.id.clear();
+        self.name.clear();
+        self.uid.clear();
+        self.namespace.clear();
+        self.labels.clear();
+        self.annotations.clear();
+        self.runtime_handler.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static PodSandbox {
+        static instance: ::protobuf::rt::Lazy<PodSandbox> = ::protobuf::rt::Lazy::new();
+        instance.get(PodSandbox::new)
+    }
+}
+
+impl ::protobuf::MessageFull for PodSandbox {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("PodSandbox").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for PodSandbox {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PodSandbox {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.Container)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct Container {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Container.id)
+    pub id: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Container.pod_sandbox_id)
+    pub pod_sandbox_id: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Container.name)
+    pub name: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Container.labels)
+    pub labels: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Container.annotations)
+    pub annotations: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Container.args)
+    pub args: ::std::vec::Vec<::std::string::String>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Container.env)
+    pub env: ::std::vec::Vec<::std::string::String>,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Container.mounts)
+    pub mounts: ::std::vec::Vec<Mount>,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.Container.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Container {
+    fn default() -> &'a Container {
+        <Container as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Container {
+    pub fn new() -> Container {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(8);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "id",
+            |m: &Container| { &m.id },
+            |m: &mut Container| { &mut m.id },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "pod_sandbox_id",
+            |m: &Container| { &m.pod_sandbox_id },
+            |m: &mut Container| { &mut m.pod_sandbox_id },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "name",
+            |m: &Container| { &m.name },
+            |m: &mut Container| { &mut m.name },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
+            "labels",
+            |m: &Container| { &m.labels },
+            |m: &mut Container| { &mut m.labels },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_map_simpler_accessor_new::<_, _>(
+            "annotations",
+            |m: &Container| { &m.annotations },
+            |m: &mut Container| { &mut m.annotations },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "args",
+            |m: &Container| { &m.args },
+            |m: &mut Container| { &mut m.args },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "env",
+            |m: &Container| { &m.env },
+            |m: &mut Container| { &mut m.env },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "mounts",
+            |m: &Container| { &m.mounts },
+            |m: &mut Container| { &mut m.mounts },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Container>(
+            "Container",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Container {
+    const NAME: &'static str = "Container";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.id = is.read_string()?;
+                },
+                18 => {
+                    self.pod_sandbox_id = is.read_string()?;
+                },
+                26 => {
+                    self.name = is.read_string()?;
+                },
+                42 => {
+                    let len = is.read_raw_varint32()?;
+                    let old_limit = is.push_limit(len as u64)?;
+                    let mut key = ::std::default::Default::default();
+                    let mut value = ::std::default::Default::default();
+                    while let Some(tag) = is.read_raw_tag_or_eof()? {
+                        match tag {
+                            10 => key = is.read_string()?,
+                            18 => value = is.read_string()?,
+                            _ => ::protobuf::rt::skip_field_for_tag(tag, is)?,
+                        };
+                    }
+                    is.pop_limit(old_limit);
+                    self.labels.insert(key, value);
+                },
+                50 => {
+                    let len = is.read_raw_varint32()?;
+                    let old_limit = is.push_limit(len as u64)?;
+                    let mut key = ::std::default::Default::default();
+                    let mut value = ::std::default::Default::default();
+                    while let Some(tag) = is.read_raw_tag_or_eof()? {
+                        match tag {
+                            10 => key = is.read_string()?,
+                            18 => value = is.read_string()?,
+                            _ => ::protobuf::rt::skip_field_for_tag(tag, is)?,
+                        };
+                    }
+                    is.pop_limit(old_limit);
+                    self.annotations.insert(key, value);
+                },
+                58 => {
+                    self.args.push(is.read_string()?);
+                },
+                66 => {
+                    self.env.push(is.read_string()?);
+                },
+                74 => {
+                    self.mounts.push(is.read_message()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.id.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.id);
+        }
+        if !self.pod_sandbox_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.pod_sandbox_id);
+        }
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.name);
+        }
+        for (k, v) in &self.labels {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::string_size(1, &k);
+            entry_size += ::protobuf::rt::string_size(2, &v);
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(entry_size) + entry_size
+        };
+        for (k, v) in &self.annotations {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::string_size(1, &k);
+            entry_size += ::protobuf::rt::string_size(2, &v);
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(entry_size) + entry_size
+        };
+        for value in &self.args {
+            my_size += ::protobuf::rt::string_size(7, &value);
+        };
+        for value in &self.env {
+            my_size += ::protobuf::rt::string_size(8, &value);
+        };
+        for value in &self.mounts {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.id.is_empty() {
+            os.write_string(1, &self.id)?;
+        }
+        if !self.pod_sandbox_id.is_empty() {
+            os.write_string(2, &self.pod_sandbox_id)?;
+        }
+        if !self.name.is_empty() {
+            os.write_string(3, &self.name)?;
+        }
+        for (k, v) in &self.labels {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::string_size(1, &k);
+            entry_size += ::protobuf::rt::string_size(2, &v);
+            os.write_raw_varint32(42)?; // Tag.
+            os.write_raw_varint32(entry_size as u32)?;
+            os.write_string(1, &k)?;
+            os.write_string(2, &v)?;
+        };
+        for (k, v) in &self.annotations {
+            let mut entry_size = 0;
+            entry_size += ::protobuf::rt::string_size(1, &k);
+            entry_size += ::protobuf::rt::string_size(2, &v);
+            os.write_raw_varint32(50)?; // Tag.
+            os.write_raw_varint32(entry_size as u32)?;
+            os.write_string(1, &k)?;
+            os.write_string(2, &v)?;
+        };
+        for v in &self.args {
+            os.write_string(7, &v)?;
+        };
+        for v in &self.env {
+            os.write_string(8, &v)?;
+        };
+        for v in &self.mounts {
+            ::protobuf::rt::write_message_field_with_cached_size(9, v, os)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Container {
+        Container::new()
+    }
+
+    fn clear(&mut self) {
+        self.id.clear();
+        self.pod_sandbox_id.clear();
+        self.name.clear();
+        self.labels.clear();
+        self.annotations.clear();
+        self.args.clear();
+        self.env.clear();
+        self.mounts.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Container {
+        static instance: ::protobuf::rt::Lazy<Container> = ::protobuf::rt::Lazy::new();
+        instance.get(Container::new)
+    }
+}
+
+impl ::protobuf::MessageFull for Container {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Container").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Container {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Container {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.Mount)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct Mount {
+    // message fields
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Mount.destination)
+    pub destination: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Mount.type)
+    pub type_: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Mount.source)
+    pub source: ::std::string::String,
+    // @@protoc_insertion_point(field:nri.pkg.api.v1alpha1.Mount.options)
+    pub options: ::std::vec::Vec<::std::string::String>,
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.Mount.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a Mount {
+    fn default() -> &'a Mount {
+        <Mount as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Mount {
+    pub fn new() -> Mount {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(4);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "destination",
+            |m: &Mount| { &m.destination },
+            |m: &mut Mount| { &mut m.destination },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "type",
+            |m: &Mount| { &m.type_ },
+            |m: &mut Mount| { &mut m.type_ },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "source",
+            |m: &Mount| { &m.source },
+            |m: &mut Mount| { &mut m.source },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
+            "options",
+            |m: &Mount| { &m.options },
+            |m: &mut Mount| { &mut m.options },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Mount>(
+            "Mount",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for Mount {
+    const NAME: &'static str = "Mount";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                10 => {
+                    self.destination = is.read_string()?;
+                },
+                18 => {
+                    self.type_ = is.read_string()?;
+                },
+                26 => {
+                    self.source = is.read_string()?;
+                },
+                34 => {
+                    self.options.push(is.read_string()?);
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if !self.destination.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.destination);
+        }
+        if !self.type_.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.type_);
+        }
+        if !self.source.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.source);
+        }
+        for value in &self.options {
+            my_size += ::protobuf::rt::string_size(4, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if !self.destination.is_empty() {
+            os.write_string(1, &self.destination)?;
+        }
+        if !self.type_.is_empty() {
+            os.write_string(2, &self.type_)?;
+        }
+        if !self.source.is_empty() {
+            os.write_string(3, &self.source)?;
+        }
+        for v in &self.options {
+            os.write_string(4, &v)?;
+        };
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> Mount {
+        Mount::new()
+    }
+
+    fn clear(&mut self) {
+        self.destination.clear();
+        self.type_.clear();
+        self.source.clear();
+        self.options.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static Mount {
+        static instance: Mount = Mount {
+            destination: ::std::string::String::new(),
+            type_: ::std::string::String::new(),
+            source: ::std::string::String::new(),
+            options: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for Mount {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("Mount").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for Mount {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Mount {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.ContainerAdjustment)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct ContainerAdjustment {
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.ContainerAdjustment.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ContainerAdjustment {
+    fn default() -> &'a ContainerAdjustment {
+        <ContainerAdjustment as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ContainerAdjustment {
+    pub fn new() -> ContainerAdjustment {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ContainerAdjustment>(
+            "ContainerAdjustment",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ContainerAdjustment {
+    const NAME: &'static str = "ContainerAdjustment";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ContainerAdjustment {
+        ContainerAdjustment::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ContainerAdjustment {
+        static instance: ContainerAdjustment = ContainerAdjustment {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ContainerAdjustment {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ContainerAdjustment").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ContainerAdjustment {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ContainerAdjustment {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.ContainerUpdate)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct ContainerUpdate {
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.ContainerUpdate.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ContainerUpdate {
+    fn default() -> &'a ContainerUpdate {
+        <ContainerUpdate as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ContainerUpdate {
+    pub fn new() -> ContainerUpdate {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ContainerUpdate>(
+            "ContainerUpdate",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ContainerUpdate {
+    const NAME: &'static str = "ContainerUpdate";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ContainerUpdate {
+        ContainerUpdate::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ContainerUpdate {
+        static instance: ContainerUpdate = ContainerUpdate {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ContainerUpdate {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ContainerUpdate").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ContainerUpdate {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ContainerUpdate {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+// @@protoc_insertion_point(message:nri.pkg.api.v1alpha1.ContainerEviction)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct ContainerEviction {
+    // special fields
+    // @@protoc_insertion_point(special_field:nri.pkg.api.v1alpha1.ContainerEviction.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ContainerEviction {
+    fn default() -> &'a ContainerEviction {
+        <ContainerEviction as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ContainerEviction {
+    pub fn new() -> ContainerEviction {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(0);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ContainerEviction>(
+            "ContainerEviction",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ContainerEviction {
+    const NAME: &'static str = "ContainerEviction";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ContainerEviction {
+        ContainerEviction::new()
+    }
+
+    fn clear(&mut self) {
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ContainerEviction {
+        static instance: ContainerEviction = ContainerEviction {
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ContainerEviction {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ContainerEviction").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ContainerEviction {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ContainerEviction {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+// @@protoc_insertion_point(enum:nri.pkg.api.v1alpha1.Event)
+pub enum Event {
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.UNKNOWN)
+    UNKNOWN = 0,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.RUN_POD_SANDBOX)
+    RUN_POD_SANDBOX = 1,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.STOP_POD_SANDBOX)
+    STOP_POD_SANDBOX = 2,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.REMOVE_POD_SANDBOX)
+    REMOVE_POD_SANDBOX = 3,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.CREATE_CONTAINER)
+    CREATE_CONTAINER = 4,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.POST_CREATE_CONTAINER)
+    POST_CREATE_CONTAINER = 5,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.START_CONTAINER)
+    START_CONTAINER = 6,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.POST_START_CONTAINER)
+    POST_START_CONTAINER = 7,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.UPDATE_CONTAINER)
+    UPDATE_CONTAINER = 8,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.POST_UPDATE_CONTAINER)
+    POST_UPDATE_CONTAINER = 9,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.STOP_CONTAINER)
+    STOP_CONTAINER = 10,
+    // @@protoc_insertion_point(enum_value:nri.pkg.api.v1alpha1.Event.REMOVE_CONTAINER)
+    REMOVE_CONTAINER = 11,
+}
+
+impl ::protobuf::Enum for Event {
+    const NAME: &'static str = "Event";
+
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Event> {
+        match value {
+            0 => ::std::option::Option::Some(Event::UNKNOWN),
+            1 => ::std::option::Option::Some(Event::RUN_POD_SANDBOX),
+            2 => ::std::option::Option::Some(Event::STOP_POD_SANDBOX),
+            3 => ::std::option::Option::Some(Event::REMOVE_POD_SANDBOX),
+            4 => ::std::option::Option::Some(Event::CREATE_CONTAINER),
+            5 => ::std::option::Option::Some(Event::POST_CREATE_CONTAINER),
+            6 => ::std::option::Option::Some(Event::START_CONTAINER),
+            7 => ::std::option::Option::Some(Event::POST_START_CONTAINER),
+            8 => ::std::option::Option::Some(Event::UPDATE_CONTAINER),
+            9 => ::std::option::Option::Some(Event::POST_UPDATE_CONTAINER),
+            10 => ::std::option::Option::Some(Event::STOP_CONTAINER),
+            11 => ::std::option::Option::Some(Event::REMOVE_CONTAINER),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn from_str(str: &str) -> ::std::option::Option<Event> {
+        match str {
+            "UNKNOWN" => ::std::option::Option::Some(Event::UNKNOWN),
+            "RUN_POD_SANDBOX" => ::std::option::Option::Some(Event::RUN_POD_SANDBOX),
+            "STOP_POD_SANDBOX" => ::std::option::Option::Some(Event::STOP_POD_SANDBOX),
+            "REMOVE_POD_SANDBOX" => ::std::option::Option::Some(Event::REMOVE_POD_SANDBOX),
+            "CREATE_CONTAINER" => ::std::option::Option::Some(Event::CREATE_CONTAINER),
+            "POST_CREATE_CONTAINER" => ::std::option::Option::Some(Event::POST_CREATE_CONTAINER),
+            "START_CONTAINER" => ::std::option::Option::Some(Event::START_CONTAINER),
+            "POST_START_CONTAINER" => ::std::option::Option::Some(Event::POST_START_CONTAINER),
+            "UPDATE_CONTAINER" => ::std::option::Option::Some(Event::UPDATE_CONTAINER),
+            "POST_UPDATE_CONTAINER" => ::std::option::Option::Some(Event::POST_UPDATE_CONTAINER),
+            "STOP_CONTAINER" => ::std::option::Option::Some(Event::STOP_CONTAINER),
+            "REMOVE_CONTAINER" => ::std::option::Option::Some(Event::REMOVE_CONTAINER),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    const VALUES: &'static [Event] = &[
+        Event::UNKNOWN,
+        Event::RUN_POD_SANDBOX,
+        Event::STOP_POD_SANDBOX,
+        Event::REMOVE_POD_SANDBOX,
+        Event::CREATE_CONTAINER,
+        Event::POST_CREATE_CONTAINER,
+        Event::START_CONTAINER,
+        Event::POST_START_CONTAINER,
+        Event::UPDATE_CONTAINER,
+        Event::POST_UPDATE_CONTAINER,
+        Event::STOP_CONTAINER,
+        Event::REMOVE_CONTAINER,
+    ];
+}
+
+impl ::protobuf::EnumFull for Event {
+    fn enum_descriptor() -> ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().enum_by_package_relative_name("Event").unwrap()).clone()
+    }
+
+    fn descriptor(&self) -> ::protobuf::reflect::EnumValueDescriptor {
+        let index = *self as usize;
+        Self::enum_descriptor().value_by_index(index)
+    }
+}
+
+impl ::std::default::Default for Event {
+    fn default() -> Self {
+        Event::UNKNOWN
+    }
+}
+
+impl Event {
+    fn generated_enum_descriptor_data() -> ::protobuf::reflect::GeneratedEnumDescriptorData {
+        ::protobuf::reflect::GeneratedEnumDescriptorData::new::<Event>("Event")
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\tnri.proto\x12\x14nri.pkg.api.v1alpha1\"\x07\n\x05Empty\"W\n\x15Regis\
+    terPluginRequest\x12\x1f\n\x0bplugin_name\x18\x01\x20\x01(\tR\npluginNam\
+    e\x12\x1d\n\nplugin_idx\x18\x02\x20\x01(\tR\tpluginIdx\"\xd2\x01\n\x10Co\
+    nfigureRequest\x12\x16\n\x06config\x18\x01\x20\x01(\tR\x06config\x12!\n\
+    \x0cruntime_name\x18\x02\x20\x01(\tR\x0bruntimeName\x12'\n\x0fruntime_ve\
+    rsion\x18\x03\x20\x01(\tR\x0eruntimeVersion\x121\n\x14registration_timeo\
+    ut\x18\x04\x20\x01(\x03R\x13registrationTimeout\x12'\n\x0frequest_timeou\
+    t\x18\x05\x20\x01(\x03R\x0erequestTimeout\"+\n\x11ConfigureResponse\x12\
+    \x16\n\x06events\x18\x02\x20\x01(\x05R\x06events\"\x9f\x01\n\x12Synchron\
+    izeRequest\x124\n\x04pods\x18\x01\x20\x03(\x0b2\x20.nri.pkg.api.v1alpha1\
+    .PodSandboxR\x04pods\x12?\n\ncontainers\x18\x02\x20\x03(\x0b2\x1f.nri.pk\
+    g.api.v1alpha1.ContainerR\ncontainers\x12\x12\n\x04more\x18\x03\x20\x01(\
+    \x08R\x04more\"h\n\x13SynchronizeResponse\x12=\n\x06update\x18\x01\x20\
+    \x03(\x0b2%.nri.pkg.api.v1alpha1.ContainerUpdateR\x06update\x12\x12\n\
+    \x04more\x18\x02\x20\x01(\x08R\x04more\"\x8b\x01\n\x16CreateContainerReq\
+    uest\x122\n\x03pod\x18\x01\x20\x01(\x0b2\x20.nri.pkg.api.v1alpha1.PodSan\
+    dboxR\x03pod\x12=\n\tcontainer\x18\x02\x20\x01(\x0b2\x1f.nri.pkg.api.v1a\
+    lpha1.ContainerR\tcontainer\"\xda\x01\n\x17CreateContainerResponse\x12A\
+    \n\x06adjust\x18\x01\x20\x01(\x0b2).nri.pkg.api.v1alpha1.ContainerAdjust\
+    mentR\x06adjust\x12=\n\x06update\x18\x02\x20\x03(\x0b2%.nri.pkg.api.v1al\
+    pha1.ContainerUpdateR\x06update\x12=\n\x05evict\x18\x03\x20\x03(\x0b2'.n\
+    ri.pkg.api.v1alpha1.ContainerEvictionR\x05evict\"\xb8\x01\n\x10StateChan\
+    geEvent\x121\n\x05event\x18\x01\x20\x01(\x0e2\x1b.nri.pkg.api.v1alpha1.E\
+    ventR\x05event\x122\n\x03pod\x18\x02\x20\x01(\x0b2\x20.nri.pkg.api.v1alp\
+    ha1.PodSandboxR\x03pod\x12=\n\tcontainer\x18\x03\x20\x01(\x0b2\x1f.nri.p\
+    kg.api.v1alpha1.ContainerR\tcontainer\"\x9f\x03\n\nPodSandbox\x12\x0e\n\
+    \x02id\x18\x01\x20\x01(\tR\x02id\x12\x12\n\x04name\x18\x02\x20\x01(\tR\
+    \x04name\x12\x10\n\x03uid\x18\x03\x20\x01(\tR\x03uid\x12\x1c\n\tnamespac\
+    e\x18\x04\x20\x01(\tR\tnamespace\x12D\n\x06labels\x18\x05\x20\x03(\x0b2,\
+    .nri.pkg.api.v1alpha1.PodSandbox.LabelsEntryR\x06labels\x12S\n\x0bannota\
+    tions\x18\x06\x20\x03(\x0b21.nri.pkg.api.v1alpha1.PodSandbox.Annotations\
+    EntryR\x0bannotations\x12'\n\x0fruntime_handler\x18\x07\x20\x01(\tR\x0er\
+    untimeHandler\x1a9\n\x0bLabelsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\
+    \x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\x1a>\n\
+    \x10AnnotationsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\
+    \n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"\xc4\x03\n\tContaine\
+    r\x12\x0e\n\x02id\x18\x01\x20\x01(\tR\x02id\x12$\n\x0epod_sandbox_id\x18\
+    \x02\x20\x01(\tR\x0cpodSandboxId\x12\x12\n\x04name\x18\x03\x20\x01(\tR\
+    \x04name\x12C\n\x06labels\x18\x05\x20\x03(\x0b2+.nri.pkg.api.v1alpha1.Co\
+    ntainer.LabelsEntryR\x06labels\x12R\n\x0bannotations\x18\x06\x20\x03(\
+    \x0b20.nri.pkg.api.v1alpha1.Container.AnnotationsEntryR\x0bannotations\
+    \x12\x12\n\x04args\x18\x07\x20\x03(\tR\x04args\x12\x10\n\x03env\x18\x08\
+    \x20\x03(\tR\x03env\x123\n\x06mounts\x18\t\x20\x03(\x0b2\x1b.nri.pkg.api\
+    .v1alpha1.MountR\x06mounts\x1a9\n\x0bLabelsEntry\x12\x10\n\x03key\x18\
+    \x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\
+    \x028\x01\x1a>\n\x10AnnotationsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\t\
+    R\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"o\n\
+    \x05Mount\x12\x20\n\x0bdestination\x18\x01\x20\x01(\tR\x0bdestination\
+    \x12\x12\n\x04type\x18\x02\x20\x01(\tR\x04type\x12\x16\n\x06source\x18\
+    \x03\x20\x01(\tR\x06source\x12\x18\n\x07options\x18\x04\x20\x03(\tR\x07o\
+    ptions\"\x15\n\x13ContainerAdjustment\"\x11\n\x0fContainerUpdate\"\x13\n\
+    \x11ContainerEviction*\x92\x02\n\x05Event\x12\x0b\n\x07UNKNOWN\x10\0\x12\
+    \x13\n\x0fRUN_POD_SANDBOX\x10\x01\x12\x14\n\x10STOP_POD_SANDBOX\x10\x02\
+    \x12\x16\n\x12REMOVE_POD_SANDBOX\x10\x03\x12\x14\n\x10CREATE_CONTAINER\
+    \x10\x04\x12\x19\n\x15POST_CREATE_CONTAINER\x10\x05\x12\x13\n\x0fSTART_C\
+    ONTAINER\x10\x06\x12\x18\n\x14POST_START_CONTAINER\x10\x07\x12\x14\n\x10\
+    UPDATE_CONTAINER\x10\x08\x12\x19\n\x15POST_UPDATE_CONTAINER\x10\t\x12\
+    \x12\n\x0eSTOP_CONTAINER\x10\n\x12\x14\n\x10REMOVE_CONTAINER\x10\x0b2e\n\
+    \x07Runtime\x12Z\n\x0eRegisterPlugin\x12+.nri.pkg.api.v1alpha1.RegisterP\
+    luginRequest\x1a\x1b.nri.pkg.api.v1alpha1.Empty2\xd4\x03\n\x06Plugin\x12\
+    \\\n\tConfigure\x12&.nri.pkg.api.v1alpha1.ConfigureRequest\x1a'.nri.pkg.\
+    api.v1alpha1.ConfigureResponse\x12b\n\x0bSynchronize\x12(.nri.pkg.api.v1\
+    alpha1.SynchronizeRequest\x1a).nri.pkg.api.v1alpha1.SynchronizeResponse\
+    \x12D\n\x08Shutdown\x12\x1b.nri.pkg.api.v1alpha1.Empty\x1a\x1b.nri.pkg.a\
+    pi.v1alpha1.Empty\x12n\n\x0fCreateContainer\x12,.nri.pkg.api.v1alpha1.Cr\
+    eateContainerRequest\x1a-.nri.pkg.api.v1alpha1.CreateContainerResponse\
+    \x12R\n\x0bStateChange\x12&.nri.pkg.api.v1alpha1.StateChangeEvent\x1a\
+    \x1b.nri.pkg.api.v1alpha1.Emptyb\x06proto3\
+";
+
+/// `FileDescriptorProto` object which was a source for this generated file
+fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    static file_descriptor_proto_lazy: ::protobuf::rt::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::Lazy::new();
+    file_descriptor_proto_lazy.get(|| {
+        ::protobuf::Message::parse_from_bytes(file_descriptor_proto_data).unwrap()
+    })
+}
+
+/// `FileDescriptor` object which allows dynamic access to files
+pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
+    static generated_file_descriptor_lazy: ::protobuf::rt::Lazy<::protobuf::reflect::GeneratedFileDescriptor> = ::protobuf::rt::Lazy::new();
+    static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
+    file_descriptor.get(|| {
+        let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
+            let mut deps = ::std::vec::Vec::with_capacity(0);
+            let mut messages = ::std::vec::Vec::with_capacity(15);
+            messages.push(Empty::generated_message_descriptor_data());
+            messages.push(RegisterPluginRequest::generated_message_descriptor_data());
+            messages.push(ConfigureRequest::generated_message_descriptor_data());
+            messages.push(ConfigureResponse::generated_message_descriptor_data());
+            messages.push(SynchronizeRequest::generated_message_descriptor_data());
+            messages.push(SynchronizeResponse::generated_message_descriptor_data());
+            messages.push(CreateContainerRequest::generated_message_descriptor_data());
+            messages.push(CreateContainerResponse::generated_message_descriptor_data());
+            messages.push(StateChangeEvent::generated_message_descriptor_data());
+            messages.push(PodSandbox::generated_message_descriptor_data());
+            messages.push(Container::generated_message_descriptor_data());
+            messages.push(Mount::generated_message_descriptor_data());
+            messages.push(ContainerAdjustment::generated_message_descriptor_data());
+            messages.push(ContainerUpdate::generated_message_descriptor_data());
+            messages.push(ContainerEviction::generated_message_descriptor_data());
+            let mut enums = ::std::vec::Vec::with_capacity(1);
+            enums.push(Event::generated_enum_descriptor_data());
+            ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
+                file_descriptor_proto(),
+                deps,
+                messages,
+                enums,
+            )
+        });
+        ::protobuf::reflect::FileDescriptor::new_generated_2(generated_file_descriptor)
+    })
+}
@@ -0,0 +1,204 @@
+// This file is generated by ttrpc-compiler 0.7.0. Do not edit
+// @generated
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unknown_lints)]
+#![allow(clipto_camel_casepy)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unsafe_code)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+#![allow(clippy::all)]
+use protobuf::{CodedInputStream, CodedOutputStream, Message};
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct RuntimeClient {
+    client: ::ttrpc::r#async::Client,
+}
+
+impl RuntimeClient {
+    pub fn new(client: ::ttrpc::r#async::Client) -> Self {
+        RuntimeClient {
+            client,
+        }
+    }
+
+    pub async fn register_plugin(&self, ctx: ttrpc::context::Context, req: &super::nri::RegisterPluginRequest) -> ::ttrpc::Result<super::nri::Empty> {
+        let mut cres = super::nri::Empty::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "nri.pkg.api.v1alpha1.Runtime", "RegisterPlugin", cres);
+    }
+}
+
+struct RegisterPluginMethod {
+    service: Arc<dyn Runtime + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for RegisterPluginMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, nri, RegisterPluginRequest, register_plugin);
+    }
+}
+
+#[async_trait]
+pub trait Runtime: Sync {
+    async fn register_plugin(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::nri::RegisterPluginRequest) -> ::ttrpc::Result<super::nri::Empty> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/nri.pkg.api.v1alpha1.Runtime/RegisterPlugin is not supported".to_string())))
+    }
+}
+
+pub fn create_runtime(service: Arc<dyn Runtime + Send + Sync>) -> HashMap<String, ::ttrpc::r#async::Service> {
+    let mut ret = HashMap::new();
+    let mut methods = HashMap::new();
+    let streams = HashMap::new();
+
+    methods.insert("RegisterPlugin".to_string(),
+                    Box::new(RegisterPluginMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    ret.insert("nri.pkg.api.v1alpha1.Runtime".to_string(), ::ttrpc::r#async::Service{ methods, streams });
+    ret
+}
+
+#[derive(Clone)]
+pub struct PluginClient {
+    client: ::ttrpc::r#async::Client,
+}
+
+impl PluginClient {
+    pub fn new(client: ::ttrpc::r#async::Client) -> Self {
+        PluginClient {
+            client,
+        }
+    }
+
+    pub async fn configure(&self, ctx: ttrpc::context::Context, req: &super::nri::ConfigureRequest) -> ::ttrpc::Result<super::nri::ConfigureResponse> {
+        let mut cres = super::nri::ConfigureResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "nri.pkg.api.v1alpha1.Plugin", "Configure", cres);
+    }
+
+    pub async fn synchronize(&self, ctx: ttrpc::context::Context, req: &super::nri::SynchronizeRequest) -> ::ttrpc::Result<super::nri::SynchronizeResponse> {
+        let mut cres = super::nri::SynchronizeResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "nri.pkg.api.v1alpha1.Plugin", "Synchronize", cres);
+    }
+
+    pub async fn shutdown(&self, ctx: ttrpc::context::Context, req: &super::nri::Empty) -> ::ttrpc::Result<super::nri::Empty> {
+        let mut cres = super::nri::Empty::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "nri.pkg.api.v1alpha1.Plugin", "Shutdown", cres);
+    }
+
+    pub async fn create_container(&self, ctx: ttrpc::context::Context, req: &super::nri::CreateContainerRequest) -> ::ttrpc::Result<super::nri::CreateContainerResponse> {
+        let mut cres = super::nri::CreateContainerResponse::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "nri.pkg.api.v1alpha1.Plugin", "CreateContainer", cres);
+    }
+
+    pub async fn state_change(&self, ctx: ttrpc::context::Context, req: &super::nri::StateChangeEvent) -> ::ttrpc::Result<super::nri::Empty> {
+        let mut cres = super::nri::Empty::new();
+        ::ttrpc::async_client_request!(self, ctx, req, "nri.pkg.api.v1alpha1.Plugin", "StateChange", cres);
+    }
+}
+
+struct ConfigureMethod {
+    service: Arc<dyn Plugin + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for ConfigureMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, nri, ConfigureRequest, configure);
+    }
+}
+
+struct SynchronizeMethod {
+    service: Arc<dyn Plugin + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for SynchronizeMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, nri, SynchronizeRequest, synchronize);
+    }
+}
+
+struct ShutdownMethod {
+    service: Arc<dyn Plugin + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for ShutdownMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, nri, Empty, shutdown);
+    }
+}
+
+struct CreateContainerMethod {
+    service: Arc<dyn Plugin + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for CreateContainerMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, nri, CreateContainerRequest, create_container);
+    }
+}
+
+struct StateChangeMethod {
+    service: Arc<dyn Plugin + Send + Sync>,
+}
+
+#[async_trait]
+impl ::ttrpc::r#async::MethodHandler for StateChangeMethod {
+    async fn handler(&self, ctx: ::ttrpc::r#async::TtrpcContext, req: ::ttrpc::Request) -> ::ttrpc::Result<::ttrpc::Response> {
+        ::ttrpc::async_request_handler!(self, ctx, req, nri, StateChangeEvent, state_change);
+    }
+}
+
+#[async_trait]
+pub trait Plugin: Sync {
+    async fn configure(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::nri::ConfigureRequest) -> ::ttrpc::Result<super::nri::ConfigureResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/nri.pkg.api.v1alpha1.Plugin/Configure is not supported".to_string())))
+    }
+    async fn synchronize(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::nri::SynchronizeRequest) -> ::ttrpc::Result<super::nri::SynchronizeResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/nri.pkg.api.v1alpha1.Plugin/Synchronize is not supported".to_string())))
+    }
+    async fn shutdown(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::nri::Empty) -> ::ttrpc::Result<super::nri::Empty> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/nri.pkg.api.v1alpha1.Plugin/Shutdown is not supported".to_string())))
+    }
+    async fn create_container(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::nri::CreateContainerRequest) -> ::ttrpc::Result<super::nri::CreateContainerResponse> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/nri.pkg.api.v1alpha1.Plugin/CreateContainer is not supported".to_string())))
+    }
+    async fn state_change(&self, _ctx: &::ttrpc::r#async::TtrpcContext, _: super::nri::StateChangeEvent) -> ::ttrpc::Result<super::nri::Empty> {
+        Err(::ttrpc::Error::RpcStatus(::ttrpc::get_status(::ttrpc::Code::NOT_FOUND, "/nri.pkg.api.v1alpha1.Plugin/StateChange is not supported".to_string())))
+    }
+}
+
+pub fn create_plugin(service: Arc<dyn Plugin + Send + Sync>) -> HashMap<String, ::ttrpc::r#async::Service> {
+    let mut ret = HashMap::new();
+    let mut methods = HashMap::new();
+    let streams = HashMap::new();
+
+    methods.insert("Configure".to_string(),
+                    Box::new(ConfigureMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("Synchronize".to_string(),
+                    Box::new(SynchronizeMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("Shutdown".to_string(),
+                    Box::new(ShutdownMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("CreateContainer".to_string(),
+                    Box::new(CreateContainerMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    methods.insert("StateChange".to_string(),
+                    Box::new(StateChangeMethod{service: service.clone()}) as Box<dyn ::ttrpc::r#async::MethodHandler + Send + Sync>);
+
+    ret.insert("nri.pkg.api.v1alpha1.Plugin".to_string(), ::ttrpc::r#async::Service{ methods, streams });
+    ret
+}
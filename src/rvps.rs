@@ -0,0 +1,206 @@
+// src/rvps.rs
+use crate::config::RvpsConfig;
+use crate::report::MeasurementRecord;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Environment variable holding the KBS admin token when no token file is
+/// configured.
+const ADMIN_TOKEN_ENV: &str = "KBS_ADMIN_TOKEN";
+
+/// Message accepted by the RVPS `sample` extractor.
+#[derive(Serialize)]
+struct RvpsMessage {
+    version: &'static str,
+    #[serde(rename = "type")]
+    message_type: &'static str,
+    /// Base64 of a JSON map from reference value name to accepted values.
+    payload: String,
+}
+
+/// The part of a `sample` message read back from a manifest.
+#[derive(Deserialize)]
+struct SampleMessage {
+    payload: String,
+}
+
+/// Layouts of an exported reference value manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// The RVPS `sample` message, as registered by `provision-rvps`.
+    Rvps,
+    /// A JSON object from reference value name to accepted values.
+    Json,
+    /// A TOML table from reference value name to accepted values.
+    Toml,
+}
+
+impl ManifestFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rvps" => Some(ManifestFormat::Rvps),
+            "json" => Some(ManifestFormat::Json),
+            "toml" => Some(ManifestFormat::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Maps measurement records to reference values named
+/// `<prefix>.<domain>.<operation>`. Every content seen for a name is
+/// accepted, so repeated extensions of the same entry stay valid.
+pub fn reference_values(
+    records: &[MeasurementRecord],
+    prefix: &str,
+) -> BTreeMap<String, Vec<String>> {
+    let mut values: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for record in records {
+        let name = format!("{}.{}.{}", prefix, record.domain, record.operation);
+        let accepted = values.entry(name).or_default();
+        if !accepted.contains(&record.content) {
+            accepted.push(record.content.clone());
+        }
+    }
+    values
+}
+
+/// Wraps the reference values in an RVPS `sample` message.
+fn sample_message(values: &BTreeMap<String, Vec<String>>) -> Result<String> {
+    let payload = serde_json::to_vec(values)?;
+    Ok(serde_json::to_string(&RvpsMessage {
+        version: "0.1.0",
+        message_type: "sample",
+        payload: base64::engine::general_purpose::STANDARD.encode(payload),
+    })?)
+}
+
+/// Renders the reference values as a manifest in `format`.
+pub fn manifest(values: &BTreeMap<String, Vec<String>>, format: ManifestFormat) -> Result<String> {
+    match format {
+        ManifestFormat::Rvps => sample_message(values),
+        ManifestFormat::Json => Ok(serde_json::to_string_pretty(values)?),
+        ManifestFormat::Toml => Ok(toml::to_string(values)?),
+    }
+}
+
+/// Reads a manifest in any of the exported formats; handwritten manifests
+/// use the JSON or TOML layout.
+pub fn load_manifest(path: &Path) -> Result<BTreeMap<String, Vec<String>>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read reference manifest {:?}", path))?;
+    if let Ok(message) = serde_json::from_str::<SampleMessage>(&content) {
+        let payload = base64::engine::general_purpose::STANDARD
+            .decode(message.payload.trim())
+            .with_context(|| format!("Invalid payload in {:?}", path))?;
+        return serde_json::from_slice(&payload)
+            .with_context(|| format!("Invalid payload in {:?}", path));
+    }
+    if let Ok(values) = serde_json::from_str(&content) {
+        return Ok(values);
+    }
+    toml::from_str(&content)
+        .with_context(|| format!("{:?} is not a reference value manifest", path))
+}
+
+/// An entry whose measured content is not among its accepted values.
+#[derive(Debug, Serialize)]
+pub struct Mismatch {
+    pub name: String,
+    pub expected: Vec<String>,
+    pub actual: Vec<String>,
+}
+
+/// How the current measurement set differs from a reference manifest.
+#[derive(Debug, Default, Serialize)]
+pub struct Verification {
+    pub mismatched: Vec<Mismatch>,
+    /// Reference values nothing was measured for.
+    pub missing: Vec<String>,
+    /// Measured entries the manifest does not name; reported, but they do
+    /// not fail verification, so a manifest may cover a subset.
+    pub unexpected: Vec<String>,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Compares the current reference values against the manifest. An entry
+/// matches when every content measured for it is accepted.
+pub fn verify(
+    reference: &BTreeMap<String, Vec<String>>,
+    current: &BTreeMap<String, Vec<String>>,
+) -> Verification {
+    let mut verification = Verification::default();
+    for (name, expected) in reference {
+        match current.get(name) {
+            Some(actual) if actual.iter().all(|content| expected.contains(content)) => {}
+            Some(actual) => verification.mismatched.push(Mismatch {
+                name: name.clone(),
+                expected: expected.clone(),
+                actual: actual.clone(),
+            }),
+            None => verification.missing.push(name.clone()),
+        }
+    }
+    verification.unexpected = current
+        .keys()
+        .filter(|name| !reference.contains_key(*name))
+        .cloned()
+        .collect();
+    verification
+}
+
+/// Registers the reference values with the RVPS behind a Trustee KBS through
+/// its admin-authenticated `reference-value` endpoint.
+pub async fn register(config: &RvpsConfig, values: &BTreeMap<String, Vec<String>>) -> Result<()> {
+    if config.kbs_url.is_empty() {
+        bail!("rvps.kbs_url is not set");
+    }
+    let token = match config.admin_token_path {
+        Some(ref path) => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read admin token {}", path))?,
+        None => env::var(ADMIN_TOKEN_ENV).map_err(|_| {
+            anyhow!(
+                "Set rvps.admin_token_path or {} to authenticate to the KBS",
+                ADMIN_TOKEN_ENV
+            )
+        })?,
+    };
+
+    let message = sample_message(values)?;
+
+    let mut builder = reqwest::Client::builder().user_agent("measurement-tool/0.1.0");
+    if let Some(ref ca_path) = config.ca_cert_path {
+        let pem = fs::read(ca_path).with_context(|| format!("Failed to read {}", ca_path))?;
+        builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+    }
+    let url = format!(
+        "{}/kbs/v0/reference-value",
+        config.kbs_url.trim_end_matches('/')
+    );
+    let resp = builder
+        .build()?
+        .post(&url)
+        .bearer_auth(token.trim())
+        .header("content-type", "application/json")
+        .body(message)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {}", url))?;
+    let status = resp.status();
+    if !status.is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        bail!("{} returned {}: {}", url, status, body.trim());
+    }
+    info!("Registered {} reference values at {}", values.len(), url);
+    Ok(())
+}
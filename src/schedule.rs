@@ -0,0 +1,118 @@
+// src/schedule.rs
+use crate::config::MaintenanceConfig;
+use crate::error::{MeasurementError, Result};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+const DAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// A recurring UTC time window, written `HH:MM-HH:MM` optionally preceded
+/// by a day or day range (`Mon-Fri 08:00-20:00`). A window whose end is
+/// before its start runs past midnight; its days name the day it starts.
+#[derive(Debug, Clone)]
+struct BlackoutWindow {
+    /// Indexed from Monday.
+    days: [bool; 7],
+    start: u32,
+    end: u32,
+}
+
+impl BlackoutWindow {
+    fn parse(spec: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            MeasurementError::Config(format!("Invalid blackout window '{}': {}", spec, reason))
+        };
+        let (days_spec, times) = match spec.trim().rsplit_once(' ') {
+            Some((days, times)) => (Some(days.trim()), times),
+            None => (None, spec.trim()),
+        };
+
+        let days = match days_spec {
+            None => [true; 7],
+            Some(days_spec) => {
+                let (first, last) = days_spec.split_once('-').unwrap_or((days_spec, days_spec));
+                let (first, last) = (
+                    day_index(first).ok_or_else(|| invalid("unknown day"))?,
+                    day_index(last).ok_or_else(|| invalid("unknown day"))?,
+                );
+                let mut days = [false; 7];
+                let mut day = first;
+                loop {
+                    days[day] = true;
+                    if day == last {
+                        break;
+                    }
+                    day = (day + 1) % 7;
+                }
+                days
+            }
+        };
+
+        let (start, end) = times
+            .split_once('-')
+            .ok_or_else(|| invalid("expected HH:MM-HH:MM"))?;
+        let start = minute_of_day(start).ok_or_else(|| invalid("bad start time"))?;
+        let end = minute_of_day(end).ok_or_else(|| invalid("bad end time"))?;
+        if start == end {
+            return Err(invalid("window is empty"));
+        }
+        Ok(Self { days, start, end })
+    }
+
+    fn contains(&self, day: usize, minute: u32) -> bool {
+        if self.start < self.end {
+            self.days[day] && (self.start..self.end).contains(&minute)
+        } else {
+            (self.days[day] && minute >= self.start)
+                || (self.days[(day + 6) % 7] && minute < self.end)
+        }
+    }
+}
+
+fn day_index(name: &str) -> Option<usize> {
+    let name = name.get(..3)?.to_lowercase();
+    DAY_NAMES.iter().position(|day| *day == name)
+}
+
+fn minute_of_day(time: &str) -> Option<u32> {
+    let (hours, minutes) = time.trim().split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+/// Times during which heavy re-measurement is held back.
+#[derive(Debug, Clone, Default)]
+pub struct BlackoutSchedule {
+    windows: Vec<BlackoutWindow>,
+}
+
+impl BlackoutSchedule {
+    pub fn from_config(config: &MaintenanceConfig) -> Result<Self> {
+        let windows = config
+            .blackout_windows
+            .iter()
+            .map(|spec| BlackoutWindow::parse(spec))
+            .collect::<Result<_>>()?;
+        Ok(Self { windows })
+    }
+
+    /// Returns how long the blackout in effect at `now` lasts, or `None`
+    /// if `now` is outside every window.
+    pub fn remaining(&self, now: SystemTime) -> Option<Duration> {
+        let epoch_minutes = now.duration_since(UNIX_EPOCH).ok()?.as_secs() / 60;
+        // Adjacent or overlapping windows can extend a blackout; a week of
+        // minutes covers every combination.
+        let minutes = (0..7 * MINUTES_PER_DAY as u64)
+            .take_while(|offset| self.is_blackout_minute(epoch_minutes + offset))
+            .count() as u64;
+        (minutes > 0).then(|| Duration::from_secs(minutes * 60))
+    }
+
+    fn is_blackout_minute(&self, epoch_minutes: u64) -> bool {
+        let days_since_epoch = epoch_minutes / MINUTES_PER_DAY as u64;
+        // 1970-01-01 was a Thursday.
+        let day = ((days_since_epoch + 3) % 7) as usize;
+        let minute = (epoch_minutes % MINUTES_PER_DAY as u64) as u32;
+        self.windows.iter().any(|w| w.contains(day, minute))
+    }
+}
@@ -0,0 +1,503 @@
+// src/sink.rs
+use crate::alias::PathAliases;
+use crate::cel::CelSink;
+use crate::config::{Config, RateLimitConfig, SinkConfig};
+use crate::descriptor::ContentLimiter;
+use crate::error::{MeasurementError, Result};
+use crate::kubernetes::NodeIdentity;
+use crate::report::MeasurementRecord;
+use crate::rpc_client::AAClient;
+use async_trait::async_trait;
+use log::{debug, error, warn};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+/// One measurement of a batch passed to `MeasurementSink::extend_batch`.
+#[derive(Debug, Clone)]
+pub struct Extension {
+    pub domain: String,
+    pub operation: String,
+    pub content: String,
+    pub pcr_index: Option<u64>,
+}
+
+impl From<&MeasurementRecord> for Extension {
+    fn from(record: &MeasurementRecord) -> Self {
+        Self {
+            domain: record.domain.clone(),
+            operation: record.operation.clone(),
+            content: record.content.clone(),
+            pcr_index: record.register_index,
+        }
+    }
+}
+
+/// Where measurers send their measurements.
+#[async_trait]
+pub trait MeasurementSink: Send + Sync {
+    /// Returns the name used in logs (e.g., "attestation_agent").
+    fn name(&self) -> &str;
+
+    /// Records one measurement; `pcr_index` of `None` uses the sink's default
+    /// register.
+    async fn extend(
+        &self,
+        domain: &str,
+        operation: &str,
+        content: &str,
+        pcr_index: Option<u64>,
+    ) -> Result<()>;
+
+    /// Records several measurements and returns one result each, in the
+    /// order given. Measurements of one domain are recorded in order; those
+    /// of different domains may be recorded concurrently.
+    async fn extend_batch(&self, extensions: &[Extension]) -> Vec<Result<()>> {
+        let mut results = Vec::with_capacity(extensions.len());
+        for extension in extensions {
+            results.push(
+                self.extend(
+                    &extension.domain,
+                    &extension.operation,
+                    &extension.content,
+                    extension.pcr_index,
+                )
+                .await,
+            );
+        }
+        results
+    }
+
+    /// Records a measurement staged by [`StagingSink`]. Sinks that keep
+    /// more than the event, such as the operation before `path_aliases`
+    /// rewrote it, override this.
+    async fn extend_record(&self, record: &MeasurementRecord) -> Result<()> {
+        self.extend(
+            &record.domain,
+            &record.operation,
+            &record.content,
+            record.register_index,
+        )
+        .await
+    }
+
+    /// Records several staged measurements and returns one result each, in
+    /// the order given.
+    async fn extend_records(&self, records: &[MeasurementRecord]) -> Vec<Result<()>> {
+        let extensions: Vec<Extension> = records.iter().map(Extension::from).collect();
+        self.extend_batch(&extensions).await
+    }
+
+    /// Whether measurements are only computed, not recorded anywhere.
+    fn is_dry_run(&self) -> bool {
+        false
+    }
+
+    /// Marks the start of a measurement run, for sinks that limit how much
+    /// one run may extend.
+    fn begin_run(&self) {}
+
+    /// Marks the end of the run started by `begin_run`.
+    fn end_run(&self) {}
+
+    /// Fails once the current run has gone over a limit of the sink, so
+    /// that the rest of the run can be skipped.
+    fn check_run(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MeasurementSink for AAClient {
+    fn name(&self) -> &str {
+        "attestation_agent"
+    }
+
+    async fn extend(
+        &self,
+        domain: &str,
+        operation: &str,
+        content: &str,
+        pcr_index: Option<u64>,
+    ) -> Result<()> {
+        self.extend_runtime_measurement(pcr_index, domain, operation, content)
+            .await
+    }
+
+    async fn extend_batch(&self, extensions: &[Extension]) -> Vec<Result<()>> {
+        self.extend_runtime_measurements(extensions).await
+    }
+
+    async fn extend_record(&self, record: &MeasurementRecord) -> Result<()> {
+        self.extend_staged(record.clone()).await
+    }
+
+    async fn extend_records(&self, records: &[MeasurementRecord]) -> Vec<Result<()>> {
+        self.extend_staged_batch(records.to_vec()).await
+    }
+
+    fn is_dry_run(&self) -> bool {
+        AAClient::is_dry_run(self)
+    }
+}
+
+/// Sends every measurement to each of its sinks in order.
+pub struct FanOutSink {
+    sinks: Vec<Arc<dyn MeasurementSink>>,
+}
+
+#[async_trait]
+impl MeasurementSink for FanOutSink {
+    fn name(&self) -> &str {
+        "fan_out"
+    }
+
+    async fn extend(
+        &self,
+        domain: &str,
+        operation: &str,
+        content: &str,
+        pcr_index: Option<u64>,
+    ) -> Result<()> {
+        self.extend_record(&MeasurementRecord::new(
+            domain, operation, content, pcr_index,
+        ))
+        .await
+    }
+
+    async fn extend_batch(&self, extensions: &[Extension]) -> Vec<Result<()>> {
+        let records: Vec<MeasurementRecord> = extensions
+            .iter()
+            .map(|e| MeasurementRecord::new(&e.domain, &e.operation, &e.content, e.pcr_index))
+            .collect();
+        self.extend_records(&records).await
+    }
+
+    /// Every sink is tried even if an earlier one failed; the first failure
+    /// is returned.
+    async fn extend_record(&self, record: &MeasurementRecord) -> Result<()> {
+        let mut first_error = None;
+        for sink in &self.sinks {
+            debug!("Extending {} via sink {}", record.operation, sink.name());
+            if let Err(e) = sink.extend_record(record).await {
+                warn!(
+                    "Sink {} failed to record {}: {}",
+                    sink.name(),
+                    record.operation,
+                    e
+                );
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    async fn extend_records(&self, records: &[MeasurementRecord]) -> Vec<Result<()>> {
+        let mut results: Vec<Result<()>> = records.iter().map(|_| Ok(())).collect();
+        for sink in &self.sinks {
+            debug!(
+                "Extending {} measurements via sink {}",
+                records.len(),
+                sink.name()
+            );
+            let sink_results = sink.extend_records(records).await;
+            for ((result, sink_result), record) in results.iter_mut().zip(sink_results).zip(records)
+            {
+                if let Err(e) = sink_result {
+                    warn!(
+                        "Sink {} failed to record {}: {}",
+                        sink.name(),
+                        record.operation,
+                        e
+                    );
+                    if result.is_ok() {
+                        *result = Err(e);
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.sinks.iter().all(|sink| sink.is_dry_run())
+    }
+}
+
+/// Turns what measurers extend into the event every sink records: applies
+/// `path_aliases`, the `tenant` prefix, the node identity and the content
+/// limits once, then hands the staged measurement to `inner`.
+pub struct StagingSink {
+    inner: Arc<dyn MeasurementSink>,
+    path_aliases: Option<PathAliases>,
+    /// Prefixed onto every domain as `<tenant>/<domain>`.
+    tenant: Option<String>,
+    identity: Option<NodeIdentity>,
+    content_limiter: ContentLimiter,
+}
+
+impl StagingSink {
+    pub fn new(config: &Config, inner: Arc<dyn MeasurementSink>) -> Result<Self> {
+        Ok(Self {
+            inner,
+            path_aliases: PathAliases::from_config(&config.path_aliases),
+            tenant: config.tenant.clone(),
+            identity: NodeIdentity::from_config(&config.kubernetes)?,
+            content_limiter: ContentLimiter::from_config(&config.content_limits),
+        })
+    }
+
+    fn stage(
+        &self,
+        domain: &str,
+        operation: &str,
+        content: &str,
+        pcr_index: Option<u64>,
+    ) -> Result<MeasurementRecord> {
+        let aliased = self
+            .path_aliases
+            .as_ref()
+            .and_then(|aliases| aliases.apply(operation));
+        let unaliased_operation = aliased.as_ref().map(|_| operation.to_string());
+        let operation = aliased.as_deref().unwrap_or(operation);
+        let domain = match &self.tenant {
+            Some(tenant) => format!("{}/{}", tenant, domain),
+            None => domain.to_string(),
+        };
+        let (domain, operation) = match &self.identity {
+            Some(identity) => identity.apply(&domain, operation),
+            None => (domain, operation.to_string()),
+        };
+        let content = self.content_limiter.limit(&domain, &operation, content)?;
+        Ok(MeasurementRecord {
+            domain,
+            operation,
+            content,
+            register_index: pcr_index,
+            unaliased_operation,
+            backend: None,
+        })
+    }
+}
+
+#[async_trait]
+impl MeasurementSink for StagingSink {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn extend(
+        &self,
+        domain: &str,
+        operation: &str,
+        content: &str,
+        pcr_index: Option<u64>,
+    ) -> Result<()> {
+        let record = self.stage(domain, operation, content, pcr_index)?;
+        self.inner.extend_record(&record).await
+    }
+
+    async fn extend_batch(&self, extensions: &[Extension]) -> Vec<Result<()>> {
+        let mut results: Vec<Result<()>> = extensions.iter().map(|_| Ok(())).collect();
+        let mut staged = Vec::new();
+        let mut positions = Vec::new();
+        for (position, e) in extensions.iter().enumerate() {
+            match self.stage(&e.domain, &e.operation, &e.content, e.pcr_index) {
+                Ok(record) => {
+                    staged.push(record);
+                    positions.push(position);
+                }
+                Err(e) => results[position] = Err(e),
+            }
+        }
+        if !staged.is_empty() {
+            let staged_results = self.inner.extend_records(&staged).await;
+            for (position, result) in positions.into_iter().zip(staged_results) {
+                results[position] = result;
+            }
+        }
+        results
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.inner.is_dry_run()
+    }
+}
+
+/// Bursts of up to this long at the full rate are let through unthrottled.
+const RATE_LIMIT_BURST: Duration = Duration::from_secs(1);
+
+/// Limits how fast and how much is extended through `inner`, so that a glob
+/// such as `/**/*` cannot flood the Attestation Agent and its event log.
+///
+/// Extensions beyond `max_events_per_sec` wait for their turn. Within a run
+/// (see `begin_run`), extensions beyond `max_events_per_run` fail with
+/// `MeasurementError::EventLimit` without reaching `inner`.
+pub struct RateLimitedSink {
+    inner: Arc<dyn MeasurementSink>,
+    /// Time per extension; zero is unlimited.
+    interval: Duration,
+    /// When the extensions let through so far would have been sent at the
+    /// full rate.
+    next: Mutex<Instant>,
+    max_events_per_run: u64,
+    in_run: AtomicBool,
+    run_events: AtomicU64,
+}
+
+impl RateLimitedSink {
+    pub fn new(inner: Arc<dyn MeasurementSink>, config: &RateLimitConfig) -> Self {
+        let interval = match config.max_events_per_sec {
+            0 => Duration::ZERO,
+            rate => Duration::from_secs(1) / rate,
+        };
+        Self {
+            inner,
+            interval,
+            next: Mutex::new(Instant::now()),
+            max_events_per_run: config.max_events_per_run,
+            in_run: AtomicBool::new(false),
+            run_events: AtomicU64::new(0),
+        }
+    }
+
+    fn limit_error(&self) -> MeasurementError {
+        MeasurementError::EventLimit(format!(
+            "more than {} extensions in one run; check for patterns that match too much, or raise rate_limit.max_events_per_run",
+            self.max_events_per_run
+        ))
+    }
+
+    /// Counts `count` extensions against the run and returns how many of
+    /// them may still be extended.
+    fn admit(&self, count: usize) -> usize {
+        if self.max_events_per_run == 0 || !self.in_run.load(Ordering::SeqCst) {
+            return count;
+        }
+        let before = self.run_events.fetch_add(count as u64, Ordering::SeqCst);
+        let allowed = self
+            .max_events_per_run
+            .saturating_sub(before)
+            .min(count as u64);
+        if before <= self.max_events_per_run && before + count as u64 > self.max_events_per_run {
+            error!("{}; aborting the run", self.limit_error());
+        }
+        allowed as usize
+    }
+
+    /// Waits until `count` more extensions fit in the rate.
+    async fn throttle(&self, count: usize) {
+        if self.interval.is_zero() || self.inner.is_dry_run() {
+            return;
+        }
+        let delay = {
+            let mut next = self.next.lock().unwrap();
+            let now = Instant::now();
+            *next = (*next).max(now) + self.interval * count as u32;
+            (*next - now).saturating_sub(RATE_LIMIT_BURST)
+        };
+        if !delay.is_zero() {
+            debug!(
+                "Rate limit reached; delaying {} extensions by {:?}",
+                count, delay
+            );
+            sleep(delay).await;
+        }
+    }
+}
+
+#[async_trait]
+impl MeasurementSink for RateLimitedSink {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn extend(
+        &self,
+        domain: &str,
+        operation: &str,
+        content: &str,
+        pcr_index: Option<u64>,
+    ) -> Result<()> {
+        if self.admit(1) == 0 {
+            return Err(self.limit_error());
+        }
+        self.throttle(1).await;
+        self.inner
+            .extend(domain, operation, content, pcr_index)
+            .await
+    }
+
+    async fn extend_batch(&self, extensions: &[Extension]) -> Vec<Result<()>> {
+        let allowed = self.admit(extensions.len());
+        self.throttle(allowed).await;
+        let mut results = self.inner.extend_batch(&extensions[..allowed]).await;
+        results.extend((allowed..extensions.len()).map(|_| Err(self.limit_error())));
+        results
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.inner.is_dry_run()
+    }
+
+    fn begin_run(&self) {
+        self.run_events.store(0, Ordering::SeqCst);
+        self.in_run.store(true, Ordering::SeqCst);
+    }
+
+    fn end_run(&self) {
+        self.in_run.store(false, Ordering::SeqCst);
+    }
+
+    fn check_run(&self) -> Result<()> {
+        if self.in_run.load(Ordering::SeqCst)
+            && self.max_events_per_run > 0
+            && self.run_events.load(Ordering::SeqCst) > self.max_events_per_run
+        {
+            return Err(self.limit_error());
+        }
+        Ok(())
+    }
+}
+
+/// Builds the sink measurers extend through: the Attestation Agent client,
+/// followed by the `sinks` configured next to it, behind staging and the
+/// `rate_limit` limits. Every sink records the same staged event.
+///
+/// A dry-run client is the only sink of a dry run: nothing is written to
+/// the configured sinks and no rate limit applies.
+pub fn from_config(config: &Config, aa_client: Arc<AAClient>) -> Result<Arc<dyn MeasurementSink>> {
+    if aa_client.is_dry_run() {
+        return Ok(Arc::new(StagingSink::new(config, aa_client)?));
+    }
+    let mut sinks: Vec<Arc<dyn MeasurementSink>> = vec![aa_client];
+    for sink_config in &config.sinks {
+        sinks.push(match sink_config {
+            SinkConfig::Cel {
+                path,
+                format,
+                hash_algorithm,
+                pcr_index,
+            } => Arc::new(CelSink::new(
+                PathBuf::from(path),
+                *format,
+                hash_algorithm.clone(),
+                *pcr_index,
+            )),
+            SinkConfig::DryRun => Arc::new(AAClient::dry_run()),
+        });
+    }
+    let sink: Arc<dyn MeasurementSink> = if sinks.len() == 1 {
+        sinks.remove(0)
+    } else {
+        Arc::new(FanOutSink { sinks })
+    };
+    let sink: Arc<dyn MeasurementSink> = Arc::new(StagingSink::new(config, sink)?);
+    let limits = &config.rate_limit;
+    if limits.max_events_per_sec == 0 && limits.max_events_per_run == 0 {
+        return Ok(sink);
+    }
+    Ok(Arc::new(RateLimitedSink::new(sink, limits)))
+}
@@ -0,0 +1,267 @@
+// src/status.rs
+use crate::config::StatusConfig;
+use crate::error::Result;
+use crate::listener;
+use crate::report::unix_timestamp;
+use crate::rpc_client::{AAClient, AaConnectivity};
+use log::{debug, info};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+const MAX_HEADER_LINES: usize = 64;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Result of a measurer's latest run.
+#[derive(Clone, Serialize)]
+struct MeasurerStatus {
+    finished_at: u64,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// State of a runtime watcher under supervision.
+#[derive(Clone, Default, Serialize)]
+struct WatcherStatus {
+    running: bool,
+    /// Failures since startup, and since the watcher last ran stably.
+    failures: u32,
+    consecutive_failures: u32,
+    /// Failed `failure_threshold` times in a row, or stopped after a failure.
+    failing: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_failure: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Unix time the latest full measurement run finished.
+    last_run: Option<u64>,
+    last_run_ok: bool,
+    measurers: BTreeMap<String, MeasurerStatus>,
+    watchers: BTreeMap<String, WatcherStatus>,
+}
+
+#[derive(Serialize)]
+struct StatusReport {
+    healthy: bool,
+    started_at: u64,
+    last_run: Option<u64>,
+    last_run_ok: bool,
+    measurers: BTreeMap<String, MeasurerStatus>,
+    watchers: BTreeMap<String, WatcherStatus>,
+    attestation_agent: AaConnectivity,
+}
+
+#[derive(Serialize)]
+struct HealthReport {
+    status: &'static str,
+}
+
+/// What the daemon has done so far, shared between the measurement runs and
+/// the status server.
+pub struct DaemonStatus {
+    started_at: u64,
+    state: Mutex<State>,
+}
+
+impl DaemonStatus {
+    pub fn new() -> Self {
+        Self {
+            started_at: unix_timestamp(),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    pub fn record_measurer(&self, name: &str, result: &Result<()>) {
+        let status = MeasurerStatus {
+            finished_at: unix_timestamp(),
+            ok: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        let mut state = self.state.lock().unwrap();
+        state.measurers.insert(name.to_string(), status);
+    }
+
+    pub fn record_run(&self, succeeded: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.last_run = Some(unix_timestamp());
+        state.last_run_ok = succeeded;
+    }
+
+    /// A watcher was started or restarted.
+    pub fn record_watcher_running(&self, name: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.watchers.entry(name.to_string()).or_default().running = true;
+    }
+
+    /// A restarted watcher has run long enough for its failures to be
+    /// forgotten.
+    pub fn record_watcher_stable(&self, name: &str) {
+        let mut state = self.state.lock().unwrap();
+        let watcher = state.watchers.entry(name.to_string()).or_default();
+        watcher.consecutive_failures = 0;
+        watcher.failing = false;
+    }
+
+    pub fn record_watcher_failure(
+        &self,
+        name: &str,
+        error: &str,
+        consecutive_failures: u32,
+        failing: bool,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        let watcher = state.watchers.entry(name.to_string()).or_default();
+        watcher.running = false;
+        watcher.failures += 1;
+        watcher.consecutive_failures = consecutive_failures;
+        watcher.failing = failing;
+        watcher.last_failure = Some(unix_timestamp());
+        watcher.last_error = Some(error.to_string());
+    }
+
+    /// A watcher returned without an error, having nothing left to watch.
+    pub fn record_watcher_finished(&self, name: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.watchers.entry(name.to_string()).or_default().running = false;
+    }
+
+    /// Healthy once the initial run has finished while the Attestation
+    /// Agent is reachable, nothing waits in the offline queue and no
+    /// watcher keeps failing. Failed non-critical measurements show in
+    /// `/status` but do not count.
+    pub fn is_healthy(&self, aa_client: &AAClient) -> bool {
+        let connectivity = aa_client.connectivity();
+        let state = self.state.lock().unwrap();
+        state.last_run.is_some()
+            && connectivity.reachable
+            && connectivity.queued == 0
+            && !state.watchers.values().any(|watcher| watcher.failing)
+    }
+
+    /// The `/status` document as JSON.
+    pub fn report_json(&self, aa_client: &AAClient) -> Vec<u8> {
+        serde_json::to_vec(&self.report(aa_client)).unwrap_or_default()
+    }
+
+    fn report(&self, aa_client: &AAClient) -> StatusReport {
+        let healthy = self.is_healthy(aa_client);
+        let state = self.state.lock().unwrap();
+        StatusReport {
+            healthy,
+            started_at: self.started_at,
+            last_run: state.last_run,
+            last_run_ok: state.last_run_ok,
+            measurers: state.measurers.clone(),
+            watchers: state.watchers.clone(),
+            attestation_agent: aa_client.connectivity(),
+        }
+    }
+}
+
+impl Default for DaemonStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Minimal HTTP/1.1 server for `GET /healthz` and `GET /status`. Every
+/// connection carries one request and is closed after the response.
+struct StatusServer {
+    status: Arc<DaemonStatus>,
+    aa_client: Arc<AAClient>,
+}
+
+impl StatusServer {
+    async fn handle_connection(&self, stream: TcpStream) -> Result<()> {
+        let mut reader = BufReader::new(stream);
+        let (status, body) =
+            match timeout(REQUEST_TIMEOUT, self.read_and_dispatch(&mut reader)).await {
+                Ok(reply) => reply,
+                Err(_) => ("408 Request Timeout", Vec::new()),
+            };
+        let mut stream = reader.into_inner();
+        let head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            body.len()
+        );
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&body).await?;
+        stream.shutdown().await?;
+        Ok(())
+    }
+
+    async fn read_and_dispatch(
+        &self,
+        reader: &mut BufReader<TcpStream>,
+    ) -> (&'static str, Vec<u8>) {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await.is_err() {
+            return ("400 Bad Request", Vec::new());
+        }
+        // Requests carry no body; drain the headers before answering.
+        for _ in 0..MAX_HEADER_LINES {
+            let mut line = String::new();
+            match reader.read_line(&mut line).await {
+                Ok(0) => break,
+                Ok(_) if line == "\r\n" || line == "\n" => break,
+                Ok(_) => {}
+                Err(_) => return ("400 Bad Request", Vec::new()),
+            }
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        if method != "GET" {
+            return ("405 Method Not Allowed", Vec::new());
+        }
+        match target {
+            "/healthz" => {
+                let healthy = self.status.is_healthy(&self.aa_client);
+                let (status, report) = if healthy {
+                    ("200 OK", HealthReport { status: "ok" })
+                } else {
+                    (
+                        "503 Service Unavailable",
+                        HealthReport {
+                            status: "unhealthy",
+                        },
+                    )
+                };
+                (status, serde_json::to_vec(&report).unwrap_or_default())
+            }
+            "/status" => ("200 OK", self.status.report_json(&self.aa_client)),
+            _ => ("404 Not Found", Vec::new()),
+        }
+    }
+}
+
+/// Serves the status endpoints until the listener fails.
+pub async fn serve(
+    config: StatusConfig,
+    status: Arc<DaemonStatus>,
+    aa_client: Arc<AAClient>,
+) -> Result<()> {
+    let listener = listener::tcp_listener("status", &config.listen).await?;
+    info!("Status server listening on {}", config.listen);
+    let server = Arc::new(StatusServer { status, aa_client });
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let server = server.clone();
+        tokio::spawn(async move {
+            if let Err(e) = server.handle_connection(stream).await {
+                debug!("Status connection from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
@@ -0,0 +1,193 @@
+// src/tpm.rs
+use crate::digest::{self, Hasher};
+use crate::error::{MeasurementError, Result};
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::path::Path;
+
+const TPM_ST_SESSIONS: u16 = 0x8002;
+const TPM_CC_PCR_EXTEND: u32 = 0x0000_0182;
+const TPM_RS_PW: u32 = 0x4000_0009;
+const TPM_ALG_SHA256: u16 = 0x000B;
+const TPM_ALG_SHA384: u16 = 0x000C;
+const TPM_ALG_SHA512: u16 = 0x000D;
+const TPM_ALG_SM3_256: u16 = 0x0012;
+/// PCRs of a PC Client TPM.
+const PCR_COUNT: u32 = 24;
+
+/// The TPM algorithm ID of the PCR bank of a registered hash algorithm.
+/// BLAKE3 has no bank.
+pub fn bank_algorithm(hash_algorithm: &str) -> Option<u16> {
+    match digest::canonical_name(hash_algorithm)? {
+        "sha256" => Some(TPM_ALG_SHA256),
+        "sha384" => Some(TPM_ALG_SHA384),
+        "sha512" => Some(TPM_ALG_SHA512),
+        "sm3" => Some(TPM_ALG_SM3_256),
+        _ => None,
+    }
+}
+
+/// The digest of `event` in the PCR bank of `hash_algorithm`, with the
+/// bank's TPM algorithm ID.
+pub fn event_digest(event: &[u8], hash_algorithm: &str) -> Result<(u16, Vec<u8>)> {
+    let algorithm = bank_algorithm(hash_algorithm)
+        .ok_or_else(|| MeasurementError::UnsupportedHashAlgorithm(hash_algorithm.to_lowercase()))?;
+    let mut hasher = Hasher::new(hash_algorithm)?;
+    hasher.update(event);
+    Ok((algorithm, hasher.finalize()))
+}
+
+/// Marshals TPM2_PCR_Extend of one bank of `pcr_index`, authorized with
+/// an empty password session.
+fn pcr_extend_command(pcr_index: u32, algorithm: u16, digest: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&pcr_index.to_be_bytes());
+    // TPMS_AUTH_COMMAND: handle, empty nonce, no attributes, empty HMAC.
+    body.extend_from_slice(&9u32.to_be_bytes());
+    body.extend_from_slice(&TPM_RS_PW.to_be_bytes());
+    body.extend_from_slice(&0u16.to_be_bytes());
+    body.push(0);
+    body.extend_from_slice(&0u16.to_be_bytes());
+    // TPML_DIGEST_VALUES with a single bank.
+    body.extend_from_slice(&1u32.to_be_bytes());
+    body.extend_from_slice(&algorithm.to_be_bytes());
+    body.extend_from_slice(digest);
+
+    let mut command = Vec::with_capacity(10 + body.len());
+    command.extend_from_slice(&TPM_ST_SESSIONS.to_be_bytes());
+    command.extend_from_slice(&(10 + body.len() as u32).to_be_bytes());
+    command.extend_from_slice(&TPM_CC_PCR_EXTEND.to_be_bytes());
+    command.extend_from_slice(&body);
+    command
+}
+
+/// The response code in the header of a TPM response (tag, size, code),
+/// or `None` if the response is shorter than the header.
+fn response_code(response: &[u8]) -> Option<u32> {
+    let code = response.get(6..10)?;
+    Some(u32::from_be_bytes([code[0], code[1], code[2], code[3]]))
+}
+
+/// Extends `digest` into `pcr_index` through the TPM character device
+/// `device` (usually the resource manager, `/dev/tpmrm0`). The command is
+/// marshalled here, so no TSS libraries are needed.
+///
+/// I/O errors mean the TPM could not be reached; a TPM response code is
+/// returned as `MeasurementError::Tpm`.
+pub fn pcr_extend(device: &Path, pcr_index: u32, algorithm: u16, digest: &[u8]) -> Result<()> {
+    if pcr_index >= PCR_COUNT {
+        return Err(MeasurementError::Config(format!(
+            "PCR {} does not exist; a TPM has PCRs 0 to {}",
+            pcr_index,
+            PCR_COUNT - 1
+        )));
+    }
+    let mut tpm = OpenOptions::new().read(true).write(true).open(device)?;
+    tpm.write_all(&pcr_extend_command(pcr_index, algorithm, digest))?;
+    let mut response = [0u8; 4096];
+    let len = tpm.read(&mut response)?;
+    let code = response_code(&response[..len]).ok_or_else(|| {
+        MeasurementError::Tpm(format!("Short response of {} bytes from {:?}", len, device))
+    })?;
+    if code != 0 {
+        return Err(MeasurementError::Tpm(format!(
+            "TPM2_PCR_Extend of PCR {} failed with response code {:#x}",
+            pcr_index, code
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SHA-256 and SHA-384 of "abc" (FIPS 180-2, appendix B and D).
+    const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const SHA384_ABC: &str = "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+                              8086072ba1e7cc2358baeca134c825a7";
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        hex::decode(hex.replace(' ', "")).unwrap()
+    }
+
+    /// TPM2_PCR_Extend as laid out in TPM 2.0 Part 3, section 22.2, with a
+    /// password session (Part 1, section 19.8.4) carrying an empty password.
+    #[test]
+    fn marshals_pcr_extend_of_a_sha256_bank() {
+        let expected = [
+            "8002",     // TPM_ST_SESSIONS
+            "00000041", // commandSize: 65
+            "00000182", // TPM_CC_PCR_Extend
+            "00000010", // pcrHandle: PCR 16
+            "00000009", // authorizationSize
+            "40000009", // TPM_RS_PW
+            "0000",     // nonceCaller: empty
+            "00",       // sessionAttributes
+            "0000",     // hmac: empty password
+            "00000001", // TPML_DIGEST_VALUES.count
+            "000b",     // TPM_ALG_SHA256
+            SHA256_ABC,
+        ]
+        .concat();
+        let command = pcr_extend_command(16, TPM_ALG_SHA256, &bytes(SHA256_ABC));
+        assert_eq!(hex::encode(command), expected);
+    }
+
+    #[test]
+    fn marshals_pcr_extend_of_a_sha384_bank() {
+        let expected = [
+            "8002",     // TPM_ST_SESSIONS
+            "00000051", // commandSize: 81
+            "00000182", // TPM_CC_PCR_Extend
+            "00000017", // pcrHandle: PCR 23
+            "00000009", // authorizationSize
+            "40000009", // TPM_RS_PW
+            "0000",     // nonceCaller: empty
+            "00",       // sessionAttributes
+            "0000",     // hmac: empty password
+            "00000001", // TPML_DIGEST_VALUES.count
+            "000c",     // TPM_ALG_SHA384
+            SHA384_ABC,
+        ]
+        .concat();
+        let command = pcr_extend_command(23, TPM_ALG_SHA384, &bytes(SHA384_ABC));
+        assert_eq!(hex::encode(command), expected);
+    }
+
+    #[test]
+    fn digests_events_in_the_configured_bank() {
+        assert_eq!(
+            event_digest(b"abc", "sha256").unwrap(),
+            (TPM_ALG_SHA256, bytes(SHA256_ABC))
+        );
+        assert_eq!(
+            event_digest(b"abc", "SHA384").unwrap(),
+            (TPM_ALG_SHA384, bytes(SHA384_ABC))
+        );
+        // GB/T 32905-2016, appendix A.1.
+        assert_eq!(
+            event_digest(b"abc", "sm3").unwrap(),
+            (
+                TPM_ALG_SM3_256,
+                bytes("66c7f0f462eeedd9d1f2d46bdc10e4e24167c4875cf2f7a2297da02b8f4ba8e0")
+            )
+        );
+        assert_eq!(bank_algorithm("sha512"), Some(TPM_ALG_SHA512));
+        assert!(matches!(
+            event_digest(b"abc", "blake3"),
+            Err(MeasurementError::UnsupportedHashAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn reads_the_response_code() {
+        // Success: no parameters, and the password session's response.
+        let success = bytes("8002 00000013 00000000 00000000 0000 01 0000");
+        assert_eq!(response_code(&success), Some(0));
+        // TPM_RC_AUTH_FAIL for session 1, without sessions in the response.
+        let failure = bytes("8001 0000000a 0000098e");
+        assert_eq!(response_code(&failure), Some(0x98e));
+        assert_eq!(response_code(&failure[..9]), None);
+    }
+}